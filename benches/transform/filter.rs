@@ -6,20 +6,26 @@ use criterion::{
 };
 use vector::{
     conditions::Condition,
-    transforms::{filter::Filter, FunctionTransform, OutputBuffer},
+    transforms::{filter::Filter, SyncTransform, TransformOutputsBuf},
+};
+use vector_core::{
+    config::{DataType, Output},
+    event::{Event, LogEvent},
 };
-use vector_core::event::{Event, LogEvent};
 
 #[derive(Debug)]
 struct Payload {
     filter: Filter,
-    output: OutputBuffer,
+    output: TransformOutputsBuf,
     events: Vec<Event>,
 }
 
 fn setup(total_events: usize, condition: Condition) -> Payload {
     let filter = Filter::new(condition);
-    let output = OutputBuffer::from(Vec::with_capacity(total_events));
+    let output = TransformOutputsBuf::new_with_capacity(
+        vec![Output::default(DataType::all())],
+        total_events,
+    );
     let events = vec![Event::Log(LogEvent::default()); total_events];
     Payload {
         filter,
@@ -34,7 +40,7 @@ fn measurement(payload: Payload) {
     let events = payload.events;
 
     for event in events {
-        filter.transform(&mut output, event)
+        filter.transform(event, &mut output)
     }
 }
 
@@ -0,0 +1,428 @@
+use std::fmt::Write;
+
+use bytes::{BufMut, BytesMut};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Encoder;
+use value::Value;
+use vector_config::configurable_component;
+use vector_core::{
+    config::{log_schema, DataType},
+    event::{Event, LogEvent},
+    schema,
+};
+
+/// The value used in place of a missing header field, as defined in RFC 5424.
+const NILVALUE: &str = "-";
+
+/// The facility names as emitted by the `syslog` decoder, indexed by their numerical code.
+const FACILITIES: [&str; 24] = [
+    "kern", "user", "mail", "daemon", "auth", "syslog", "lpr", "news", "uucp", "cron", "authpriv",
+    "ftp", "ntp", "audit", "alert", "clockd", "local0", "local1", "local2", "local3", "local4",
+    "local5", "local6", "local7",
+];
+
+/// The severity names as emitted by the `syslog` decoder, indexed by their numerical code.
+const SEVERITIES: [&str; 8] = [
+    "emerg", "alert", "crit", "err", "warning", "notice", "info", "debug",
+];
+
+/// The `user` facility, used when an event has no facility.
+const DEFAULT_FACILITY: u8 = 1;
+
+/// The `notice` severity, used when an event has no severity.
+const DEFAULT_SEVERITY: u8 = 5;
+
+/// Config used to build a `SyslogSerializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct SyslogSerializerConfig {
+    /// Options for the syslog serializer.
+    pub syslog: SyslogSerializerOptions,
+}
+
+impl SyslogSerializerConfig {
+    /// Creates a new `SyslogSerializerConfig`.
+    pub const fn new(syslog: SyslogSerializerOptions) -> Self {
+        Self { syslog }
+    }
+
+    /// Build the `SyslogSerializer` from this configuration.
+    pub fn build(&self) -> SyslogSerializer {
+        SyslogSerializer::new(self.syslog.clone())
+    }
+
+    /// The data type of events that are accepted by `SyslogSerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        // All header fields are optional and fall back to sensible defaults.
+        schema::Requirement::empty()
+    }
+}
+
+/// The syslog message format.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SyslogRfc {
+    /// The legacy BSD syslog format, as described in RFC 3164.
+    Rfc3164,
+
+    /// The syslog protocol, as defined in RFC 5424.
+    Rfc5424,
+}
+
+impl Default for SyslogRfc {
+    fn default() -> Self {
+        Self::Rfc5424
+    }
+}
+
+/// Syslog serializer options.
+///
+/// Each header field is read from the configured event field. Header fields missing from an event
+/// are set to their nil value, or a sensible default where the format requires one.
+#[configurable_component]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[serde(default)]
+pub struct SyslogSerializerOptions {
+    /// The syslog message format.
+    pub rfc: SyslogRfc,
+
+    /// The event field holding the facility, either as a name, such as `local0`, or as a number.
+    ///
+    /// Defaults to the `user` facility if the field is missing.
+    pub facility_field: String,
+
+    /// The event field holding the severity, either as a name, such as `err`, or as a number.
+    ///
+    /// Defaults to the `notice` severity if the field is missing.
+    pub severity_field: String,
+
+    /// The event field holding the application name.
+    pub app_name_field: String,
+
+    /// The event field holding the process ID.
+    pub proc_id_field: String,
+
+    /// The event field holding the message ID.
+    ///
+    /// Only used with the RFC 5424 format.
+    pub msg_id_field: String,
+
+    /// The event field holding the structured data.
+    ///
+    /// The field is expected to be an object whose keys are SD-IDs, each holding an object of
+    /// parameters. Only used with the RFC 5424 format.
+    pub structured_data_field: String,
+}
+
+impl Default for SyslogSerializerOptions {
+    fn default() -> Self {
+        Self {
+            rfc: SyslogRfc::default(),
+            facility_field: "facility".into(),
+            severity_field: "severity".into(),
+            app_name_field: "appname".into(),
+            proc_id_field: "procid".into(),
+            msg_id_field: "msgid".into(),
+            structured_data_field: "structured_data".into(),
+        }
+    }
+}
+
+/// Serializer that converts an `Event` to bytes using the syslog format.
+#[derive(Debug, Clone)]
+pub struct SyslogSerializer {
+    options: SyslogSerializerOptions,
+}
+
+impl SyslogSerializer {
+    /// Creates a new `SyslogSerializer`.
+    pub const fn new(options: SyslogSerializerOptions) -> Self {
+        Self { options }
+    }
+
+    fn priority(&self, log: &LogEvent) -> u8 {
+        let facility = log
+            .get(self.options.facility_field.as_str())
+            .and_then(|value| code(value, &FACILITIES))
+            .unwrap_or(DEFAULT_FACILITY);
+        let severity = log
+            .get(self.options.severity_field.as_str())
+            .and_then(|value| code(value, &SEVERITIES).or_else(|| severity_alias(value)))
+            .unwrap_or(DEFAULT_SEVERITY);
+        facility * 8 + severity
+    }
+
+    fn header_field(&self, log: &LogEvent, field: &str, max_length: usize) -> Option<String> {
+        log.get(field)
+            .map(|value| sanitize(&value.to_string_lossy(), max_length))
+            .filter(|value| !value.is_empty())
+    }
+
+    fn encode_rfc5424(&self, log: &LogEvent, buffer: &mut String) {
+        let timestamp = timestamp(log).to_rfc3339_opts(SecondsFormat::Micros, true);
+        let hostname = self.header_field(log, log_schema().host_key(), 255);
+        let app_name = self.header_field(log, &self.options.app_name_field, 48);
+        let proc_id = self.header_field(log, &self.options.proc_id_field, 128);
+        let msg_id = self.header_field(log, &self.options.msg_id_field, 32);
+
+        let _ = write!(
+            buffer,
+            "<{}>1 {} {} {} {} {} ",
+            self.priority(log),
+            timestamp,
+            hostname.as_deref().unwrap_or(NILVALUE),
+            app_name.as_deref().unwrap_or(NILVALUE),
+            proc_id.as_deref().unwrap_or(NILVALUE),
+            msg_id.as_deref().unwrap_or(NILVALUE),
+        );
+
+        match log
+            .get(self.options.structured_data_field.as_str())
+            .and_then(Value::as_object)
+            .filter(|elements| !elements.is_empty())
+        {
+            Some(elements) => {
+                for (id, params) in elements {
+                    buffer.push('[');
+                    buffer.push_str(&sanitize_sd_name(id));
+                    if let Some(params) = params.as_object() {
+                        for (name, value) in params {
+                            let _ = write!(
+                                buffer,
+                                " {}=\"{}\"",
+                                sanitize_sd_name(name),
+                                escape_param_value(&value.to_string_lossy())
+                            );
+                        }
+                    }
+                    buffer.push(']');
+                }
+            }
+            None => buffer.push_str(NILVALUE),
+        }
+
+        if let Some(message) = message(log) {
+            buffer.push(' ');
+            buffer.push_str(&message);
+        }
+    }
+
+    fn encode_rfc3164(&self, log: &LogEvent, buffer: &mut String) {
+        let timestamp = timestamp(log).format("%b %e %H:%M:%S");
+        let hostname = self.header_field(log, log_schema().host_key(), 255);
+        let app_name = self.header_field(log, &self.options.app_name_field, 32);
+        let proc_id = self.header_field(log, &self.options.proc_id_field, 128);
+
+        let _ = write!(
+            buffer,
+            "<{}>{} {} {}",
+            self.priority(log),
+            timestamp,
+            hostname.as_deref().unwrap_or("localhost"),
+            app_name.as_deref().unwrap_or("vector"),
+        );
+        if let Some(proc_id) = proc_id {
+            let _ = write!(buffer, "[{}]", proc_id);
+        }
+        buffer.push(':');
+
+        if let Some(message) = message(log) {
+            buffer.push(' ');
+            buffer.push_str(&message);
+        }
+    }
+}
+
+impl Encoder<Event> for SyslogSerializer {
+    type Error = vector_core::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let log = event.into_log();
+        let mut message = String::new();
+
+        match self.options.rfc {
+            SyslogRfc::Rfc3164 => self.encode_rfc3164(&log, &mut message),
+            SyslogRfc::Rfc5424 => self.encode_rfc5424(&log, &mut message),
+        }
+
+        buffer.put_slice(message.as_bytes());
+        Ok(())
+    }
+}
+
+/// Gets the numerical code of a facility or severity, given either as a name or as a number.
+fn code(value: &Value, names: &[&str]) -> Option<u8> {
+    match value {
+        Value::Integer(code) => u8::try_from(*code)
+            .ok()
+            .filter(|code| usize::from(*code) < names.len()),
+        Value::Bytes(bytes) => {
+            let name = String::from_utf8_lossy(bytes);
+            let name = name.trim().to_lowercase();
+            names
+                .iter()
+                .position(|candidate| *candidate == name)
+                .or_else(|| {
+                    name.parse::<usize>()
+                        .ok()
+                        .filter(|code| *code < names.len())
+                })
+                .map(|code| code as u8)
+        }
+        _ => None,
+    }
+}
+
+/// Gets the numerical code of a severity given by one of its commonly used aliases.
+fn severity_alias(value: &Value) -> Option<u8> {
+    match value.to_string_lossy().trim().to_lowercase().as_str() {
+        "emergency" | "panic" => Some(0),
+        "critical" => Some(2),
+        "error" => Some(3),
+        "warn" => Some(4),
+        "informational" => Some(6),
+        _ => None,
+    }
+}
+
+fn timestamp(log: &LogEvent) -> DateTime<Utc> {
+    log.get(log_schema().timestamp_key())
+        .and_then(Value::as_timestamp)
+        .copied()
+        .unwrap_or_else(Utc::now)
+}
+
+fn message(log: &LogEvent) -> Option<String> {
+    log.get(log_schema().message_key())
+        .map(Value::to_string_lossy)
+}
+
+/// Restricts a header field to printable US-ASCII characters, excluding spaces, and to the given
+/// maximum length.
+fn sanitize(value: &str, max_length: usize) -> String {
+    value
+        .chars()
+        .filter(|c| c.is_ascii_graphic())
+        .take(max_length)
+        .collect()
+}
+
+/// Restricts an SD-ID or parameter name to the characters allowed by RFC 5424.
+fn sanitize_sd_name(value: &str) -> String {
+    sanitize(value, 32).replace(['=', ']', '"'], "")
+}
+
+/// Escapes the characters that are not allowed unescaped in a structured data parameter value.
+fn escape_param_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '"' | '\\' | ']') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::BytesMut;
+    use chrono::TimeZone;
+    use vector_common::btreemap;
+
+    use super::*;
+
+    fn serialize(options: SyslogSerializerOptions, log: LogEvent) -> String {
+        let mut serializer = SyslogSerializerConfig::new(options).build();
+        let mut bytes = BytesMut::new();
+        serializer.encode(Event::from(log), &mut bytes).unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    fn event() -> LogEvent {
+        let mut log = LogEvent::from("hello world");
+        log.insert(
+            log_schema().timestamp_key(),
+            Utc.ymd(2022, 8, 3).and_hms_micro(9, 5, 2, 120_000),
+        );
+        log.insert(log_schema().host_key(), "web-01");
+        log.insert("appname", "nginx");
+        log.insert("procid", 4242);
+        log
+    }
+
+    #[test]
+    fn serialize_rfc5424() {
+        let mut log = event();
+        log.insert("facility", "local0");
+        log.insert("severity", "err");
+        log.insert("msgid", "ID47");
+        log.insert(
+            "structured_data",
+            Value::from(btreemap! {
+                "origin@32473" => Value::from(btreemap! {
+                    "ip" => "10.0.0.1",
+                    "note" => "a \"quoted\" ]",
+                }),
+            }),
+        );
+
+        assert_eq!(
+            serialize(SyslogSerializerOptions::default(), log),
+            r#"<131>1 2022-08-03T09:05:02.120000Z web-01 nginx 4242 ID47 [origin@32473 ip="10.0.0.1" note="a \"quoted\" \]"] hello world"#
+        );
+    }
+
+    #[test]
+    fn serialize_rfc5424_defaults() {
+        let mut log = LogEvent::from("hello world");
+        log.insert(
+            log_schema().timestamp_key(),
+            Utc.ymd(2022, 8, 3).and_hms(9, 5, 2),
+        );
+
+        assert_eq!(
+            serialize(SyslogSerializerOptions::default(), log),
+            "<13>1 2022-08-03T09:05:02.000000Z - - - - - hello world"
+        );
+    }
+
+    #[test]
+    fn serialize_rfc3164() {
+        let mut log = event();
+        log.insert("facility", 4);
+        log.insert("severity", "warn");
+
+        let options = SyslogSerializerOptions {
+            rfc: SyslogRfc::Rfc3164,
+            ..Default::default()
+        };
+        assert_eq!(
+            serialize(options, log),
+            "<36>Aug  3 09:05:02 web-01 nginx[4242]: hello world"
+        );
+    }
+
+    #[test]
+    fn serialize_custom_fields() {
+        let mut log = event();
+        log.insert("level", "debug");
+        log.insert("service", "api gateway");
+
+        let options = SyslogSerializerOptions {
+            severity_field: "level".into(),
+            app_name_field: "service".into(),
+            ..Default::default()
+        };
+        assert_eq!(
+            serialize(options, log),
+            "<15>1 2022-08-03T09:05:02.120000Z web-01 apigateway 4242 - - hello world"
+        );
+    }
+}
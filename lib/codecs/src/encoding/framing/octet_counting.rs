@@ -0,0 +1,61 @@
+use bytes::{BufMut, BytesMut};
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Encoder;
+
+use super::BoxedFramingError;
+
+/// Config used to build an `OctetCountingEncoder`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct OctetCountingEncoderConfig;
+
+impl OctetCountingEncoderConfig {
+    /// Creates a new `OctetCountingEncoderConfig`.
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Build the `OctetCountingEncoder` from this configuration.
+    pub const fn build(&self) -> OctetCountingEncoder {
+        OctetCountingEncoder::new()
+    }
+}
+
+/// An encoder for prefixing bytes with their length in octets, as specified in
+/// https://tools.ietf.org/html/rfc6587#section-3.4.1.
+#[derive(Debug, Clone, Default)]
+pub struct OctetCountingEncoder;
+
+impl OctetCountingEncoder {
+    /// Creates a new `OctetCountingEncoder`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Encoder<()> for OctetCountingEncoder {
+    type Error = BoxedFramingError;
+
+    fn encode(&mut self, _: (), buffer: &mut BytesMut) -> Result<(), BoxedFramingError> {
+        let frame = buffer.split();
+        let prefix = format!("{} ", frame.len());
+        buffer.reserve(prefix.len() + frame.len());
+        buffer.put_slice(prefix.as_bytes());
+        buffer.put_slice(&frame);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode() {
+        let mut codec = OctetCountingEncoder::new();
+
+        let mut buffer = BytesMut::from("<13>1 - - - - - - hello");
+        codec.encode((), &mut buffer).unwrap();
+
+        assert_eq!(&buffer[..], b"23 <13>1 - - - - - - hello");
+    }
+}
@@ -11,13 +11,15 @@ pub use format::{
     AvroSerializer, AvroSerializerConfig, AvroSerializerOptions, GelfSerializer,
     GelfSerializerConfig, JsonSerializer, JsonSerializerConfig, LogfmtSerializer,
     LogfmtSerializerConfig, NativeJsonSerializer, NativeJsonSerializerConfig, NativeSerializer,
-    NativeSerializerConfig, RawMessageSerializer, RawMessageSerializerConfig, TextSerializer,
+    NativeSerializerConfig, RawMessageSerializer, RawMessageSerializerConfig, SyslogRfc,
+    SyslogSerializer, SyslogSerializerConfig, SyslogSerializerOptions, TextSerializer,
     TextSerializerConfig,
 };
 pub use framing::{
     BoxedFramer, BoxedFramingError, BytesEncoder, BytesEncoderConfig, CharacterDelimitedEncoder,
     CharacterDelimitedEncoderConfig, CharacterDelimitedEncoderOptions, LengthDelimitedEncoder,
     LengthDelimitedEncoderConfig, NewlineDelimitedEncoder, NewlineDelimitedEncoderConfig,
+    OctetCountingEncoder, OctetCountingEncoderConfig,
 };
 use vector_config::configurable_component;
 use vector_core::{config::DataType, event::Event, schema};
@@ -72,6 +74,10 @@ pub enum FramingConfig {
 
     /// Event data is delimited by a newline (LF) character.
     NewlineDelimited,
+
+    /// Event data is prefixed with its length in bytes as an ASCII decimal number followed by a
+    /// space, as used by syslog over TCP.
+    OctetCounting,
 }
 
 impl From<BytesEncoderConfig> for FramingConfig {
@@ -100,6 +106,12 @@ impl From<NewlineDelimitedEncoderConfig> for FramingConfig {
     }
 }
 
+impl From<OctetCountingEncoderConfig> for FramingConfig {
+    fn from(_: OctetCountingEncoderConfig) -> Self {
+        Self::OctetCounting
+    }
+}
+
 impl FramingConfig {
     /// Build the `Framer` from this configuration.
     pub fn build(&self) -> Framer {
@@ -119,6 +131,9 @@ impl FramingConfig {
             FramingConfig::NewlineDelimited => {
                 Framer::NewlineDelimited(NewlineDelimitedEncoderConfig.build())
             }
+            FramingConfig::OctetCounting => {
+                Framer::OctetCounting(OctetCountingEncoderConfig.build())
+            }
         }
     }
 }
@@ -134,6 +149,8 @@ pub enum Framer {
     LengthDelimited(LengthDelimitedEncoder),
     /// Uses a `NewlineDelimitedEncoder` for framing.
     NewlineDelimited(NewlineDelimitedEncoder),
+    /// Uses an `OctetCountingEncoder` for framing.
+    OctetCounting(OctetCountingEncoder),
     /// Uses an opaque `Encoder` implementation for framing.
     Boxed(BoxedFramer),
 }
@@ -162,6 +179,12 @@ impl From<NewlineDelimitedEncoder> for Framer {
     }
 }
 
+impl From<OctetCountingEncoder> for Framer {
+    fn from(encoder: OctetCountingEncoder) -> Self {
+        Self::OctetCounting(encoder)
+    }
+}
+
 impl From<BoxedFramer> for Framer {
     fn from(encoder: BoxedFramer) -> Self {
         Self::Boxed(encoder)
@@ -177,6 +200,7 @@ impl tokio_util::codec::Encoder<()> for Framer {
             Framer::CharacterDelimited(framer) => framer.encode((), buffer),
             Framer::LengthDelimited(framer) => framer.encode((), buffer),
             Framer::NewlineDelimited(framer) => framer.encode((), buffer),
+            Framer::OctetCounting(framer) => framer.encode((), buffer),
             Framer::Boxed(framer) => framer.encode((), buffer),
         }
     }
@@ -216,6 +240,16 @@ pub enum SerializerConfig {
     /// event.
     RawMessage,
 
+    /// Syslog serialization.
+    ///
+    /// Events are formatted according to RFC 5424 or RFC 3164, with the header fields read from
+    /// configurable event fields.
+    Syslog {
+        /// Options for the syslog serializer.
+        #[serde(default)]
+        syslog: SyslogSerializerOptions,
+    },
+
     /// Plaintext serialization.
     ///
     /// This encoding, specifically, will only encode the `message` field of a log event. Users should take care if
@@ -267,6 +301,14 @@ impl From<RawMessageSerializerConfig> for SerializerConfig {
     }
 }
 
+impl From<SyslogSerializerConfig> for SerializerConfig {
+    fn from(config: SyslogSerializerConfig) -> Self {
+        Self::Syslog {
+            syslog: config.syslog,
+        }
+    }
+}
+
 impl From<TextSerializerConfig> for SerializerConfig {
     fn from(_: TextSerializerConfig) -> Self {
         Self::Text
@@ -290,6 +332,9 @@ impl SerializerConfig {
             SerializerConfig::RawMessage => {
                 Ok(Serializer::RawMessage(RawMessageSerializerConfig.build()))
             }
+            SerializerConfig::Syslog { syslog } => Ok(Serializer::Syslog(
+                SyslogSerializerConfig::new(syslog.clone()).build(),
+            )),
             SerializerConfig::Text => Ok(Serializer::Text(TextSerializerConfig.build())),
         }
     }
@@ -306,6 +351,9 @@ impl SerializerConfig {
             SerializerConfig::Native => NativeSerializerConfig.input_type(),
             SerializerConfig::NativeJson => NativeJsonSerializerConfig.input_type(),
             SerializerConfig::RawMessage => RawMessageSerializerConfig.input_type(),
+            SerializerConfig::Syslog { syslog } => {
+                SyslogSerializerConfig::new(syslog.clone()).input_type()
+            }
             SerializerConfig::Text => TextSerializerConfig.input_type(),
         }
     }
@@ -322,6 +370,9 @@ impl SerializerConfig {
             SerializerConfig::Native => NativeSerializerConfig.schema_requirement(),
             SerializerConfig::NativeJson => NativeJsonSerializerConfig.schema_requirement(),
             SerializerConfig::RawMessage => RawMessageSerializerConfig.schema_requirement(),
+            SerializerConfig::Syslog { syslog } => {
+                SyslogSerializerConfig::new(syslog.clone()).schema_requirement()
+            }
             SerializerConfig::Text => TextSerializerConfig.schema_requirement(),
         }
     }
@@ -344,6 +395,8 @@ pub enum Serializer {
     NativeJson(NativeJsonSerializer),
     /// Uses a `RawMessageSerializer` for serialization.
     RawMessage(RawMessageSerializer),
+    /// Uses a `SyslogSerializer` for serialization.
+    Syslog(SyslogSerializer),
     /// Uses a `TextSerializer` for serialization.
     Text(TextSerializer),
 }
@@ -357,7 +410,8 @@ impl Serializer {
             | Serializer::Logfmt(_)
             | Serializer::Text(_)
             | Serializer::Native(_)
-            | Serializer::RawMessage(_) => false,
+            | Serializer::RawMessage(_)
+            | Serializer::Syslog(_) => false,
         }
     }
 
@@ -376,7 +430,8 @@ impl Serializer {
             | Serializer::Logfmt(_)
             | Serializer::Text(_)
             | Serializer::Native(_)
-            | Serializer::RawMessage(_) => {
+            | Serializer::RawMessage(_)
+            | Serializer::Syslog(_) => {
                 panic!("Serializer does not support JSON")
             }
         }
@@ -425,6 +480,12 @@ impl From<RawMessageSerializer> for Serializer {
     }
 }
 
+impl From<SyslogSerializer> for Serializer {
+    fn from(serializer: SyslogSerializer) -> Self {
+        Self::Syslog(serializer)
+    }
+}
+
 impl From<TextSerializer> for Serializer {
     fn from(serializer: TextSerializer) -> Self {
         Self::Text(serializer)
@@ -443,6 +504,7 @@ impl tokio_util::codec::Encoder<Event> for Serializer {
             Serializer::Native(serializer) => serializer.encode(event, buffer),
             Serializer::NativeJson(serializer) => serializer.encode(event, buffer),
             Serializer::RawMessage(serializer) => serializer.encode(event, buffer),
+            Serializer::Syslog(serializer) => serializer.encode(event, buffer),
             Serializer::Text(serializer) => serializer.encode(event, buffer),
         }
     }
@@ -16,11 +16,15 @@ extern crate tracing;
 mod buffer_usage_data;
 
 pub mod config;
-pub use config::{BufferConfig, BufferType};
+pub use config::{
+    BufferConfig, BufferType, DiskBufferCompression, DiskBufferEncryption, MemoryBufferSize,
+};
 use encoding::Encodable;
 
 pub mod encoding;
 
+pub use variants::disk_v2::{inspect, DiskBufferConfig, ProductionFilesystem};
+
 mod internal_events;
 
 #[cfg(test)]
@@ -14,7 +14,7 @@ use crate::{
         channel::{BufferReceiver, BufferSender},
     },
     variants::{DiskV1Buffer, DiskV2Buffer, MemoryBuffer},
-    Bufferable, WhenFull,
+    Bufferable, MemoryBufferSize, WhenFull,
 };
 
 #[cfg(test)]
@@ -60,7 +60,10 @@ impl Variant {
                 when_full,
                 ..
             } => {
-                builder.stage(MemoryBuffer::new(*max_events), *when_full);
+                builder.stage(
+                    MemoryBuffer::new(MemoryBufferSize::MaxEvents(*max_events)),
+                    *when_full,
+                );
             }
             Variant::DiskV1 {
                 max_size,
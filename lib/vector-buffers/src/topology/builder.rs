@@ -9,7 +9,7 @@ use crate::{
     buffer_usage_data::{BufferUsage, BufferUsageHandle},
     topology::channel::{BufferReceiver, BufferSender},
     variants::MemoryBuffer,
-    Bufferable, WhenFull,
+    Bufferable, MemoryBufferSize, WhenFull,
 };
 
 /// Value that can be used as a stage in a buffer topology.
@@ -189,7 +189,7 @@ impl<T: Bufferable> TopologyBuilder<T> {
     ) -> (BufferSender<T>, BufferReceiver<T>) {
         let usage_handle = BufferUsageHandle::noop();
 
-        let memory_buffer = Box::new(MemoryBuffer::new(max_events));
+        let memory_buffer = Box::new(MemoryBuffer::new(MemoryBufferSize::MaxEvents(max_events)));
         let (sender, receiver) = memory_buffer
             .into_buffer_parts(usage_handle.clone())
             .await
@@ -224,7 +224,7 @@ impl<T: Bufferable> TopologyBuilder<T> {
         when_full: WhenFull,
         usage_handle: BufferUsageHandle,
     ) -> (BufferSender<T>, BufferReceiver<T>) {
-        let memory_buffer = Box::new(MemoryBuffer::new(max_events));
+        let memory_buffer = Box::new(MemoryBuffer::new(MemoryBufferSize::MaxEvents(max_events)));
         let (sender, receiver) = memory_buffer
             .into_buffer_parts(usage_handle.clone())
             .await
@@ -261,14 +261,14 @@ mod tests {
         topology::builder::TopologyError,
         topology::test_util::{assert_current_send_capacity, Sample},
         variants::MemoryBuffer,
-        WhenFull,
+        MemoryBufferSize, WhenFull,
     };
 
     #[tokio::test]
     async fn single_stage_topology_block() {
         let mut builder = TopologyBuilder::<Sample>::default();
         builder.stage(
-            MemoryBuffer::new(NonZeroUsize::new(1).unwrap()),
+            MemoryBuffer::new(MemoryBufferSize::MaxEvents(NonZeroUsize::new(1).unwrap())),
             WhenFull::Block,
         );
         let result = builder.build(String::from("test"), Span::none()).await;
@@ -282,7 +282,7 @@ mod tests {
     async fn single_stage_topology_drop_newest() {
         let mut builder = TopologyBuilder::<Sample>::default();
         builder.stage(
-            MemoryBuffer::new(NonZeroUsize::new(1).unwrap()),
+            MemoryBuffer::new(MemoryBufferSize::MaxEvents(NonZeroUsize::new(1).unwrap())),
             WhenFull::DropNewest,
         );
         let result = builder.build(String::from("test"), Span::none()).await;
@@ -296,7 +296,7 @@ mod tests {
     async fn single_stage_topology_overflow() {
         let mut builder = TopologyBuilder::<Sample>::default();
         builder.stage(
-            MemoryBuffer::new(NonZeroUsize::new(1).unwrap()),
+            MemoryBuffer::new(MemoryBufferSize::MaxEvents(NonZeroUsize::new(1).unwrap())),
             WhenFull::Overflow,
         );
         let result = builder.build(String::from("test"), Span::none()).await;
@@ -310,11 +310,11 @@ mod tests {
     async fn two_stage_topology_block() {
         let mut builder = TopologyBuilder::<Sample>::default();
         builder.stage(
-            MemoryBuffer::new(NonZeroUsize::new(1).unwrap()),
+            MemoryBuffer::new(MemoryBufferSize::MaxEvents(NonZeroUsize::new(1).unwrap())),
             WhenFull::Block,
         );
         builder.stage(
-            MemoryBuffer::new(NonZeroUsize::new(1).unwrap()),
+            MemoryBuffer::new(MemoryBufferSize::MaxEvents(NonZeroUsize::new(1).unwrap())),
             WhenFull::Block,
         );
         let result = builder.build(String::from("test"), Span::none()).await;
@@ -328,11 +328,11 @@ mod tests {
     async fn two_stage_topology_drop_newest() {
         let mut builder = TopologyBuilder::<Sample>::default();
         builder.stage(
-            MemoryBuffer::new(NonZeroUsize::new(1).unwrap()),
+            MemoryBuffer::new(MemoryBufferSize::MaxEvents(NonZeroUsize::new(1).unwrap())),
             WhenFull::DropNewest,
         );
         builder.stage(
-            MemoryBuffer::new(NonZeroUsize::new(1).unwrap()),
+            MemoryBuffer::new(MemoryBufferSize::MaxEvents(NonZeroUsize::new(1).unwrap())),
             WhenFull::Block,
         );
         let result = builder.build(String::from("test"), Span::none()).await;
@@ -346,11 +346,11 @@ mod tests {
     async fn two_stage_topology_overflow() {
         let mut builder = TopologyBuilder::<Sample>::default();
         builder.stage(
-            MemoryBuffer::new(NonZeroUsize::new(1).unwrap()),
+            MemoryBuffer::new(MemoryBufferSize::MaxEvents(NonZeroUsize::new(1).unwrap())),
             WhenFull::Overflow,
         );
         builder.stage(
-            MemoryBuffer::new(NonZeroUsize::new(1).unwrap()),
+            MemoryBuffer::new(MemoryBufferSize::MaxEvents(NonZeroUsize::new(1).unwrap())),
             WhenFull::Block,
         );
 
@@ -8,11 +8,11 @@ use std::{
 };
 
 use async_stream::stream;
-use crossbeam_queue::ArrayQueue;
+use crossbeam_queue::{ArrayQueue, SegQueue};
 use futures::Stream;
 use tokio::sync::{Notify, OwnedSemaphorePermit, Semaphore, TryAcquireError};
 
-use crate::Bufferable;
+use crate::{config::MemoryBufferSize, Bufferable};
 
 /// Error returned by `LimitedSender::send` when the receiver has disconnected.
 #[derive(Debug, PartialEq, Eq)]
@@ -54,10 +54,57 @@ impl<T> fmt::Display for TrySendError<T> {
 
 impl<T: fmt::Debug> std::error::Error for TrySendError<T> {}
 
+/// The maximum number of permits a semaphore can hold.
+///
+/// This mirrors `tokio::sync::Semaphore::MAX_PERMITS`, which byte-based limits could otherwise exceed.
+const MAX_PERMITS: usize = usize::MAX >> 3;
+
+/// The storage backing the channel.
+///
+/// When the channel is limited by the number of events, the number of items it can hold is known
+/// upfront, so we can use a fixed-size queue. When it's limited by size, any number of items could
+/// fit, so we use an unbounded queue and rely on the semaphore alone.
+#[derive(Debug)]
+enum Queue<T> {
+    Bounded(ArrayQueue<T>),
+    Unbounded(SegQueue<T>),
+}
+
+impl<T> Queue<T> {
+    fn new(limit: MemoryBufferSize) -> Self {
+        match limit {
+            MemoryBufferSize::MaxEvents(max_events) => {
+                Self::Bounded(ArrayQueue::new(max_events.get()))
+            }
+            MemoryBufferSize::MaxSize(_) => Self::Unbounded(SegQueue::new()),
+        }
+    }
+
+    fn push(&self, item: T) {
+        match self {
+            Self::Bounded(queue) => {
+                let result = queue.push(item);
+                assert!(
+                    result.is_ok(),
+                    "acquired permits but channel reported being full"
+                );
+            }
+            Self::Unbounded(queue) => queue.push(item),
+        }
+    }
+
+    fn pop(&self) -> Option<T> {
+        match self {
+            Self::Bounded(queue) => queue.pop(),
+            Self::Unbounded(queue) => queue.pop(),
+        }
+    }
+}
+
 #[derive(Debug)]
 struct Inner<T> {
-    data: Arc<ArrayQueue<(OwnedSemaphorePermit, T)>>,
-    limit: usize,
+    data: Arc<Queue<(OwnedSemaphorePermit, T)>>,
+    limit: MemoryBufferSize,
     limiter: Arc<Semaphore>,
     read_waker: Arc<Notify>,
 }
@@ -85,10 +132,14 @@ impl<T: Bufferable> LimitedSender<T> {
         // We have to limit the number of permits we ask for to the overall limit since we're always
         // willing to store more items than the limit if the queue is entirely empty, because
         // otherwise we might deadlock ourselves by not being able to send a single item.
-        cmp::min(self.inner.limit, item.event_count()) as u32
+        let (limit, required) = match self.inner.limit {
+            MemoryBufferSize::MaxEvents(max_events) => (max_events.get(), item.event_count()),
+            MemoryBufferSize::MaxSize(max_size) => (max_size.get(), item.size_of()),
+        };
+        cmp::min(cmp::min(limit, MAX_PERMITS), required).min(u32::MAX as usize) as u32
     }
 
-    /// Gets the number of items that this channel could accept.
+    /// Gets the number of events, or bytes if limited by size, that this channel could accept.
     pub fn available_capacity(&self) -> usize {
         self.inner.limiter.available_permits()
     }
@@ -113,10 +164,7 @@ impl<T: Bufferable> LimitedSender<T> {
             Err(_) => return Err(SendError(item)),
         };
 
-        self.inner
-            .data
-            .push((permits, item))
-            .expect("acquired permits but channel reported being full");
+        self.inner.data.push((permits, item));
         self.inner.read_waker.notify_one();
 
         trace!("Sent item.");
@@ -150,10 +198,7 @@ impl<T: Bufferable> LimitedSender<T> {
             }
         };
 
-        self.inner
-            .data
-            .push((permits, item))
-            .expect("acquired permits but channel reported being full");
+        self.inner.data.push((permits, item));
         self.inner.read_waker.notify_one();
 
         trace!("Attempt to send item succeeded.");
@@ -189,7 +234,7 @@ pub struct LimitedReceiver<T> {
 }
 
 impl<T: Send + 'static> LimitedReceiver<T> {
-    /// Gets the number of items that this channel could accept.
+    /// Gets the number of events, or bytes if limited by size, that this channel could accept.
     pub fn available_capacity(&self) -> usize {
         self.inner.limiter.available_permits()
     }
@@ -232,11 +277,15 @@ impl<T> Drop for LimitedReceiver<T> {
     }
 }
 
-pub fn limited<T>(limit: usize) -> (LimitedSender<T>, LimitedReceiver<T>) {
+pub fn limited<T>(limit: MemoryBufferSize) -> (LimitedSender<T>, LimitedReceiver<T>) {
+    let permits = match limit {
+        MemoryBufferSize::MaxEvents(max_events) => max_events.get(),
+        MemoryBufferSize::MaxSize(max_size) => max_size.get(),
+    };
     let inner = Inner {
-        data: Arc::new(ArrayQueue::new(limit)),
+        data: Arc::new(Queue::new(limit)),
         limit,
-        limiter: Arc::new(Semaphore::new(limit)),
+        limiter: Arc::new(Semaphore::new(cmp::min(permits, MAX_PERMITS))),
         read_waker: Arc::new(Notify::new()),
     };
 
@@ -251,17 +300,20 @@ pub fn limited<T>(limit: usize) -> (LimitedSender<T>, LimitedReceiver<T>) {
 
 #[cfg(test)]
 mod tests {
+    use std::num::NonZeroUsize;
+
     use tokio_test::{assert_pending, assert_ready, task::spawn};
+    use vector_common::byte_size_of::ByteSizeOf;
 
     use super::limited;
     use crate::{
-        test::MultiEventRecord, topology::channel::limited_queue::SendError,
-        topology::test_util::Sample,
+        config::MemoryBufferSize, test::MultiEventRecord,
+        topology::channel::limited_queue::SendError, topology::test_util::Sample,
     };
 
     #[tokio::test]
     async fn send_receive() {
-        let (mut tx, mut rx) = limited(2);
+        let (mut tx, mut rx) = limited(MemoryBufferSize::MaxEvents(NonZeroUsize::new(2).unwrap()));
 
         assert_eq!(2, tx.available_capacity());
 
@@ -289,7 +341,7 @@ mod tests {
 
     #[test]
     fn sender_waits_for_more_capacity_when_none_available() {
-        let (mut tx, mut rx) = limited(1);
+        let (mut tx, mut rx) = limited(MemoryBufferSize::MaxEvents(NonZeroUsize::new(1).unwrap()));
 
         assert_eq!(1, tx.available_capacity());
 
@@ -350,7 +402,7 @@ mod tests {
 
     #[test]
     fn sender_waits_for_more_capacity_when_partial_available() {
-        let (mut tx, mut rx) = limited(7);
+        let (mut tx, mut rx) = limited(MemoryBufferSize::MaxEvents(NonZeroUsize::new(7).unwrap()));
 
         assert_eq!(7, tx.available_capacity());
 
@@ -438,7 +490,7 @@ mod tests {
 
     #[test]
     fn empty_receiver_returns_none_when_last_sender_drops() {
-        let (mut tx, mut rx) = limited(1);
+        let (mut tx, mut rx) = limited(MemoryBufferSize::MaxEvents(NonZeroUsize::new(1).unwrap()));
 
         assert_eq!(1, tx.available_capacity());
 
@@ -480,7 +532,8 @@ mod tests {
 
     #[test]
     fn receiver_returns_none_once_empty_when_last_sender_drops() {
-        let (tx, mut rx) = limited::<Sample>(1);
+        let (tx, mut rx) =
+            limited::<Sample>(MemoryBufferSize::MaxEvents(NonZeroUsize::new(1).unwrap()));
 
         assert_eq!(1, tx.available_capacity());
 
@@ -509,7 +562,7 @@ mod tests {
 
     #[test]
     fn oversized_send_allowed_when_empty() {
-        let (mut tx, mut rx) = limited(1);
+        let (mut tx, mut rx) = limited(MemoryBufferSize::MaxEvents(NonZeroUsize::new(1).unwrap()));
 
         assert_eq!(1, tx.available_capacity());
 
@@ -541,7 +594,7 @@ mod tests {
 
     #[test]
     fn oversized_send_allowed_when_partial_capacity() {
-        let (mut tx, mut rx) = limited(2);
+        let (mut tx, mut rx) = limited(MemoryBufferSize::MaxEvents(NonZeroUsize::new(2).unwrap()));
 
         assert_eq!(2, tx.available_capacity());
 
@@ -592,4 +645,33 @@ mod tests {
 
         assert_eq!(2, tx.available_capacity());
     }
+
+    #[test]
+    fn sender_waits_for_more_capacity_when_limited_by_size() {
+        let item_size = Sample(42).size_of();
+        let (mut tx, mut rx) = limited(MemoryBufferSize::MaxSize(
+            NonZeroUsize::new(item_size * 2).unwrap(),
+        ));
+
+        assert_eq!(item_size * 2, tx.available_capacity());
+
+        // The first two sends fit within the byte limit.
+        assert_eq!(Ok(()), assert_ready!(spawn(tx.send(Sample(42))).poll()));
+        assert_eq!(Ok(()), assert_ready!(spawn(tx.send(Sample(43))).poll()));
+        assert_eq!(0, tx.available_capacity());
+
+        // The third one has to wait until an item has been received.
+        let mut send = spawn(tx.send(Sample(44)));
+        assert_pending!(send.poll());
+
+        let mut recv = spawn(async { rx.next().await });
+        assert_eq!(Some(Sample(42)), assert_ready!(recv.poll()));
+        drop(recv);
+
+        assert!(send.is_woken());
+        assert_eq!(Ok(()), assert_ready!(send.poll()));
+        drop(send);
+
+        assert_eq!(0, rx.available_capacity());
+    }
 }
@@ -0,0 +1,189 @@
+use std::{fmt, io};
+
+use aes_gcm::{
+    aead::{generic_array::GenericArray, AeadCore, AeadInPlace, KeyInit, OsRng},
+    Aes256Gcm, Key,
+};
+use snafu::{ResultExt, Snafu};
+
+/// Record payloads are compressed with zstd.
+pub const CODEC_FLAG_ZSTD: u32 = 1 << 0;
+
+/// Record payloads are encrypted with AES-256-GCM.
+///
+/// The encrypted payload is prefixed with its nonce, and suffixed with its authentication tag.
+pub const CODEC_FLAG_AES_256_GCM: u32 = 1 << 1;
+
+/// All codec flags understood by this version of the buffer.
+pub const CODEC_FLAGS_SUPPORTED: u32 = CODEC_FLAG_ZSTD | CODEC_FLAG_AES_256_GCM;
+
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+
+/// Length, in bytes, of the key used to encrypt records.
+pub const ENCRYPTION_KEY_LEN: usize = 32;
+
+/// Error that occurred while compressing/encrypting, or decrypting/decompressing, a record payload.
+#[derive(Debug, Snafu)]
+pub enum CodecError {
+    #[snafu(display("failed to compress record: {}", source))]
+    Compress { source: io::Error },
+
+    #[snafu(display("failed to decompress record: {}", source))]
+    Decompress { source: io::Error },
+
+    #[snafu(display("failed to encrypt record"))]
+    Encrypt,
+
+    /// The record could not be authenticated, which means it was either encrypted with a different
+    /// key or tampered with.
+    #[snafu(display("failed to decrypt record; was the encryption key changed?"))]
+    Decrypt,
+}
+
+/// Record payload codec.
+///
+/// Optionally compresses, and then encrypts, the encoded form of records before they're written to
+/// disk.  Every record in a buffer is transformed the same way: the transformations in use are
+/// stored as flags in the buffer header, which is checked against the configured codec when the
+/// buffer is loaded.
+///
+/// Encryption uses a random nonce for every record, and the record ID as associated data, so that an
+/// encrypted payload can't be passed off as a different record.
+#[derive(Clone, Default)]
+pub struct RecordCodec {
+    compression_level: Option<i32>,
+    cipher: Option<Aes256Gcm>,
+}
+
+impl RecordCodec {
+    /// Compresses records with zstd, at the default compression level.
+    #[must_use]
+    pub fn with_zstd(mut self) -> Self {
+        self.compression_level = Some(zstd::DEFAULT_COMPRESSION_LEVEL);
+        self
+    }
+
+    /// Encrypts records with AES-256-GCM, using the given key.
+    #[must_use]
+    pub fn with_encryption_key(mut self, key: &[u8; ENCRYPTION_KEY_LEN]) -> Self {
+        self.cipher = Some(Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key)));
+        self
+    }
+
+    /// Whether or not record payloads are written as-is.
+    pub fn is_passthrough(&self) -> bool {
+        self.compression_level.is_none() && self.cipher.is_none()
+    }
+
+    /// Gets the flags describing the transformations applied by this codec.
+    ///
+    /// These are stored in the buffer header.
+    pub fn flags(&self) -> u32 {
+        let mut flags = 0;
+        if self.compression_level.is_some() {
+            flags |= CODEC_FLAG_ZSTD;
+        }
+        if self.cipher.is_some() {
+            flags |= CODEC_FLAG_AES_256_GCM;
+        }
+        flags
+    }
+
+    /// Transforms the encoded `payload` of the given record into `out`.
+    ///
+    /// # Errors
+    ///
+    /// If the payload cannot be compressed or encrypted, an error variant will be returned
+    /// describing the error.
+    pub fn encode(
+        &self,
+        record_id: u64,
+        payload: &[u8],
+        out: &mut Vec<u8>,
+    ) -> Result<(), CodecError> {
+        out.clear();
+
+        let nonce = self.cipher.as_ref().map(|_| {
+            let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+            out.extend_from_slice(&nonce);
+            nonce
+        });
+
+        if let Some(level) = self.compression_level {
+            zstd::stream::copy_encode(payload, &mut *out, level).context(CompressSnafu)?;
+        } else {
+            out.extend_from_slice(payload);
+        }
+
+        if let (Some(cipher), Some(nonce)) = (self.cipher.as_ref(), nonce) {
+            let tag = cipher
+                .encrypt_in_place_detached(&nonce, &record_id.to_be_bytes(), &mut out[NONCE_LEN..])
+                .map_err(|_| CodecError::Encrypt)?;
+            out.extend_from_slice(&tag);
+        }
+
+        Ok(())
+    }
+
+    /// Reverses the transformations applied by this codec on the given record payload.
+    ///
+    /// If the codec is a passthrough, `payload` is returned directly.  Otherwise, `buf` is used to
+    /// hold the original payload.
+    ///
+    /// # Errors
+    ///
+    /// If the payload cannot be decrypted or decompressed, an error variant will be returned
+    /// describing the error.
+    pub fn decode<'a>(
+        &self,
+        record_id: u64,
+        payload: &'a [u8],
+        buf: &'a mut Vec<u8>,
+    ) -> Result<&'a [u8], CodecError> {
+        let cipher = match self.cipher.as_ref() {
+            Some(cipher) => cipher,
+            None if self.compression_level.is_some() => {
+                buf.clear();
+                zstd::stream::copy_decode(payload, &mut *buf).context(DecompressSnafu)?;
+                return Ok(buf.as_slice());
+            }
+            None => return Ok(payload),
+        };
+
+        if payload.len() < NONCE_LEN + TAG_LEN {
+            return Err(CodecError::Decrypt);
+        }
+
+        let (nonce, rest) = payload.split_at(NONCE_LEN);
+        let (ciphertext, tag) = rest.split_at(rest.len() - TAG_LEN);
+
+        buf.clear();
+        buf.extend_from_slice(ciphertext);
+        cipher
+            .decrypt_in_place_detached(
+                GenericArray::from_slice(nonce),
+                &record_id.to_be_bytes(),
+                buf,
+                GenericArray::from_slice(tag),
+            )
+            .map_err(|_| CodecError::Decrypt)?;
+
+        if self.compression_level.is_some() {
+            // The decompressed payload goes into `buf`, so move the decrypted one out of the way first.
+            let decrypted = std::mem::take(buf);
+            zstd::stream::copy_decode(decrypted.as_slice(), &mut *buf).context(DecompressSnafu)?;
+        }
+
+        Ok(buf.as_slice())
+    }
+}
+
+impl fmt::Debug for RecordCodec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RecordCodec")
+            .field("compression_level", &self.compression_level)
+            .field("encrypted", &self.cipher.is_some())
+            .finish()
+    }
+}
@@ -7,6 +7,7 @@ use crc32fast::Hasher;
 use snafu::Snafu;
 
 use super::{
+    codec::RecordCodec,
     io::{Filesystem, ProductionFilesystem},
     ledger::LEDGER_LEN,
     record::RECORD_HEADER_LEN,
@@ -153,6 +154,13 @@ pub struct DiskBufferConfig<FS> {
     /// amount of data written since the last flush would be lost.
     pub(crate) flush_interval: Duration,
 
+    /// Codec used to compress and/or encrypt records.
+    ///
+    /// The codec in use is recorded in the buffer header.  It can only be changed between runs once
+    /// the buffer has been fully drained, and the encryption key must not be changed at all while
+    /// unread records remain.
+    pub(crate) codec: RecordCodec,
+
     /// Filesystem implementation for opening data files.
     ///
     /// We allow parameterizing the filesystem implementation for ease of testing.  The "filesystem"
@@ -173,6 +181,7 @@ where
     pub(crate) max_record_size: Option<usize>,
     pub(crate) write_buffer_size: Option<usize>,
     pub(crate) flush_interval: Option<Duration>,
+    pub(crate) codec: RecordCodec,
     pub(crate) filesystem: FS,
 }
 
@@ -188,6 +197,7 @@ impl DiskBufferConfigBuilder {
             max_record_size: None,
             write_buffer_size: None,
             flush_interval: None,
+            codec: RecordCodec::default(),
             filesystem: ProductionFilesystem,
         }
     }
@@ -268,6 +278,19 @@ where
         self
     }
 
+    /// Sets the codec used to compress and/or encrypt records.
+    ///
+    /// The codec in use is recorded in the buffer header.  It can only be changed between runs once
+    /// the buffer has been fully drained, and the encryption key must not be changed at all while
+    /// unread records remain.
+    ///
+    /// Defaults to writing records as-is.
+    #[allow(dead_code)]
+    pub fn codec(mut self, codec: RecordCodec) -> Self {
+        self.codec = codec;
+        self
+    }
+
     /// Filesystem implementation for opening data files.
     ///
     /// We allow parameterizing the filesystem implementation for ease of testing.  The "filesystem"
@@ -287,6 +310,7 @@ where
             max_record_size: self.max_record_size,
             write_buffer_size: self.write_buffer_size,
             flush_interval: self.flush_interval,
+            codec: self.codec,
             filesystem,
         }
     }
@@ -301,6 +325,7 @@ where
         let max_record_size = self.max_record_size.unwrap_or(DEFAULT_MAX_RECORD_SIZE);
         let write_buffer_size = self.write_buffer_size.unwrap_or(DEFAULT_WRITE_BUFFER_SIZE);
         let flush_interval = self.flush_interval.unwrap_or(DEFAULT_FLUSH_INTERVAL);
+        let codec = self.codec;
         let filesystem = self.filesystem;
 
         // Validate the input parameters.
@@ -427,6 +452,7 @@ where
             max_record_size,
             write_buffer_size,
             flush_interval,
+            codec,
             filesystem,
         })
     }
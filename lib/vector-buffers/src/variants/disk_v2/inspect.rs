@@ -0,0 +1,356 @@
+//! Offline tooling for disk buffers.
+//!
+//! These functions are meant to be used on a buffer that is not currently in use by a running
+//! Vector process, in order to see what is in it, move its contents elsewhere, or get it back into a
+//! usable state after data files have been corrupted.  The ledger lock is held for their duration,
+//! so a buffer in use by Vector cannot be opened here, and vice versa.
+use std::{
+    ffi::OsString,
+    fmt, io,
+    path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+use snafu::{ResultExt, Snafu};
+use tokio::fs;
+use vector_common::finalization::{EventStatus, Finalizable};
+
+use super::{
+    common::MAX_FILE_ID,
+    ledger::{Ledger, LedgerLoadCreateError},
+    reader::{ReaderError, RecordReader},
+    Buffer, DiskBufferConfig, Filesystem,
+};
+use crate::{buffer_usage_data::BufferUsageHandle, Bufferable};
+
+/// Error that occurred while inspecting, draining, or repairing a disk buffer.
+#[derive(Debug, Snafu)]
+pub enum InspectError {
+    /// No buffer exists at the given location.
+    #[snafu(display("no disk buffer found at {:?}", data_dir))]
+    NotFound { data_dir: PathBuf },
+
+    /// The ledger could not be loaded.
+    ///
+    /// This is most commonly because the buffer is currently in use by a running Vector process.
+    #[snafu(display("failed to load ledger: {}", source))]
+    Ledger { source: LedgerLoadCreateError },
+
+    /// The buffer could not be opened.
+    #[snafu(display("failed to open buffer: {}", reason))]
+    FailedToOpen { reason: String },
+
+    /// A record could not be written to the repaired buffer.
+    #[snafu(display("failed to write record to repaired buffer: {}", reason))]
+    FailedToWrite { reason: String },
+
+    /// A general I/O error occurred.
+    #[snafu(display("I/O error: {}", source))]
+    Io { source: io::Error },
+}
+
+/// Snapshot of the state held by the ledger of a disk buffer.
+#[derive(Clone, Debug, Serialize)]
+pub struct LedgerStats {
+    /// ID of the next record that will be written.
+    pub writer_next_record_id: u64,
+    /// ID of the data file currently being written to.
+    pub writer_current_data_file_id: u16,
+    /// ID of the last record that was read and acknowledged.
+    pub reader_last_record_id: u64,
+    /// ID of the data file currently being read from.
+    pub reader_current_data_file_id: u16,
+    /// Number of events that have not yet been acknowledged.
+    pub unread_events: u64,
+    /// Total size, in bytes, of all data files on disk.
+    pub data_file_bytes: u64,
+}
+
+/// Data that was skipped because it could not be read.
+#[derive(Clone, Debug, Serialize)]
+pub struct SkippedData {
+    /// ID of the data file the data was skipped in, if known.
+    pub data_file_id: Option<u16>,
+    /// Why the data was skipped.
+    pub reason: String,
+}
+
+/// Summary of the records visited when inspecting, draining, or repairing a disk buffer.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct InspectReport {
+    /// Number of records visited.
+    pub records: u64,
+    /// Number of events contained in the records visited.
+    pub events: u64,
+    /// Data that was skipped because it was corrupted, or could not be decoded.
+    pub skipped: Vec<SkippedData>,
+}
+
+impl InspectReport {
+    fn track_record<T: Bufferable>(&mut self, record: &T) {
+        self.records += 1;
+        self.events += u64::try_from(record.event_count()).unwrap_or(u64::MAX);
+    }
+
+    fn track_skipped<T: Bufferable>(&mut self, data_file_id: Option<u16>, error: &ReaderError<T>) {
+        warn!(?data_file_id, %error, "Skipped unreadable data in buffer.");
+        self.skipped.push(SkippedData {
+            data_file_id,
+            reason: error.to_string(),
+        });
+    }
+}
+
+fn ensure_buffer_exists(data_dir: &Path) -> Result<(), InspectError> {
+    if data_dir.join("buffer.db").exists() {
+        Ok(())
+    } else {
+        Err(InspectError::NotFound {
+            data_dir: data_dir.to_path_buf(),
+        })
+    }
+}
+
+/// Gets a snapshot of the ledger state of the given buffer.
+///
+/// # Errors
+///
+/// If the buffer does not exist, or the ledger cannot be loaded, an error variant will be returned
+/// describing the error.
+pub async fn ledger_stats<FS>(config: DiskBufferConfig<FS>) -> Result<LedgerStats, InspectError>
+where
+    FS: Filesystem + 'static,
+    FS::File: Unpin,
+{
+    ensure_buffer_exists(&config.data_dir)?;
+
+    let ledger = Ledger::load_or_create(config, BufferUsageHandle::noop())
+        .await
+        .context(LedgerSnafu)?;
+    let (reader_file_id, writer_file_id) = ledger.get_current_reader_writer_file_id();
+
+    Ok(LedgerStats {
+        writer_next_record_id: ledger.state().get_next_writer_record_id(),
+        writer_current_data_file_id: writer_file_id,
+        reader_last_record_id: ledger.state().get_last_reader_record_id(),
+        reader_current_data_file_id: reader_file_id,
+        unread_events: ledger.get_total_records(),
+        data_file_bytes: ledger.get_total_buffer_size(),
+    })
+}
+
+/// Visits every unacknowledged record in the given buffer, without modifying it.
+///
+/// Data files are read directly, in order, starting from the data file the reader would start from.
+/// When a corrupted record is encountered, the rest of its data file is skipped, in the same way
+/// that the reader does.  Records which can be read but not decoded are skipped individually.
+///
+/// # Errors
+///
+/// If the buffer does not exist, the ledger cannot be loaded, a data file cannot be read, or `visit`
+/// returns an error, an error variant will be returned describing the error.
+pub async fn dump<T, FS, F>(
+    config: DiskBufferConfig<FS>,
+    mut visit: F,
+) -> Result<InspectReport, InspectError>
+where
+    T: Bufferable,
+    FS: Filesystem + 'static,
+    FS::File: Unpin,
+    F: FnMut(T) -> io::Result<()>,
+{
+    ensure_buffer_exists(&config.data_dir)?;
+
+    let ledger = Ledger::load_or_create(config, BufferUsageHandle::noop())
+        .await
+        .context(LedgerSnafu)?;
+    let last_reader_record_id = ledger.state().get_last_reader_record_id();
+    let (mut data_file_id, writer_file_id) = ledger.get_current_reader_writer_file_id();
+
+    let mut report = InspectReport::default();
+    loop {
+        let data_file_path = ledger.get_data_file_path(data_file_id);
+        match ledger
+            .filesystem()
+            .open_file_readable(&data_file_path)
+            .await
+        {
+            Ok(data_file) => {
+                let mut reader =
+                    RecordReader::<_, T>::new(data_file, ledger.config().codec.clone());
+                loop {
+                    match reader.try_next_record(true).await {
+                        Ok(None) => break,
+                        // Records at or below the last reader record ID have already been
+                        // acknowledged, and only linger because their data file hasn't been
+                        // deleted yet.
+                        Ok(Some(token)) if token.record_id() <= last_reader_record_id => {}
+                        Ok(Some(token)) => match reader.read_record(token) {
+                            Ok(record) => {
+                                report.track_record(&record);
+                                visit(record).context(IoSnafu)?;
+                            }
+                            Err(e) => report.track_skipped(Some(data_file_id), &e),
+                        },
+                        Err(ReaderError::Io { source }) => return Err(InspectError::Io { source }),
+                        Err(e) => {
+                            report.track_skipped(Some(data_file_id), &e);
+                            if e.is_bad_read() {
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+            // The writer may not have created its current data file yet.
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(source) => return Err(InspectError::Io { source }),
+        }
+
+        if data_file_id == writer_file_id {
+            break;
+        }
+        data_file_id = (data_file_id + 1) % MAX_FILE_ID;
+    }
+
+    Ok(report)
+}
+
+/// Reads, and acknowledges, every record in the given buffer.
+///
+/// Each record is handed to `visit`, and is acknowledged -- and eventually deleted from the buffer --
+/// once `visit` has returned.  Corrupted records, and records which cannot be decoded, are skipped
+/// and included in the report.
+///
+/// # Errors
+///
+/// If the buffer does not exist or cannot be opened, an I/O error occurs while reading, or `visit`
+/// returns an error, an error variant will be returned describing the error.
+pub async fn drain<T, FS, F>(
+    config: DiskBufferConfig<FS>,
+    mut visit: F,
+) -> Result<InspectReport, InspectError>
+where
+    T: Bufferable + Finalizable,
+    FS: Filesystem + fmt::Debug + Clone + 'static,
+    FS::File: Unpin,
+    F: FnMut(T) -> io::Result<()>,
+{
+    ensure_buffer_exists(&config.data_dir)?;
+
+    let (writer, mut reader) = Buffer::<T>::from_config(config, BufferUsageHandle::noop())
+        .await
+        .map_err(|e| InspectError::FailedToOpen {
+            reason: e.to_string(),
+        })?;
+
+    // Nothing else will be writing to the buffer, so close the writer so that the reader knows to
+    // stop once it has read everything.
+    drop(writer);
+
+    let mut report = InspectReport::default();
+    loop {
+        match reader.next().await {
+            Ok(Some(mut record)) => {
+                report.track_record(&record);
+
+                // `visit` may hold on to the record, so acknowledge it ourselves rather than waiting
+                // for it to be dropped.
+                let finalizers = record.take_finalizers();
+                visit(record).context(IoSnafu)?;
+                finalizers.update_status(EventStatus::Delivered);
+            }
+            Ok(None) => break,
+            Err(ReaderError::Io { source }) => return Err(InspectError::Io { source }),
+            Err(e) => report.track_skipped(None, &e),
+        }
+    }
+
+    Ok(report)
+}
+
+/// Repairs the given buffer by skipping past any corrupted data.
+///
+/// Every readable record is copied, in order, to a new buffer, which then replaces the given buffer.
+/// Corrupted records, and records which cannot be decoded, are skipped and included in the report.
+///
+/// # Errors
+///
+/// If the buffer does not exist or cannot be opened, a previous repair was left unfinished, or an
+/// error occurs while copying records, an error variant will be returned describing the error.  The
+/// given buffer is only replaced once all records have been copied successfully.
+pub async fn repair<T, FS>(config: DiskBufferConfig<FS>) -> Result<InspectReport, InspectError>
+where
+    T: Bufferable,
+    FS: Filesystem + fmt::Debug + Clone + 'static,
+    FS::File: Unpin,
+{
+    ensure_buffer_exists(&config.data_dir)?;
+
+    let data_dir = config.data_dir.clone();
+    let mut repaired_dir_name = data_dir
+        .file_name()
+        .map_or_else(OsString::new, ToOwned::to_owned);
+    repaired_dir_name.push(".repair");
+    let repaired_dir = data_dir.with_file_name(repaired_dir_name);
+    if repaired_dir.exists() {
+        return Err(InspectError::Io {
+            source: io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!(
+                    "{:?} already exists; remove it before trying again",
+                    repaired_dir
+                ),
+            ),
+        });
+    }
+
+    // The repaired buffer can't be drained while we're writing to it, so it can't be limited in size
+    // like the original buffer is.
+    let mut repaired_config = config.clone();
+    repaired_config.data_dir = repaired_dir.clone();
+    repaired_config.max_buffer_size = u64::MAX;
+
+    let (writer, mut reader) = Buffer::<T>::from_config(config, BufferUsageHandle::noop())
+        .await
+        .map_err(|e| InspectError::FailedToOpen {
+            reason: e.to_string(),
+        })?;
+    drop(writer);
+
+    let (mut repaired_writer, repaired_reader) =
+        Buffer::<T>::from_config(repaired_config, BufferUsageHandle::noop())
+            .await
+            .map_err(|e| InspectError::FailedToOpen {
+                reason: e.to_string(),
+            })?;
+
+    let mut report = InspectReport::default();
+    loop {
+        match reader.next().await {
+            Ok(Some(record)) => {
+                report.track_record(&record);
+                repaired_writer.write_record(record).await.map_err(|e| {
+                    InspectError::FailedToWrite {
+                        reason: e.to_string(),
+                    }
+                })?;
+            }
+            Ok(None) => break,
+            Err(ReaderError::Io { source }) => return Err(InspectError::Io { source }),
+            Err(e) => report.track_skipped(None, &e),
+        }
+    }
+
+    repaired_writer.flush().await.context(IoSnafu)?;
+    drop(repaired_writer);
+    drop(repaired_reader);
+    drop(reader);
+
+    fs::remove_dir_all(&data_dir).await.context(IoSnafu)?;
+    fs::rename(&repaired_dir, &data_dir)
+        .await
+        .context(IoSnafu)?;
+
+    Ok(report)
+}
@@ -13,11 +13,16 @@ use fslock::LockFile;
 use futures::StreamExt;
 use rkyv::{with::Atomic, Archive, Serialize};
 use snafu::{ResultExt, Snafu};
-use tokio::{fs, io::AsyncWriteExt, sync::Notify};
+use tokio::{
+    fs,
+    io::{AsyncReadExt, AsyncWriteExt},
+    sync::Notify,
+};
 use vector_common::{finalizer::OrderedFinalizer, shutdown::ShutdownSignal};
 
 use super::{
     backed_archive::BackedArchive,
+    codec::CODEC_FLAGS_SUPPORTED,
     common::{align16, DiskBufferConfig, MAX_FILE_ID},
     io::{AsyncFile, WritableMemoryMap},
     ser::SerializeError,
@@ -27,6 +32,15 @@ use crate::buffer_usage_data::BufferUsageHandle;
 
 pub const LEDGER_LEN: usize = align16(mem::size_of::<ArchivedLedgerState>());
 
+/// Current version of the buffer format, as recorded in the buffer header.
+///
+/// Buffers created before the buffer header was introduced have no header, and are treated as
+/// version 0, with no codec flags set.
+pub const BUFFER_FORMAT_VERSION: u32 = 1;
+
+const BUFFER_HEADER_MAGIC: [u8; 4] = *b"VBUF";
+const BUFFER_HEADER_LEN: usize = 12;
+
 /// Error that occurred during calls to [`Ledger`].
 #[derive(Debug, Snafu)]
 pub enum LedgerLoadCreateError {
@@ -70,6 +84,105 @@ pub enum LedgerLoadCreateError {
     /// buffers required for the serialization step.
     #[snafu(display("failed to serialize ledger to buffer: {}", reason))]
     FailedToSerialize { reason: String },
+
+    /// The buffer header was invalid, or written by a newer version of Vector.
+    ///
+    /// This should only occur if the header file was modified or truncated outside of the Vector
+    /// process, or if the buffer was last used by a newer version of Vector that supports buffer
+    /// formats or codecs that this version does not.
+    #[snafu(display("unsupported buffer header: {}", reason))]
+    UnsupportedHeader { reason: String },
+
+    /// The configured codec does not match the codec the buffer was written with.
+    ///
+    /// Records are compressed and/or encrypted as configured when they're written, and the codec in
+    /// use is recorded in the buffer header.  The codec can only be changed once the buffer has been
+    /// fully drained, as otherwise existing records could not be read back.
+    #[snafu(display(
+        "buffer was written with codec flags {:#x}, but is configured with codec flags {:#x}; the buffer must be drained with its previous compression and encryption settings before they can be changed",
+        stored,
+        configured
+    ))]
+    CodecMismatch { stored: u32, configured: u32 },
+}
+
+/// Buffer header.
+///
+/// Records the version of the buffer format, and the codec flags describing how the record payloads
+/// in the data files were compressed and/or encrypted.  As the layout of the ledger state can't be
+/// changed, this is stored in its own file, next to the ledger.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct BufferHeader {
+    version: u32,
+    codec_flags: u32,
+}
+
+impl BufferHeader {
+    /// Creates a header for the current buffer format version, with the given codec flags.
+    pub const fn new(codec_flags: u32) -> Self {
+        Self {
+            version: BUFFER_FORMAT_VERSION,
+            codec_flags,
+        }
+    }
+
+    /// Gets the header of a buffer created before the buffer header was introduced.
+    const fn legacy() -> Self {
+        Self {
+            version: 0,
+            codec_flags: 0,
+        }
+    }
+
+    /// Gets the buffer format version.
+    pub const fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Gets the codec flags.
+    pub const fn codec_flags(&self) -> u32 {
+        self.codec_flags
+    }
+
+    fn to_bytes(self) -> [u8; BUFFER_HEADER_LEN] {
+        let mut buf = [0; BUFFER_HEADER_LEN];
+        buf[..4].copy_from_slice(&BUFFER_HEADER_MAGIC);
+        buf[4..8].copy_from_slice(&self.version.to_le_bytes());
+        buf[8..].copy_from_slice(&self.codec_flags.to_le_bytes());
+        buf
+    }
+
+    fn from_bytes(buf: &[u8]) -> Result<Self, LedgerLoadCreateError> {
+        if buf.len() != BUFFER_HEADER_LEN || buf[..4] != BUFFER_HEADER_MAGIC {
+            return Err(LedgerLoadCreateError::UnsupportedHeader {
+                reason: "header is corrupted".to_string(),
+            });
+        }
+
+        let mut version = [0; 4];
+        version.copy_from_slice(&buf[4..8]);
+        let mut codec_flags = [0; 4];
+        codec_flags.copy_from_slice(&buf[8..]);
+        let header = Self {
+            version: u32::from_le_bytes(version),
+            codec_flags: u32::from_le_bytes(codec_flags),
+        };
+
+        if header.version > BUFFER_FORMAT_VERSION {
+            return Err(LedgerLoadCreateError::UnsupportedHeader {
+                reason: format!("unknown buffer format version {}", header.version),
+            });
+        }
+
+        let unsupported = header.codec_flags & !CODEC_FLAGS_SUPPORTED;
+        if unsupported != 0 {
+            return Err(LedgerLoadCreateError::UnsupportedHeader {
+                reason: format!("unknown codec flags {:#x}", unsupported),
+            });
+        }
+
+        Ok(header)
+    }
 }
 
 /// Ledger state.
@@ -171,6 +284,16 @@ impl ArchivedLedgerState {
             .fetch_add(amount, Ordering::AcqRel);
     }
 
+    /// Resets the state to that of a newly-created buffer.
+    ///
+    /// This must only be done when the buffer is empty, and all of its data files have been removed.
+    fn reset(&self) {
+        self.writer_next_record_id.store(1, Ordering::Release);
+        self.writer_current_data_file_id.store(0, Ordering::Release);
+        self.reader_current_data_file_id.store(0, Ordering::Release);
+        self.reader_last_record_id.store(0, Ordering::Release);
+    }
+
     #[cfg(test)]
     pub unsafe fn unsafe_set_writer_next_record_id(&self, id: u64) {
         // UNSAFETY:
@@ -216,6 +339,8 @@ where
     ledger_lock: LockFile,
     // Ledger state.
     state: BackedArchive<FS::MutableMemoryMap, LedgerState>,
+    // Buffer header.
+    header: BufferHeader,
     // The total size, in bytes, of all unread records in the buffer.
     total_buffer_size: AtomicU64,
     // Notifier for reader-related progress.
@@ -255,6 +380,11 @@ where
         self.state.get_archive_ref()
     }
 
+    /// Gets the buffer header.
+    pub fn header(&self) -> BufferHeader {
+        self.header
+    }
+
     /// Gets the total number of unread records in the buffer.
     ///
     /// This number is based on acknowledged reads only, which is to say that if 10 records are
@@ -593,7 +723,8 @@ where
         // then serialize and write to the file, before trying to load it as a memory-mapped file.
         let ledger_metadata = ledger_handle.metadata().await.context(IoSnafu)?;
         let ledger_len = ledger_metadata.len();
-        if ledger_len == 0 {
+        let ledger_created = ledger_len == 0;
+        if ledger_created {
             debug!("Ledger file empty.  Initializing with default ledger state.");
             let mut buf = BytesMut::new();
             loop {
@@ -642,6 +773,7 @@ where
             config,
             ledger_lock,
             state: ledger_state,
+            header: BufferHeader::legacy(),
             total_buffer_size: AtomicU64::new(0),
             reader_notify: Notify::new(),
             writer_notify: Notify::new(),
@@ -651,11 +783,108 @@ where
             last_flush: AtomicCell::new(Instant::now()),
             usage_handle,
         };
+        ledger.load_or_create_header(ledger_created).await?;
         ledger.update_buffer_size().await?;
 
         Ok(ledger)
     }
 
+    async fn load_or_create_header(
+        &mut self,
+        ledger_created: bool,
+    ) -> Result<(), LedgerLoadCreateError> {
+        let header_path = self.config.data_dir.join("buffer.header");
+        let configured = BufferHeader::new(self.config.codec.flags());
+
+        // Buffers created before the header existed have a ledger, but no header.  Their records
+        // were never compressed or encrypted.
+        let stored = match self
+            .config
+            .filesystem
+            .open_file_readable(&header_path)
+            .await
+        {
+            Ok(mut header_handle) => {
+                let mut buf = Vec::with_capacity(BUFFER_HEADER_LEN);
+                header_handle.read_to_end(&mut buf).await.context(IoSnafu)?;
+                Some(BufferHeader::from_bytes(&buf)?)
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => return Err(LedgerLoadCreateError::Io { source: e }),
+        };
+        let stored = match stored {
+            Some(header) => header,
+            None if ledger_created => configured,
+            None => BufferHeader::legacy(),
+        };
+
+        if stored.codec_flags != configured.codec_flags {
+            // Records written with one codec can't be read with another, so the codec can only be
+            // changed once every record has been read.  The data files left behind may still
+            // contain records that were already read, which neither the reader nor the writer could
+            // decode when catching up to where they left off, so we remove them and reset the
+            // ledger as if the buffer was newly created.
+            let stored_flags = stored.codec_flags;
+            let configured_flags = configured.codec_flags;
+            if self.get_total_records() != 0 {
+                return Err(LedgerLoadCreateError::CodecMismatch {
+                    stored: stored_flags,
+                    configured: configured_flags,
+                });
+            }
+
+            debug!(
+                stored_flags,
+                configured_flags, "Buffer is empty. Resetting buffer to change codec."
+            );
+
+            let (reader_file_id, writer_file_id) = self.get_current_reader_writer_file_id();
+            let mut file_id = reader_file_id;
+            loop {
+                let data_file_path = self.get_data_file_path(file_id);
+                match self.config.filesystem.delete_file(&data_file_path).await {
+                    Ok(()) => {}
+                    Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                    Err(e) => return Err(LedgerLoadCreateError::Io { source: e }),
+                }
+
+                if file_id == writer_file_id {
+                    break;
+                }
+                file_id = (file_id + 1) % MAX_FILE_ID;
+            }
+
+            self.state().reset();
+            self.flush().context(IoSnafu)?;
+        }
+
+        // Write out the header if it doesn't exist yet, or doesn't match the current version of the
+        // buffer format and the configured codec.
+        if stored != configured || ledger_created {
+            match self.config.filesystem.delete_file(&header_path).await {
+                Ok(()) => {}
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                Err(e) => return Err(LedgerLoadCreateError::Io { source: e }),
+            }
+
+            let mut header_handle = self
+                .config
+                .filesystem
+                .open_file_writable_atomic(&header_path)
+                .await
+                .context(IoSnafu)?;
+            header_handle
+                .write_all(&configured.to_bytes())
+                .await
+                .context(IoSnafu)?;
+            header_handle.sync_all().await.context(IoSnafu)?;
+        }
+
+        self.header = configured;
+
+        Ok(())
+    }
+
     async fn update_buffer_size(&mut self) -> Result<(), LedgerLoadCreateError> {
         // Under normal operation, the reader and writer maintain a consistent state within the
         // ledger.  However, due to the nature of how we update the ledger, process crashes could
@@ -724,6 +953,7 @@ where
         f.debug_struct("Ledger")
             .field("config", &self.config)
             .field("state", &self.state.get_archive_ref())
+            .field("header", &self.header)
             .field(
                 "total_buffer_size",
                 &self.total_buffer_size.load(Ordering::Acquire),
@@ -149,7 +149,9 @@ use snafu::{ResultExt, Snafu};
 use vector_common::finalization::Finalizable;
 
 mod backed_archive;
+mod codec;
 mod common;
+pub mod inspect;
 mod io;
 mod ledger;
 mod reader;
@@ -162,7 +164,8 @@ mod writer;
 mod tests;
 
 pub use self::{
-    common::{DiskBufferConfig, DiskBufferConfigBuilder},
+    codec::{CodecError, RecordCodec, ENCRYPTION_KEY_LEN},
+    common::{BuildError, DiskBufferConfig, DiskBufferConfigBuilder},
     io::{Filesystem, ProductionFilesystem},
     ledger::LedgerLoadCreateError,
     reader::{Reader, ReaderError},
@@ -268,6 +271,7 @@ pub struct DiskV2Buffer {
     id: String,
     data_dir: PathBuf,
     max_size: NonZeroU64,
+    codec: RecordCodec,
 }
 
 impl DiskV2Buffer {
@@ -276,8 +280,16 @@ impl DiskV2Buffer {
             id,
             data_dir,
             max_size,
+            codec: RecordCodec::default(),
         }
     }
+
+    /// Sets the codec used to compress and/or encrypt records.
+    #[must_use]
+    pub fn with_codec(mut self, codec: RecordCodec) -> Self {
+        self.codec = codec;
+        self
+    }
 }
 
 #[async_trait]
@@ -295,7 +307,7 @@ where
     ) -> Result<(SenderAdapter<T>, ReceiverAdapter<T>), Box<dyn Error + Send + Sync>> {
         // Attempt to migrate a disk v1 buffer based on the same data directory and buffer ID if one
         // exists. If one doesn't exist, then this method does nothing.
-        try_disk_v1_migration::<T>(self.data_dir.as_path(), self.id.as_str()).await?;
+        try_disk_v1_migration::<T>(self.data_dir.as_path(), self.id.as_str(), &self.codec).await?;
 
         // Now that we've handled any necessary migrations, go ahead and build the buffer.
         let (writer, reader) = build_disk_v2_buffer(
@@ -303,6 +315,7 @@ where
             &self.data_dir,
             self.id.as_str(),
             self.max_size,
+            self.codec,
         )
         .await?;
 
@@ -315,6 +328,7 @@ async fn build_disk_v2_buffer<T>(
     data_dir: &Path,
     id: &str,
     max_size: NonZeroU64,
+    codec: RecordCodec,
 ) -> Result<
     (
         Writer<T, ProductionFilesystem>,
@@ -327,15 +341,26 @@ where
 {
     usage_handle.set_buffer_limits(Some(max_size.get()), None);
 
-    let buffer_path = get_disk_v2_data_dir_path(data_dir, id);
-    let config = DiskBufferConfigBuilder::from_path(buffer_path)
-        .max_buffer_size(max_size.get())
-        .build()?;
+    let config = build_disk_v2_config(data_dir, id, max_size, codec)?;
     Buffer::from_config(config, usage_handle)
         .await
         .map_err(Into::into)
 }
 
+/// Builds the configuration for the disk buffer with the given ID.
+pub(crate) fn build_disk_v2_config(
+    data_dir: &Path,
+    id: &str,
+    max_size: NonZeroU64,
+    codec: RecordCodec,
+) -> Result<DiskBufferConfig<ProductionFilesystem>, BuildError> {
+    let buffer_path = get_disk_v2_data_dir_path(data_dir, id);
+    DiskBufferConfigBuilder::from_path(buffer_path)
+        .max_buffer_size(max_size.get())
+        .codec(codec)
+        .build()
+}
+
 pub(crate) fn get_disk_v2_data_dir_path(base_dir: &Path, buffer_id: &str) -> PathBuf {
     base_dir.join("buffer").join("v2").join(buffer_id)
 }
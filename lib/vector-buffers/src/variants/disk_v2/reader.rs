@@ -14,6 +14,7 @@ use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
 use vector_common::{finalization::BatchNotifier, finalizer::OrderedFinalizer};
 
 use super::{
+    codec::{CodecError, RecordCodec},
     common::create_crc32c_hasher,
     ledger::Ledger,
     record::{validate_record_archive, ArchivedRecord, Record, RecordStatus},
@@ -108,6 +109,14 @@ where
     #[snafu(display("record version not compatible: {}", reason))]
     Incompatible { reason: String },
 
+    /// The record could not be decrypted or decompressed.
+    ///
+    /// At this stage, the record can be assumed to have been written correctly, and read correctly
+    /// from disk, as the checksum was also validated.  This typically means the record was
+    /// encrypted, and the encryption key has since been changed.
+    #[snafu(display("failed to decrypt or decompress record: {}", source))]
+    Transform { source: CodecError },
+
     /// The reader detected that a data file contains a partially-written record.
     ///
     /// Records should never be partially written to a data file (we don't split records across data
@@ -129,7 +138,7 @@ impl<T> ReaderError<T>
 where
     T: Bufferable,
 {
-    fn is_bad_read(&self) -> bool {
+    pub(super) fn is_bad_read(&self) -> bool {
         matches!(
             self,
             ReaderError::Checksum { .. }
@@ -145,6 +154,7 @@ where
             ReaderError::Checksum { .. } => "checksum_mismatch",
             ReaderError::Decode { .. } => "decode_failed",
             ReaderError::Incompatible { .. } => "incompatible_record_version",
+            ReaderError::Transform { .. } => "transform_failed",
             ReaderError::PartialWrite => "partial_write",
             ReaderError::EmptyRecord => "empty_record",
         }
@@ -160,6 +170,7 @@ where
             | ReaderError::Checksum { .. }
             | ReaderError::Decode { .. }
             | ReaderError::Incompatible { .. }
+            | ReaderError::Transform { .. }
             | ReaderError::PartialWrite => Some(BufferReadError { error_code, error }),
         }
     }
@@ -199,6 +210,8 @@ pub(super) struct RecordReader<R, T> {
     reader: BufReader<R>,
     aligned_buf: AlignedVec,
     checksummer: Hasher,
+    codec: RecordCodec,
+    codec_buf: Vec<u8>,
     current_record_id: u64,
     _t: PhantomData<T>,
 }
@@ -212,11 +225,13 @@ where
     ///
     /// Internally, the reader is wrapped in a [`BufReader`], so callers should not pass in an
     /// already buffered reader.
-    pub fn new(reader: R) -> Self {
+    pub fn new(reader: R, codec: RecordCodec) -> Self {
         Self {
             reader: BufReader::with_capacity(256 * 1024, reader),
             aligned_buf: AlignedVec::new(),
             checksummer: create_crc32c_hasher(),
+            codec,
+            codec_buf: Vec::new(),
             current_record_id: 0,
             _t: PhantomData,
         }
@@ -376,7 +391,7 @@ where
         // - `try_next_record` does all the archive checks, checksum validation, etc
         let record = unsafe { archived_root::<Record<'_>>(&self.aligned_buf) };
 
-        decode_record_payload(record)
+        decode_record_payload(record, &self.codec, &mut self.codec_buf)
     }
 }
 
@@ -389,6 +404,7 @@ where
             .field("reader", &self.reader)
             .field("aligned_buf", &self.aligned_buf)
             .field("checksummer", &self.checksummer)
+            .field("codec", &self.codec)
             .field("current_record_id", &self.current_record_id)
             .finish()
     }
@@ -787,7 +803,10 @@ where
                 "Opened data file for reading."
             );
 
-            self.reader = Some(RecordReader::new(data_file));
+            self.reader = Some(RecordReader::new(
+                data_file,
+                self.ledger.config().codec.clone(),
+            ));
             return Ok(());
         }
     }
@@ -852,7 +871,8 @@ where
                 } => {
                     let record = try_as_record_archive(data_file_mmap.as_ref())
                         .expect("record was already validated");
-                    let item = match decode_record_payload::<T>(record) {
+                    let codec = &self.ledger.config().codec;
+                    let item = match decode_record_payload::<T>(record, codec, &mut Vec::new()) {
                         Ok(item) => item,
                         // If there's an error decoding the item, just fall back to the slow path,
                         // because this file might actually be where we left off, so we don't want
@@ -1104,6 +1124,8 @@ where
 
 pub(crate) fn decode_record_payload<T: Bufferable>(
     record: &ArchivedRecord<'_>,
+    codec: &RecordCodec,
+    codec_buf: &mut Vec<u8>,
 ) -> Result<T, ReaderError<T>> {
     // Try and convert the raw record metadata into the true metadata type used by `T`, and then
     // also verify that `T` is able to decode records with the metadata used for this record in particular.
@@ -1120,6 +1142,9 @@ pub(crate) fn decode_record_payload<T: Bufferable>(
         });
     }
 
-    // Now we can finally try decoding.
-    T::decode(metadata, record.payload()).context(DecodeSnafu)
+    // Now we can finally try decoding, once the payload is decrypted and/or decompressed.
+    let payload = codec
+        .decode(record.id(), record.payload(), codec_buf)
+        .context(TransformSnafu)?;
+    T::decode(metadata, payload).context(DecodeSnafu)
 }
@@ -0,0 +1,173 @@
+use std::{io::SeekFrom, path::Path};
+
+use tokio::{
+    fs::OpenOptions,
+    io::{AsyncSeekExt, AsyncWriteExt},
+};
+
+use super::{
+    create_buffer_v2_with_max_data_file_size, create_default_buffer_v2,
+    get_minimum_data_file_size_for_record_payload,
+};
+use crate::{
+    test::{with_temp_dir, SizedRecord},
+    variants::disk_v2::{
+        inspect::{self, InspectError},
+        DiskBufferConfig, DiskBufferConfigBuilder, ProductionFilesystem,
+    },
+};
+
+fn default_config(data_dir: &Path) -> DiskBufferConfig<ProductionFilesystem> {
+    DiskBufferConfigBuilder::from_path(data_dir)
+        .build()
+        .expect("creating buffer config should not fail")
+}
+
+async fn write_records(data_dir: &Path, records: Vec<SizedRecord>) {
+    let (mut writer, _, ledger) = create_default_buffer_v2(data_dir).await;
+    for record in records {
+        writer
+            .write_record(record)
+            .await
+            .expect("write should not fail");
+    }
+    writer.flush().await.expect("flush should not fail");
+    ledger.flush().expect("flush should not fail");
+
+    // Give the dropped buffer a chance to release its ledger lock before it's reopened.
+    drop(writer);
+    drop(ledger);
+    tokio::task::yield_now().await;
+}
+
+async fn dump_records(data_dir: &Path) -> Vec<SizedRecord> {
+    let mut records = Vec::new();
+    let report = inspect::dump(default_config(data_dir), |record: SizedRecord| {
+        records.push(record);
+        Ok(())
+    })
+    .await
+    .expect("dump should not fail");
+    assert!(report.skipped.is_empty());
+    assert_eq!(report.records, records.len() as u64);
+    records
+}
+
+#[tokio::test]
+async fn dump_does_not_consume_records() {
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            let expected_items = (512..522).map(SizedRecord::new).collect::<Vec<_>>();
+            write_records(&data_dir, expected_items.clone()).await;
+
+            assert_eq!(dump_records(&data_dir).await, expected_items);
+            assert_eq!(dump_records(&data_dir).await, expected_items);
+
+            let stats = inspect::ledger_stats(default_config(&data_dir))
+                .await
+                .expect("loading ledger stats should not fail");
+            assert_eq!(stats.unread_events, 10);
+            assert_eq!(stats.writer_next_record_id, 11);
+            assert_eq!(stats.reader_last_record_id, 0);
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn drain_consumes_records() {
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            let expected_items = (512..522).map(SizedRecord::new).collect::<Vec<_>>();
+            write_records(&data_dir, expected_items.clone()).await;
+
+            let mut items = Vec::new();
+            let report = inspect::drain(default_config(&data_dir), |record: SizedRecord| {
+                items.push(record);
+                Ok(())
+            })
+            .await
+            .expect("drain should not fail");
+            tokio::task::yield_now().await;
+            assert_eq!(report.records, 10);
+            assert_eq!(report.events, 10);
+            assert!(report.skipped.is_empty());
+            assert_eq!(items, expected_items);
+
+            assert!(dump_records(&data_dir).await.is_empty());
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn repair_keeps_readable_records() {
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            // Write each record to its own data file, so that corrupting the first one leaves the
+            // others readable.
+            let record = SizedRecord::new(64);
+            let max_data_file_size = get_minimum_data_file_size_for_record_payload(&record);
+            let (mut writer, _, ledger) =
+                create_buffer_v2_with_max_data_file_size(&data_dir, max_data_file_size).await;
+            for _ in 0..3 {
+                writer
+                    .write_record(record.clone())
+                    .await
+                    .expect("write should not fail");
+                writer.flush().await.expect("flush should not fail");
+            }
+            ledger.flush().expect("flush should not fail");
+            let first_data_file_path = ledger.get_data_file_path(0);
+            drop(writer);
+            drop(ledger);
+
+            // Scramble the end of the first record, but not its length delimiter, so that it can't
+            // be deserialized.
+            let mut data_file = OpenOptions::new()
+                .write(true)
+                .open(&first_data_file_path)
+                .await
+                .expect("open should not fail");
+            data_file
+                .seek(SeekFrom::End(-8))
+                .await
+                .expect("seek should not fail");
+            data_file
+                .write_all(&[0xd, 0xe, 0xa, 0xd, 0xb, 0xe, 0xe, 0xf])
+                .await
+                .expect("write should not fail");
+            data_file.sync_all().await.expect("sync should not fail");
+            drop(data_file);
+
+            let report = inspect::repair::<SizedRecord, _>(default_config(&data_dir))
+                .await
+                .expect("repair should not fail");
+            assert_eq!(report.records, 2);
+            assert!(!report.skipped.is_empty());
+
+            assert_eq!(dump_records(&data_dir).await, vec![record; 2]);
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn missing_buffer_is_not_created() {
+    with_temp_dir(|dir| {
+        let data_dir = dir.join("missing");
+
+        async move {
+            let result = inspect::ledger_stats(default_config(&data_dir)).await;
+            assert!(matches!(result, Err(InspectError::NotFound { .. })));
+            assert!(!data_dir.exists());
+        }
+    })
+    .await;
+}
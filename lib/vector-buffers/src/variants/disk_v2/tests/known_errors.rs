@@ -11,15 +11,22 @@ use tokio::{
 };
 use tracing::Instrument;
 use vector_common::byte_size_of::ByteSizeOf;
-use vector_common::finalization::{AddBatchNotifier, BatchNotifier};
+use vector_common::finalization::{AddBatchNotifier, BatchNotifier, Finalizable};
 
-use super::{create_buffer_v2_with_max_data_file_size, create_default_buffer_v2};
+use super::{
+    create_buffer_v2_with_codec, create_buffer_v2_with_max_data_file_size,
+    create_default_buffer_v2, read_next, read_next_some,
+};
 use crate::{
-    assert_buffer_size, assert_enough_bytes_written, assert_file_does_not_exist_async,
-    assert_file_exists_async, assert_reader_writer_v2_file_positions, await_timeout,
+    assert_buffer_is_empty, assert_buffer_size, assert_enough_bytes_written,
+    assert_file_does_not_exist_async, assert_file_exists_async,
+    assert_reader_writer_v2_file_positions, await_timeout,
     encoding::{AsMetadata, Encodable},
     test::{acknowledge, install_tracing_helpers, with_temp_dir, SizedRecord, UndecodableRecord},
-    variants::disk_v2::{backed_archive::BackedArchive, record::Record, ReaderError},
+    variants::disk_v2::{
+        backed_archive::BackedArchive, ledger::BUFFER_FORMAT_VERSION, record::Record, BufferError,
+        LedgerLoadCreateError, ReaderError, RecordCodec,
+    },
     EventCount,
 };
 
@@ -819,3 +826,75 @@ async fn reader_throws_error_when_record_is_undecodable_via_metadata() {
     })
     .await;
 }
+
+#[tokio::test]
+async fn buffer_rejects_codec_change_until_drained() {
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            // Create a regular buffer, which writes records as-is, and write a record to it.
+            let (mut writer, _, ledger) = create_default_buffer_v2(data_dir.clone()).await;
+            assert_eq!(ledger.header().version(), BUFFER_FORMAT_VERSION);
+            assert_eq!(ledger.header().codec_flags(), 0);
+
+            writer
+                .write_record(SizedRecord::new(64))
+                .await
+                .expect("write should not fail");
+            writer.flush().await.expect("flush should not fail");
+            drop(writer);
+            drop(ledger);
+
+            // Reopening the buffer with compression enabled should fail, as the record we wrote
+            // couldn't be read back.
+            let codec = RecordCodec::default().with_zstd();
+            let result =
+                create_buffer_v2_with_codec::<_, SizedRecord>(data_dir.clone(), codec.clone())
+                    .await;
+            match result {
+                Err(BufferError::LedgerError {
+                    source: LedgerLoadCreateError::CodecMismatch { stored, configured },
+                }) => {
+                    assert_eq!(stored, 0);
+                    assert_eq!(configured, codec.flags());
+                }
+                _ => panic!("buffer should not load with a different codec"),
+            }
+
+            // Drain the buffer with the codec it was written with.
+            let (mut writer, mut reader, ledger) =
+                create_default_buffer_v2::<_, SizedRecord>(data_dir.clone()).await;
+            writer.close();
+            let mut record = read_next_some(&mut reader).await;
+            assert_eq!(record, SizedRecord::new(64));
+            acknowledge(record.take_finalizers()).await;
+            assert_eq!(read_next(&mut reader).await, None);
+            assert_buffer_is_empty!(ledger);
+            drop(writer);
+            drop(reader);
+            drop(ledger);
+
+            // Let the acknowledgement task notice the reader is gone, so that it releases the ledger.
+            tokio::task::yield_now().await;
+
+            // Now the codec can be changed, and records are written with it.
+            let (mut writer, mut reader, ledger) =
+                create_buffer_v2_with_codec::<_, SizedRecord>(data_dir, codec.clone())
+                    .await
+                    .expect("buffer should load with a different codec once drained");
+            assert_eq!(ledger.header().codec_flags(), codec.flags());
+            assert_buffer_is_empty!(ledger);
+
+            writer
+                .write_record(SizedRecord::new(96))
+                .await
+                .expect("write should not fail");
+            writer.flush().await.expect("flush should not fail");
+            let mut record = read_next_some(&mut reader).await;
+            assert_eq!(record, SizedRecord::new(96));
+            acknowledge(record.take_finalizers()).await;
+        }
+    })
+    .await;
+}
@@ -11,7 +11,7 @@ use super::{
     io::{AsyncFile, Metadata, ProductionFilesystem, ReadableMemoryMap, WritableMemoryMap},
     ledger::LEDGER_LEN,
     record::RECORD_HEADER_LEN,
-    Buffer, DiskBufferConfigBuilder, Filesystem, Ledger, Reader, Writer,
+    Buffer, BufferError, DiskBufferConfigBuilder, Filesystem, Ledger, Reader, RecordCodec, Writer,
 };
 use crate::{
     buffer_usage_data::BufferUsageHandle, encoding::FixedEncodable,
@@ -22,6 +22,7 @@ type FilesystemUnderTest = ProductionFilesystem;
 
 mod acknowledgements;
 mod basic;
+mod inspect;
 mod invariants;
 mod known_errors;
 mod model;
@@ -215,6 +216,33 @@ where
     (writer, reader, ledger, usage_handle)
 }
 
+/// Creates a disk v2 buffer with all default values, but with the given record codec.
+///
+/// Unlike the other helpers, this returns any error from creating the buffer, as the codec must
+/// match the one the buffer was previously written with.
+pub(crate) async fn create_buffer_v2_with_codec<P, R>(
+    data_dir: P,
+    codec: RecordCodec,
+) -> Result<
+    (
+        Writer<R, FilesystemUnderTest>,
+        Reader<R, FilesystemUnderTest>,
+        Arc<Ledger<FilesystemUnderTest>>,
+    ),
+    BufferError<R>,
+>
+where
+    P: AsRef<Path>,
+    R: Bufferable,
+{
+    let config = DiskBufferConfigBuilder::from_path(data_dir)
+        .codec(codec)
+        .build()
+        .expect("creating buffer should not fail");
+    let usage_handle = BufferUsageHandle::noop();
+    Buffer::from_config_inner(config, usage_handle).await
+}
+
 /// Creates a disk v2 buffer that is sized such that only a fixed number of data files are allowed.
 ///
 /// We do this based on limiting the maximum buffer size, knowing that if the maximum data file size is N, and we want
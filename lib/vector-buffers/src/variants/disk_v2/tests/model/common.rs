@@ -4,7 +4,8 @@ use proptest::{arbitrary::any, strategy::Strategy};
 
 use super::{filesystem::TestFilesystem, record::Record};
 use crate::variants::disk_v2::{
-    DiskBufferConfig, DiskBufferConfigBuilder, Reader, ReaderError, Writer, WriterError,
+    DiskBufferConfig, DiskBufferConfigBuilder, Reader, ReaderError, RecordCodec, Writer,
+    WriterError,
 };
 
 pub type TestReader = Reader<Record, TestFilesystem>;
@@ -33,14 +34,22 @@ pub enum Progress {
 }
 
 pub fn arb_buffer_config() -> impl Strategy<Value = DiskBufferConfig<TestFilesystem>> {
-    any::<(u16, u16, u16)>()
-        .prop_map(|(n1, n2, n3)| {
+    any::<(u16, u16, u16, bool, bool)>()
+        .prop_map(|(n1, n2, n3, compressed, encrypted)| {
             let max_buffer_size = u64::from(n1) * 64;
             let max_data_file_size = u64::from(n2) * 2;
             let max_record_size = n3
                 .try_into()
                 .expect("u16 should never be smaller than usize");
 
+            let mut codec = RecordCodec::default();
+            if compressed {
+                codec = codec.with_zstd();
+            }
+            if encrypted {
+                codec = codec.with_encryption_key(&[0x42; 32]);
+            }
+
             let mut path = std::env::temp_dir();
             path.push("vector-disk-v2-model");
 
@@ -54,6 +63,7 @@ pub fn arb_buffer_config() -> impl Strategy<Value = DiskBufferConfig<TestFilesys
                 // readers to make progress, and we're not testing anything about whether or not the
                 // ledger makes it to disk durably.
                 .flush_interval(Duration::from_secs(10))
+                .codec(codec)
                 .filesystem(TestFilesystem::default())
         })
         .prop_filter_map(
@@ -544,6 +544,7 @@ impl WriterModel {
             ledger.config().write_buffer_size,
             ledger.config().max_data_file_size,
             ledger.config().max_record_size,
+            ledger.config().codec.clone(),
         );
 
         let mut writer = Self {
@@ -563,15 +564,20 @@ impl WriterModel {
         writer
     }
 
-    fn get_archived_record_len(&mut self, record: Record) -> u64 {
+    fn get_archived_record_len(&mut self, record: Record) -> Result<u64, WriterError<Record>> {
         // We do a dummy `archive_record` call to simply do the work of encoding/archiving without
         // writing the value anywhere.  `RecordWriter` clears its encoding/serialization buffers on
         // each call to `archive_record` so we don't have to do any pre/post-cleanup to avoid memory
         // growth, etc.
+        //
+        // When the buffer is configured to compress and/or encrypt records, the transformed payload
+        // can end up exceeding the maximum record size even though the encoded record did not, which
+        // the writer reports as the record being too large, so we pass that error back as-is.
         let record_len = record.archived_len();
 
         match self.record_writer.archive_record(1, record) {
-            Ok(token) => token.serialized_len() as u64,
+            Ok(token) => Ok(token.serialized_len() as u64),
+            Err(e @ WriterError::RecordTooLarge { .. }) => Err(e),
             Err(e) => panic!(
                 "unexpected encode error: archived_len={} max_record_size={} error={:?}",
                 record_len,
@@ -703,7 +709,10 @@ impl WriterModel {
 
             // Write the record in the same way that the buffer would, which is the only way we can
             // calculate the true size that record occupies.
-            let archived_len = self.get_archived_record_len(record.clone());
+            let archived_len = match self.get_archived_record_len(record.clone()) {
+                Ok(len) => len,
+                Err(e) => return Progress::WriteError(e),
+            };
 
             // If this record would cause us to exceed the maximum data file size of the current data file, mark the
             // current data file full so that we can loop around and open the next one.
@@ -2,16 +2,24 @@ use std::io::Cursor;
 
 use crate::{
     test::SizedRecord,
-    variants::disk_v2::{reader::RecordReader, writer::RecordWriter},
+    variants::disk_v2::{
+        reader::{ReaderError, RecordReader},
+        writer::RecordWriter,
+        CodecError, RecordCodec,
+    },
 };
 
-#[tokio::test]
-async fn roundtrip_through_record_writer_and_record_reader() {
+const TEST_ENCRYPTION_KEY: [u8; 32] = [42; 32];
+
+async fn roundtrip_with_codecs(
+    writer_codec: RecordCodec,
+    reader_codec: RecordCodec,
+) -> Result<SizedRecord, ReaderError<SizedRecord>> {
     // Create a duplex stream that's more than big enough to ship a record through.
     let (writer_io, reader_io) = tokio::io::duplex(4096);
 
-    let mut record_writer = RecordWriter::new(writer_io, 0, 16_384, u64::MAX, 2048);
-    let mut record_reader = RecordReader::new(reader_io);
+    let mut record_writer = RecordWriter::new(writer_io, 0, 16_384, u64::MAX, 2048, writer_codec);
+    let mut record_reader = RecordReader::new(reader_io, reader_codec);
 
     let record = SizedRecord::new(73);
 
@@ -32,17 +40,54 @@ async fn roundtrip_through_record_writer_and_record_reader() {
     assert_eq!(flush_result, None);
     assert_eq!(314, read_token.record_id());
 
-    let roundtrip_record = record_reader
-        .read_record(read_token)
+    record_reader.read_record(read_token)
+}
+
+#[tokio::test]
+async fn roundtrip_through_record_writer_and_record_reader() {
+    let roundtrip_record = roundtrip_with_codecs(RecordCodec::default(), RecordCodec::default())
+        .await
         .expect("read should not fail");
-    assert_eq!(record, roundtrip_record);
+    assert_eq!(SizedRecord::new(73), roundtrip_record);
+}
+
+#[tokio::test]
+async fn roundtrip_through_record_writer_and_record_reader_with_codec() {
+    let codecs = [
+        RecordCodec::default().with_zstd(),
+        RecordCodec::default().with_encryption_key(&TEST_ENCRYPTION_KEY),
+        RecordCodec::default()
+            .with_zstd()
+            .with_encryption_key(&TEST_ENCRYPTION_KEY),
+    ];
+
+    for codec in codecs {
+        let roundtrip_record = roundtrip_with_codecs(codec.clone(), codec)
+            .await
+            .expect("read should not fail");
+        assert_eq!(SizedRecord::new(73), roundtrip_record);
+    }
+}
+
+#[tokio::test]
+async fn encrypted_record_requires_same_key() {
+    let writer_codec = RecordCodec::default().with_encryption_key(&TEST_ENCRYPTION_KEY);
+    let other_codec = RecordCodec::default().with_encryption_key(&[7; 32]);
+
+    let result = roundtrip_with_codecs(writer_codec, other_codec).await;
+    assert!(matches!(
+        result,
+        Err(ReaderError::Transform {
+            source: CodecError::Decrypt
+        })
+    ));
 }
 
 #[tokio::test]
 async fn record_reader_always_returns_none_when_no_data() {
     let reader_io = Cursor::new(Vec::new());
 
-    let mut record_reader = RecordReader::<_, SizedRecord>::new(reader_io);
+    let mut record_reader = RecordReader::<_, SizedRecord>::new(reader_io, RecordCodec::default());
     let read_token = record_reader
         .try_next_record(false)
         .await
@@ -6,13 +6,17 @@ use crate::{
     buffer_usage_data::BufferUsageHandle,
     topology::{builder::IntoBuffer, channel::ReceiverAdapter},
     variants::{
-        disk_v2::{build_disk_v2_buffer, get_disk_v2_data_dir_path},
+        disk_v2::{build_disk_v2_buffer, get_disk_v2_data_dir_path, RecordCodec},
         DiskV1Buffer,
     },
     Bufferable,
 };
 
-pub async fn try_disk_v1_migration<T>(base_data_dir: &Path, id: &str) -> Result<(), String>
+pub async fn try_disk_v1_migration<T>(
+    base_data_dir: &Path,
+    id: &str,
+    codec: &RecordCodec,
+) -> Result<(), String>
 where
     T: Bufferable + Clone + Finalizable,
 {
@@ -60,10 +64,17 @@ where
 
     let dst_buffer_dir = get_disk_v2_data_dir_path(base_data_dir, id);
 
-    let (mut dst_writer, _) =
-        build_disk_v2_buffer(usage_handle, base_data_dir, id, buffer_max_size)
-            .await
-            .map_err(|e| format!("Failed to build `disk_v2` buffer: {}", e))?;
+    // Records are written with the codec the buffer will be opened with after migrating, as the codec
+    // can't be changed while the buffer still holds unread records.
+    let (mut dst_writer, _) = build_disk_v2_buffer(
+        usage_handle,
+        base_data_dir,
+        id,
+        buffer_max_size,
+        codec.clone(),
+    )
+    .await
+    .map_err(|e| format!("Failed to build `disk_v2` buffer: {}", e))?;
 
     // Now that we've got our source and destination buffers configured, read each record from the
     // source and write it to the destination. If the write succeeds, we acknowledge it in the
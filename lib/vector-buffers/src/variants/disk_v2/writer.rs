@@ -24,6 +24,7 @@ use snafu::{ResultExt, Snafu};
 use tokio::io::{AsyncWrite, AsyncWriteExt};
 
 use super::{
+    codec::{CodecError, RecordCodec},
     common::{create_crc32c_hasher, DiskBufferConfig},
     io::Filesystem,
     ledger::Ledger,
@@ -95,6 +96,10 @@ where
         source: <T as Encodable>::EncodeError,
     },
 
+    /// The encoded record could not be compressed or encrypted.
+    #[snafu(display("failed to compress or encrypt record: {}", source))]
+    FailedToTransform { source: CodecError },
+
     /// The writer failed to serialize the record.
     ///
     /// As records are encoded and then wrapped in a container which carries metadata about the size
@@ -360,6 +365,8 @@ impl<W: fmt::Debug> fmt::Debug for TrackingBufWriter<W> {
 pub(super) struct RecordWriter<W, T> {
     writer: TrackingBufWriter<W>,
     encode_buf: Vec<u8>,
+    codec: RecordCodec,
+    codec_buf: Vec<u8>,
     ser_buf: AlignedVec,
     ser_scratch: AlignedVec,
     checksummer: Hasher,
@@ -384,6 +391,7 @@ where
         write_buffer_size: usize,
         max_data_file_size: u64,
         max_record_size: usize,
+        codec: RecordCodec,
     ) -> Self {
         // These should also be getting checked at a higher level, but we're double-checking them here to be absolutely sure.
         let max_record_size_converted = u64::try_from(max_record_size)
@@ -410,6 +418,8 @@ where
         Self {
             writer: TrackingBufWriter::with_capacity(write_buffer_size, writer),
             encode_buf: Vec::with_capacity(16_384),
+            codec,
+            codec_buf: Vec::new(),
             ser_buf: AlignedVec::with_capacity(16_384),
             ser_scratch: AlignedVec::with_capacity(16_384),
             checksummer: create_crc32c_hasher(),
@@ -481,8 +491,25 @@ where
         }
 
         let metadata = T::get_metadata().into_u32();
-        let wrapped_record =
-            Record::with_checksum(id, metadata, &self.encode_buf, &self.checksummer);
+
+        // Compress and/or encrypt the encoded record, if configured to, which can make it larger
+        // than the original, so we have to check it against the limit again.
+        let payload = if self.codec.is_passthrough() {
+            &self.encode_buf[..]
+        } else {
+            self.codec
+                .encode(id, &self.encode_buf, &mut self.codec_buf)
+                .context(FailedToTransformSnafu)?;
+            if self.codec_buf.len() > self.max_record_size {
+                return Err(WriterError::RecordTooLarge {
+                    limit: self.max_record_size,
+                });
+            }
+
+            &self.codec_buf[..]
+        };
+
+        let wrapped_record = Record::with_checksum(id, metadata, payload, &self.checksummer);
 
         // Push 8 dummy bytes where our length delimiter will sit.  We'll fix this up after
         // serialization.  Notably, `AlignedSerializer` will report the serializer position as
@@ -664,7 +691,9 @@ where
             }
         })?;
 
-        // Now we can actually decode it as `T`.
+        // Now we can actually decode it as `T`.  The archived payload may have been compressed and/or
+        // encrypted, but the encoding buffer still holds the encoded record it was generated from,
+        // so we decode that instead of reversing those transformations.
         let record_metadata = T::Metadata::from_u32(wrapped_record.metadata()).ok_or(
             WriterError::InconsistentState {
                 reason: "failed to decode record metadata immediately after encoding it"
@@ -672,7 +701,7 @@ where
             },
         )?;
 
-        T::decode(record_metadata, wrapped_record.payload()).map_err(|_| {
+        T::decode(record_metadata, &self.encode_buf[..]).map_err(|_| {
             WriterError::InconsistentState {
                 reason: "failed to decode record immediately after encoding it".to_string(),
             }
@@ -881,11 +910,10 @@ where
                 // next writer record ID should be.
                 let record = try_as_record_archive(data_file_mmap.as_ref())
                     .expect("record was already validated");
-                let item = decode_record_payload::<T>(record).map_err(|e| {
-                    WriterError::FailedToValidate {
+                let item = decode_record_payload::<T>(record, &self.config.codec, &mut Vec::new())
+                    .map_err(|e| WriterError::FailedToValidate {
                         reason: e.to_string(),
-                    }
-                })?;
+                    })?;
 
                 // Since we have a valid record, checksum and all, see if the writer record ID
                 // in the ledger lines up with the record ID we have here.  Specifically, the record
@@ -1125,6 +1153,7 @@ where
                     self.config.write_buffer_size,
                     self.config.max_data_file_size,
                     self.config.max_record_size,
+                    self.config.codec.clone(),
                 ));
                 self.data_file_size = data_file_size;
 
@@ -1,4 +1,4 @@
-use std::{error::Error, num::NonZeroUsize};
+use std::error::Error;
 
 use async_trait::async_trait;
 
@@ -8,15 +8,15 @@ use crate::{
         builder::IntoBuffer,
         channel::{limited, ReceiverAdapter, SenderAdapter},
     },
-    Bufferable,
+    Bufferable, MemoryBufferSize,
 };
 
 pub struct MemoryBuffer {
-    capacity: NonZeroUsize,
+    capacity: MemoryBufferSize,
 }
 
 impl MemoryBuffer {
-    pub fn new(capacity: NonZeroUsize) -> Self {
+    pub fn new(capacity: MemoryBufferSize) -> Self {
         MemoryBuffer { capacity }
     }
 }
@@ -30,9 +30,17 @@ where
         self: Box<Self>,
         usage_handle: BufferUsageHandle,
     ) -> Result<(SenderAdapter<T>, ReceiverAdapter<T>), Box<dyn Error + Send + Sync>> {
-        usage_handle.set_buffer_limits(None, Some(self.capacity.get()));
+        match self.capacity {
+            MemoryBufferSize::MaxEvents(max_events) => {
+                usage_handle.set_buffer_limits(None, Some(max_events.get()));
+            }
+            MemoryBufferSize::MaxSize(max_size) => {
+                let max_size = u64::try_from(max_size.get()).unwrap_or(u64::MAX);
+                usage_handle.set_buffer_limits(Some(max_size), None);
+            }
+        }
 
-        let (tx, rx) = limited(self.capacity.get());
+        let (tx, rx) = limited(self.capacity);
         Ok((tx.into(), rx.into()))
     }
 }
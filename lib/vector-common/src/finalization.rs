@@ -3,7 +3,14 @@
 //! as it flows through transforms, being duplicated and merged, and
 //! then report its status when the last copy is delivered or dropped.
 
-use std::{cmp, future::Future, mem, pin::Pin, sync::Arc, task::Poll};
+use std::{
+    cmp,
+    future::Future,
+    mem,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::Poll,
+};
 
 use crossbeam_utils::atomic::AtomicCell;
 use futures::future::FutureExt;
@@ -86,6 +93,22 @@ impl EventFinalizers {
         }
     }
 
+    /// Marks all event finalizers in the collection as rejected, recording why they were rejected.
+    ///
+    /// The reason is typically the error response of the downstream service. It is only kept by
+    /// batches created through [`BatchNotifier::new_with_reason_receiver`], whose receivers get it
+    /// through [`BatchStatusReceiver::with_reason`].
+    pub fn reject_with(&self, reason: &str) {
+        let mut shared = None;
+        for finalizer in &self.0 {
+            finalizer.update_status(EventStatus::Rejected);
+            if finalizer.batch.records_reason() {
+                let reason = shared.get_or_insert_with(|| Arc::<str>::from(reason));
+                finalizer.batch.update_reason(Arc::clone(reason));
+            }
+        }
+    }
+
     /// Consumes all event finalizers and updates their underlying batches immediately.
     pub fn update_sources(&mut self) {
         let finalizers = mem::take(&mut self.0);
@@ -157,17 +180,20 @@ impl Drop for EventFinalizer {
     }
 }
 
+/// The final status of a batch, along with the reason it was rejected, if any.
+type BatchResult = (BatchStatus, Option<Arc<str>>);
+
 /// A convenience newtype wrapper for the one-shot receiver for an
 /// individual batch status.
 #[pin_project::pin_project]
-pub struct BatchStatusReceiver(oneshot::Receiver<BatchStatus>);
+pub struct BatchStatusReceiver(oneshot::Receiver<BatchResult>);
 
 impl Future for BatchStatusReceiver {
     type Output = BatchStatus;
     fn poll(mut self: Pin<&mut Self>, ctx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
         match self.0.poll_unpin(ctx) {
             Poll::Pending => Poll::Pending,
-            Poll::Ready(Ok(status)) => Poll::Ready(status),
+            Poll::Ready(Ok((status, _))) => Poll::Ready(status),
             Poll::Ready(Err(error)) => {
                 error!(%error, "Batch status receiver dropped before sending.");
                 Poll::Ready(BatchStatus::Errored)
@@ -184,7 +210,21 @@ impl BatchStatusReceiver {
     /// - `TryRecvError::Empty` if no value has been sent yet.
     /// - `TryRecvError::Closed` if the sender has dropped without sending a value.
     pub fn try_recv(&mut self) -> Result<BatchStatus, oneshot::error::TryRecvError> {
-        self.0.try_recv()
+        self.0.try_recv().map(|(status, _)| status)
+    }
+
+    /// Waits for the status of the batch, along with the reason it was rejected.
+    ///
+    /// The reason is only set when the batch was rejected by a sink that reported why, such as
+    /// through [`EventFinalizers::reject_with`].
+    pub async fn with_reason(self) -> (BatchStatus, Option<Arc<str>>) {
+        match self.0.await {
+            Ok(result) => result,
+            Err(error) => {
+                error!(%error, "Batch status receiver dropped before sending.");
+                (BatchStatus::Errored, None)
+            }
+        }
     }
 }
 
@@ -198,9 +238,23 @@ impl BatchNotifier {
     /// Creates a new `BatchNotifier` along with the receiver used to await its finalization status.
     #[must_use]
     pub fn new_with_receiver() -> (Self, BatchStatusReceiver) {
+        Self::new(None)
+    }
+
+    /// Creates a new `BatchNotifier` which also records why it was rejected, along with the
+    /// receiver used to await its finalization status and that reason.
+    ///
+    /// Recording the reason costs an allocation per batch, so other batches don't keep it.
+    #[must_use]
+    pub fn new_with_reason_receiver() -> (Self, BatchStatusReceiver) {
+        Self::new(Some(Box::default()))
+    }
+
+    fn new(reason: Option<Box<Mutex<Option<Arc<str>>>>>) -> (Self, BatchStatusReceiver) {
         let (sender, receiver) = oneshot::channel();
         let notifier = OwnedBatchNotifier {
             status: AtomicCell::new(BatchStatus::Delivered),
+            reason,
             notifier: Some(sender),
         };
         (Self(Arc::new(notifier)), BatchStatusReceiver(receiver))
@@ -250,13 +304,30 @@ impl BatchNotifier {
                 .unwrap_or_else(|_| unreachable!());
         }
     }
+
+    /// Whether the batch records why it was rejected.
+    fn records_reason(&self) -> bool {
+        self.0.reason.is_some()
+    }
+
+    /// Records why the batch was rejected, keeping only the first reason.
+    fn update_reason(&self, reason: Arc<str>) {
+        if let Some(current) = &self.0.reason {
+            let mut current = current.lock().expect("poisoned lock");
+            if current.is_none() {
+                *current = Some(reason);
+            }
+        }
+    }
 }
 
 /// The non-shared data underlying the shared `BatchNotifier`
 #[derive(Debug)]
 pub struct OwnedBatchNotifier {
     status: AtomicCell<BatchStatus>,
-    notifier: Option<oneshot::Sender<BatchStatus>>,
+    /// Only set for batches which record why they were rejected.
+    reason: Option<Box<Mutex<Option<Arc<str>>>>>,
+    notifier: Option<oneshot::Sender<BatchResult>>,
 }
 
 impl OwnedBatchNotifier {
@@ -264,9 +335,15 @@ impl OwnedBatchNotifier {
     fn send_status(&mut self) {
         if let Some(notifier) = self.notifier.take() {
             let status = self.status.load();
+            let reason = match (status, self.reason.as_mut()) {
+                (BatchStatus::Rejected, Some(reason)) => {
+                    reason.get_mut().expect("poisoned lock").take()
+                }
+                _ => None,
+            };
             // Ignore the error case, as it will happen during normal
             // source shutdown and we can't detect that here.
-            let _ = notifier.send(status);
+            let _ = notifier.send((status, reason));
         }
     }
 }
@@ -428,6 +505,34 @@ mod tests {
         assert_eq!(receiver.try_recv(), Ok(BatchStatus::Rejected));
     }
 
+    #[tokio::test]
+    async fn rejection_reason() {
+        let make_finalizer = || {
+            let (batch, receiver) = BatchNotifier::new_with_reason_receiver();
+            (EventFinalizers::new(EventFinalizer::new(batch)), receiver)
+        };
+
+        let (fin, receiver) = make_finalizer();
+        fin.reject_with("invalid payload");
+        fin.reject_with("ignored");
+        drop(fin);
+        let (status, reason) = receiver.with_reason().await;
+        assert_eq!(status, BatchStatus::Rejected);
+        assert_eq!(reason.as_deref(), Some("invalid payload"));
+
+        let (fin, receiver) = make_finalizer();
+        fin.update_status(EventStatus::Delivered);
+        drop(fin);
+        assert_eq!(receiver.with_reason().await, (BatchStatus::Delivered, None));
+
+        // Other batches don't record the reason
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let fin = EventFinalizers::new(EventFinalizer::new(batch));
+        fin.reject_with("invalid payload");
+        drop(fin);
+        assert_eq!(receiver.with_reason().await, (BatchStatus::Rejected, None));
+    }
+
     #[test]
     fn clone_events() {
         let (fin1, mut receiver) = make_finalizer();
@@ -47,6 +47,11 @@ pub use finalizer::EmptyStream;
 
 pub mod internal_event;
 
+#[cfg(feature = "serde")]
+pub mod sensitive_string;
+#[cfg(feature = "serde")]
+pub use sensitive_string::SensitiveString;
+
 pub mod shutdown;
 
 #[cfg(feature = "tokenize")]
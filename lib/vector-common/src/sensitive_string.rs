@@ -0,0 +1,68 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// A string, such as a password or a key, that is never printed.
+///
+/// The value is redacted when it is formatted, so that it doesn't show up in logs or in printed
+/// configurations, but it is serialized as-is, so that configurations going through serialization,
+/// such as when they are cloned or compared, keep it.
+#[derive(Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(transparent)]
+#[allow(clippy::module_name_repetitions)]
+pub struct SensitiveString(String);
+
+impl SensitiveString {
+    /// Returns the actual value of the string.
+    #[must_use]
+    pub fn inner(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+impl From<String> for SensitiveString {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl From<&str> for SensitiveString {
+    fn from(value: &str) -> Self {
+        Self(value.to_string())
+    }
+}
+
+impl fmt::Debug for SensitiveString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("\"**REDACTED**\"")
+    }
+}
+
+impl fmt::Display for SensitiveString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("**REDACTED**")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SensitiveString;
+
+    #[test]
+    fn redacts_when_formatted() {
+        let value = SensitiveString::from("hunter2");
+        assert_eq!(format!("{}", value), "**REDACTED**");
+        assert_eq!(format!("{:?}", value), "\"**REDACTED**\"");
+    }
+
+    #[test]
+    fn serializes_value() {
+        let value = SensitiveString::from("hunter2");
+        let serialized = serde_json::to_string(&value).unwrap();
+        assert_eq!(serialized, "\"hunter2\"");
+        assert_eq!(
+            serde_json::from_str::<SensitiveString>(&serialized).unwrap(),
+            value
+        );
+    }
+}
@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 use snafu::{ResultExt, Snafu};
 use vector_common::TimeZone;
 
-use super::{proxy::ProxyConfig, AcknowledgementsConfig, LogSchema};
+use super::{proxy::ProxyConfig, AcknowledgementsConfig, LogSchema, TelemetryOptions};
 use crate::serde::bool_or_struct;
 
 #[derive(Debug, Snafu)]
@@ -48,6 +48,34 @@ pub struct GlobalOptions {
     pub acknowledgements: AcknowledgementsConfig,
     #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
     pub expire_metrics: Option<Duration>,
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    pub event_latency: EventLatencyOptions,
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    pub telemetry: TelemetryOptions,
+}
+
+/// Controls the internal metrics measuring how long events take to go through the topology.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct EventLatencyOptions {
+    /// Record `event_latency_seconds`, the time from an event being ingested by a source until it
+    /// is acknowledged by each sink.
+    pub enabled: bool,
+    /// Also record `component_residency_seconds`, the time an event spends in each transform and
+    /// sink, including the time spent waiting in its input buffer. Only takes effect when
+    /// `enabled` is set.
+    pub component_residency: bool,
+}
+
+impl EventLatencyOptions {
+    /// Combines these options with the given ones, enabling everything enabled by either.
+    #[must_use]
+    pub const fn merge(self, other: Self) -> Self {
+        Self {
+            enabled: self.enabled || other.enabled,
+            component_residency: self.component_residency || other.component_residency,
+        }
+    }
 }
 
 impl GlobalOptions {
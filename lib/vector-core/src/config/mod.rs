@@ -5,13 +5,15 @@ use bitmask_enum::bitmask;
 mod global_options;
 mod log_schema;
 pub mod proxy;
+mod telemetry;
 
 use crate::event::LogEvent;
-pub use global_options::GlobalOptions;
+pub use global_options::{EventLatencyOptions, GlobalOptions};
 pub use log_schema::{init_log_schema, log_schema, LogSchema};
 use lookup::lookup_v2::Path;
 use lookup::path;
 use serde::{Deserialize, Serialize};
+pub use telemetry::{InternalMetricsOptions, TelemetryOptions, TracingExportOptions};
 use value::Value;
 pub use vector_common::config::ComponentKey;
 use vector_config::configurable_component;
@@ -0,0 +1,102 @@
+use std::num::{NonZeroU64, NonZeroUsize};
+
+use serde::{Deserialize, Serialize};
+
+/// Telemetry about Vector itself.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct TelemetryOptions {
+    /// Export of Vector's internal `tracing` spans.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tracing: Option<TracingExportOptions>,
+
+    /// Controls over the internal metrics Vector collects about itself.
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    pub metrics: InternalMetricsOptions,
+}
+
+impl TelemetryOptions {
+    /// Merges these options with the given ones.
+    ///
+    /// # Errors
+    ///
+    /// If both set the same option, an error describing the conflict is returned.
+    pub fn merge(&mut self, other: Self) -> Result<(), String> {
+        match (&self.tracing, other.tracing) {
+            (Some(_), Some(_)) => {
+                return Err("conflicting values for 'telemetry.tracing' found".to_owned())
+            }
+            (None, tracing) => self.tracing = tracing,
+            (Some(_), None) => {}
+        }
+        self.metrics.merge(other.metrics)
+    }
+}
+
+/// Controls over the internal metrics Vector collects about itself.
+///
+/// These are applied to every internal metric before it is stored, and so also apply to everything
+/// exposed by the `internal_metrics` source.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct InternalMetricsOptions {
+    /// Names of internal metrics which are not collected at all.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub drop_metrics: Vec<String>,
+
+    /// Tag keys which are removed from all internal metrics.
+    ///
+    /// Series which only differ by these tags are aggregated into a single series.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub drop_tags: Vec<String>,
+
+    /// The maximum number of internal metric series to collect.
+    ///
+    /// Once reached, new series are discarded and a warning is logged, while existing series
+    /// continue to be updated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_series: Option<NonZeroUsize>,
+}
+
+impl InternalMetricsOptions {
+    fn merge(&mut self, other: Self) -> Result<(), String> {
+        for name in other.drop_metrics {
+            if !self.drop_metrics.contains(&name) {
+                self.drop_metrics.push(name);
+            }
+        }
+        for tag in other.drop_tags {
+            if !self.drop_tags.contains(&tag) {
+                self.drop_tags.push(tag);
+            }
+        }
+        match (self.max_series, other.max_series) {
+            (Some(a), Some(b)) if a != b => {
+                Err("conflicting values for 'telemetry.metrics.max_series' found".to_owned())
+            }
+            (None, max_series) => {
+                self.max_series = max_series;
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Exports Vector's internal `tracing` spans to an OpenTelemetry collector, over OTLP/gRPC.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct TracingExportOptions {
+    /// The OTLP/gRPC endpoint to export spans to, such as `http://localhost:4317`.
+    pub endpoint: String,
+
+    /// The rate at which traces are sampled.
+    ///
+    /// One out of every `sample_rate` traces is exported, and all other traces are discarded.
+    #[serde(default = "default_sample_rate")]
+    pub sample_rate: NonZeroU64,
+}
+
+fn default_sample_rate() -> NonZeroU64 {
+    NonZeroU64::new(1).expect("one is not zero")
+}
@@ -1,7 +1,9 @@
 #![deny(missing_docs)]
 
+use std::cmp::Ordering;
 use std::collections::BTreeMap;
 use std::sync::Arc;
+use std::time::Instant;
 
 use serde::{Deserialize, Serialize};
 use value::{Kind, Secrets, Value};
@@ -35,6 +37,33 @@ pub struct EventMetadata {
     /// TODO(Jean): must not skip serialization to track schemas across restarts.
     #[serde(default = "default_schema_definition", skip)]
     schema_definition: Arc<schema::Definition>,
+
+    /// When the event passed through the topology, used to measure its latency.
+    #[serde(default, skip)]
+    timestamps: LatencyTimestamps,
+}
+
+/// The instants at which an event entered the topology, and was last handed off from one component
+/// to the next.
+///
+/// These are only meaningful within the running process, so they are never serialized, and two
+/// events never compare differently because of them.
+#[derive(Clone, Copy, Debug, Default)]
+struct LatencyTimestamps {
+    ingested: Option<Instant>,
+    handed_off: Option<Instant>,
+}
+
+impl PartialEq for LatencyTimestamps {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl PartialOrd for LatencyTimestamps {
+    fn partial_cmp(&self, _other: &Self) -> Option<Ordering> {
+        Some(Ordering::Equal)
+    }
 }
 
 fn default_metadata_value() -> Value {
@@ -91,6 +120,7 @@ impl Default for EventMetadata {
             secrets: Secrets::new(),
             finalizers: Default::default(),
             schema_definition: default_schema_definition(),
+            timestamps: LatencyTimestamps::default(),
         }
     }
 }
@@ -151,9 +181,14 @@ impl EventMetadata {
     /// Merge the other `EventMetadata` into this.
     /// If a Datadog API key is not set in `self`, the one from `other` will be used.
     /// If a Splunk HEC token is not set in `self`, the one from `other` will be used.
+    /// The earliest ingest timestamp of the two is kept.
     pub fn merge(&mut self, other: Self) {
         self.finalizers.merge(other.finalizers);
         self.secrets.merge(other.secrets);
+        self.timestamps.ingested = match (self.timestamps.ingested, other.timestamps.ingested) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
     }
 
     /// Update the finalizer(s) status.
@@ -195,6 +230,30 @@ impl EventMetadata {
     pub fn set_schema_definition(&mut self, definition: &Arc<schema::Definition>) {
         self.schema_definition = Arc::clone(definition);
     }
+
+    /// Get the instant at which the event was ingested by its source, if known.
+    pub fn ingest_timestamp(&self) -> Option<Instant> {
+        self.timestamps.ingested
+    }
+
+    /// Set the instant at which the event was ingested by its source.
+    ///
+    /// This also marks the event as having been handed off at the same instant.
+    pub fn set_ingest_timestamp(&mut self, timestamp: Instant) {
+        self.timestamps.ingested = Some(timestamp);
+        self.timestamps.handed_off = Some(timestamp);
+    }
+
+    /// Get the instant at which the event was last handed off from one component to the next, if
+    /// known.
+    pub fn handoff_timestamp(&self) -> Option<Instant> {
+        self.timestamps.handed_off
+    }
+
+    /// Set the instant at which the event was last handed off from one component to the next.
+    pub fn set_handoff_timestamp(&mut self, timestamp: Instant) {
+        self.timestamps.handed_off = Some(timestamp);
+    }
 }
 
 impl EventDataEq for EventMetadata {
@@ -242,4 +301,22 @@ mod test {
         assert_eq!(metadata.datadog_api_key().unwrap().as_ref(), SECRET);
         assert_eq!(metadata.splunk_hec_token().unwrap().as_ref(), SECRET2);
     }
+
+    #[test]
+    fn timestamps_do_not_affect_equality() {
+        let mut metadata = EventMetadata::default();
+        metadata.set_ingest_timestamp(Instant::now());
+        assert_eq!(metadata, EventMetadata::default());
+    }
+
+    #[test]
+    fn merge_keeps_earliest_ingest_timestamp() {
+        let earliest = Instant::now();
+        let mut metadata = EventMetadata::default();
+        metadata.set_ingest_timestamp(earliest + std::time::Duration::from_secs(1));
+        let mut other = EventMetadata::default();
+        other.set_ingest_timestamp(earliest);
+        metadata.merge(other);
+        assert_eq!(metadata.ingest_timestamp(), Some(earliest));
+    }
 }
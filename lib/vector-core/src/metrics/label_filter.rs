@@ -1,6 +1,17 @@
-use metrics::Label;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, RwLock},
+};
+
+use metrics::{Key, Label};
 use metrics_tracing_context::LabelFilter;
 
+use crate::config::InternalMetricsOptions;
+
+/// The number of stripped keys cached before the cache is emptied, so that dropping a tag with an
+/// unbounded number of values doesn't grow the cache without bounds.
+const MAX_STRIPPED_KEYS: usize = 10_000;
+
 #[derive(Debug, Clone)]
 pub(crate) struct VectorLabelFilter;
 
@@ -14,3 +25,85 @@ impl LabelFilter for VectorLabelFilter {
             || key == "buffer_type"
     }
 }
+
+/// Filters the series stored by the registry, as configured through the `telemetry.metrics` global
+/// options and the `telemetry` option of each component.
+///
+/// Unlike [`VectorLabelFilter`], which only applies to labels captured from tracing spans, this
+/// applies to every label of a metric.
+#[derive(Debug, Default)]
+pub(crate) struct MetricFilter {
+    dropped_metrics: HashSet<String>,
+    dropped_tags: HashSet<String>,
+    disabled_components: HashSet<String>,
+    max_series: Option<usize>,
+    // The keys with dropped tags that have been seen, along with the key they are stored under.
+    stripped_keys: RwLock<HashMap<Key, Arc<Key>>>,
+}
+
+impl MetricFilter {
+    pub(crate) fn new(
+        options: &InternalMetricsOptions,
+        disabled_components: impl IntoIterator<Item = String>,
+    ) -> Self {
+        Self {
+            dropped_metrics: options.drop_metrics.iter().cloned().collect(),
+            dropped_tags: options.drop_tags.iter().cloned().collect(),
+            disabled_components: disabled_components.into_iter().collect(),
+            max_series: options.max_series.map(Into::into),
+            stripped_keys: RwLock::default(),
+        }
+    }
+
+    pub(crate) const fn max_series(&self) -> Option<usize> {
+        self.max_series
+    }
+
+    /// Whether the given series should be stored at all.
+    pub(crate) fn should_include_metric(&self, key: &Key) -> bool {
+        !self.dropped_metrics.contains(key.name())
+            && !key.labels().any(|label| {
+                label.key() == "component_id" && self.disabled_components.contains(label.value())
+            })
+    }
+
+    /// Removes the dropped tags from the given key, so that series only differing by them are
+    /// aggregated together, or returns `None` if the key has none of them.
+    ///
+    /// Metrics are registered each time they are emitted, so the stripped keys are cached rather
+    /// than allocated anew every time.
+    pub(crate) fn strip_tags(&self, key: &Key) -> Option<Arc<Key>> {
+        if !key
+            .labels()
+            .any(|label| self.dropped_tags.contains(label.key()))
+        {
+            return None;
+        }
+
+        if let Some(stripped) = self
+            .stripped_keys
+            .read()
+            .expect("Failed to acquire read lock on stripped keys")
+            .get(key)
+        {
+            return Some(Arc::clone(stripped));
+        }
+
+        let labels = key
+            .labels()
+            .filter(|label| !self.dropped_tags.contains(label.key()))
+            .cloned()
+            .collect::<Vec<_>>();
+        let stripped = Arc::new(Key::from_parts(key.name().to_owned(), labels));
+
+        let mut stripped_keys = self
+            .stripped_keys
+            .write()
+            .expect("Failed to acquire write lock on stripped keys");
+        if stripped_keys.len() >= MAX_STRIPPED_KEYS {
+            stripped_keys.clear();
+        }
+        stripped_keys.insert(key.clone(), Arc::clone(&stripped));
+        Some(stripped)
+    }
+}
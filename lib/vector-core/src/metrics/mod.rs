@@ -13,8 +13,15 @@ use once_cell::sync::OnceCell;
 use snafu::Snafu;
 
 pub use self::ddsketch::{AgentDDSketch, BinMap, Config};
-use self::{label_filter::VectorLabelFilter, recorder::Registry, recorder::VectorRecorder};
-use crate::event::{Metric, MetricKind, MetricValue};
+use self::{
+    label_filter::{MetricFilter, VectorLabelFilter},
+    recorder::Registry,
+    recorder::VectorRecorder,
+};
+use crate::{
+    config::InternalMetricsOptions,
+    event::{Metric, MetricKind, MetricValue},
+};
 
 type Result<T> = std::result::Result<T, Error>;
 
@@ -136,6 +143,21 @@ impl Controller {
             .with_registry(|registry| registry.set_expiry(timeout));
     }
 
+    /// Set the names and tags of metrics to drop, the limit on the number of series, and the
+    /// components whose metrics are not collected at all.
+    ///
+    /// Series already stored are not affected, other than those of the given components no longer
+    /// being captured.
+    pub fn set_filter(
+        &self,
+        options: &InternalMetricsOptions,
+        disabled_components: impl IntoIterator<Item = String>,
+    ) {
+        let filter = MetricFilter::new(options, disabled_components);
+        self.recorder
+            .with_registry(|registry| registry.set_filter(filter));
+    }
+
     /// Take a snapshot of all gathered metrics and expose them as metric
     /// [`Event`](crate::event::Event)s.
     pub fn capture_metrics(&self) -> Vec<Metric> {
@@ -292,4 +314,87 @@ mod tests {
         metrics::counter!("test2", 3);
         assert_eq!(controller.capture_metrics().len(), 3);
     }
+
+    fn filtered_controller(
+        options: &InternalMetricsOptions,
+        disabled_components: &[&str],
+    ) -> &'static Controller {
+        let controller = init_metrics();
+        controller.reset();
+        controller.set_filter(
+            options,
+            disabled_components.iter().map(|id| (*id).to_owned()),
+        );
+        controller
+    }
+
+    fn captured(controller: &Controller) -> Vec<Metric> {
+        controller
+            .capture_metrics()
+            .into_iter()
+            .filter(|metric| {
+                metric.name() != CARDINALITY_KEY_NAME
+                    && metric.name() != CARDINALITY_COUNTER_KEY_NAME
+            })
+            .collect()
+    }
+
+    #[test]
+    fn drops_metrics_and_tags() {
+        let options = InternalMetricsOptions {
+            drop_metrics: vec!["dropped".to_owned()],
+            drop_tags: vec!["file".to_owned()],
+            ..Default::default()
+        };
+        let controller = filtered_controller(&options, &[]);
+
+        metrics::counter!("dropped", 1);
+        metrics::counter!("kept", 1, "file" => "a.log", "host" => "h");
+        metrics::counter!("kept", 2, "file" => "b.log", "host" => "h");
+
+        let metrics = captured(controller);
+        assert_eq!(metrics.len(), 1);
+        assert_eq!(metrics[0].name(), "kept");
+        assert!(metrics[0].tag_matches("host", "h"));
+        assert_eq!(metrics[0].tag_value("file"), None);
+        assert_eq!(metrics[0].value(), &MetricValue::Counter { value: 3.0 });
+    }
+
+    #[test]
+    fn drops_metrics_of_disabled_components() {
+        let controller = filtered_controller(&InternalMetricsOptions::default(), &["quiet"]);
+
+        metrics::counter!("events", 1, "component_id" => "quiet");
+        metrics::counter!("events", 1, "component_id" => "loud");
+
+        let metrics = captured(controller);
+        assert_eq!(metrics.len(), 1);
+        assert!(metrics[0].tag_matches("component_id", "loud"));
+    }
+
+    #[test]
+    fn limits_series() {
+        let options = InternalMetricsOptions {
+            max_series: std::num::NonZeroUsize::new(2),
+            ..Default::default()
+        };
+        let controller = filtered_controller(&options, &[]);
+
+        for idx in 0..5 {
+            metrics::counter!("test", 1, "idx" => idx.to_string());
+        }
+        // Existing series are still updated once the limit is reached.
+        metrics::counter!("test", 1, "idx" => "0");
+
+        let metrics = captured(controller);
+        assert_eq!(metrics.len(), 2);
+        let total: f64 = metrics
+            .iter()
+            .map(|metric| match metric.value() {
+                MetricValue::Counter { value } => *value,
+                _ => panic!("unexpected metric value"),
+            })
+            .sum();
+        assert!((total - 3.0).abs() < f64::EPSILON);
+    }
 }
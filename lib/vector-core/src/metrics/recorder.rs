@@ -1,6 +1,8 @@
+use std::collections::HashSet;
 use std::sync::{atomic::Ordering, Arc, RwLock};
 use std::time::Duration;
 
+use arc_swap::ArcSwap;
 use chrono::Utc;
 use metrics::{Counter, Gauge, Histogram, Key, KeyName, Recorder, SharedString, Unit};
 use metrics_util::{
@@ -10,7 +12,7 @@ use metrics_util::{
 use once_cell::unsync::OnceCell;
 use quanta::Clock;
 
-use super::storage::VectorStorage;
+use super::{label_filter::MetricFilter, storage::VectorStorage};
 use crate::event::{Metric, MetricKind, MetricValue};
 
 thread_local!(static LOCAL_REGISTRY: OnceCell<Registry> = OnceCell::new());
@@ -19,6 +21,11 @@ thread_local!(static LOCAL_REGISTRY: OnceCell<Registry> = OnceCell::new());
 pub(super) struct Registry {
     registry: MetricsRegistry<Key, GenerationalStorage<VectorStorage>>,
     recency: RwLock<Option<Recency<Key>>>,
+    // Both are read each time a metric is registered, which happens each time it is emitted, while
+    // they are only replaced when the configuration is reloaded or a new series is stored.
+    filter: ArcSwap<MetricFilter>,
+    // The series currently stored, only tracked while the number of series is limited.
+    series: ArcSwap<HashSet<Key>>,
 }
 
 impl Registry {
@@ -26,11 +33,14 @@ impl Registry {
         Self {
             registry: MetricsRegistry::new(GenerationalStorage::new(VectorStorage)),
             recency: RwLock::new(None),
+            filter: ArcSwap::from_pointee(MetricFilter::default()),
+            series: ArcSwap::from_pointee(HashSet::new()),
         }
     }
 
     pub(super) fn clear(&self) {
         self.registry.clear();
+        self.series.store(Arc::default());
     }
 
     pub(super) fn set_expiry(&self, timeout: Option<Duration>) {
@@ -38,6 +48,49 @@ impl Registry {
         *(self.recency.write()).expect("Failed to acquire write lock on recency map") = recency;
     }
 
+    pub(super) fn set_filter(&self, filter: MetricFilter) {
+        self.filter.store(Arc::new(filter));
+    }
+
+    /// Applies the filter to the given key, registering the series under the key it should be
+    /// stored under, or returning `None` if it should not be stored.
+    fn admit<T>(&self, key: &Key, register: impl FnOnce(&Key) -> T) -> Option<T> {
+        let filter = self.filter.load();
+        if !filter.should_include_metric(key) {
+            return None;
+        }
+        let stripped = filter.strip_tags(key);
+        let key = stripped.as_deref().unwrap_or(key);
+        let max_series = match filter.max_series() {
+            Some(max_series) => max_series,
+            None => return Some(register(key)),
+        };
+
+        if !self.series.load().contains(key) {
+            // The set is bounded by the limit, so copying it is only ever as costly as storing that
+            // many series.
+            let previous = self.series.rcu(|series| {
+                if series.contains(key) || series.len() >= max_series {
+                    Arc::clone(series)
+                } else {
+                    let mut series = HashSet::clone(series);
+                    series.insert(key.clone());
+                    Arc::new(series)
+                }
+            });
+            if !previous.contains(key) && previous.len() >= max_series {
+                warn!(
+                    message = "Internal metrics series limit reached; dropping new series.",
+                    max_series,
+                    metric = %key.name(),
+                    internal_log_rate_secs = 30,
+                );
+                return None;
+            }
+        }
+        Some(register(key))
+    }
+
     pub(super) fn visit_metrics(&self) -> Vec<Metric> {
         let timestamp = Utc::now();
 
@@ -47,11 +100,15 @@ impl Registry {
             .read()
             .expect("Failed to acquire read lock on recency map");
         let recency = recency.as_ref();
+        let filter = self.filter.load();
 
+        // Components may have had their telemetry disabled since their series were stored.
         for (key, counter) in self.registry.get_counter_handles() {
-            if recency.map_or(true, |recency| {
-                recency.should_store_counter(&key, counter.get_generation(), &self.registry)
-            }) {
+            if filter.should_include_metric(&key)
+                && recency.map_or(true, |recency| {
+                    recency.should_store_counter(&key, counter.get_generation(), &self.registry)
+                })
+            {
                 // NOTE this will truncate if the value is greater than 2**52.
                 #[allow(clippy::cast_precision_loss)]
                 let value = counter.get_inner().swap(0, Ordering::Relaxed) as f64;
@@ -64,9 +121,11 @@ impl Registry {
             }
         }
         for (key, gauge) in self.registry.get_gauge_handles() {
-            if recency.map_or(true, |recency| {
-                recency.should_store_gauge(&key, gauge.get_generation(), &self.registry)
-            }) {
+            if filter.should_include_metric(&key)
+                && recency.map_or(true, |recency| {
+                    recency.should_store_gauge(&key, gauge.get_generation(), &self.registry)
+                })
+            {
                 let value = gauge.get_inner().load(Ordering::Relaxed);
                 metrics.push(Metric::from_metric_kv(
                     &key,
@@ -77,9 +136,11 @@ impl Registry {
             }
         }
         for (key, histogram) in self.registry.get_histogram_handles() {
-            if recency.map_or(true, |recency| {
-                recency.should_store_histogram(&key, histogram.get_generation(), &self.registry)
-            }) {
+            if filter.should_include_metric(&key)
+                && recency.map_or(true, |recency| {
+                    recency.should_store_histogram(&key, histogram.get_generation(), &self.registry)
+                })
+            {
                 let value = histogram.get_inner().make_metric();
                 metrics.push(Metric::from_metric_kv(
                     &key,
@@ -89,21 +150,48 @@ impl Registry {
                 ));
             }
         }
+
+        // Expired series no longer count towards the limit.
+        if filter.max_series().is_some() && recency.is_some() {
+            let live = self
+                .registry
+                .get_counter_handles()
+                .into_keys()
+                .chain(self.registry.get_gauge_handles().into_keys())
+                .chain(self.registry.get_histogram_handles().into_keys())
+                .collect::<HashSet<_>>();
+            self.series.rcu(|series| {
+                series
+                    .iter()
+                    .filter(|key| live.contains(*key))
+                    .cloned()
+                    .collect::<HashSet<_>>()
+            });
+        }
         metrics
     }
 
     fn get_counter(&self, key: &Key) -> Counter {
-        self.registry
-            .get_or_create_counter(key, |c| c.clone().into())
+        self.admit(key, |key| {
+            self.registry
+                .get_or_create_counter(key, |c| c.clone().into())
+        })
+        .unwrap_or_else(Counter::noop)
     }
 
     fn get_gauge(&self, key: &Key) -> Gauge {
-        self.registry.get_or_create_gauge(key, |c| c.clone().into())
+        self.admit(key, |key| {
+            self.registry.get_or_create_gauge(key, |c| c.clone().into())
+        })
+        .unwrap_or_else(Gauge::noop)
     }
 
     fn get_histogram(&self, key: &Key) -> Histogram {
-        self.registry
-            .get_or_create_histogram(key, |c| c.clone().into())
+        self.admit(key, |key| {
+            self.registry
+                .get_or_create_histogram(key, |c| c.clone().into())
+        })
+        .unwrap_or_else(Histogram::noop)
     }
 }
 
@@ -22,6 +22,14 @@ pub trait DriverResponse {
     fn bytes_sent(&self) -> Option<BytesSent> {
         None
     }
+
+    /// The reason the events were rejected, such as the error response of the downstream service.
+    ///
+    /// This is only used when `event_status` is `EventStatus::Rejected`, and is attached to the
+    /// events routed to the dead-letter output of the sink, if any.
+    fn rejection_reason(&self) -> Option<String> {
+        None
+    }
 }
 
 /// Drives the interaction between a stream of items and a service which processes them
@@ -169,11 +177,14 @@ where
         match result {
             Err(error) => {
                 error!(message = "Service call failed.", ?error, request_id);
-                finalizers.update_status(EventStatus::Rejected);
+                finalizers.reject_with(&format!("{:?}", error));
             }
             Ok(response) => {
                 trace!(message = "Service call succeeded.", request_id);
-                finalizers.update_status(response.event_status());
+                match (response.event_status(), response.rejection_reason()) {
+                    (EventStatus::Rejected, Some(reason)) => finalizers.reject_with(&reason),
+                    (status, _) => finalizers.update_status(status),
+                }
                 if response.event_status() == EventStatus::Delivered {
                     if let Some(bytes_sent) = response.bytes_sent() {
                         emit(bytes_sent);
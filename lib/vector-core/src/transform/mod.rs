@@ -381,6 +381,17 @@ impl TransformOutputsBuf {
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    /// Applies the given function to every array of events buffered for any output.
+    pub fn for_each_array_mut(&mut self, mut f: impl FnMut(&mut EventArray)) {
+        for buf in self
+            .primary_buffer
+            .iter_mut()
+            .chain(self.named_buffers.values_mut())
+        {
+            buf.0.iter_mut().for_each(&mut f);
+        }
+    }
 }
 
 impl ByteSizeOf for TransformOutputsBuf {
@@ -3,21 +3,52 @@ use std::sync::{
     Arc,
 };
 
-use serde_json::json;
-use warp::{reply::json, Rejection, Reply};
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use warp::{http::StatusCode, reply::json, Rejection, Reply};
+
+use crate::{
+    config::ComponentKey,
+    topology::health::{component_health, ComponentHealth},
+};
+
+#[derive(Debug, Default, Deserialize)]
+pub(super) struct HealthParams {
+    /// Comma-separated IDs of the components which must not be failing.
+    components: Option<String>,
+}
 
 // Health handler, responds with '{ ok: true }' when running and '{ ok: false}'
-// when shutting down
-pub(super) async fn health(running: Arc<AtomicBool>) -> Result<impl Reply, Rejection> {
-    if running.load(atomic::Ordering::Relaxed) {
-        Ok(warp::reply::with_status(
-            json(&json!({"ok": true})),
-            warp::http::StatusCode::OK,
-        ))
-    } else {
-        Ok(warp::reply::with_status(
-            json(&json!({"ok": false})),
-            warp::http::StatusCode::SERVICE_UNAVAILABLE,
-        ))
+// when shutting down. When `components` are given, they must also be running,
+// and not failing, and their health is included in the response. The health of
+// components with their telemetry disabled is unknown, which doesn't count as failing.
+pub(super) async fn health(
+    running: Arc<AtomicBool>,
+    params: HealthParams,
+) -> Result<impl Reply, Rejection> {
+    let mut ok = running.load(atomic::Ordering::Relaxed);
+    let mut body = Map::new();
+
+    if let Some(components) = params.components {
+        let components = components
+            .split(',')
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .map(|id| {
+                let health = component_health(&ComponentKey::from(id));
+                ok &= matches!(health, Some(health) if health != ComponentHealth::Failing);
+                let health = health.map_or("unknown", ComponentHealth::as_str);
+                (id.to_owned(), Value::from(health))
+            })
+            .collect::<Map<_, _>>();
+        body.insert("components".to_owned(), Value::Object(components));
     }
+    body.insert("ok".to_owned(), json!(ok));
+
+    let status = if ok {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    Ok(warp::reply::with_status(json(&body), status))
 }
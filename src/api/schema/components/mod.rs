@@ -18,7 +18,7 @@ use crate::{
     api::schema::{
         components::state::component_by_component_key,
         filter::{self, filter_items},
-        relay, sort,
+        health, relay, sort,
     },
     config::{ComponentKey, Config},
     filter_check,
@@ -27,7 +27,8 @@ use crate::{
 #[derive(Debug, Clone, Interface)]
 #[graphql(
     field(name = "component_id", type = "String"),
-    field(name = "component_type", type = "String")
+    field(name = "component_type", type = "String"),
+    field(name = "health", type = "Option<health::ComponentHealth>")
 )]
 pub enum Component {
     Source(source::Source),
@@ -5,11 +5,12 @@ use async_graphql::{Enum, InputObject, Object};
 use super::{source, state, transform, Component};
 use crate::{
     api::schema::{
-        filter,
+        filter, health,
         metrics::{self, IntoSinkMetrics},
         sort,
     },
     config::{ComponentKey, OutputId},
+    event::MetricValue,
     filter_check,
 };
 
@@ -77,6 +78,30 @@ impl sort::SortableByField<SinksSortFieldName> for Sink {
     }
 }
 
+/// The state of a sink's circuit breaker
+#[derive(Enum, Debug, Copy, Clone, Eq, PartialEq)]
+pub enum CircuitBreakerState {
+    /// Requests are sent as normal
+    Closed,
+    /// Requests are being probed to check whether the service has recovered
+    HalfOpen,
+    /// Requests are held back, as the service is failing
+    Open,
+}
+
+impl CircuitBreakerState {
+    /// Reads the state from the value of the `circuit_breaker_state` gauge
+    fn from_gauge(value: f64) -> Self {
+        if value >= 2.0 {
+            Self::Open
+        } else if value >= 1.0 {
+            Self::HalfOpen
+        } else {
+            Self::Closed
+        }
+    }
+}
+
 #[Object]
 impl Sink {
     /// Sink component_id
@@ -89,6 +114,11 @@ impl Sink {
         self.get_component_type()
     }
 
+    /// Sink health, from its recent errors, circuit breaker and healthchecks
+    pub async fn health(&self) -> Option<health::ComponentHealth> {
+        health::component_health(self.get_component_key())
+    }
+
     /// Source inputs
     pub async fn sources(&self) -> Vec<source::Source> {
         self.0
@@ -118,6 +148,17 @@ impl Sink {
         metrics::by_component_key(self.get_component_key())
             .into_sink_metrics(self.get_component_type())
     }
+
+    /// State of the sink's circuit breaker, if it has one configured
+    pub async fn circuit_breaker_state(&self) -> Option<CircuitBreakerState> {
+        metrics::by_component_key(self.get_component_key())
+            .iter()
+            .find(|metric| metric.name() == "circuit_breaker_state")
+            .and_then(|metric| match metric.value() {
+                MetricValue::Gauge { value } => Some(CircuitBreakerState::from_gauge(*value)),
+                _ => None,
+            })
+    }
 }
 
 #[cfg(test)]
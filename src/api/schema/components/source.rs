@@ -5,7 +5,7 @@ use async_graphql::{Enum, InputObject, Object};
 use super::{sink, state, transform, Component};
 use crate::{
     api::schema::{
-        filter,
+        filter, health,
         metrics::{self, outputs_by_component_key, IntoSourceMetrics, Output},
         sort,
     },
@@ -101,6 +101,11 @@ impl Source {
         self.get_component_type()
     }
 
+    /// Source health, from its recent errors
+    pub async fn health(&self) -> Option<health::ComponentHealth> {
+        health::component_health(self.get_component_key())
+    }
+
     /// Source output type
     pub async fn output_types(&self) -> Vec<SourceOutputType> {
         self.get_output_types()
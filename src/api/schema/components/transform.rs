@@ -5,7 +5,7 @@ use async_graphql::{Enum, InputObject, Object};
 use super::{sink, source, state, Component};
 use crate::{
     api::schema::{
-        filter,
+        filter, health,
         metrics::{self, outputs_by_component_key, IntoTransformMetrics, Output},
         sort,
     },
@@ -67,6 +67,11 @@ impl Transform {
         self.get_component_type()
     }
 
+    /// Transform health, from its recent errors
+    pub async fn health(&self) -> Option<health::ComponentHealth> {
+        health::component_health(self.get_component_key())
+    }
+
     /// Transform output streams
     pub async fn outputs(&self) -> Vec<Output> {
         outputs_by_component_key(self.get_component_key(), self.get_outputs())
@@ -1,8 +1,10 @@
-use async_graphql::{Object, SimpleObject, Subscription};
+use async_graphql::{Enum, Object, SimpleObject, Subscription};
 use chrono::{DateTime, Utc};
 use tokio::time::Duration;
 use tokio_stream::{wrappers::IntervalStream, Stream, StreamExt};
 
+use crate::{config::ComponentKey, topology::health};
+
 #[derive(SimpleObject)]
 pub struct Heartbeat {
     utc: DateTime<Utc>,
@@ -14,6 +16,35 @@ impl Heartbeat {
     }
 }
 
+/// Health of a running component
+#[derive(Enum, Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ComponentHealth {
+    /// The component is working as expected
+    Healthy,
+    /// The component is working, but some of its recent requests failed, or its healthcheck did
+    Degraded,
+    /// The component isn't getting anything through
+    Failing,
+    /// The component's health can't be told, as its telemetry is disabled
+    Unknown,
+}
+
+impl From<health::ComponentHealth> for ComponentHealth {
+    fn from(health: health::ComponentHealth) -> Self {
+        match health {
+            health::ComponentHealth::Healthy => Self::Healthy,
+            health::ComponentHealth::Degraded => Self::Degraded,
+            health::ComponentHealth::Failing => Self::Failing,
+            health::ComponentHealth::Unknown => Self::Unknown,
+        }
+    }
+}
+
+/// Returns the health of a component, if it's running
+pub(super) fn component_health(component_key: &ComponentKey) -> Option<ComponentHealth> {
+    health::component_health(component_key).map(Into::into)
+}
+
 #[derive(Default)]
 pub(super) struct HealthQuery;
 
@@ -70,6 +70,11 @@ fn make_routes(
     // Health.
     let health = warp::path("health")
         .and(with_shared(running))
+        .and(
+            warp::query::<handler::HealthParams>()
+                .or(warp::any().map(handler::HealthParams::default))
+                .unify(),
+        )
         .and_then(handler::health);
 
     // 404.
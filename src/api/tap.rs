@@ -629,6 +629,7 @@ mod tests {
                     namespace: None,
                     tags: None,
                 })],
+                reroute_dropped: false,
             },
         );
         config.add_sink(
@@ -22,6 +22,7 @@ use crate::service;
 #[cfg(feature = "api")]
 use crate::{api, internal_events::ApiStarted};
 use crate::{
+    buffer,
     cli::{handle_config_errors, Color, LogFormat, Opts, RootOpts, SubCommand},
     config::{self},
     generate, graph, heartbeat, list,
@@ -146,6 +147,7 @@ impl Application {
                     let code = match s {
                         SubCommand::Generate(g) => generate::cmd(&g),
                         SubCommand::Graph(g) => graph::cmd(&g),
+                        SubCommand::Buffer(b) => buffer::cmd(&b).await,
                         SubCommand::Config(c) => config::cmd(&c),
                         SubCommand::List(l) => list::cmd(&l),
                         SubCommand::Test(t) => unit_test::cmd(&t, &mut signal_handler).await,
@@ -197,6 +199,11 @@ impl Application {
                 }
                 config.healthchecks.set_require_healthy(require_healthy);
 
+                trace::start_span_export(&config.global.telemetry).map_err(|error| {
+                    error!(message = "Failed to start exporting spans.", %error);
+                    exitcode::CONFIG
+                })?;
+
                 #[cfg(feature = "enterprise")]
                 // Enable enterprise features, if applicable.
                 let enterprise = match EnterpriseMetadata::try_from(&config) {
@@ -0,0 +1,285 @@
+use std::{
+    fs::OpenOptions,
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+};
+
+use clap::Parser;
+use serde::Serialize;
+use vector_buffers::{
+    inspect::{self, InspectReport},
+    DiskBufferConfig, ProductionFilesystem,
+};
+
+use crate::{config, event::EventArray};
+
+#[derive(Parser, Debug)]
+#[clap(rename_all = "kebab-case")]
+pub struct Opts {
+    /// Read configuration from one or more files. Wildcard paths are supported.
+    /// File format is detected from the file name.
+    /// If zero files are specified the default config path
+    /// `/etc/vector/vector.toml` will be targeted.
+    #[clap(
+        name = "config",
+        short,
+        long,
+        env = "VECTOR_CONFIG",
+        use_value_delimiter(true)
+    )]
+    paths: Vec<PathBuf>,
+
+    /// Vector config files in TOML format.
+    #[clap(name = "config-toml", long, use_value_delimiter(true))]
+    paths_toml: Vec<PathBuf>,
+
+    /// Vector config files in JSON format.
+    #[clap(name = "config-json", long, use_value_delimiter(true))]
+    paths_json: Vec<PathBuf>,
+
+    /// Vector config files in YAML format.
+    #[clap(name = "config-yaml", long, use_value_delimiter(true))]
+    paths_yaml: Vec<PathBuf>,
+
+    /// Read configuration from files in one or more directories.
+    /// File format is detected from the file name.
+    ///
+    /// Files not ending in .toml, .json, .yaml, or .yml will be ignored.
+    #[clap(
+        name = "config-dir",
+        short = 'C',
+        long,
+        env = "VECTOR_CONFIG_DIR",
+        use_value_delimiter(true)
+    )]
+    pub config_dirs: Vec<PathBuf>,
+
+    #[clap(subcommand)]
+    sub_command: SubCommand,
+}
+
+#[derive(Parser, Debug)]
+#[clap(rename_all = "kebab-case")]
+enum SubCommand {
+    /// Show the state of a sink's disk buffer, as recorded in its ledger.
+    Stats(SinkOpts),
+
+    /// Print the unacknowledged events in a sink's disk buffer as native JSON, one per line,
+    /// without removing them from the buffer.
+    Dump(SinkOpts),
+
+    /// Remove all events from a sink's disk buffer, writing them to a file as native JSON, one per
+    /// line.
+    Drain(DrainOpts),
+
+    /// Skip past corrupted data in a sink's disk buffer, keeping all events that can still be read.
+    Repair(SinkOpts),
+}
+
+#[derive(Parser, Debug)]
+#[clap(rename_all = "kebab-case")]
+struct SinkOpts {
+    /// The ID of the sink whose buffer to operate on.
+    sink: String,
+}
+
+#[derive(Parser, Debug)]
+#[clap(rename_all = "kebab-case")]
+struct DrainOpts {
+    /// The ID of the sink whose buffer to operate on.
+    sink: String,
+
+    /// The file to write the events to. It must not already exist.
+    #[clap(short, long)]
+    output: PathBuf,
+}
+
+impl Opts {
+    fn paths_with_formats(&self) -> Vec<config::ConfigPath> {
+        config::merge_path_lists(vec![
+            (&self.paths, None),
+            (&self.paths_toml, Some(config::Format::Toml)),
+            (&self.paths_json, Some(config::Format::Json)),
+            (&self.paths_yaml, Some(config::Format::Yaml)),
+        ])
+        .map(|(path, hint)| config::ConfigPath::File(path, hint))
+        .chain(
+            self.config_dirs
+                .iter()
+                .map(|dir| config::ConfigPath::Dir(dir.to_path_buf())),
+        )
+        .collect()
+    }
+
+    fn sink(&self) -> &str {
+        match &self.sub_command {
+            SubCommand::Stats(opts) | SubCommand::Dump(opts) | SubCommand::Repair(opts) => {
+                &opts.sink
+            }
+            SubCommand::Drain(opts) => &opts.sink,
+        }
+    }
+}
+
+/// Loads the configuration, and finds the disk buffer of the requested sink.
+fn load_buffer_config(
+    opts: &Opts,
+) -> Result<DiskBufferConfig<ProductionFilesystem>, exitcode::ExitCode> {
+    let paths = opts.paths_with_formats();
+    let paths = match config::process_paths(&paths) {
+        Some(paths) => paths,
+        None => return Err(exitcode::CONFIG),
+    };
+
+    let config = match config::load_from_paths(&paths) {
+        Ok(config) => config,
+        Err(errs) => {
+            for err in errs {
+                error!(message = "Configuration error.", error = %err);
+            }
+            return Err(exitcode::CONFIG);
+        }
+    };
+
+    let sink_id = opts.sink();
+    let sink = match config.sink(&config::ComponentKey::from(sink_id)) {
+        Some(sink) => sink,
+        None => {
+            error!(message = "Sink not found in configuration.", sink = %sink_id);
+            return Err(exitcode::USAGE);
+        }
+    };
+
+    let data_dir = match &config.global.data_dir {
+        Some(data_dir) => data_dir,
+        None => {
+            error!("The `data_dir` option must be set to locate disk buffers.");
+            return Err(exitcode::CONFIG);
+        }
+    };
+
+    // Buffers are built with the sink's component key as their ID, just like the topology does.
+    let buffer_config = sink
+        .buffer
+        .stages()
+        .iter()
+        .find_map(|stage| stage.disk_v2_config(data_dir, sink_id).transpose());
+    match buffer_config {
+        Some(Ok(buffer_config)) => Ok(buffer_config),
+        Some(Err(error)) => {
+            error!(message = "Invalid buffer configuration.", sink = %sink_id, %error);
+            Err(exitcode::CONFIG)
+        }
+        None => {
+            error!(message = "Sink does not have a `disk` buffer.", sink = %sink_id);
+            Err(exitcode::USAGE)
+        }
+    }
+}
+
+fn write_events<W: Write>(writer: &mut W, events: EventArray) -> io::Result<()> {
+    for event in events {
+        serde_json::to_writer(&mut *writer, &event)?;
+        writer.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+fn print_json<T: Serialize>(value: &T) {
+    #[allow(clippy::print_stdout)]
+    {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(value).expect("serializing to JSON never fails")
+        );
+    }
+}
+
+fn finish(result: Result<InspectReport, inspect::InspectError>) -> exitcode::ExitCode {
+    match result {
+        Ok(report) => {
+            if !report.skipped.is_empty() {
+                warn!(
+                    message = "Some data in the buffer could not be read and was skipped.",
+                    skipped = report.skipped.len()
+                );
+            }
+            info!(
+                message = "Finished reading buffer.",
+                records = report.records,
+                events = report.events
+            );
+            exitcode::OK
+        }
+        Err(error) => {
+            error!(message = "Failed to read buffer.", %error);
+            exitcode::SOFTWARE
+        }
+    }
+}
+
+pub(crate) async fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    let buffer_config = match load_buffer_config(opts) {
+        Ok(buffer_config) => buffer_config,
+        Err(code) => return code,
+    };
+
+    match &opts.sub_command {
+        SubCommand::Stats(_) => match inspect::ledger_stats(buffer_config).await {
+            Ok(stats) => {
+                print_json(&stats);
+                exitcode::OK
+            }
+            Err(error) => {
+                error!(message = "Failed to load buffer.", %error);
+                exitcode::SOFTWARE
+            }
+        },
+        SubCommand::Dump(_) => {
+            let mut output = BufWriter::new(io::stdout());
+            let result = inspect::dump(buffer_config, |events: EventArray| {
+                write_events(&mut output, events)
+            })
+            .await;
+            if let Err(error) = output.flush() {
+                error!(message = "Failed to write events.", %error);
+                return exitcode::IOERR;
+            }
+            finish(result)
+        }
+        SubCommand::Drain(drain_opts) => {
+            let file = match OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&drain_opts.output)
+            {
+                Ok(file) => file,
+                Err(error) => {
+                    error!(message = "Failed to create output file.", path = ?drain_opts.output, %error);
+                    return exitcode::CANTCREAT;
+                }
+            };
+
+            let mut output = BufWriter::new(file);
+            let result = inspect::drain(buffer_config, |events: EventArray| {
+                write_events(&mut output, events)
+            })
+            .await;
+            if let Err(error) = output.flush() {
+                error!(message = "Failed to write output file.", path = ?drain_opts.output, %error);
+                return exitcode::IOERR;
+            }
+            if let Ok(report) = &result {
+                print_json(report);
+            }
+            finish(result)
+        }
+        SubCommand::Repair(_) => {
+            let result = inspect::repair::<EventArray, _>(buffer_config).await;
+            if let Ok(report) = &result {
+                print_json(report);
+            }
+            finish(result)
+        }
+    }
+}
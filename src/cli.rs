@@ -8,7 +8,7 @@ use crate::service;
 use crate::tap;
 #[cfg(feature = "api-client")]
 use crate::top;
-use crate::{config, generate, get_version, graph, list, unit_test, validate};
+use crate::{buffer, config, generate, get_version, graph, list, unit_test, validate};
 
 #[derive(Parser, Debug)]
 #[clap(rename_all = "kebab-case")]
@@ -193,6 +193,9 @@ pub enum SubCommand {
     /// Output the topology as visual representation using the DOT language which can be rendered by GraphViz
     Graph(graph::Opts),
 
+    /// Inspect, export, or repair the disk buffer of a sink while Vector is not running.
+    Buffer(buffer::Opts),
+
     /// Display topology and metrics in the console, for a local or remote Vector instance
     #[cfg(feature = "api-client")]
     Top(top::Opts),
@@ -112,6 +112,7 @@ impl EncodingConfigWithFraming {
                 | Serializer::Logfmt(_)
                 | Serializer::NativeJson(_)
                 | Serializer::RawMessage(_)
+                | Serializer::Syslog(_)
                 | Serializer::Text(_),
             ) => NewlineDelimitedEncoder::new().into(),
         };
@@ -143,6 +144,7 @@ where
 
 #[cfg(test)]
 mod test {
+    use codecs::encoding::SyslogRfc;
     use lookup::lookup_v2::parse_path;
 
     use super::*;
@@ -234,4 +236,35 @@ mod test {
         );
         assert_eq!(transformer.timestamp_format(), &Some(TimestampFormat::Unix));
     }
+
+    #[test]
+    fn deserialize_syslog_encoding_config_with_octet_counting() {
+        let string = r#"
+            {
+                "framing": {
+                    "method": "octet_counting"
+                },
+                "encoding": {
+                    "codec": "syslog",
+                    "syslog": {
+                        "rfc": "rfc3164",
+                        "app_name_field": "service"
+                    }
+                }
+            }
+        "#;
+
+        let encoding = serde_json::from_str::<EncodingConfigWithFraming>(string).unwrap();
+        let (framing, serializer) = encoding.config();
+
+        assert!(matches!(framing, Some(FramingConfig::OctetCounting)));
+        match serializer {
+            SerializerConfig::Syslog { syslog } => {
+                assert_eq!(syslog.rfc, SyslogRfc::Rfc3164);
+                assert_eq!(syslog.app_name_field, "service");
+                assert_eq!(syslog.facility_field, "facility");
+            }
+            _ => panic!("expected syslog serializer"),
+        }
+    }
 }
@@ -0,0 +1,175 @@
+//! Reading of MaxMind GeoIP databases, shared by the `geoip` transform and enrichment table.
+
+use std::{fs, time::SystemTime};
+
+use maxminddb::Reader;
+
+// MaxMind GeoIP database files have a type field we can use to recognize specific
+// products. If we encounter one of these types, we look for the matching information;
+// otherwise we expect to be working with a City or Country database.
+const ASN_DATABASE_TYPE: &str = "GeoLite2-ASN";
+const ISP_DATABASE_TYPE: &str = "GeoIP2-ISP";
+const ANONYMOUS_IP_DATABASE_TYPE: &str = "GeoIP2-Anonymous-IP";
+const CONNECTION_TYPE_DATABASE_TYPE: &str = "GeoIP2-Connection-Type";
+
+/// The kind of records held by a MaxMind database.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum DatabaseKind {
+    Asn,
+    Isp,
+    AnonymousIp,
+    ConnectionType,
+    City,
+}
+
+impl From<&str> for DatabaseKind {
+    fn from(database_type: &str) -> Self {
+        match database_type {
+            ASN_DATABASE_TYPE => Self::Asn,
+            ISP_DATABASE_TYPE => Self::Isp,
+            ANONYMOUS_IP_DATABASE_TYPE => Self::AnonymousIp,
+            CONNECTION_TYPE_DATABASE_TYPE => Self::ConnectionType,
+            _ => Self::City,
+        }
+    }
+}
+
+/// Identifies a version of the database file, so that replacing the file is noticed even when the
+/// new file has an older modification time, as happens when it is moved into place.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct FileVersion {
+    modified: SystemTime,
+    len: u64,
+    #[cfg(unix)]
+    inode: u64,
+}
+
+impl FileVersion {
+    fn read(path: &str) -> std::io::Result<Self> {
+        let metadata = fs::metadata(path)?;
+        Ok(Self {
+            modified: metadata.modified()?,
+            len: metadata.len(),
+            #[cfg(unix)]
+            inode: std::os::unix::fs::MetadataExt::ino(&metadata),
+        })
+    }
+}
+
+/// An opened database, along with the version of the file it was read from.
+pub(crate) struct Database {
+    pub(crate) reader: Reader<Vec<u8>>,
+    pub(crate) kind: DatabaseKind,
+    version: FileVersion,
+}
+
+impl Database {
+    pub(crate) fn open(path: &str) -> crate::Result<Self> {
+        // The version is read first, so that a file replaced while it is read is noticed later on.
+        let version = FileVersion::read(path)?;
+        let reader = Reader::open_readfile(path)?;
+        Ok(Self {
+            kind: DatabaseKind::from(reader.metadata.database_type.as_str()),
+            reader,
+            version,
+        })
+    }
+
+    /// Returns true if the file at `path` is not the one the database was read from.
+    #[cfg(feature = "enrichment-tables-geoip")]
+    pub(crate) fn has_changed(&self, path: &str) -> bool {
+        matches!(FileVersion::read(path), Ok(version) if version != self.version)
+    }
+}
+
+#[cfg(feature = "transforms-geoip")]
+pub(crate) use self::reloading::ReloadingDatabase;
+
+#[cfg(feature = "transforms-geoip")]
+mod reloading {
+    use std::{sync::Arc, time::Duration};
+
+    use arc_swap::{ArcSwap, Guard};
+    use tracing::Instrument;
+
+    use super::{Database, FileVersion};
+    use crate::internal_events::GeoipDatabaseReloadError;
+
+    /// How often the database file is checked for replacement.
+    const RELOAD_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+    /// A database that is reopened in the background when its file is replaced, so that reading it
+    /// never waits on the file system.
+    #[derive(Clone)]
+    pub(crate) struct ReloadingDatabase {
+        path: String,
+        current: Arc<ArcSwap<Database>>,
+    }
+
+    impl ReloadingDatabase {
+        pub(crate) fn open(path: String) -> crate::Result<Self> {
+            let database = Database::open(&path)?;
+            Ok(Self {
+                path,
+                current: Arc::new(ArcSwap::from_pointee(database)),
+            })
+        }
+
+        pub(crate) fn load(&self) -> Guard<Arc<Database>> {
+            self.current.load()
+        }
+
+        /// Spawns the task checking the file for replacement, which stops once every clone of the
+        /// database has been dropped.
+        pub(crate) fn spawn_reloader(&self) {
+            let path = self.path.clone();
+            let current = Arc::downgrade(&self.current);
+            tokio::spawn(
+                async move {
+                    loop {
+                        tokio::time::sleep(RELOAD_CHECK_INTERVAL).await;
+                        let database = match current.upgrade() {
+                            Some(current) => Self {
+                                path: path.clone(),
+                                current,
+                            },
+                            None => break,
+                        };
+                        if tokio::task::spawn_blocking(move || database.reload_if_changed())
+                            .await
+                            .is_err()
+                        {
+                            break;
+                        }
+                    }
+                }
+                .in_current_span(),
+            );
+        }
+
+        /// Reopens the database if the file has been replaced since it was last read.
+        ///
+        /// If the new file can't be read, the current database stays in use.
+        pub(crate) fn reload_if_changed(&self) {
+            match FileVersion::read(&self.path) {
+                Ok(version) if version != self.current.load().version => (),
+                Ok(_) => return,
+                Err(error) => {
+                    emit!(GeoipDatabaseReloadError {
+                        error: error.into(),
+                        database: &self.path,
+                    });
+                    return;
+                }
+            }
+
+            match Database::open(&self.path) {
+                Ok(database) => self.current.store(Arc::new(database)),
+                Err(error) => emit!(GeoipDatabaseReloadError {
+                    error,
+                    database: &self.path,
+                }),
+            }
+        }
+    }
+}
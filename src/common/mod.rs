@@ -9,6 +9,9 @@
 ))]
 pub(crate) mod datadog;
 
+#[cfg(any(feature = "enrichment-tables-geoip", feature = "transforms-geoip"))]
+pub(crate) mod geoip;
+
 #[cfg(any(
     feature = "sources-aws_sqs",
     feature = "sinks-aws_sqs",
@@ -270,6 +270,7 @@ impl ConfigBuilder {
         let transform = TransformOuter {
             inner: Box::new(transform),
             inputs,
+            telemetry: true,
         };
 
         self.transforms
@@ -321,6 +322,12 @@ impl ConfigBuilder {
 
         self.global.expire_metrics = self.global.expire_metrics.or(with.global.expire_metrics);
 
+        self.global.event_latency = self.global.event_latency.merge(with.global.event_latency);
+
+        if let Err(error) = self.global.telemetry.merge(with.global.telemetry) {
+            errors.push(error);
+        }
+
         self.schema.append(with.schema, &mut errors);
 
         self.schema.log_namespace = self.schema.log_namespace.or(with.schema.log_namespace);
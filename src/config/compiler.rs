@@ -4,6 +4,7 @@ use indexmap::{IndexMap, IndexSet};
 
 use super::{
     builder::ConfigBuilder, graph::Graph, schema, validation, ComponentKey, Config, OutputId,
+    SinkOuter,
 };
 
 /// to handle the expansions when building the graph we need to be able to get the list of inputs
@@ -24,6 +25,10 @@ pub(crate) fn to_string_expansions(
 pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<String>> {
     let mut errors = Vec::new();
 
+    if let Err(table_errors) = add_enrichment_table_sinks(&mut builder) {
+        errors.extend(table_errors);
+    }
+
     // component names should not have dots in the configuration file
     // but components can expand (like route) to have components with a dot
     // so this check should be done before expanding components
@@ -143,6 +148,60 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
     }
 }
 
+/// Adds the sinks writing into the enrichment tables that have `inputs`.
+///
+/// The sinks are named after their table, so that they show up in the topology like any other sink.
+fn add_enrichment_table_sinks(builder: &mut ConfigBuilder) -> Result<(), Vec<String>> {
+    let mut errors = Vec::new();
+
+    for (key, sink) in &builder.sinks {
+        let has_inputs = builder
+            .enrichment_tables
+            .get(key)
+            .map_or(false, |table| !table.inputs.is_empty());
+        if sink.inner.is_enrichment_table_sink() && !has_inputs {
+            errors.push(format!(
+                "Sink \"{}\" writes to an enrichment table, so it can't be configured directly. Set the `inputs` of the enrichment table instead.",
+                key
+            ));
+        }
+    }
+
+    for (key, table) in &builder.enrichment_tables {
+        if table.inputs.is_empty() {
+            continue;
+        }
+
+        let is_taken = builder
+            .sinks
+            .get(key)
+            .map_or(false, |sink| !sink.inner.is_enrichment_table_sink());
+        match table.inner.sink_config() {
+            Some(_) if is_taken => errors.push(format!(
+                "Enrichment table \"{}\" has inputs, but a sink with the same name already exists.",
+                key
+            )),
+            // When building a config that was compiled before, the sink writing to the table is
+            // already there, and is replaced by one sharing the data of the table.
+            Some(sink) => {
+                builder
+                    .sinks
+                    .insert(key.clone(), SinkOuter::new(table.inputs.clone(), sink));
+            }
+            None => errors.push(format!(
+                "Enrichment table \"{}\" does not accept inputs.",
+                key
+            )),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
 /// Some component configs can act like macros and expand themselves into multiple replacement
 /// configs. Performs those expansions and records the relevant metadata.
 pub(super) fn expand_macros(
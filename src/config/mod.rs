@@ -3,6 +3,7 @@ use std::{
     fmt::{self, Display, Formatter},
     hash::Hash,
     net::SocketAddr,
+    num::NonZeroU64,
     path::PathBuf,
 };
 
@@ -207,6 +208,10 @@ impl Config {
 pub struct HealthcheckOptions {
     pub enabled: bool,
     pub require_healthy: bool,
+    /// How often the healthchecks of sinks are re-run while they are running.
+    ///
+    /// Unless set, they only run when sinks are built, on startup and reload.
+    pub interval_secs: Option<NonZeroU64>,
 }
 
 impl HealthcheckOptions {
@@ -219,6 +224,10 @@ impl HealthcheckOptions {
     fn merge(&mut self, other: Self) {
         self.enabled &= other.enabled;
         self.require_healthy |= other.require_healthy;
+        self.interval_secs = match (self.interval_secs, other.interval_secs) {
+            (Some(interval_secs), Some(other)) => Some(interval_secs.min(other)),
+            (interval_secs, other) => interval_secs.or(other),
+        };
     }
 }
 
@@ -227,6 +236,7 @@ impl Default for HealthcheckOptions {
         Self {
             enabled: true,
             require_healthy: false,
+            interval_secs: None,
         }
     }
 }
@@ -248,13 +258,19 @@ macro_rules! impl_generate_config_from_default {
 
 #[derive(Deserialize, Serialize, Debug)]
 pub struct EnrichmentTableOuter {
+    // The components whose events are written into the table, for tables that support it.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub inputs: Vec<String>,
     #[serde(flatten)]
     pub inner: Box<dyn EnrichmentTableConfig>,
 }
 
 impl EnrichmentTableOuter {
     pub fn new(inner: Box<dyn EnrichmentTableConfig>) -> Self {
-        EnrichmentTableOuter { inner }
+        EnrichmentTableOuter {
+            inputs: Vec::new(),
+            inner,
+        }
     }
 }
 
@@ -265,6 +281,12 @@ pub trait EnrichmentTableConfig: core::fmt::Debug + Send + Sync + dyn_clone::Dyn
         &self,
         globals: &GlobalOptions,
     ) -> crate::Result<Box<dyn enrichment::Table + Send + Sync>>;
+
+    /// The sink writing the events of the table's `inputs` into the table, for tables that can be
+    /// written to.
+    fn sink_config(&self) -> Option<Box<dyn SinkConfig>> {
+        None
+    }
 }
 
 pub type EnrichmentTableDescription = ComponentDescription<Box<dyn EnrichmentTableConfig>>;
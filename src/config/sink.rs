@@ -1,11 +1,16 @@
+use std::num::NonZeroU64;
+
 use async_trait::async_trait;
 use component::ComponentDescription;
 use serde::{Deserialize, Serialize};
 use vector_buffers::{BufferConfig, BufferType};
-use vector_core::config::{AcknowledgementsConfig, GlobalOptions, Input};
+use vector_core::config::{AcknowledgementsConfig, GlobalOptions, Input, Output};
 
 use super::{component, schema, ComponentKey, ProxyConfig, Resource};
-use crate::sinks::{self, util::UriSerde};
+use crate::{
+    sinks::{self, util::UriSerde},
+    topology::dead_letter::REJECTED,
+};
 
 #[derive(Deserialize, Serialize, Debug)]
 pub struct SinkOuter<T> {
@@ -28,6 +33,27 @@ pub struct SinkOuter<T> {
     )]
     proxy: ProxyConfig,
 
+    /// Whether events the sink rejects are rerouted to its `rejected` output instead of being
+    /// discarded.
+    ///
+    /// Rerouted events are annotated with the error the sink reported, and can be consumed by other
+    /// components by using `<sink_id>.rejected` as an input.
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    pub reroute_rejected: bool,
+
+    /// Whether the internal metrics of this sink are collected.
+    ///
+    /// The sink keeps sending events as usual when they are disabled, and its healthcheck still
+    /// runs.
+    #[serde(
+        default = "crate::serde::default_true",
+        skip_serializing_if = "crate::serde::is_true"
+    )]
+    pub telemetry: bool,
+
     #[serde(flatten)]
     pub inner: Box<dyn SinkConfig>,
 }
@@ -41,6 +67,8 @@ impl<T> SinkOuter<T> {
             healthcheck_uri: None,
             inner,
             proxy: Default::default(),
+            reroute_rejected: false,
+            telemetry: true,
         }
     }
 
@@ -79,6 +107,16 @@ impl<T> SinkOuter<T> {
         &self.proxy
     }
 
+    /// Clones the configuration of the sink itself.
+    ///
+    /// Trait objects can't be cloned, so like `ConfigBuilder`, this round-trips the configuration
+    /// through JSON.
+    pub(crate) fn clone_inner(&self) -> Box<dyn SinkConfig> {
+        let json =
+            serde_json::to_value(&self.inner).expect("Should serialize to JSON. Please report.");
+        serde_json::from_value(json).expect("Should deserialize from JSON. Please report.")
+    }
+
     pub(super) fn map_inputs<U>(self, f: impl Fn(&T) -> U) -> SinkOuter<U> {
         let inputs = self.inputs.iter().map(f).collect();
         self.with_inputs(inputs)
@@ -92,6 +130,20 @@ impl<T> SinkOuter<T> {
             healthcheck: self.healthcheck,
             healthcheck_uri: self.healthcheck_uri,
             proxy: self.proxy,
+            reroute_rejected: self.reroute_rejected,
+            telemetry: self.telemetry,
+        }
+    }
+
+    /// Gets the outputs of this sink.
+    ///
+    /// Sinks only have an output when `reroute_rejected` is enabled, carrying the events they
+    /// rejected.
+    pub fn outputs(&self) -> Vec<Output> {
+        if self.reroute_rejected {
+            vec![Output::default(self.inner.input().data_type()).with_port(REJECTED)]
+        } else {
+            Vec::new()
         }
     }
 }
@@ -101,6 +153,9 @@ impl<T> SinkOuter<T> {
 pub struct SinkHealthcheckOptions {
     pub enabled: bool,
     pub uri: Option<UriSerde>,
+    /// How often the healthcheck is re-run while the sink is running, overriding the global
+    /// `healthchecks.interval_secs` option.
+    pub interval_secs: Option<NonZeroU64>,
 }
 
 impl Default for SinkHealthcheckOptions {
@@ -108,21 +163,25 @@ impl Default for SinkHealthcheckOptions {
         Self {
             enabled: true,
             uri: None,
+            interval_secs: None,
         }
     }
 }
 
 impl From<bool> for SinkHealthcheckOptions {
     fn from(enabled: bool) -> Self {
-        Self { enabled, uri: None }
+        Self {
+            enabled,
+            ..Default::default()
+        }
     }
 }
 
 impl From<UriSerde> for SinkHealthcheckOptions {
     fn from(uri: UriSerde) -> Self {
         Self {
-            enabled: true,
             uri: Some(uri),
+            ..Default::default()
         }
     }
 }
@@ -135,10 +194,30 @@ pub trait SinkConfig: core::fmt::Debug + Send + Sync {
         cx: SinkContext,
     ) -> crate::Result<(sinks::VectorSink, sinks::Healthcheck)>;
 
+    /// Builds the healthcheck of the sink again, so that it can be re-run while the sink is
+    /// running.
+    ///
+    /// By default the whole sink is built, and only its healthcheck is kept. Sinks which can build
+    /// their healthcheck on its own should do so instead, and sinks whose healthcheck can't be
+    /// re-run return `None`, so that it only runs when they are built.
+    async fn rebuild_healthcheck(
+        &self,
+        cx: SinkContext,
+    ) -> crate::Result<Option<sinks::Healthcheck>> {
+        let (_sink, healthcheck) = self.build(cx).await?;
+        Ok(Some(healthcheck))
+    }
+
     fn input(&self) -> Input;
 
     fn sink_type(&self) -> &'static str;
 
+    /// Whether the sink writes to an enrichment table. Such sinks are only created from the
+    /// `inputs` of their enrichment table, and can't be configured directly.
+    fn is_enrichment_table_sink(&self) -> bool {
+        false
+    }
+
     /// Resources that the sink is using.
     fn resources(&self) -> Vec<Resource> {
         Vec::new()
@@ -15,6 +15,13 @@ pub struct SourceOuter {
         skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
     )]
     pub proxy: ProxyConfig,
+    /// Whether the internal metrics of this source, such as the number of events it received, are
+    /// collected.
+    #[serde(
+        default = "crate::serde::default_true",
+        skip_serializing_if = "crate::serde::is_true"
+    )]
+    pub telemetry: bool,
     #[serde(flatten)]
     pub(crate) inner: Box<dyn SourceConfig>,
     #[serde(default, skip)]
@@ -27,6 +34,7 @@ impl SourceOuter {
             inner: Box::new(source),
             proxy: Default::default(),
             sink_acknowledgements: false,
+            telemetry: true,
         }
     }
 }
@@ -11,6 +11,15 @@ use super::{component, ComponentKey};
 pub struct TransformOuter<T> {
     #[serde(default = "Default::default")] // https://github.com/serde-rs/serde/issues/1541
     pub inputs: Vec<T>,
+    /// Whether the internal metrics of this transform are collected.
+    ///
+    /// This only applies to the series tagged with this transform's `component_id`, so the metrics
+    /// of the components it sends events to are unaffected.
+    #[serde(
+        default = "crate::serde::default_true",
+        skip_serializing_if = "crate::serde::is_true"
+    )]
+    pub telemetry: bool,
     #[serde(flatten)]
     pub inner: Box<dyn TransformConfig>,
 }
@@ -21,6 +30,7 @@ impl<T> TransformOuter<T> {
         TransformOuter {
             inputs,
             inner: Box::new(transform),
+            telemetry: true,
         }
     }
 
@@ -33,6 +43,7 @@ impl<T> TransformOuter<T> {
         TransformOuter {
             inputs,
             inner: self.inner,
+            telemetry: self.telemetry,
         }
     }
 }
@@ -77,6 +88,7 @@ impl TransformOuter<String> {
                 let child = TransformOuter {
                     inputs: inner_transform.inputs,
                     inner: inner_transform.inner,
+                    telemetry: self.telemetry,
                 };
                 children.push(inner_name.clone());
                 transforms.insert(inner_name, child);
@@ -32,20 +32,45 @@ pub fn spawn_thread<'a>(
     config_paths: impl IntoIterator<Item = &'a PathBuf> + 'a,
     delay: impl Into<Option<Duration>>,
 ) -> Result<(), Error> {
-    let config_paths: Vec<_> = config_paths.into_iter().cloned().collect();
+    info!("Watching configuration files.");
+
+    spawn_watcher(config_paths.into_iter().cloned().collect(), delay, || {
+        info!("Configuration file changed.");
+        raise_sighup();
+        true
+    })
+}
+
+#[cfg(windows)]
+/// Errors on Windows.
+pub fn spawn_thread<'a>(
+    _config_paths: impl IntoIterator<Item = &'a PathBuf> + 'a,
+    _delay: impl Into<Option<Duration>>,
+) -> Result<(), Error> {
+    Err("Reloading config on Windows isn't currently supported. Related issue https://github.com/vectordotdev/vector/issues/938 .".into())
+}
+
+/// Calls `on_change` when any of `paths` changes.
+/// Accumulates file changes until no change for given duration has occurred.
+/// Watching stops once `on_change` returns `false`, which is only noticed on the
+/// next change.
+#[cfg(unix)]
+pub fn spawn_watcher(
+    paths: Vec<PathBuf>,
+    delay: impl Into<Option<Duration>>,
+    mut on_change: impl FnMut() -> bool + Send + 'static,
+) -> Result<(), Error> {
     let delay = delay.into().unwrap_or(CONFIG_WATCH_DELAY);
 
     // Create watcher now so not to miss any changes happening between
     // returning from this function and the thread starting.
-    let mut watcher = Some(create_watcher(&config_paths)?);
-
-    info!("Watching configuration files.");
+    let mut watcher = Some(create_watcher(&paths)?);
 
     thread::spawn(move || loop {
         if let Some((mut watcher, receiver)) = watcher.take() {
             while let Ok(RawEvent { op: Ok(event), .. }) = receiver.recv() {
                 if event.intersects(Op::CREATE | Op::REMOVE | Op::WRITE | Op::CLOSE_WRITE) {
-                    debug!(message = "Configuration file change detected.", event = ?event);
+                    debug!(message = "File change detected.", event = ?event);
 
                     // Consume events until delay amount of time has passed since the latest event.
                     while let Ok(..) = receiver.recv_timeout(delay) {}
@@ -53,16 +78,17 @@ pub fn spawn_thread<'a>(
                     debug!(message = "Consumed file change events for delay.", delay = ?delay);
 
                     // We need to read paths to resolve any inode changes that may have happened.
-                    // And we need to do it before raising sighup to avoid missing any change.
-                    if let Err(error) = add_paths(&mut watcher, &config_paths) {
+                    // And we need to do it before notifying about the change to avoid missing any.
+                    if let Err(error) = add_paths(&mut watcher, &paths) {
                         error!(message = "Failed to read files to watch.", %error);
                         break;
                     }
 
                     debug!(message = "Reloaded paths.");
 
-                    info!("Configuration file changed.");
-                    raise_sighup();
+                    if !on_change() {
+                        return;
+                    }
                 } else {
                     debug!(message = "Ignoring event.", event = ?event)
                 }
@@ -71,16 +97,18 @@ pub fn spawn_thread<'a>(
 
         thread::sleep(RETRY_TIMEOUT);
 
-        watcher = create_watcher(&config_paths)
+        watcher = create_watcher(&paths)
             .map_err(|error| error!(message = "Failed to create file watcher.", %error))
             .ok();
 
         if watcher.is_some() {
-            // Config files could have changed while we weren't watching,
-            // so for a good measure raise SIGHUP and let reload logic
-            // determine if anything changed.
-            info!("Speculating that configuration files have changed.");
-            raise_sighup();
+            // Files could have changed while we weren't watching,
+            // so for a good measure notify about a change and let
+            // the caller determine if anything changed.
+            info!("Speculating that watched files have changed.");
+            if !on_change() {
+                return;
+            }
         }
     });
 
@@ -89,11 +117,12 @@ pub fn spawn_thread<'a>(
 
 #[cfg(windows)]
 /// Errors on Windows.
-pub fn spawn_thread<'a>(
-    _config_paths: impl IntoIterator<Item = &'a PathBuf> + 'a,
+pub fn spawn_watcher(
+    _paths: Vec<PathBuf>,
     _delay: impl Into<Option<Duration>>,
+    _on_change: impl FnMut() -> bool + Send + 'static,
 ) -> Result<(), Error> {
-    Err("Reloading config on Windows isn't currently supported. Related issue https://github.com/vectordotdev/vector/issues/938 .".into())
+    Err("Watching files on Windows isn't currently supported.".into())
 }
 
 #[cfg(unix)]
@@ -105,19 +134,17 @@ fn raise_sighup() {
 }
 
 #[cfg(unix)]
-fn create_watcher(
-    config_paths: &[PathBuf],
-) -> Result<(RecommendedWatcher, Receiver<RawEvent>), Error> {
-    info!("Creating configuration file watcher.");
+fn create_watcher(paths: &[PathBuf]) -> Result<(RecommendedWatcher, Receiver<RawEvent>), Error> {
+    info!("Creating file watcher.");
     let (sender, receiver) = channel();
     let mut watcher = raw_watcher(sender)?;
-    add_paths(&mut watcher, config_paths)?;
+    add_paths(&mut watcher, paths)?;
     Ok((watcher, receiver))
 }
 
 #[cfg(unix)]
-fn add_paths(watcher: &mut RecommendedWatcher, config_paths: &[PathBuf]) -> Result<(), Error> {
-    for path in config_paths {
+fn add_paths(watcher: &mut RecommendedWatcher, paths: &[PathBuf]) -> Result<(), Error> {
+    for path in paths {
         watcher.watch(path, RecursiveMode::NonRecursive)?;
     }
     Ok(())
@@ -24,6 +24,10 @@ enum Encoding {
         #[serde(default = "default_delimiter")]
         delimiter: char,
     },
+    /// A JSON array of objects, each object being a row.
+    Json,
+    /// One JSON object per line, each object being a row.
+    Ndjson,
 }
 
 impl Default for Encoding {
@@ -110,15 +114,105 @@ impl FileConfig {
         })
     }
 
+    /// Parses a field of a JSON row.
+    ///
+    /// The types of the JSON values are kept, and strings are only converted if the schema declares
+    /// a type for the field, such as `timestamp`.
+    fn parse_json_field(
+        &self,
+        timezone: TimeZone,
+        field: &str,
+        row: usize,
+        value: serde_json::Value,
+    ) -> Result<Value, String> {
+        match value {
+            serde_json::Value::String(value) => self.parse_column(timezone, field, row, &value),
+            value => Ok(value.into()),
+        }
+    }
+
     fn load_file(
         &self,
         timezone: TimeZone,
     ) -> crate::Result<(Vec<String>, Vec<Vec<Value>>, SystemTime)> {
-        let Encoding::Csv {
-            include_headers,
-            delimiter,
-        } = self.file.encoding;
+        let (headers, data) = match self.file.encoding {
+            Encoding::Csv {
+                include_headers,
+                delimiter,
+            } => self.load_csv(timezone, include_headers, delimiter)?,
+            Encoding::Json => {
+                let rows: Vec<serde_json::Value> = serde_json::from_reader(
+                    std::io::BufReader::new(fs::File::open(&self.file.path)?),
+                )?;
+                self.load_json_rows(timezone, rows)?
+            }
+            Encoding::Ndjson => {
+                let rows = fs::read_to_string(&self.file.path)?
+                    .lines()
+                    .filter(|line| !line.trim().is_empty())
+                    .map(serde_json::from_str)
+                    .collect::<Result<Vec<serde_json::Value>, _>>()?;
+                self.load_json_rows(timezone, rows)?
+            }
+        };
+
+        trace!(
+            "Loaded enrichment file {} with headers {:?}.",
+            self.file.path.to_str().unwrap_or("path with invalid utf"),
+            headers
+        );
+
+        let modified = fs::metadata(&self.file.path)?.modified()?;
+
+        Ok((headers, data, modified))
+    }
+
+    /// Converts JSON objects into rows.
+    ///
+    /// The headers are the fields found in any of the objects, in the order they are first found.
+    /// Fields missing from an object are null in its row.
+    fn load_json_rows(
+        &self,
+        timezone: TimeZone,
+        rows: Vec<serde_json::Value>,
+    ) -> crate::Result<(Vec<String>, Vec<Vec<Value>>)> {
+        let mut headers: Vec<String> = Vec::new();
+        let mut data = Vec::with_capacity(rows.len());
+
+        for (idx, row) in rows.into_iter().enumerate() {
+            let object = match row {
+                serde_json::Value::Object(object) => object,
+                _ => return Err(format!("row {} is not a JSON object", idx).into()),
+            };
+
+            let mut values = vec![Value::Null; headers.len()];
+            for (field, value) in object {
+                let value = self.parse_json_field(timezone, &field, idx, value)?;
+                match headers.iter().position(|header| *header == field) {
+                    Some(pos) => values[pos] = value,
+                    None => {
+                        headers.push(field);
+                        values.push(value);
+                    }
+                }
+            }
+            data.push(values);
+        }
+
+        // Rows loaded before all of the headers were found are missing the later fields.
+        for row in &mut data {
+            row.resize(headers.len(), Value::Null);
+        }
 
+        Ok((headers, data))
+    }
+
+    fn load_csv(
+        &self,
+        timezone: TimeZone,
+        include_headers: bool,
+        delimiter: char,
+    ) -> crate::Result<(Vec<String>, Vec<Vec<Value>>)> {
         let mut reader = csv::ReaderBuilder::new()
             .has_headers(include_headers)
             .delimiter(delimiter as u8)
@@ -150,15 +244,7 @@ impl FileConfig {
             })
             .collect::<crate::Result<Vec<_>>>()?;
 
-        trace!(
-            "Loaded enrichment file {} with headers {:?}.",
-            self.file.path.to_str().unwrap_or("path with invalid utf"),
-            headers
-        );
-
-        let modified = fs::metadata(&self.file.path)?.modified()?;
-
-        Ok((headers, data, modified))
+        Ok((headers, data))
     }
 }
 
@@ -521,6 +607,7 @@ impl std::fmt::Debug for File {
 #[cfg(test)]
 mod tests {
     use chrono::TimeZone;
+    use vector_common::btreemap;
 
     use super::*;
 
@@ -945,4 +1032,100 @@ mod tests {
             file.find_table_row(Case::Sensitive, &[condition], None, Some(handle))
         );
     }
+
+    fn load_json(encoding: Encoding, schema: HashMap<String, String>, contents: &str) -> File {
+        let path = crate::test_util::temp_file();
+        fs::write(&path, contents).unwrap();
+        let config = FileConfig {
+            file: FileC { path, encoding },
+            schema,
+        };
+
+        let (headers, data, modified) = config.load_file(Default::default()).unwrap();
+        File::new(config, modified, data, headers)
+    }
+
+    #[test]
+    fn loads_json_with_types() {
+        let mut schema = HashMap::new();
+        schema.insert("seen".to_string(), "timestamp".to_string());
+        let file = load_json(
+            Encoding::Json,
+            schema,
+            r#"[
+                {"name": "zip", "count": 3, "enabled": true, "seen": "2020-03-05T00:00:00Z", "note": "2021-01-01T00:00:00Z"},
+                {"name": "zup", "details": {"color": "red", "sizes": [1, 2]}}
+            ]"#,
+        );
+
+        let condition = Condition::Equals {
+            field: "count",
+            value: Value::from(3),
+        };
+        let row = file
+            .find_table_row(Case::Sensitive, &[condition], None, None)
+            .unwrap();
+        assert_eq!(Some(&Value::from("zip")), row.get("name"));
+        assert_eq!(Some(&Value::from(true)), row.get("enabled"));
+        assert_eq!(
+            Some(&Value::from(chrono::Utc.ymd(2020, 3, 5).and_hms(0, 0, 0))),
+            row.get("seen")
+        );
+        // Fields not declared as timestamps in the schema are kept as strings.
+        assert_eq!(Some(&Value::from("2021-01-01T00:00:00Z")), row.get("note"));
+        assert_eq!(Some(&Value::Null), row.get("details"));
+
+        let condition = Condition::Equals {
+            field: "name",
+            value: Value::from("zup"),
+        };
+        let row = file
+            .find_table_row(Case::Sensitive, &[condition], None, None)
+            .unwrap();
+        let details: BTreeMap<String, Value> = btreemap! {
+            "color" => "red",
+            "sizes" => vec![1, 2],
+        };
+        assert_eq!(Some(&Value::from(details)), row.get("details"));
+        assert_eq!(Some(&Value::Null), row.get("count"));
+    }
+
+    #[test]
+    fn loads_ndjson() {
+        let file = load_json(
+            Encoding::Ndjson,
+            HashMap::new(),
+            "{\"name\": \"zip\", \"count\": 3}\n\n{\"name\": \"zup\", \"count\": 4}\n",
+        );
+
+        let condition = Condition::Equals {
+            field: "name",
+            value: Value::from("zup"),
+        };
+        let row = file
+            .find_table_row(Case::Insensitive, &[condition], None, None)
+            .unwrap();
+        assert_eq!(Some(&Value::from(4)), row.get("count"));
+    }
+
+    #[test]
+    fn rejects_json_rows_that_are_not_objects() {
+        let path = crate::test_util::temp_file();
+        fs::write(&path, r#"[{"name": "zip"}, "zup"]"#).unwrap();
+        let config = FileConfig {
+            file: FileC {
+                path,
+                encoding: Encoding::Json,
+            },
+            schema: HashMap::new(),
+        };
+
+        assert_eq!(
+            "row 1 is not a JSON object",
+            config
+                .load_file(Default::default())
+                .unwrap_err()
+                .to_string()
+        );
+    }
 }
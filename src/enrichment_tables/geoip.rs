@@ -1,20 +1,17 @@
-use std::{collections::BTreeMap, fs, net::IpAddr, sync::Arc, time::SystemTime};
+use std::{collections::BTreeMap, net::IpAddr, sync::Arc};
 
 use enrichment::{Case, Condition, IndexHandle, Table};
 use maxminddb::{
-    geoip2::{City, Isp},
-    MaxMindDBError, Reader,
+    geoip2::{AnonymousIp, City, ConnectionType, Isp},
+    MaxMindDBError,
 };
 use serde::{Deserialize, Serialize};
 use value::Value;
 
-use crate::config::{EnrichmentTableConfig, EnrichmentTableDescription, GenerateConfig};
-
-// MaxMind GeoIP database files have a type field we can use to recognize specific
-// products. If we encounter one of these two types, we look for ASN/ISP information;
-// otherwise we expect to be working with a City database.
-const ASN_DATABASE_TYPE: &str = "GeoLite2-ASN";
-const ISP_DATABASE_TYPE: &str = "GeoIP2-ISP";
+use crate::{
+    common::geoip::{Database, DatabaseKind},
+    config::{EnrichmentTableConfig, EnrichmentTableDescription, GenerateConfig},
+};
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct GeoipConfig {
@@ -60,24 +57,31 @@ impl EnrichmentTableConfig for GeoipConfig {
 #[derive(Clone)]
 pub struct Geoip {
     config: GeoipConfig,
-    dbreader: Arc<maxminddb::Reader<Vec<u8>>>,
-    last_modified: SystemTime,
+    database: Arc<Database>,
 }
 
 impl Geoip {
     pub fn new(config: GeoipConfig) -> crate::Result<Self> {
         let table = Geoip {
-            last_modified: fs::metadata(&config.path)?.modified()?,
-            dbreader: Arc::new(Reader::open_readfile(config.path.clone())?),
+            database: Arc::new(Database::open(&config.path)?),
             config,
         };
 
         // Check if we can read database with dummy Ip.
         let ip = IpAddr::V4(std::net::Ipv4Addr::new(0, 0, 0, 0));
-        let result = if table.has_isp_db() {
-            table.dbreader.lookup::<Isp>(ip).map(|_| ())
-        } else {
-            table.dbreader.lookup::<City>(ip).map(|_| ())
+        let result = match table.database.kind {
+            DatabaseKind::Asn | DatabaseKind::Isp => {
+                table.database.reader.lookup::<Isp>(ip).map(|_| ())
+            }
+            DatabaseKind::AnonymousIp => {
+                table.database.reader.lookup::<AnonymousIp>(ip).map(|_| ())
+            }
+            DatabaseKind::ConnectionType => table
+                .database
+                .reader
+                .lookup::<ConnectionType>(ip)
+                .map(|_| ()),
+            DatabaseKind::City => table.database.reader.lookup::<City>(ip).map(|_| ()),
         };
 
         match result {
@@ -88,11 +92,6 @@ impl Geoip {
         Ok(table)
     }
 
-    fn has_isp_db(&self) -> bool {
-        self.dbreader.metadata.database_type == ASN_DATABASE_TYPE
-            || self.dbreader.metadata.database_type == ISP_DATABASE_TYPE
-    }
-
     fn lookup(&self, ip: IpAddr, select: Option<&[String]>) -> Option<BTreeMap<String, Value>> {
         let mut map = BTreeMap::new();
         let mut add_field = |key: &str, value: Option<Value>| {
@@ -104,105 +103,142 @@ impl Geoip {
             }
         };
 
-        if self.has_isp_db() {
-            let data = self.dbreader.lookup::<Isp>(ip).ok()?;
-
-            add_field(
-                "autonomous_system_number",
-                data.autonomous_system_number.map(Into::into),
-            );
-            add_field(
-                "autonomous_system_organization",
-                data.autonomous_system_organization.map(Into::into),
-            );
-
-            add_field("isp", data.isp.map(Into::into));
-
-            add_field("organization", data.organization.map(Into::into));
-        } else {
-            let data = self.dbreader.lookup::<City>(ip).ok()?;
-
-            add_field(
-                "city_name",
-                data.city
-                    .as_ref()
-                    .and_then(|c| c.names.as_ref())
-                    .and_then(|names| names.get(&*self.config.locale))
-                    .map(|&name| name.into()),
-            );
-
-            add_field(
-                "continent_code",
-                data.continent.and_then(|c| c.code).map(Into::into),
-            );
-
-            let country = data.country.as_ref();
-            add_field(
-                "country_code",
-                country.and_then(|country| country.iso_code).map(Into::into),
-            );
-            add_field(
-                "country_name",
-                country
-                    .and_then(|country| {
-                        country
-                            .names
-                            .as_ref()
-                            .and_then(|names| names.get(&*self.config.locale))
-                    })
-                    .map(|&name| name.into()),
-            );
-
-            let location = data.location.as_ref();
-            add_field(
-                "timezone",
-                location
-                    .and_then(|location| location.time_zone)
-                    .map(Into::into),
-            );
-            add_field(
-                "latitude",
-                location
-                    .and_then(|location| location.latitude)
-                    .map(Into::into),
-            );
-            add_field(
-                "longitude",
-                location
-                    .and_then(|location| location.longitude)
-                    .map(Into::into),
-            );
-            add_field(
-                "metro_code",
-                location
-                    .and_then(|location| location.metro_code)
-                    .map(Into::into),
-            );
-
-            // last subdivision is most specific per https://github.com/maxmind/GeoIP2-java/blob/39385c6ce645374039450f57208b886cf87ade47/src/main/java/com/maxmind/geoip2/model/AbstractCityResponse.java#L96-L107
-            let subdivision = data.subdivisions.as_ref().and_then(|s| s.last());
-            add_field(
-                "region_name",
-                subdivision
-                    .and_then(|subdivision| {
-                        subdivision
-                            .names
-                            .as_ref()
-                            .and_then(|names| names.get(&*self.config.locale))
-                    })
-                    .map(|&name| name.into()),
-            );
-            add_field(
-                "region_code",
-                subdivision
-                    .and_then(|subdivision| subdivision.iso_code)
-                    .map(Into::into),
-            );
-
-            add_field(
-                "postal_code",
-                data.postal.and_then(|p| p.code).map(Into::into),
-            );
+        match self.database.kind {
+            DatabaseKind::Asn | DatabaseKind::Isp => {
+                let data = self.database.reader.lookup::<Isp>(ip).ok()?;
+
+                add_field(
+                    "autonomous_system_number",
+                    data.autonomous_system_number.map(Into::into),
+                );
+                add_field(
+                    "autonomous_system_organization",
+                    data.autonomous_system_organization.map(Into::into),
+                );
+
+                add_field("isp", data.isp.map(Into::into));
+
+                add_field("organization", data.organization.map(Into::into));
+            }
+            DatabaseKind::AnonymousIp => {
+                let data = self.database.reader.lookup::<AnonymousIp>(ip).ok()?;
+
+                // Flags that don't apply to an address are left out of the database.
+                add_field(
+                    "is_anonymous",
+                    Some(data.is_anonymous.unwrap_or(false).into()),
+                );
+                add_field(
+                    "is_anonymous_vpn",
+                    Some(data.is_anonymous_vpn.unwrap_or(false).into()),
+                );
+                add_field(
+                    "is_hosting_provider",
+                    Some(data.is_hosting_provider.unwrap_or(false).into()),
+                );
+                add_field(
+                    "is_public_proxy",
+                    Some(data.is_public_proxy.unwrap_or(false).into()),
+                );
+                add_field(
+                    "is_residential_proxy",
+                    Some(data.is_residential_proxy.unwrap_or(false).into()),
+                );
+                add_field(
+                    "is_tor_exit_node",
+                    Some(data.is_tor_exit_node.unwrap_or(false).into()),
+                );
+            }
+            DatabaseKind::ConnectionType => {
+                let data = self.database.reader.lookup::<ConnectionType>(ip).ok()?;
+
+                add_field("connection_type", data.connection_type.map(Into::into));
+            }
+            DatabaseKind::City => {
+                let data = self.database.reader.lookup::<City>(ip).ok()?;
+
+                add_field(
+                    "city_name",
+                    data.city
+                        .as_ref()
+                        .and_then(|c| c.names.as_ref())
+                        .and_then(|names| names.get(&*self.config.locale))
+                        .map(|&name| name.into()),
+                );
+
+                add_field(
+                    "continent_code",
+                    data.continent.and_then(|c| c.code).map(Into::into),
+                );
+
+                let country = data.country.as_ref();
+                add_field(
+                    "country_code",
+                    country.and_then(|country| country.iso_code).map(Into::into),
+                );
+                add_field(
+                    "country_name",
+                    country
+                        .and_then(|country| {
+                            country
+                                .names
+                                .as_ref()
+                                .and_then(|names| names.get(&*self.config.locale))
+                        })
+                        .map(|&name| name.into()),
+                );
+
+                let location = data.location.as_ref();
+                add_field(
+                    "timezone",
+                    location
+                        .and_then(|location| location.time_zone)
+                        .map(Into::into),
+                );
+                add_field(
+                    "latitude",
+                    location
+                        .and_then(|location| location.latitude)
+                        .map(Into::into),
+                );
+                add_field(
+                    "longitude",
+                    location
+                        .and_then(|location| location.longitude)
+                        .map(Into::into),
+                );
+                add_field(
+                    "metro_code",
+                    location
+                        .and_then(|location| location.metro_code)
+                        .map(Into::into),
+                );
+
+                // last subdivision is most specific per https://github.com/maxmind/GeoIP2-java/blob/39385c6ce645374039450f57208b886cf87ade47/src/main/java/com/maxmind/geoip2/model/AbstractCityResponse.java#L96-L107
+                let subdivision = data.subdivisions.as_ref().and_then(|s| s.last());
+                add_field(
+                    "region_name",
+                    subdivision
+                        .and_then(|subdivision| {
+                            subdivision
+                                .names
+                                .as_ref()
+                                .and_then(|names| names.get(&*self.config.locale))
+                        })
+                        .map(|&name| name.into()),
+                );
+                add_field(
+                    "region_code",
+                    subdivision
+                        .and_then(|subdivision| subdivision.iso_code)
+                        .map(Into::into),
+                );
+
+                add_field(
+                    "postal_code",
+                    data.postal.and_then(|p| p.code).map(Into::into),
+                );
+            }
         }
 
         Some(map)
@@ -278,9 +314,7 @@ impl Table for Geoip {
 
     /// Returns true if the underlying data has changed and the table needs reloading.
     fn needs_reload(&self) -> bool {
-        matches!(fs::metadata(&self.config.path)
-            .and_then(|metadata| metadata.modified()),
-            Ok(modified) if modified > self.last_modified)
+        self.database.has_changed(&self.config.path)
     }
 }
 
@@ -400,6 +434,80 @@ mod tests {
         assert!(values.is_none());
     }
 
+    #[test]
+    fn anonymous_ip_lookup() {
+        let values = find("81.2.69.142", "tests/data/GeoIP2-Anonymous-IP-Test.mmdb").unwrap();
+
+        let mut expected = BTreeMap::<String, Value>::new();
+        expected.insert("is_anonymous".to_string(), true.into());
+        expected.insert("is_anonymous_vpn".to_string(), true.into());
+        expected.insert("is_hosting_provider".to_string(), true.into());
+        expected.insert("is_public_proxy".to_string(), true.into());
+        expected.insert("is_residential_proxy".to_string(), true.into());
+        expected.insert("is_tor_exit_node".to_string(), true.into());
+
+        assert_eq!(values, expected);
+    }
+
+    #[test]
+    fn anonymous_ip_lookup_partial_results() {
+        let values = find("186.30.236.233", "tests/data/GeoIP2-Anonymous-IP-Test.mmdb").unwrap();
+
+        let mut expected = BTreeMap::<String, Value>::new();
+        expected.insert("is_anonymous".to_string(), true.into());
+        expected.insert("is_anonymous_vpn".to_string(), false.into());
+        expected.insert("is_hosting_provider".to_string(), false.into());
+        expected.insert("is_public_proxy".to_string(), true.into());
+        expected.insert("is_residential_proxy".to_string(), false.into());
+        expected.insert("is_tor_exit_node".to_string(), false.into());
+
+        assert_eq!(values, expected);
+    }
+
+    #[test]
+    fn anonymous_ip_lookup_no_results() {
+        let values = find("10.1.12.1", "tests/data/GeoIP2-Anonymous-IP-Test.mmdb");
+
+        assert!(values.is_none());
+    }
+
+    #[test]
+    fn connection_type_lookup() {
+        let values = find("80.214.0.1", "tests/data/GeoIP2-Connection-Type-Test.mmdb").unwrap();
+
+        let mut expected = BTreeMap::<String, Value>::new();
+        expected.insert("connection_type".to_string(), "Cellular".into());
+
+        assert_eq!(values, expected);
+    }
+
+    #[test]
+    fn connection_type_lookup_no_results() {
+        let values = find("10.1.12.1", "tests/data/GeoIP2-Connection-Type-Test.mmdb");
+
+        assert!(values.is_none());
+    }
+
+    #[test]
+    fn needs_reload_when_replaced() {
+        let path = crate::test_util::temp_file();
+        fs::copy("tests/data/GeoIP2-City-Test.mmdb", &path).unwrap();
+
+        let table = Geoip::new(GeoipConfig {
+            path: path.to_str().unwrap().to_string(),
+            locale: default_locale(),
+        })
+        .unwrap();
+        assert!(!table.needs_reload());
+
+        // Move the new database into place, as database updaters do.
+        let replacement = crate::test_util::temp_file();
+        fs::copy("tests/data/GeoIP2-ISP-Test.mmdb", &replacement).unwrap();
+        fs::rename(&replacement, &path).unwrap();
+
+        assert!(table.needs_reload());
+    }
+
     fn find(ip: &str, database: &str) -> Option<BTreeMap<String, Value>> {
         find_select(ip, database, None)
     }
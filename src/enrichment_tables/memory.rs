@@ -0,0 +1,593 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    num::NonZeroUsize,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use enrichment::{Case, Condition, IndexHandle, Table};
+use futures::{future, stream::BoxStream, FutureExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use value::Value;
+use vector_core::{internal_event::EventsSent, ByteSizeOf};
+
+use crate::{
+    config::{
+        AcknowledgementsConfig, EnrichmentTableConfig, EnrichmentTableDescription, GenerateConfig,
+        Input, SinkConfig, SinkContext,
+    },
+    event::{Event, EventStatus, Finalizable},
+    internal_events::{MemoryEnrichmentTableEvicted, MemoryEnrichmentTableKeyMissing},
+    sinks::{util::StreamSink, Healthcheck, VectorSink},
+};
+
+fn default_key_field() -> String {
+    "key".to_string()
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct MemoryConfig {
+    /// The field of the incoming events holding the key of the row to upsert.
+    #[serde(default = "default_key_field")]
+    #[derivative(Default(value = "default_key_field()"))]
+    pub key_field: String,
+    /// The number of seconds a row is kept after it was last upserted.
+    ///
+    /// By default, rows never expire.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ttl_secs: Option<u64>,
+    /// The maximum number of rows kept in the table. Once reached, the rows that were upserted the
+    /// longest time ago are evicted first.
+    ///
+    /// By default, the number of rows is unbounded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_entries: Option<NonZeroUsize>,
+
+    /// The rows of the table, shared by the table and the sink writing to it.
+    #[serde(skip)]
+    store: Arc<Store>,
+}
+
+impl GenerateConfig for MemoryConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            ttl_secs: Some(3600),
+            max_entries: NonZeroUsize::new(100_000),
+            ..Self::default()
+        })
+        .unwrap()
+    }
+}
+
+inventory::submit! {
+    EnrichmentTableDescription::new::<MemoryConfig>("memory")
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "memory")]
+impl EnrichmentTableConfig for MemoryConfig {
+    async fn build(
+        &self,
+        _: &crate::config::GlobalOptions,
+    ) -> crate::Result<Box<dyn Table + Send + Sync>> {
+        Ok(Box::new(Memory::new(self.clone())))
+    }
+
+    fn sink_config(&self) -> Option<Box<dyn SinkConfig>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+// The sink writing to the table is generated from the `inputs` of the enrichment table, rather than
+// being configured directly, so it is not registered as a sink description, and the config compiler
+// rejects it when it is configured as a sink.
+#[async_trait::async_trait]
+#[typetag::serde(name = "memory_enrichment_table")]
+impl SinkConfig for MemoryConfig {
+    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let sink = MemorySink {
+            config: self.clone(),
+        };
+        let healthcheck = future::ok(()).boxed();
+
+        Ok((VectorSink::Stream(Box::new(sink)), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "memory_enrichment_table"
+    }
+
+    fn is_enrichment_table_sink(&self) -> bool {
+        true
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &AcknowledgementsConfig::DEFAULT
+    }
+}
+
+/// A row of the table.
+#[derive(Debug)]
+struct Row {
+    data: BTreeMap<String, Value>,
+    upserted_at: Instant,
+    /// The position of the row in the upsert order.
+    sequence: u64,
+}
+
+#[derive(Debug, Default)]
+struct Store {
+    inner: RwLock<StoreInner>,
+}
+
+#[derive(Debug, Default)]
+struct StoreInner {
+    rows: HashMap<String, Row>,
+    /// The keys of the rows, in the order they were last upserted.
+    order: BTreeMap<u64, String>,
+    next_sequence: u64,
+}
+
+impl StoreInner {
+    fn upsert(&mut self, key: String, data: BTreeMap<String, Value>, now: Instant) {
+        let sequence = self.next_sequence;
+        self.next_sequence += 1;
+        self.order.insert(sequence, key.clone());
+
+        let row = Row {
+            data,
+            upserted_at: now,
+            sequence,
+        };
+        if let Some(previous) = self.rows.insert(key, row) {
+            self.order.remove(&previous.sequence);
+        }
+    }
+
+    /// Removes the expired rows, and the oldest rows in excess of the maximum number of rows.
+    fn evict(&mut self, ttl: Option<Duration>, max_entries: Option<NonZeroUsize>, now: Instant) {
+        let mut expired = 0;
+        let mut excess = 0;
+
+        while let Some((&sequence, key)) = self.order.iter().next() {
+            let row = &self.rows[key];
+            if ttl.map_or(false, |ttl| now.duration_since(row.upserted_at) >= ttl) {
+                expired += 1;
+            } else if max_entries.map_or(false, |max| self.rows.len() > max.get()) {
+                excess += 1;
+            } else {
+                break;
+            }
+
+            let key = self.order.remove(&sequence).expect("order entry exists");
+            self.rows.remove(&key);
+        }
+
+        if expired > 0 {
+            emit!(MemoryEnrichmentTableEvicted {
+                count: expired,
+                reason: "expired",
+            });
+        }
+        if excess > 0 {
+            emit!(MemoryEnrichmentTableEvicted {
+                count: excess,
+                reason: "max_entries",
+            });
+        }
+    }
+}
+
+/// An enrichment table holding rows upserted from the events of its `inputs`.
+///
+/// The rows are kept in memory only, and are lost when Vector restarts or when the configuration
+/// of the table changes.
+#[derive(Clone)]
+pub struct Memory {
+    config: MemoryConfig,
+    indexes: Vec<(Case, Vec<String>)>,
+}
+
+impl Memory {
+    pub fn new(config: MemoryConfig) -> Self {
+        Self {
+            config,
+            indexes: Vec::new(),
+        }
+    }
+
+    fn ttl(&self) -> Option<Duration> {
+        self.config.ttl_secs.map(Duration::from_secs)
+    }
+
+    fn is_live(&self, row: &Row, now: Instant) -> bool {
+        self.ttl()
+            .map_or(true, |ttl| now.duration_since(row.upserted_at) < ttl)
+    }
+}
+
+fn row_matches(case: Case, condition: &[Condition<'_>], row: &BTreeMap<String, Value>) -> bool {
+    condition.iter().all(|condition| match condition {
+        Condition::Equals { field, value } => match (row.get(*field), value) {
+            (Some(Value::Bytes(bytes)), Value::Bytes(value)) if case == Case::Insensitive => {
+                String::from_utf8_lossy(bytes).to_lowercase()
+                    == String::from_utf8_lossy(value).to_lowercase()
+            }
+            (Some(field), value) => field == value,
+            (None, _) => false,
+        },
+        Condition::BetweenDates { field, from, to } => match row.get(*field) {
+            Some(Value::Timestamp(date)) => from <= date && date <= to,
+            _ => false,
+        },
+    })
+}
+
+fn select_fields(
+    select: Option<&[String]>,
+    row: &BTreeMap<String, Value>,
+) -> BTreeMap<String, Value> {
+    match select {
+        Some(select) => row
+            .iter()
+            .filter(|(field, _)| select.contains(field))
+            .map(|(field, value)| (field.clone(), value.clone()))
+            .collect(),
+        None => row.clone(),
+    }
+}
+
+impl Table for Memory {
+    fn find_table_row<'a>(
+        &self,
+        case: Case,
+        condition: &'a [Condition<'a>],
+        select: Option<&[String]>,
+        index: Option<IndexHandle>,
+    ) -> Result<BTreeMap<String, Value>, String> {
+        let mut rows = self.find_table_rows(case, condition, select, index)?;
+
+        match rows.pop() {
+            Some(row) if rows.is_empty() => Ok(row),
+            Some(_) => Err("more than one row found".to_string()),
+            None => Err("no rows found".to_string()),
+        }
+    }
+
+    /// Looks up rows by their key directly when the conditions contain a case sensitive match on
+    /// the key field, and scans all of the rows otherwise.
+    fn find_table_rows<'a>(
+        &self,
+        case: Case,
+        condition: &'a [Condition<'a>],
+        select: Option<&[String]>,
+        _: Option<IndexHandle>,
+    ) -> Result<Vec<BTreeMap<String, Value>>, String> {
+        let now = Instant::now();
+        let store = self
+            .config
+            .store
+            .inner
+            .read()
+            .expect("memory table lock poisoned");
+
+        let key = condition.iter().find_map(|condition| match condition {
+            Condition::Equals { field, value } if *field == self.config.key_field => {
+                Some(value.to_string_lossy())
+            }
+            _ => None,
+        });
+
+        let matches =
+            |row: &&Row| self.is_live(row, now) && row_matches(case, condition, &row.data);
+        Ok(match key {
+            Some(key) if case == Case::Sensitive => store
+                .rows
+                .get(&key)
+                .filter(matches)
+                .map(|row| select_fields(select, &row.data))
+                .into_iter()
+                .collect(),
+            _ => store
+                .rows
+                .values()
+                .filter(matches)
+                .map(|row| select_fields(select, &row.data))
+                .collect(),
+        })
+    }
+
+    /// Rows can be upserted with any fields, so any index is accepted. Only lookups by the key
+    /// field are actually indexed.
+    fn add_index(&mut self, case: Case, fields: &[&str]) -> Result<IndexHandle, String> {
+        let fields = fields
+            .iter()
+            .map(|field| field.to_string())
+            .collect::<Vec<_>>();
+        let pos = match self
+            .indexes
+            .iter()
+            .position(|index| index.0 == case && index.1 == fields)
+        {
+            Some(pos) => pos,
+            None => {
+                self.indexes.push((case, fields));
+                self.indexes.len() - 1
+            }
+        };
+        Ok(IndexHandle(pos))
+    }
+
+    fn index_fields(&self) -> Vec<(Case, Vec<String>)> {
+        self.indexes.clone()
+    }
+
+    /// The rows are only ever upserted by the sink, so the table never needs reloading.
+    fn needs_reload(&self) -> bool {
+        false
+    }
+}
+
+impl std::fmt::Debug for Memory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let rows = self
+            .config
+            .store
+            .inner
+            .read()
+            .map_or(0, |store| store.rows.len());
+        write!(f, "Memory {} row(s)", rows)
+    }
+}
+
+/// Upserts the incoming events into the rows of the table.
+struct MemorySink {
+    config: MemoryConfig,
+}
+
+#[async_trait]
+impl StreamSink<Event> for MemorySink {
+    async fn run(self: Box<Self>, mut input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let ttl = self.config.ttl_secs.map(Duration::from_secs);
+
+        while let Some(mut event) = input.next().await {
+            let finalizers = event.take_finalizers();
+            let byte_size = event.size_of();
+            let log = event.into_log();
+
+            let key = match log.get(self.config.key_field.as_str()) {
+                Some(key) => key.to_string_lossy(),
+                None => {
+                    emit!(MemoryEnrichmentTableKeyMissing {
+                        field: &self.config.key_field
+                    });
+                    finalizers.update_status(EventStatus::Rejected);
+                    continue;
+                }
+            };
+            let data = log.as_map().cloned().unwrap_or_default();
+
+            let now = Instant::now();
+            {
+                let mut store = self
+                    .config
+                    .store
+                    .inner
+                    .write()
+                    .expect("memory table lock poisoned");
+                store.upsert(key, data, now);
+                store.evict(ttl, self.config.max_entries, now);
+            }
+
+            finalizers.update_status(EventStatus::Delivered);
+            emit!(EventsSent {
+                count: 1,
+                byte_size,
+                output: None,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::stream;
+
+    use super::*;
+    use crate::event::LogEvent;
+
+    fn log(key: &str, ip: &str) -> Event {
+        let mut log = LogEvent::default();
+        log.insert("key", key);
+        log.insert("ip", ip);
+        Event::from(log)
+    }
+
+    async fn upsert(config: &MemoryConfig, events: Vec<Event>) {
+        let sink = Box::new(MemorySink {
+            config: config.clone(),
+        });
+        sink.run(stream::iter(events).boxed()).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn upserts_rows() {
+        let config = MemoryConfig::default();
+        let table = Memory::new(config.clone());
+
+        upsert(
+            &config,
+            vec![
+                log("alice", "10.0.0.1"),
+                log("bob", "10.0.0.2"),
+                log("alice", "10.0.0.3"),
+            ],
+        )
+        .await;
+
+        let condition = Condition::Equals {
+            field: "key",
+            value: Value::from("alice"),
+        };
+        assert_eq!(
+            Ok(BTreeMap::from([(
+                "ip".to_string(),
+                Value::from("10.0.0.3")
+            )])),
+            table.find_table_row(
+                Case::Sensitive,
+                &[condition],
+                Some(&["ip".to_string()]),
+                None
+            )
+        );
+
+        let condition = Condition::Equals {
+            field: "ip",
+            value: Value::from("10.0.0.2"),
+        };
+        assert_eq!(
+            Ok(vec![BTreeMap::from([
+                ("ip".to_string(), Value::from("10.0.0.2")),
+                ("key".to_string(), Value::from("bob")),
+            ])]),
+            table.find_table_rows(Case::Sensitive, &[condition], None, None)
+        );
+    }
+
+    #[tokio::test]
+    async fn finds_rows_case_insensitive() {
+        let config = MemoryConfig::default();
+        let table = Memory::new(config.clone());
+
+        upsert(&config, vec![log("Alice", "10.0.0.1")]).await;
+
+        let condition = Condition::Equals {
+            field: "key",
+            value: Value::from("alice"),
+        };
+        assert_eq!(
+            Err("no rows found".to_string()),
+            table.find_table_row(Case::Sensitive, &[condition.clone()], None, None)
+        );
+        assert!(table
+            .find_table_row(Case::Insensitive, &[condition], None, None)
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn evicts_oldest_rows() {
+        let config = MemoryConfig {
+            max_entries: NonZeroUsize::new(2),
+            ..Default::default()
+        };
+        let table = Memory::new(config.clone());
+
+        upsert(
+            &config,
+            vec![
+                log("alice", "10.0.0.1"),
+                log("bob", "10.0.0.2"),
+                log("alice", "10.0.0.3"),
+                log("carol", "10.0.0.4"),
+            ],
+        )
+        .await;
+
+        let keys = table
+            .find_table_rows(Case::Sensitive, &[], Some(&["key".to_string()]), None)
+            .unwrap()
+            .into_iter()
+            .map(|row| row["key"].to_string_lossy())
+            .collect::<std::collections::BTreeSet<_>>();
+        assert_eq!(keys, ["alice", "carol"].map(String::from).into());
+    }
+
+    #[tokio::test]
+    async fn expires_rows() {
+        let config = MemoryConfig {
+            ttl_secs: Some(0),
+            ..Default::default()
+        };
+        let table = Memory::new(config.clone());
+
+        upsert(&config, vec![log("alice", "10.0.0.1")]).await;
+
+        let condition = Condition::Equals {
+            field: "key",
+            value: Value::from("alice"),
+        };
+        assert_eq!(
+            Ok(vec![]),
+            table.find_table_rows(Case::Sensitive, &[condition], None, None)
+        );
+    }
+
+    #[test]
+    fn adds_sink_for_inputs() {
+        let config = crate::config::load_from_str(
+            indoc::indoc! {r#"
+                [sources.in]
+                type = "basic_source"
+
+                [enrichment_tables.last_seen]
+                type = "memory"
+                inputs = ["in"]
+                ttl_secs = 60
+            "#},
+            crate::config::Format::Toml,
+        )
+        .unwrap();
+
+        let sink = config
+            .sink(&crate::config::ComponentKey::from("last_seen"))
+            .expect("missing enrichment table sink");
+        assert_eq!(sink.inner.sink_type(), "memory_enrichment_table");
+        assert_eq!(sink.inputs, vec!["in".into()]);
+    }
+
+    #[test]
+    fn rejects_sink_configured_directly() {
+        let errors = crate::config::load_from_str(
+            indoc::indoc! {r#"
+                [sources.in]
+                type = "basic_source"
+
+                [sinks.last_seen]
+                type = "memory_enrichment_table"
+                inputs = ["in"]
+            "#},
+            crate::config::Format::Toml,
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            errors,
+            vec!["Sink \"last_seen\" writes to an enrichment table, so it can't be configured directly. Set the `inputs` of the enrichment table instead.".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_events_without_key() {
+        let config = MemoryConfig::default();
+        let table = Memory::new(config.clone());
+
+        let mut event = LogEvent::default();
+        event.insert("ip", "10.0.0.1");
+        upsert(&config, vec![event.into()]).await;
+
+        assert_eq!(
+            Ok(vec![]),
+            table.find_table_rows(Case::Sensitive, &[], None, None)
+        );
+    }
+}
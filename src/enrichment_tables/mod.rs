@@ -5,3 +5,9 @@ pub mod file;
 
 #[cfg(feature = "enrichment-tables-geoip")]
 pub mod geoip;
+
+#[cfg(feature = "enrichment-tables-memory")]
+pub mod memory;
+
+#[cfg(feature = "enrichment-tables-sqlite")]
+pub mod sqlite;
@@ -0,0 +1,637 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use enrichment::{Case, Condition, IndexHandle, Table};
+use ordered_float::NotNan;
+use rusqlite::{
+    params_from_iter,
+    types::{Value as SqlValue, ValueRef},
+    Connection, DatabaseName, OpenFlags,
+};
+use serde::{Deserialize, Serialize};
+use tempfile::NamedTempFile;
+use tracing::trace;
+use value::Value;
+
+use crate::config::{EnrichmentTableConfig, EnrichmentTableDescription, GenerateConfig};
+
+/// The format SQLite uses for dates and times, as returned by its `datetime` function.
+const DATETIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// The number of idle connections kept open for lookups. More connections are opened while there
+/// are more concurrent lookups, and closed once they are done.
+const MAX_IDLE_CONNECTIONS: usize = 16;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct SqliteConfig {
+    /// The path of the SQLite database file.
+    pub path: PathBuf,
+    /// The table, or view, of the database to look up rows in.
+    pub table: String,
+}
+
+impl GenerateConfig for SqliteConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            path: "/path/to/lookup.db".into(),
+            table: "assets".to_string(),
+        })
+        .unwrap()
+    }
+}
+
+inventory::submit! {
+    EnrichmentTableDescription::new::<SqliteConfig>("sqlite")
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "sqlite")]
+impl EnrichmentTableConfig for SqliteConfig {
+    async fn build(
+        &self,
+        _: &crate::config::GlobalOptions,
+    ) -> crate::Result<Box<dyn Table + Send + Sync>> {
+        Ok(Box::new(Sqlite::new(self.clone())?))
+    }
+}
+
+/// A column of the looked up table.
+#[derive(Clone, Debug)]
+struct Column {
+    name: String,
+    /// Whether the column is declared with a date or time type, in which case its values are
+    /// returned as timestamps.
+    is_timestamp: bool,
+    /// Whether the column is declared with an integer type, in which case dates are compared as
+    /// Unix timestamps rather than as text.
+    is_integer: bool,
+}
+
+/// Read-only connections to the copy of a database, shared by all the clones of a table so that
+/// concurrent lookups don't wait on each other.
+struct ConnectionPool {
+    idle: Mutex<Vec<Connection>>,
+    // Declared last so that the connections are closed before the file is removed.
+    copy: NamedTempFile,
+}
+
+impl ConnectionPool {
+    fn get(&self) -> rusqlite::Result<Connection> {
+        let idle = self
+            .idle
+            .lock()
+            .expect("SQLite connection pool lock poisoned")
+            .pop();
+        match idle {
+            Some(connection) => Ok(connection),
+            None => Connection::open_with_flags(
+                self.copy.path(),
+                OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+            ),
+        }
+    }
+
+    fn put(&self, connection: Connection) {
+        let mut idle = self
+            .idle
+            .lock()
+            .expect("SQLite connection pool lock poisoned");
+        if idle.len() < MAX_IDLE_CONNECTIONS {
+            idle.push(connection);
+        }
+    }
+
+    /// Opens a connection that can modify the copy, to create indexes.
+    fn open_writable(&self) -> rusqlite::Result<Connection> {
+        Connection::open(self.copy.path())
+    }
+}
+
+/// An enrichment table backed by a table of a SQLite database.
+///
+/// The database is copied to a temporary file when the table is built, so that the file can be
+/// replaced while it is in use, and so that the indexes needed for the lookups can be created
+/// without modifying the file. Lookups use read-only connections to the copy.
+#[derive(Clone)]
+pub struct Sqlite {
+    config: SqliteConfig,
+    last_modified: SystemTime,
+    pool: Arc<ConnectionPool>,
+    columns: Vec<Column>,
+    indexes: Vec<(Case, Vec<String>)>,
+}
+
+impl Sqlite {
+    pub fn new(config: SqliteConfig) -> crate::Result<Self> {
+        let last_modified = fs::metadata(&config.path)?.modified()?;
+
+        let copy = NamedTempFile::new()?;
+        let mut connection = Connection::open(copy.path())?;
+        connection.restore(
+            DatabaseName::Main,
+            &config.path,
+            None::<fn(rusqlite::backup::Progress)>,
+        )?;
+
+        let columns = connection
+            .prepare(&format!("PRAGMA table_info({})", quote(&config.table)))?
+            .query_map([], |row| {
+                let name: String = row.get("name")?;
+                let ty = row.get::<_, String>("type")?.to_uppercase();
+                Ok(Column {
+                    name,
+                    is_timestamp: ty.contains("DATE") || ty.contains("TIME"),
+                    is_integer: ty.contains("INT"),
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        if columns.is_empty() {
+            return Err(format!(
+                "table {:?} not found in SQLite database {:?}",
+                config.table, config.path
+            )
+            .into());
+        }
+
+        trace!(
+            "Loaded enrichment table {} from SQLite database {} with columns {:?}.",
+            config.table,
+            config.path.to_str().unwrap_or("path with invalid utf"),
+            columns
+                .iter()
+                .map(|column| &column.name)
+                .collect::<Vec<_>>(),
+        );
+
+        Ok(Self {
+            config,
+            last_modified,
+            pool: Arc::new(ConnectionPool {
+                idle: Mutex::new(vec![]),
+                copy,
+            }),
+            columns,
+            indexes: Vec::new(),
+        })
+    }
+
+    fn column(&self, name: &str) -> Result<&Column, String> {
+        self.columns
+            .iter()
+            .find(|column| column.name == name)
+            .ok_or_else(|| format!("field {} not found in table {}", name, self.config.table))
+    }
+
+    /// Builds the query selecting the rows matching all of the conditions, along with its
+    /// parameters.
+    fn query(
+        &self,
+        case: Case,
+        condition: &[Condition<'_>],
+        select: Option<&[String]>,
+    ) -> Result<(String, Vec<&Column>, Vec<SqlValue>), String> {
+        let columns = match select {
+            Some(select) => select
+                .iter()
+                .filter_map(|name| self.columns.iter().find(|column| &column.name == name))
+                .collect::<Vec<_>>(),
+            None => self.columns.iter().collect(),
+        };
+        if columns.is_empty() {
+            return Err("no selected fields found in table".to_string());
+        }
+
+        let mut sql = format!(
+            "SELECT {} FROM {}",
+            columns
+                .iter()
+                .map(|column| quote(&column.name))
+                .collect::<Vec<_>>()
+                .join(", "),
+            quote(&self.config.table)
+        );
+        let mut params = Vec::new();
+
+        for (i, condition) in condition.iter().enumerate() {
+            sql.push_str(if i == 0 { " WHERE " } else { " AND " });
+            match condition {
+                Condition::Equals { field, value } => {
+                    let column = self.column(field)?;
+                    sql.push_str(&quote(&column.name));
+                    sql.push_str(" IS ?");
+                    if case == Case::Insensitive && matches!(value, Value::Bytes(_)) {
+                        sql.push_str(" COLLATE NOCASE");
+                    }
+                    params.push(to_sql_value(value));
+                }
+                Condition::BetweenDates { field, from, to } => {
+                    let column = self.column(field)?;
+                    sql.push_str(&quote(&column.name));
+                    sql.push_str(" BETWEEN ? AND ?");
+                    if column.is_integer {
+                        params.push(SqlValue::Integer(from.timestamp()));
+                        params.push(SqlValue::Integer(to.timestamp()));
+                    } else {
+                        params.push(SqlValue::Text(from.format(DATETIME_FORMAT).to_string()));
+                        params.push(SqlValue::Text(to.format(DATETIME_FORMAT).to_string()));
+                    }
+                }
+            }
+        }
+
+        Ok((sql, columns, params))
+    }
+}
+
+impl Table for Sqlite {
+    fn find_table_row<'a>(
+        &self,
+        case: Case,
+        condition: &'a [Condition<'a>],
+        select: Option<&[String]>,
+        index: Option<IndexHandle>,
+    ) -> Result<BTreeMap<String, Value>, String> {
+        let mut rows = self.find_table_rows(case, condition, select, index)?;
+
+        match rows.pop() {
+            Some(row) if rows.is_empty() => Ok(row),
+            Some(_) => Err("more than one row found".to_string()),
+            None => Err("no rows found".to_string()),
+        }
+    }
+
+    /// Translates the conditions into a query, which uses the indexes created by `add_index` when
+    /// the conditions match their fields.
+    fn find_table_rows<'a>(
+        &self,
+        case: Case,
+        condition: &'a [Condition<'a>],
+        select: Option<&[String]>,
+        _: Option<IndexHandle>,
+    ) -> Result<Vec<BTreeMap<String, Value>>, String> {
+        let (sql, columns, params) = self.query(case, condition, select)?;
+
+        let connection = self.pool.get().map_err(|error| error.to_string())?;
+        let rows = query_rows(&connection, &sql, &columns, params);
+        self.pool.put(connection);
+
+        rows.map_err(|error| error.to_string())
+    }
+
+    /// Creates an index on the given fields of the copy of the table.
+    ///
+    /// # Errors
+    /// Errors if the fields are not in the table.
+    fn add_index(&mut self, case: Case, fields: &[&str]) -> Result<IndexHandle, String> {
+        let fields = fields
+            .iter()
+            .map(|field| self.column(field).map(|column| column.name.clone()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if let Some(pos) = self
+            .indexes
+            .iter()
+            .position(|index| index.0 == case && index.1 == fields)
+        {
+            // This index already exists
+            return Ok(IndexHandle(pos));
+        }
+
+        let collation = match case {
+            Case::Sensitive => "",
+            Case::Insensitive => " COLLATE NOCASE",
+        };
+        let sql = format!(
+            "CREATE INDEX {} ON {} ({})",
+            quote(&format!("vector_index_{}", self.indexes.len())),
+            quote(&self.config.table),
+            fields
+                .iter()
+                .map(|field| format!("{}{}", quote(field), collation))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        self.pool
+            .open_writable()
+            .and_then(|connection| connection.execute(&sql, []))
+            .map_err(|error| format!("unable to create index: {}", error))?;
+
+        self.indexes.push((case, fields));
+        // The returned index handle is the position of the index in our list of indexes.
+        Ok(IndexHandle(self.indexes.len() - 1))
+    }
+
+    /// Returns a list of the field names that are in each index
+    fn index_fields(&self) -> Vec<(Case, Vec<String>)> {
+        self.indexes.clone()
+    }
+
+    /// Checks the modified timestamp of the database file to see if data has changed.
+    fn needs_reload(&self) -> bool {
+        matches!(fs::metadata(&self.config.path)
+            .and_then(|metadata| metadata.modified()),
+            Ok(modified) if modified > self.last_modified)
+    }
+}
+
+impl std::fmt::Debug for Sqlite {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Sqlite {} column(s) {} index(es)",
+            self.columns.len(),
+            self.indexes.len()
+        )
+    }
+}
+
+fn query_rows(
+    connection: &Connection,
+    sql: &str,
+    columns: &[&Column],
+    params: Vec<SqlValue>,
+) -> rusqlite::Result<Vec<BTreeMap<String, Value>>> {
+    let mut statement = connection.prepare_cached(sql)?;
+    let rows = statement
+        .query_map(params_from_iter(params), |row| {
+            columns
+                .iter()
+                .enumerate()
+                .map(|(idx, column)| {
+                    Ok((
+                        column.name.clone(),
+                        from_sql_value(column, row.get_ref(idx)?),
+                    ))
+                })
+                .collect::<rusqlite::Result<BTreeMap<_, _>>>()
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>();
+    rows
+}
+
+/// Quotes an identifier, such as the name of a table or column.
+fn quote(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+fn to_sql_value(value: &Value) -> SqlValue {
+    match value {
+        Value::Bytes(bytes) => SqlValue::Text(String::from_utf8_lossy(bytes).into_owned()),
+        Value::Integer(i) => SqlValue::Integer(*i),
+        Value::Float(f) => SqlValue::Real(f.into_inner()),
+        Value::Boolean(b) => SqlValue::Integer(*b as i64),
+        Value::Timestamp(ts) => SqlValue::Text(ts.format(DATETIME_FORMAT).to_string()),
+        Value::Null => SqlValue::Null,
+        value => SqlValue::Text(value.to_string_lossy()),
+    }
+}
+
+fn from_sql_value(column: &Column, value: ValueRef<'_>) -> Value {
+    if column.is_timestamp {
+        if let Some(timestamp) = parse_timestamp(value) {
+            return Value::Timestamp(timestamp);
+        }
+    }
+
+    match value {
+        ValueRef::Null => Value::Null,
+        ValueRef::Integer(i) => Value::Integer(i),
+        ValueRef::Real(f) => NotNan::new(f).map(Value::Float).unwrap_or(Value::Null),
+        ValueRef::Text(text) | ValueRef::Blob(text) => Value::Bytes(text.to_vec().into()),
+    }
+}
+
+/// Parses a date or time stored in one of the formats supported by SQLite's date and time
+/// functions.
+fn parse_timestamp(value: ValueRef<'_>) -> Option<DateTime<Utc>> {
+    match value {
+        ValueRef::Integer(seconds) => Utc.timestamp_opt(seconds, 0).single(),
+        ValueRef::Text(text) => {
+            let text = std::str::from_utf8(text).ok()?;
+            DateTime::parse_from_rfc3339(text)
+                .map(|ts| ts.with_timezone(&Utc))
+                .ok()
+                .or_else(|| {
+                    NaiveDateTime::parse_from_str(text, DATETIME_FORMAT)
+                        .ok()
+                        .or_else(|| {
+                            NaiveDate::parse_from_str(text, "%Y-%m-%d")
+                                .ok()
+                                .map(|date| date.and_hms(0, 0, 0))
+                        })
+                        .map(|ts| Utc.from_utc_datetime(&ts))
+                })
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_table(path: &std::path::Path) -> Sqlite {
+        let connection = Connection::open(path).unwrap();
+        connection
+            .execute_batch(
+                r#"
+                CREATE TABLE assets (
+                    host TEXT NOT NULL,
+                    team TEXT NOT NULL,
+                    cores INTEGER,
+                    since DATETIME
+                );
+                INSERT INTO assets VALUES ('web-1', 'frontend', 4, '2022-01-01 00:00:00');
+                INSERT INTO assets VALUES ('Web-2', 'frontend', 8, '2022-06-01 00:00:00');
+                INSERT INTO assets VALUES ('db-1', 'storage', 16, '2021-01-01 00:00:00');
+                "#,
+            )
+            .unwrap();
+        drop(connection);
+
+        Sqlite::new(SqliteConfig {
+            path: path.into(),
+            table: "assets".to_string(),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn finds_row() {
+        let path = crate::test_util::temp_file();
+        let table = make_table(&path);
+
+        let condition = Condition::Equals {
+            field: "host",
+            value: Value::from("web-1"),
+        };
+
+        assert_eq!(
+            Ok(BTreeMap::from([
+                ("host".to_string(), Value::from("web-1")),
+                ("team".to_string(), Value::from("frontend")),
+                ("cores".to_string(), Value::from(4)),
+                (
+                    "since".to_string(),
+                    Value::Timestamp(Utc.ymd(2022, 1, 1).and_hms(0, 0, 0))
+                ),
+            ])),
+            table.find_table_row(Case::Sensitive, &[condition], None, None)
+        );
+    }
+
+    #[test]
+    fn finds_rows_with_index_case_insensitive() {
+        let path = crate::test_util::temp_file();
+        let mut table = make_table(&path);
+        let handle = table.add_index(Case::Insensitive, &["host"]).unwrap();
+
+        assert_eq!(
+            Ok(IndexHandle(0)),
+            table.add_index(Case::Insensitive, &["host"])
+        );
+        assert_eq!(
+            vec![(Case::Insensitive, vec!["host".to_string()])],
+            table.index_fields()
+        );
+
+        let condition = Condition::Equals {
+            field: "host",
+            value: Value::from("web-2"),
+        };
+
+        assert_eq!(
+            Ok(vec![BTreeMap::from([(
+                "cores".to_string(),
+                Value::from(8)
+            )])]),
+            table.find_table_rows(
+                Case::Insensitive,
+                &[condition.clone()],
+                Some(&["cores".to_string()]),
+                Some(handle)
+            )
+        );
+        assert_eq!(
+            Ok(vec![]),
+            table.find_table_rows(
+                Case::Sensitive,
+                &[condition],
+                Some(&["cores".to_string()]),
+                None
+            )
+        );
+    }
+
+    #[test]
+    fn finds_rows_with_dates() {
+        let path = crate::test_util::temp_file();
+        let table = make_table(&path);
+
+        let conditions = [
+            Condition::Equals {
+                field: "team",
+                value: Value::from("frontend"),
+            },
+            Condition::BetweenDates {
+                field: "since",
+                from: Utc.ymd(2022, 3, 1).and_hms(0, 0, 0),
+                to: Utc.ymd(2022, 12, 31).and_hms(0, 0, 0),
+            },
+        ];
+
+        assert_eq!(
+            Ok(BTreeMap::from([("host".to_string(), Value::from("Web-2"))])),
+            table.find_table_row(
+                Case::Sensitive,
+                &conditions,
+                Some(&["host".to_string()]),
+                None
+            )
+        );
+    }
+
+    #[test]
+    fn finds_rows_concurrently() {
+        let path = crate::test_util::temp_file();
+        let table = make_table(&path);
+        // Lookups use the copy of the database.
+        std::fs::remove_file(&path).unwrap();
+
+        let threads = (0..4)
+            .map(|_| {
+                let table = table.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..100 {
+                        let condition = Condition::Equals {
+                            field: "host",
+                            value: Value::from("db-1"),
+                        };
+                        assert_eq!(
+                            Ok(BTreeMap::from([(
+                                "team".to_string(),
+                                Value::from("storage")
+                            )])),
+                            table.find_table_row(
+                                Case::Sensitive,
+                                &[condition],
+                                Some(&["team".to_string()]),
+                                None
+                            )
+                        );
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+    }
+
+    #[test]
+    fn errors_on_missing_columns() {
+        let path = crate::test_util::temp_file();
+        let mut table = make_table(&path);
+
+        assert_eq!(
+            Err("field nonexistent not found in table assets".to_string()),
+            table.add_index(Case::Sensitive, &["host", "nonexistent"])
+        );
+    }
+
+    #[test]
+    fn errors_on_missing_table() {
+        let path = crate::test_util::temp_file();
+        make_table(&path);
+
+        let error = Sqlite::new(SqliteConfig {
+            path,
+            table: "nonexistent".to_string(),
+        })
+        .unwrap_err();
+        assert!(error.to_string().contains("not found"));
+    }
+
+    #[test]
+    fn errors_on_multiple_rows() {
+        let path = crate::test_util::temp_file();
+        let table = make_table(&path);
+
+        let condition = Condition::Equals {
+            field: "team",
+            value: Value::from("frontend"),
+        };
+
+        assert_eq!(
+            Err("more than one row found".to_string()),
+            table.find_table_row(Case::Sensitive, &[condition], None, None)
+        );
+    }
+}
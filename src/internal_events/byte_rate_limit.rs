@@ -0,0 +1,18 @@
+use std::time::Duration;
+
+use metrics::histogram;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct ByteRateLimitWait {
+    pub wait: Duration,
+}
+
+impl InternalEvent for ByteRateLimitWait {
+    fn emit(self) {
+        trace!(message = "Waited on byte rate limit.", wait = ?self.wait);
+        // Requests which didn't have to wait are recorded too, so that the share of requests which
+        // were delayed can be told apart.
+        histogram!("byte_rate_limit_wait_seconds", self.wait);
+    }
+}
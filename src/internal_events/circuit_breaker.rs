@@ -0,0 +1,30 @@
+use metrics::{counter, gauge};
+use vector_core::internal_event::InternalEvent;
+
+use crate::sinks::util::service::CircuitState;
+
+#[derive(Debug)]
+pub struct CircuitBreakerStateChanged {
+    pub state: CircuitState,
+}
+
+impl InternalEvent for CircuitBreakerStateChanged {
+    fn emit(self) {
+        match self.state {
+            CircuitState::Open => {
+                warn!(
+                    message =
+                        "Circuit breaker opened; holding requests until the service recovers."
+                );
+                counter!("circuit_breaker_opened_total", 1);
+            }
+            CircuitState::HalfOpen => {
+                debug!(message = "Circuit breaker half-open; probing the service.");
+            }
+            CircuitState::Closed => {
+                info!(message = "Circuit breaker closed; resuming requests.");
+            }
+        }
+        gauge!("circuit_breaker_state", self.state.as_gauge());
+    }
+}
@@ -0,0 +1,46 @@
+use metrics::gauge;
+use vector_core::internal_event::InternalEvent;
+
+use crate::{config::ComponentKey, topology::health::ComponentHealth};
+
+#[derive(Debug)]
+pub struct ComponentHealthUpdated<'a> {
+    pub component_key: &'a ComponentKey,
+    pub component_kind: &'static str,
+    pub component_type: &'static str,
+    pub health: ComponentHealth,
+    pub previous: ComponentHealth,
+}
+
+impl<'a> InternalEvent for ComponentHealthUpdated<'a> {
+    fn emit(self) {
+        if self.health != self.previous {
+            match self.health {
+                ComponentHealth::Healthy => info!(
+                    message = "Component is healthy.",
+                    component_kind = self.component_kind,
+                    component_type = self.component_type,
+                    component_id = %self.component_key.id(),
+                    previous = self.previous.as_str(),
+                ),
+                _ => warn!(
+                    message = "Component health changed.",
+                    health = self.health.as_str(),
+                    component_kind = self.component_kind,
+                    component_type = self.component_type,
+                    component_id = %self.component_key.id(),
+                    previous = self.previous.as_str(),
+                ),
+            }
+        }
+        // Components are evaluated outside of their spans, so they're labelled explicitly.
+        if let Some(value) = self.health.as_gauge() {
+            gauge!(
+                "component_health", value,
+                "component_kind" => self.component_kind,
+                "component_type" => self.component_type,
+                "component_id" => self.component_key.id().to_owned(),
+            );
+        }
+    }
+}
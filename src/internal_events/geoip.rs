@@ -35,3 +35,29 @@ impl<'a> InternalEvent for GeoipIpAddressParseError<'a> {
         );
     }
 }
+
+#[derive(Debug)]
+pub struct GeoipDatabaseReloadError<'a> {
+    pub(crate) error: crate::Error,
+    pub database: &'a str,
+}
+
+impl<'a> InternalEvent for GeoipDatabaseReloadError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to reload GeoIP database; keeping the previous one.",
+            error = %self.error,
+            error_code = "database_reload_failed",
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::PROCESSING,
+            database = %self.database,
+            internal_log_rate_secs = 30
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "database_reload_failed",
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
@@ -0,0 +1,106 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use super::prelude::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct KubernetesEventsReceived<'a> {
+    pub byte_size: usize,
+    pub namespace: Option<&'a str>,
+}
+
+impl InternalEvent for KubernetesEventsReceived<'_> {
+    fn emit(self) {
+        trace!(
+            message = "Events received.",
+            count = 1,
+            byte_size = %self.byte_size,
+            namespace = ?self.namespace,
+        );
+        counter!("component_received_events_total", 1);
+        counter!(
+            "component_received_event_bytes_total",
+            self.byte_size as u64
+        );
+        counter!("events_in_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct KubernetesEventsWatchError<'a> {
+    pub error: kube::runtime::watcher::Error,
+    pub namespace: Option<&'a str>,
+}
+
+impl InternalEvent for KubernetesEventsWatchError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed watching events. Retrying.",
+            error = %self.error,
+            error_code = "watch_failed",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            namespace = ?self.namespace,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "watch_failed",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct KubernetesEventsSerializationError {
+    pub error: serde_json::Error,
+}
+
+impl InternalEvent for KubernetesEventsSerializationError {
+    fn emit(self) {
+        error!(
+            message = "Failed converting Kubernetes event. Dropping it.",
+            error = %self.error,
+            error_code = "conversion_failed",
+            error_type = error_type::CONVERSION_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "conversion_failed",
+            "error_type" => error_type::CONVERSION_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+        counter!(
+            "component_discarded_events_total", 1,
+            "error_code" => "conversion_failed",
+            "error_type" => error_type::CONVERSION_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct KubernetesEventsCheckpointWriteError {
+    pub error: std::io::Error,
+}
+
+impl InternalEvent for KubernetesEventsCheckpointWriteError {
+    fn emit(self) {
+        error!(
+            message = "Failed writing checkpoints.",
+            error = %self.error,
+            error_code = "writing_checkpoints",
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "writing_checkpoints",
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
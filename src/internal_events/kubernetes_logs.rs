@@ -186,3 +186,31 @@ impl<E: std::fmt::Display> InternalEvent for KubernetesLifecycleError<E> {
         );
     }
 }
+
+#[derive(Debug)]
+pub struct KubernetesLogsPodAnnotationError<'a, E> {
+    pub pod_namespace: &'a str,
+    pub pod_name: &'a str,
+    pub annotation: &'static str,
+    pub error: E,
+}
+
+impl<E: std::fmt::Display> InternalEvent for KubernetesLogsPodAnnotationError<'_, E> {
+    fn emit(self) {
+        error!(
+            message = "Ignoring invalid Pod annotation.",
+            pod_namespace = %self.pod_namespace,
+            pod_name = %self.pod_name,
+            annotation = %self.annotation,
+            error = %self.error,
+            error_type = error_type::CONFIGURATION_FAILED,
+            stage = error_stage::PROCESSING,
+            rate_limit_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::CONFIGURATION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
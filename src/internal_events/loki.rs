@@ -1,6 +1,8 @@
 use metrics::counter;
 use vector_core::internal_event::InternalEvent;
 
+use super::prelude::{error_stage, error_type, http_error_code};
+
 #[derive(Debug)]
 pub struct LokiEventUnlabeled;
 
@@ -49,3 +51,29 @@ impl InternalEvent for LokiOutOfOrderEventRewritten {
         counter!("rewritten_timestamp_events_total", self.count as u64);
     }
 }
+
+#[derive(Debug)]
+pub struct LokiResponseRejected<'a> {
+    pub status: http::StatusCode,
+    pub reason: &'a str,
+}
+
+impl<'a> InternalEvent for LokiResponseRejected<'a> {
+    fn emit(self) {
+        let error_code = http_error_code(self.status.as_u16());
+        error!(
+            message = "Loki rejected the request.",
+            reason = %self.reason,
+            error_code = %error_code,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => error_code,
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+    }
+}
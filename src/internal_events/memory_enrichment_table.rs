@@ -0,0 +1,54 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use super::prelude::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct MemoryEnrichmentTableKeyMissing<'a> {
+    pub field: &'a str,
+}
+
+impl<'a> InternalEvent for MemoryEnrichmentTableKeyMissing<'a> {
+    fn emit(self) {
+        error!(
+            message = "Event is missing the key field, discarding.",
+            field = %self.field,
+            error_code = "key_field_missing",
+            error_type = error_type::CONDITION_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_secs = 30,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "key_field_missing",
+            "error_type" => error_type::CONDITION_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+        counter!(
+            "component_discarded_events_total", 1,
+            "error_code" => "key_field_missing",
+            "error_type" => error_type::CONDITION_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct MemoryEnrichmentTableEvicted {
+    pub count: usize,
+    pub reason: &'static str,
+}
+
+impl InternalEvent for MemoryEnrichmentTableEvicted {
+    fn emit(self) {
+        trace!(
+            message = "Evicted rows from the memory enrichment table.",
+            count = %self.count,
+            reason = %self.reason,
+        );
+        counter!(
+            "memory_enrichment_table_evictions_total", self.count as u64,
+            "reason" => self.reason,
+        );
+    }
+}
@@ -21,7 +21,10 @@ mod aws_sqs;
 #[cfg(any(feature = "sinks-azure_blob", feature = "sinks-datadog_archives"))]
 pub(crate) mod azure_blob;
 mod batch;
+mod byte_rate_limit;
+mod circuit_breaker;
 mod common;
+mod component_health;
 mod conditions;
 #[cfg(feature = "sinks-datadog_metrics")]
 mod datadog_metrics;
@@ -59,6 +62,8 @@ mod internal_logs;
 mod journald;
 #[cfg(any(feature = "sources-kafka", feature = "sinks-kafka"))]
 mod kafka;
+#[cfg(feature = "sources-kubernetes_events")]
+mod kubernetes_events;
 #[cfg(feature = "sources-kubernetes_logs")]
 mod kubernetes_logs;
 #[cfg(feature = "transforms-log_to_metric")]
@@ -68,6 +73,8 @@ mod logplex;
 mod loki;
 #[cfg(feature = "transforms-lua")]
 mod lua;
+#[cfg(feature = "enrichment-tables-memory")]
+mod memory_enrichment_table;
 #[cfg(feature = "transforms-metric_to_log")]
 mod metric_to_log;
 #[cfg(feature = "sources-mongodb_metrics")]
@@ -193,6 +200,8 @@ pub(crate) use self::internal_logs::*;
 pub(crate) use self::journald::*;
 #[cfg(any(feature = "sources-kafka", feature = "sinks-kafka"))]
 pub(crate) use self::kafka::*;
+#[cfg(feature = "sources-kubernetes_events")]
+pub(crate) use self::kubernetes_events::*;
 #[cfg(feature = "sources-kubernetes_logs")]
 pub(crate) use self::kubernetes_logs::*;
 #[cfg(feature = "transforms-log_to_metric")]
@@ -203,6 +212,8 @@ pub(crate) use self::logplex::*;
 pub(crate) use self::loki::*;
 #[cfg(feature = "transforms-lua")]
 pub(crate) use self::lua::*;
+#[cfg(feature = "enrichment-tables-memory")]
+pub(crate) use self::memory_enrichment_table::*;
 #[cfg(feature = "transforms-metric_to_log")]
 pub(crate) use self::metric_to_log::*;
 #[cfg(feature = "sinks-nats")]
@@ -262,8 +273,9 @@ pub(crate) use self::websocket::*;
 #[cfg(windows)]
 pub(crate) use self::windows::*;
 pub(crate) use self::{
-    adaptive_concurrency::*, batch::*, common::*, conditions::*, encoding_transcode::*,
-    heartbeat::*, open::*, process::*, socket::*, tcp::*, template::*, udp::*,
+    adaptive_concurrency::*, batch::*, byte_rate_limit::*, circuit_breaker::*, common::*,
+    component_health::*, conditions::*, encoding_transcode::*, heartbeat::*, open::*, process::*,
+    socket::*, tcp::*, template::*, udp::*,
 };
 
 // this version won't be needed once all `InternalEvent`s implement `name()`
@@ -0,0 +1,135 @@
+//! Where the metadata annotated onto events is written to.
+
+#![deny(missing_docs)]
+
+use std::collections::BTreeMap;
+
+use lookup::lookup_v2::{parse_path, OwnedSegment};
+
+use crate::event::{Event, LogEvent, Metric};
+
+/// Something that can be annotated with Kubernetes metadata.
+///
+/// Logs store the metadata as fields, while metrics store it as tags. Metric tags are flat, so
+/// lists are joined with commas and maps are stored as one tag per entry, prefixed with the
+/// configured field name.
+pub trait MetadataTarget {
+    /// Inserts `value` at `field`.
+    fn insert_str(&mut self, field: &str, value: String);
+
+    /// Inserts the list of `values` at `field`.
+    fn insert_list(&mut self, field: &str, values: Vec<String>);
+
+    /// Inserts each of the `entries` under the `prefix` field.
+    fn insert_map(&mut self, prefix: &str, entries: &BTreeMap<String, String>);
+}
+
+impl MetadataTarget for LogEvent {
+    fn insert_str(&mut self, field: &str, value: String) {
+        self.insert(field, value);
+    }
+
+    fn insert_list(&mut self, field: &str, values: Vec<String>) {
+        self.insert(field, values);
+    }
+
+    fn insert_map(&mut self, prefix: &str, entries: &BTreeMap<String, String>) {
+        // Calculate and cache the prefix path.
+        let prefix_path = parse_path(prefix);
+        for (key, val) in entries.iter() {
+            let mut path = prefix_path.clone().segments;
+            path.push(OwnedSegment::Field(key.clone()));
+            self.insert(&path, val.to_owned());
+        }
+    }
+}
+
+impl MetadataTarget for Metric {
+    fn insert_str(&mut self, field: &str, value: String) {
+        // An empty field disables the annotation, as it does for logs.
+        if !field.is_empty() {
+            self.insert_tag(field.to_owned(), value);
+        }
+    }
+
+    fn insert_list(&mut self, field: &str, values: Vec<String>) {
+        self.insert_str(field, values.join(","));
+    }
+
+    fn insert_map(&mut self, prefix: &str, entries: &BTreeMap<String, String>) {
+        if !prefix.is_empty() {
+            for (key, val) in entries.iter() {
+                self.insert_tag(format!("{}.{}", prefix, key), val.to_owned());
+            }
+        }
+    }
+}
+
+impl MetadataTarget for Event {
+    fn insert_str(&mut self, field: &str, value: String) {
+        match self {
+            Event::Log(log) => log.insert_str(field, value),
+            Event::Metric(metric) => metric.insert_str(field, value),
+            Event::Trace(trace) => trace.as_mut().insert_str(field, value),
+        }
+    }
+
+    fn insert_list(&mut self, field: &str, values: Vec<String>) {
+        match self {
+            Event::Log(log) => log.insert_list(field, values),
+            Event::Metric(metric) => metric.insert_list(field, values),
+            Event::Trace(trace) => trace.as_mut().insert_list(field, values),
+        }
+    }
+
+    fn insert_map(&mut self, prefix: &str, entries: &BTreeMap<String, String>) {
+        match self {
+            Event::Log(log) => log.insert_map(prefix, entries),
+            Event::Metric(metric) => metric.insert_map(prefix, entries),
+            Event::Trace(trace) => trace.as_mut().insert_map(prefix, entries),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_common::btreemap;
+
+    use super::*;
+    use crate::event::{MetricKind, MetricValue};
+
+    #[test]
+    fn metric_stores_metadata_as_tags() {
+        let mut metric = Metric::new(
+            "requests",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        );
+
+        metric.insert_str("kubernetes.pod_name", "sandbox0-name".to_owned());
+        metric.insert_list(
+            "kubernetes.pod_ips",
+            vec!["192.168.1.2".to_owned(), "fd00::2".to_owned()],
+        );
+        metric.insert_map(
+            "kubernetes.pod_labels",
+            &btreemap! {
+                "app" => "vector",
+                "app.kubernetes.io/name" => "vector",
+            },
+        );
+        // Empty fields are disabled.
+        metric.insert_str("", "ignored".to_owned());
+        metric.insert_map("", &btreemap! { "ignored" => "ignored" });
+
+        assert_eq!(
+            metric.tags().cloned(),
+            Some(btreemap! {
+                "kubernetes.pod_name" => "sandbox0-name",
+                "kubernetes.pod_ips" => "192.168.1.2,fd00::2",
+                "kubernetes.pod_labels.app" => "vector",
+                "kubernetes.pod_labels.app.kubernetes.io/name" => "vector",
+            })
+        );
+    }
+}
@@ -1,12 +1,42 @@
 //! This module contains helpers Kubernetes helpers as well as a
 //! `custom_reflector` which delays the removal of metadata allowing
 //! us to enrich events even after the resource is deleted from the
-//! Kubernetes cluster.
+//! Kubernetes cluster, and the annotators that do this enrichment.
 //!
 
 #![cfg(feature = "kubernetes")]
 
+use std::path::Path;
+
+use kube::{
+    config::{self, KubeConfigOptions},
+    Client, Config as ClientConfig,
+};
+
+pub mod metadata_target;
+pub mod namespace_metadata_annotator;
+pub mod node_metadata_annotator;
+pub mod path_helpers;
 pub mod pod_manager_logic;
+pub mod pod_metadata_annotator;
 pub mod reflector;
 
-pub use reflector::custom_reflector;
+pub use reflector::{custom_reflector, custom_reflector_with};
+
+/// Creates a client for the Kubernetes API server.
+///
+/// If a custom kubeconfig file is passed it's used, otherwise we attempt to load the local
+/// kubeconfig, followed by the in-cluster environment variables.
+pub async fn client(kube_config_file: Option<&Path>) -> crate::Result<Client> {
+    let client_config = match kube_config_file {
+        Some(kc) => {
+            ClientConfig::from_custom_kubeconfig(
+                config::Kubeconfig::read_from(kc)?,
+                &KubeConfigOptions::default(),
+            )
+            .await?
+        }
+        None => ClientConfig::infer().await?,
+    };
+    Ok(Client::try_from(client_config)?)
+}
@@ -4,10 +4,9 @@
 
 use k8s_openapi::{api::core::v1::Node, apimachinery::pkg::apis::meta::v1::ObjectMeta};
 use kube::runtime::reflector::{store::Store, ObjectRef};
-use lookup::lookup_v2::{parse_path, OwnedSegment};
 use vector_config::configurable_component;
 
-use crate::event::{Event, LogEvent};
+use super::metadata_target::MetadataTarget;
 
 /// Configuration for how the events are annotated with Node metadata.
 #[configurable_component]
@@ -45,26 +44,23 @@ impl NodeMetadataAnnotator {
 impl NodeMetadataAnnotator {
     /// Annotates an event with the information from the [`Node::metadata`].
     /// The event has to have a [`VECTOR_SELF_NODE_NAME`] field set.
-    pub fn annotate(&self, event: &mut Event, node: &str) -> Option<()> {
-        let log = event.as_mut_log();
+    pub fn annotate(&self, target: &mut impl MetadataTarget, node: &str) -> Option<()> {
         let obj = ObjectRef::<Node>::new(node);
         let resource = self.node_state_reader.get(&obj)?;
         let node: &Node = resource.as_ref();
 
-        annotate_from_metadata(log, &self.fields_spec, &node.metadata);
+        annotate_from_metadata(target, &self.fields_spec, &node.metadata);
         Some(())
     }
 }
 
-fn annotate_from_metadata(log: &mut LogEvent, fields_spec: &FieldsSpec, metadata: &ObjectMeta) {
-    // Calculate and cache the prefix path.
-    let prefix_path = parse_path(&fields_spec.node_labels);
+fn annotate_from_metadata(
+    target: &mut impl MetadataTarget,
+    fields_spec: &FieldsSpec,
+    metadata: &ObjectMeta,
+) {
     if let Some(labels) = &metadata.labels {
-        for (key, val) in labels.iter() {
-            let mut path = prefix_path.clone().segments;
-            path.push(OwnedSegment::Field(key.clone()));
-            log.insert(&path, val.to_owned());
-        }
+        target.insert_map(&fields_spec.node_labels, labels);
     }
 }
 
@@ -73,6 +69,7 @@ mod tests {
     use vector_common::assert_event_data_eq;
 
     use super::*;
+    use crate::event::LogEvent;
 
     #[test]
     fn test_annotate_from_metadata() {
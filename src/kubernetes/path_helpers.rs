@@ -15,11 +15,7 @@ const LOG_PATH_DELIMITER: &str = "_";
 /// Builds absolute log directory path for a pod sandbox.
 ///
 /// Based on https://github.com/kubernetes/kubernetes/blob/31305966789525fca49ec26c289e565467d1f1c4/pkg/kubelet/kuberuntime/helpers.go#L178
-pub(super) fn build_pod_logs_directory(
-    pod_namespace: &str,
-    pod_name: &str,
-    pod_uid: &str,
-) -> PathBuf {
+pub fn build_pod_logs_directory(pod_namespace: &str, pod_name: &str, pod_uid: &str) -> PathBuf {
     [
         K8S_LOGS_DIR,
         &[pod_namespace, pod_name, pod_uid].join(LOG_PATH_DELIMITER),
@@ -33,7 +29,7 @@ pub(super) fn build_pod_logs_directory(
 /// Assumes the input is a valid pod log file name.
 ///
 /// Inspired by https://github.com/kubernetes/kubernetes/blob/31305966789525fca49ec26c289e565467d1f1c4/pkg/kubelet/kuberuntime/helpers.go#L186
-pub(super) fn parse_log_file_path(path: &str) -> Option<LogFileInfo<'_>> {
+pub fn parse_log_file_path(path: &str) -> Option<LogFileInfo<'_>> {
     let mut components = path.rsplit('/');
 
     let _log_file_name = components.next()?;
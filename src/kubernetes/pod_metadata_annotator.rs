@@ -2,16 +2,20 @@
 
 #![deny(missing_docs)]
 
+use std::sync::Arc;
+
 use k8s_openapi::{
     api::core::v1::{Container, ContainerStatus, Pod, PodSpec, PodStatus},
     apimachinery::pkg::apis::meta::v1::ObjectMeta,
 };
 use kube::runtime::reflector::{store::Store, ObjectRef};
-use lookup::lookup_v2::{parse_path, OwnedSegment};
 use vector_config::configurable_component;
 
-use super::path_helpers::{parse_log_file_path, LogFileInfo};
-use crate::event::{Event, LogEvent};
+use super::{
+    metadata_target::MetadataTarget,
+    path_helpers::{parse_log_file_path, LogFileInfo},
+};
+use crate::event::Event;
 
 /// Configuration for how the events are annotated with `Pod` metadata.
 #[configurable_component]
@@ -95,55 +99,69 @@ impl PodMetadataAnnotator {
     /// The event has to be obtained from kubernetes log file, and have a
     /// [`FILE_KEY`] field set with a file that the line came from.
     pub fn annotate<'a>(&self, event: &mut Event, file: &'a str) -> Option<LogFileInfo<'a>> {
-        let log = event.as_mut_log();
         let file_info = parse_log_file_path(file)?;
-        let obj = ObjectRef::<Pod>::new(file_info.pod_name).within(file_info.pod_namespace);
-        let resource = self.pods_state_reader.get(&obj)?;
-        let pod: &Pod = resource.as_ref();
+        let pod = self.get(file_info.pod_namespace, file_info.pod_name)?;
+
+        annotate_from_file_info(event, &self.fields_spec, &file_info);
+        self.annotate_pod(event, &pod, Some(file_info.container_name));
+        Some(file_info)
+    }
 
-        annotate_from_file_info(log, &self.fields_spec, &file_info);
-        annotate_from_metadata(log, &self.fields_spec, &pod.metadata);
+    /// Returns the [`Pod`] with the given namespace and name.
+    pub fn get(&self, pod_namespace: &str, pod_name: &str) -> Option<Arc<Pod>> {
+        let obj = ObjectRef::<Pod>::new(pod_name).within(pod_namespace);
+        self.pods_state_reader.get(&obj)
+    }
+
+    /// Annotates the target with the information from the [`Pod`], and from its container
+    /// named `container_name`, if any.
+    pub fn annotate_pod(
+        &self,
+        target: &mut impl MetadataTarget,
+        pod: &Pod,
+        container_name: Option<&str>,
+    ) {
+        annotate_from_metadata(target, &self.fields_spec, &pod.metadata);
 
-        let container;
         if let Some(ref pod_spec) = pod.spec {
-            annotate_from_pod_spec(log, &self.fields_spec, pod_spec);
+            annotate_from_pod_spec(target, &self.fields_spec, pod_spec);
 
-            container = pod_spec
-                .containers
-                .iter()
-                .find(|c| c.name == file_info.container_name);
+            let container =
+                container_name.and_then(|name| pod_spec.containers.iter().find(|c| c.name == name));
             if let Some(container) = container {
-                annotate_from_container(log, &self.fields_spec, container);
+                annotate_from_container(target, &self.fields_spec, container);
             }
         }
 
         if let Some(ref pod_status) = pod.status {
-            annotate_from_pod_status(log, &self.fields_spec, pod_status);
+            annotate_from_pod_status(target, &self.fields_spec, pod_status);
             if let Some(ref container_statuses) = pod_status.container_statuses {
-                let container_status = container_statuses
-                    .iter()
-                    .find(|c| c.name == file_info.container_name);
+                let container_status = container_name
+                    .and_then(|name| container_statuses.iter().find(|c| c.name == name));
                 if let Some(container_status) = container_status {
-                    annotate_from_container_status(log, &self.fields_spec, container_status)
+                    annotate_from_container_status(target, &self.fields_spec, container_status)
                 }
             }
         }
-        Some(file_info)
     }
 }
 
 fn annotate_from_file_info(
-    log: &mut LogEvent,
+    target: &mut impl MetadataTarget,
     fields_spec: &FieldsSpec,
     file_info: &LogFileInfo<'_>,
 ) {
-    log.insert(
+    target.insert_str(
         fields_spec.container_name.as_str(),
         file_info.container_name.to_owned(),
     );
 }
 
-fn annotate_from_metadata(log: &mut LogEvent, fields_spec: &FieldsSpec, metadata: &ObjectMeta) {
+fn annotate_from_metadata(
+    target: &mut impl MetadataTarget,
+    fields_spec: &FieldsSpec,
+    metadata: &ObjectMeta,
+) {
     for (key, val) in [
         (&fields_spec.pod_name, &metadata.name),
         (&fields_spec.pod_namespace, &metadata.namespace),
@@ -152,49 +170,46 @@ fn annotate_from_metadata(log: &mut LogEvent, fields_spec: &FieldsSpec, metadata
     .iter()
     {
         if let Some(val) = val {
-            log.insert(key.as_str(), val.to_owned());
+            target.insert_str(key.as_str(), val.to_owned());
         }
     }
 
     if let Some(owner_references) = &metadata.owner_references {
-        log.insert(
+        target.insert_str(
             fields_spec.pod_owner.as_str(),
             format!("{}/{}", owner_references[0].kind, owner_references[0].name),
         );
     }
 
     if let Some(labels) = &metadata.labels {
-        // Calculate and cache the prefix path.
-        let prefix_path = parse_path(&fields_spec.pod_labels);
-        for (key, val) in labels.iter() {
-            let mut path = prefix_path.clone().segments;
-            path.push(OwnedSegment::Field(key.clone()));
-            log.insert(&path, val.to_owned());
-        }
+        target.insert_map(&fields_spec.pod_labels, labels);
     }
 
     if let Some(annotations) = &metadata.annotations {
-        let prefix_path = parse_path(&fields_spec.pod_annotations);
-        for (key, val) in annotations.iter() {
-            let mut path = prefix_path.clone().segments;
-            path.push(OwnedSegment::Field(key.clone()));
-            log.insert(&path, val.to_owned());
-        }
+        target.insert_map(&fields_spec.pod_annotations, annotations);
     }
 }
 
-fn annotate_from_pod_spec(log: &mut LogEvent, fields_spec: &FieldsSpec, pod_spec: &PodSpec) {
+fn annotate_from_pod_spec(
+    target: &mut impl MetadataTarget,
+    fields_spec: &FieldsSpec,
+    pod_spec: &PodSpec,
+) {
     for (key, val) in [(&fields_spec.pod_node_name, &pod_spec.node_name)].iter() {
         if let Some(val) = val {
-            log.insert(key.as_str(), val.to_owned());
+            target.insert_str(key.as_str(), val.to_owned());
         }
     }
 }
 
-fn annotate_from_pod_status(log: &mut LogEvent, fields_spec: &FieldsSpec, pod_status: &PodStatus) {
+fn annotate_from_pod_status(
+    target: &mut impl MetadataTarget,
+    fields_spec: &FieldsSpec,
+    pod_status: &PodStatus,
+) {
     for (key, val) in [(&fields_spec.pod_ip, &pod_status.pod_ip)].iter() {
         if let Some(val) = val {
-            log.insert(key.as_str(), val.to_owned());
+            target.insert_str(key.as_str(), val.to_owned());
         }
     }
 
@@ -204,27 +219,31 @@ fn annotate_from_pod_status(log: &mut LogEvent, fields_spec: &FieldsSpec, pod_st
                 .iter()
                 .filter_map(|v| v.ip.clone())
                 .collect::<Vec<String>>();
-            log.insert(key.as_str(), inner);
+            target.insert_list(key.as_str(), inner);
         }
     }
 }
 
 fn annotate_from_container_status(
-    log: &mut LogEvent,
+    target: &mut impl MetadataTarget,
     fields_spec: &FieldsSpec,
     container_status: &ContainerStatus,
 ) {
     for (key, val) in [(&fields_spec.container_id, &container_status.container_id)].iter() {
         if let Some(val) = val {
-            log.insert(key.as_str(), val.to_owned());
+            target.insert_str(key.as_str(), val.to_owned());
         }
     }
 }
 
-fn annotate_from_container(log: &mut LogEvent, fields_spec: &FieldsSpec, container: &Container) {
+fn annotate_from_container(
+    target: &mut impl MetadataTarget,
+    fields_spec: &FieldsSpec,
+    container: &Container,
+) {
     for (key, val) in [(&fields_spec.container_image, &container.image)].iter() {
         if let Some(val) = val {
-            log.insert(key.as_str(), val.to_owned());
+            target.insert_str(key.as_str(), val.to_owned());
         }
     }
 }
@@ -235,6 +254,7 @@ mod tests {
     use vector_common::assert_event_data_eq;
 
     use super::*;
+    use crate::event::LogEvent;
 
     #[test]
     fn test_annotate_from_metadata() {
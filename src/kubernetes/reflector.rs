@@ -12,14 +12,27 @@ use tokio::pin;
 use tokio_util::time::DelayQueue;
 
 /// Handles events from a [`kube::runtime::watcher`] to delay the application of Deletion events.
-pub async fn custom_reflector<K, W>(
+pub async fn custom_reflector<K, W>(store: store::Writer<K>, stream: W, delay_deletion: Duration)
+where
+    K: Resource + Clone + std::fmt::Debug,
+    K::DynamicType: Eq + Hash + Clone,
+    W: Stream<Item = watcher::Result<watcher::Event<K>>>,
+{
+    custom_reflector_with(store, stream, delay_deletion, |_| ()).await
+}
+
+/// Same as [`custom_reflector`], calling `on_apply` with each event once it's applied to the store,
+/// so that data derived from the store can be kept up to date.
+pub async fn custom_reflector_with<K, W, F>(
     mut store: store::Writer<K>,
     stream: W,
     delay_deletion: Duration,
+    mut on_apply: F,
 ) where
     K: Resource + Clone + std::fmt::Debug,
     K::DynamicType: Eq + Hash + Clone,
     W: Stream<Item = watcher::Result<watcher::Event<K>>>,
+    F: FnMut(&watcher::Event<K>),
 {
     pin!(stream);
     let mut delay_queue = DelayQueue::default();
@@ -33,6 +46,7 @@ pub async fn custom_reflector<K, W>(
                             watcher::Event::Applied(_) => {
                                 trace!(message = "Processing Applied event.", ?event);
                                 store.apply_watcher_event(&event);
+                                on_apply(&event);
                             }
                             // Delay reconciling any `Deleted` events
                             watcher::Event::Deleted(_) => {
@@ -44,6 +58,7 @@ pub async fn custom_reflector<K, W>(
                                 trace!(message = "Processing Restarted event.", ?event);
                                 delay_queue.clear();
                                 store.apply_watcher_event(&event);
+                                on_apply(&event);
                             }
                         }
                     },
@@ -61,7 +76,9 @@ pub async fn custom_reflector<K, W>(
                 match result {
                     Some(event) => {
                         trace!(message = "Processing Deleted event.", ?event);
-                        store.apply_watcher_event(&event.into_inner());
+                        let event = event.into_inner();
+                        store.apply_watcher_event(&event);
+                        on_apply(&event);
                     },
                     // DelayQueue returns None if the queue is exhausted,
                     // however we disable the DelayQueue branch if there are
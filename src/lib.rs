@@ -46,6 +46,7 @@ pub mod app;
 pub mod async_read;
 #[cfg(feature = "aws-config")]
 pub mod aws;
+pub(crate) mod buffer;
 #[allow(unreachable_pub)]
 pub mod codecs;
 pub(crate) mod common;
@@ -6,6 +6,7 @@ use std::{
     collections::{hash_map::Entry, HashMap},
     hash::Hash,
     pin::Pin,
+    sync::Arc,
     task::{Context, Poll},
     time::Duration,
 };
@@ -116,8 +117,8 @@ pub struct LineAgg<T, K, C> {
 /// Encapsulates the essential state and the core logic for the line
 /// aggregation algorithm.
 pub struct Logic<K, C> {
-    /// Configuration parameters to use.
-    config: Config,
+    /// Where the configuration parameters to use come from.
+    rules: Rules<K, C>,
 
     /// Line per key.
     /// Key is usually a filename or other line source identifier.
@@ -127,11 +128,35 @@ pub struct Logic<K, C> {
     timeouts: DelayQueue<K>,
 }
 
+/// Where the `Config` used to aggregate the lines of a key comes from.
+enum Rules<K, C> {
+    /// The same `Config` is used for all the keys.
+    Static(Arc<Config>),
+
+    /// The `Config` is looked up at the start of each message.
+    PerKey(Box<dyn FnMut(&K, &C) -> Option<Arc<Config>> + Send>),
+}
+
 impl<K, C> Logic<K, C> {
     /// Create a new `Logic` using the specified `Config`.
     pub fn new(config: Config) -> Self {
+        Self::with_rules(Rules::Static(Arc::new(config)))
+    }
+
+    /// Create a new `Logic` that looks up the `Config` to use with `rules`
+    /// every time a line could start a new message for a key.
+    ///
+    /// Lines for which `rules` returns `None` are passed through as is.
+    pub fn per_key<F>(rules: F) -> Self
+    where
+        F: FnMut(&K, &C) -> Option<Arc<Config>> + Send + 'static,
+    {
+        Self::with_rules(Rules::PerKey(Box::new(rules)))
+    }
+
+    fn with_rules(rules: Rules<K, C>) -> Self {
         Self {
-            config,
+            rules,
             buffers: HashMap::new(),
             timeouts: DelayQueue::new(),
         }
@@ -298,8 +323,9 @@ where
         // Check if we already have the buffered data for the source.
         match self.buffers.entry(src) {
             Entry::Occupied(mut entry) => {
-                let condition_matched = self.config.condition_pattern.is_match(line.as_ref());
-                let decision = match (self.config.mode, condition_matched) {
+                let config = Arc::clone(&entry.get().1.config);
+                let condition_matched = config.condition_pattern.is_match(line.as_ref());
+                let decision = match (config.mode, condition_matched) {
                     // All consecutive lines matching this pattern are included in
                     // the group.
                     (Mode::ContinueThrough, true) => Decision::Continue,
@@ -321,7 +347,7 @@ where
                 match decision {
                     Decision::Continue => {
                         let buffered = entry.get_mut();
-                        self.timeouts.reset(&buffered.0, self.config.timeout);
+                        self.timeouts.reset(&buffered.0, config.timeout);
                         buffered.1.add_next_line(line);
                         None
                     }
@@ -339,18 +365,22 @@ where
                 }
             }
             Entry::Vacant(entry) => {
+                let config = match &mut self.rules {
+                    Rules::Static(config) => Some(Arc::clone(config)),
+                    Rules::PerKey(rules) => rules(entry.key(), &context),
+                };
+
                 // This line is a candidate for buffering, or passing through.
-                if self.config.start_pattern.is_match(line.as_ref()) {
-                    // It was indeed a new line we need to filter.
-                    // Set the timeout and buffer this line.
-                    let key = self
-                        .timeouts
-                        .insert(entry.key().clone(), self.config.timeout);
-                    entry.insert((key, Aggregate::new(line, context)));
-                    None
-                } else {
+                match config {
+                    Some(config) if config.start_pattern.is_match(line.as_ref()) => {
+                        // It was indeed a new line we need to filter.
+                        // Set the timeout and buffer this line.
+                        let key = self.timeouts.insert(entry.key().clone(), config.timeout);
+                        entry.insert((key, Aggregate::new(line, context, config)));
+                        None
+                    }
                     // It's just a regular line we don't really care about.
-                    Some((entry.into_key(), Emit::One((line, context))))
+                    _ => Some((entry.into_key(), Emit::One((line, context)))),
                 }
             }
         }
@@ -360,13 +390,15 @@ where
 struct Aggregate<C> {
     lines: Vec<Bytes>,
     context: C,
+    config: Arc<Config>,
 }
 
 impl<C> Aggregate<C> {
-    fn new(first_line: Bytes, context: C) -> Self {
+    fn new(first_line: Bytes, context: C, config: Arc<Config>) -> Self {
         Self {
             lines: vec![first_line],
             context,
+            config,
         }
     }
 
@@ -723,6 +755,45 @@ mod tests {
         assert_results(results.await.unwrap(), &[expected.as_str()]);
     }
 
+    #[tokio::test]
+    async fn per_key_rules() {
+        let lines = vec![
+            ("a.log", "first part"),
+            ("b.log", "first part"),
+            ("a.log", " second part"),
+            ("b.log", " second part"),
+            ("a.log", "another message"),
+        ];
+        let config = Arc::new(Config {
+            start_pattern: Regex::new("^[^\\s]").unwrap(),
+            condition_pattern: Regex::new("^[\\s]+").unwrap(),
+            mode: Mode::ContinueThrough,
+            timeout: Duration::from_millis(10),
+        });
+
+        // Only the lines of `a.log` are aggregated.
+        let logic = Logic::per_key(move |filename: &Filename, _: &()| {
+            (filename == "a.log").then(|| Arc::clone(&config))
+        });
+        let stream = futures::stream::iter(lines.into_iter().map(|(filename, line)| {
+            (filename.to_owned(), Bytes::from_static(line.as_bytes()), ())
+        }));
+        let results: Vec<_> = LineAgg::new(stream, logic)
+            .map(|(filename, line, _)| (filename, String::from_utf8(line.to_vec()).unwrap()))
+            .collect()
+            .await;
+
+        assert_eq!(
+            results,
+            vec![
+                ("b.log".to_owned(), "first part".to_owned()),
+                ("b.log".to_owned(), " second part".to_owned()),
+                ("a.log".to_owned(), "first part\n second part".to_owned()),
+                ("a.log".to_owned(), "another message".to_owned()),
+            ]
+        );
+    }
+
     // Test helpers.
 
     /// Private type alias to be more expressive in the internal implementation.
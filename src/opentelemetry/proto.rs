@@ -6,6 +6,12 @@ pub mod collector {
             tonic::include_proto!("opentelemetry.proto.collector.logs.v1");
         }
     }
+
+    pub mod trace {
+        pub mod v1 {
+            tonic::include_proto!("opentelemetry.proto.collector.trace.v1");
+        }
+    }
 }
 
 pub mod common {
@@ -25,3 +31,9 @@ pub mod resource {
         tonic::include_proto!("opentelemetry.proto.resource.v1");
     }
 }
+
+pub mod trace {
+    pub mod v1 {
+        tonic::include_proto!("opentelemetry.proto.trace.v1");
+    }
+}
@@ -14,6 +14,12 @@ pub const fn default_false() -> bool {
     false
 }
 
+/// Used to skip serializing boolean options that default to `true`.
+#[allow(clippy::trivially_copy_pass_by_ref)] // serde requires a reference.
+pub const fn is_true(value: &bool) -> bool {
+    *value
+}
+
 /// The default max length of the input buffer.
 ///
 /// Any input exceeding this limit will be discarded.
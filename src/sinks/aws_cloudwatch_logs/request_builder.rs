@@ -12,7 +12,7 @@ use crate::{
     config::LogSchema,
     event::{Event, Value},
     internal_events::{AwsCloudwatchLogsEncoderError, AwsCloudwatchLogsMessageSizeError},
-    sinks::aws_cloudwatch_logs::CloudwatchKey,
+    sinks::{aws_cloudwatch_logs::CloudwatchKey, util::EncodedLength},
     template::Template,
 };
 
@@ -103,13 +103,19 @@ impl CloudwatchRequestBuilder {
     }
 }
 
+impl EncodedLength for CloudwatchRequest {
+    fn encoded_length(&self) -> usize {
+        self.message.len() + 26
+    }
+}
+
 /// ByteSizeOf is being abused to represent the encoded size of a request for the Partitioned Batcher
 ///
 /// The maximum batch size is 1,048,576 bytes. This size is calculated as the sum of all event messages in UTF-8, plus 26 bytes for each log event.
 /// source: https://docs.aws.amazon.com/AmazonCloudWatchLogs/latest/APIReference/API_PutLogEvents.html
 impl ByteSizeOf for CloudwatchRequest {
     fn size_of(&self) -> usize {
-        self.message.len() + 26
+        self.encoded_length()
     }
 
     fn allocated_bytes(&self) -> usize {
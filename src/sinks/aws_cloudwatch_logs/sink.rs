@@ -17,7 +17,7 @@ use crate::{
             request_builder::{CloudwatchRequest, CloudwatchRequestBuilder},
             CloudwatchKey,
         },
-        util::SinkBuilderExt,
+        util::{EncodedLength, SinkBuilderExt},
     },
 };
 
@@ -68,6 +68,12 @@ impl Finalizable for BatchCloudwatchRequest {
     }
 }
 
+impl EncodedLength for BatchCloudwatchRequest {
+    fn encoded_length(&self) -> usize {
+        self.events.encoded_length()
+    }
+}
+
 struct CloudwatchParititoner;
 
 impl Partitioner for CloudwatchParititoner {
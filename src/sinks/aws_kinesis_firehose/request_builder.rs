@@ -8,7 +8,7 @@ use vector_core::ByteSizeOf;
 use crate::{
     codecs::{Encoder, Transformer},
     event::{Event, EventFinalizers, Finalizable, LogEvent},
-    sinks::util::{request_builder::EncodeResult, Compression, RequestBuilder},
+    sinks::util::{request_builder::EncodeResult, Compression, EncodedLength, RequestBuilder},
 };
 
 pub struct KinesisRequestBuilder {
@@ -34,7 +34,7 @@ impl Finalizable for KinesisRequest {
     }
 }
 
-impl KinesisRequest {
+impl EncodedLength for KinesisRequest {
     fn encoded_length(&self) -> usize {
         let data_len = self
             .record
@@ -10,7 +10,7 @@ use crate::{
     event::{Event, EventFinalizers, Finalizable},
     sinks::{
         aws_kinesis_streams::sink::KinesisProcessedEvent,
-        util::{request_builder::EncodeResult, Compression, RequestBuilder},
+        util::{request_builder::EncodeResult, Compression, EncodedLength, RequestBuilder},
     },
 };
 
@@ -38,7 +38,7 @@ impl Finalizable for KinesisRequest {
     }
 }
 
-impl KinesisRequest {
+impl EncodedLength for KinesisRequest {
     fn encoded_length(&self) -> usize {
         let hash_key_size = self
             .put_records_request
@@ -12,7 +12,10 @@ use vector_core::{internal_event::EventsSent, stream::DriverResponse};
 
 use crate::{
     event::{EventFinalizers, EventStatus, Finalizable},
-    sinks::{util::retries::RetryLogic, Healthcheck},
+    sinks::{
+        util::{retries::RetryLogic, EncodedLength},
+        Healthcheck,
+    },
 };
 
 #[derive(Debug, Clone)]
@@ -29,6 +32,12 @@ impl Finalizable for AzureBlobRequest {
     }
 }
 
+impl EncodedLength for AzureBlobRequest {
+    fn encoded_length(&self) -> usize {
+        self.blob_data.len()
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct AzureBlobMetadata {
     pub partition_key: String,
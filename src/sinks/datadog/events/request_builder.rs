@@ -8,7 +8,9 @@ use vector_core::ByteSizeOf;
 use crate::{
     codecs::{Encoder, TimestampFormat, Transformer},
     event::{Event, EventFinalizers, Finalizable},
-    sinks::util::{request_builder::EncodeResult, Compression, ElementCount, RequestBuilder},
+    sinks::util::{
+        request_builder::EncodeResult, Compression, ElementCount, EncodedLength, RequestBuilder,
+    },
 };
 
 #[derive(Clone)]
@@ -23,6 +25,12 @@ impl Finalizable for DatadogEventsRequest {
     }
 }
 
+impl EncodedLength for DatadogEventsRequest {
+    fn encoded_length(&self) -> usize {
+        self.body.len()
+    }
+}
+
 impl ByteSizeOf for DatadogEventsRequest {
     fn allocated_bytes(&self) -> usize {
         self.body.allocated_bytes() + self.metadata.finalizers.allocated_bytes()
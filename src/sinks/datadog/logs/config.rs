@@ -192,6 +192,11 @@ impl SinkConfig for DatadogLogsConfig {
         Ok((sink, healthcheck))
     }
 
+    async fn rebuild_healthcheck(&self, cx: SinkContext) -> crate::Result<Option<Healthcheck>> {
+        let client = self.create_client(&cx.proxy)?;
+        self.build_healthcheck(client).map(Some)
+    }
+
     fn input(&self) -> Input {
         let requirement = schema::Requirement::empty()
             .required_meaning("message", Kind::bytes())
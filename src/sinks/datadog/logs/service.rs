@@ -22,7 +22,7 @@ use vector_core::{
 use crate::{
     http::HttpClient,
     sinks::datadog::DatadogApiError,
-    sinks::util::{retries::RetryLogic, Compression},
+    sinks::util::{retries::RetryLogic, Compression, EncodedLength},
 };
 
 #[derive(Debug, Default, Clone)]
@@ -54,6 +54,12 @@ impl Finalizable for LogApiRequest {
     }
 }
 
+impl EncodedLength for LogApiRequest {
+    fn encoded_length(&self) -> usize {
+        self.body.len()
+    }
+}
+
 #[derive(Debug)]
 pub struct LogApiResponse {
     event_status: EventStatus,
@@ -20,7 +20,10 @@ use vector_core::{
 use crate::{
     http::{BuildRequestSnafu, CallRequestSnafu, HttpClient},
     sinks::datadog::DatadogApiError,
-    sinks::util::retries::{RetryAction, RetryLogic},
+    sinks::util::{
+        retries::{RetryAction, RetryLogic},
+        EncodedLength,
+    },
 };
 
 /// Retry logic specific to the Datadog metrics endpoints.
@@ -111,6 +114,12 @@ impl Finalizable for DatadogMetricsRequest {
     }
 }
 
+impl EncodedLength for DatadogMetricsRequest {
+    fn encoded_length(&self) -> usize {
+        self.payload.len()
+    }
+}
+
 // Generalized wrapper around the raw response from Hyper.
 #[derive(Debug)]
 pub struct DatadogMetricsResponse {
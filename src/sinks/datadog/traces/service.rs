@@ -18,7 +18,10 @@ use vector_core::{
 
 use crate::{
     http::{BuildRequestSnafu, CallRequestSnafu, HttpClient, HttpError},
-    sinks::util::retries::{RetryAction, RetryLogic},
+    sinks::util::{
+        retries::{RetryAction, RetryLogic},
+        EncodedLength,
+    },
 };
 
 #[derive(Debug, Default, Clone)]
@@ -80,6 +83,12 @@ impl Finalizable for TraceApiRequest {
     }
 }
 
+impl EncodedLength for TraceApiRequest {
+    fn encoded_length(&self) -> usize {
+        self.body.len()
+    }
+}
+
 #[derive(Debug)]
 pub struct TraceApiResponse {
     status_code: StatusCode,
@@ -410,6 +410,12 @@ impl SinkConfig for ElasticsearchConfig {
         Ok((stream, healthcheck))
     }
 
+    async fn rebuild_healthcheck(&self, cx: SinkContext) -> crate::Result<Option<Healthcheck>> {
+        let common = ElasticsearchCommon::parse_config(self).await?;
+        let client = HttpClient::new(common.tls_settings.clone(), cx.proxy())?;
+        Ok(Some(common.healthcheck(client).boxed()))
+    }
+
     fn input(&self) -> Input {
         Input::new(DataType::Metric | DataType::Log)
     }
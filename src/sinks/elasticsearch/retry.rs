@@ -1,11 +1,15 @@
-use http::StatusCode;
+use bytes::Bytes;
+use http::{Response, StatusCode};
 use serde::Deserialize;
 
 use crate::{
     http::HttpError,
     sinks::{
         elasticsearch::service::ElasticsearchResponse,
-        util::retries::{RetryAction, RetryLogic},
+        util::{
+            http::{retry_after, server_error_retry},
+            retries::{RetryAction, RetryLogic},
+        },
     },
 };
 
@@ -44,6 +48,12 @@ struct EsErrorDetails {
     err_type: String,
 }
 
+/// The body of a response to a request that failed as a whole.
+#[derive(Deserialize, Debug)]
+struct EsErrorResponse {
+    error: EsErrorDetails,
+}
+
 #[derive(Clone)]
 pub struct ElasticsearchRetryLogic;
 
@@ -59,17 +69,20 @@ impl RetryLogic for ElasticsearchRetryLogic {
         let status = response.http_response.status();
 
         match status {
-            StatusCode::TOO_MANY_REQUESTS => RetryAction::Retry("too many requests".into()),
+            StatusCode::TOO_MANY_REQUESTS => RetryAction::Throttled(
+                "too many requests".into(),
+                retry_after(response.http_response.headers()),
+            ),
             StatusCode::NOT_IMPLEMENTED => {
                 RetryAction::DontRetry("endpoint not implemented".into())
             }
-            _ if status.is_server_error() => RetryAction::Retry(
+            _ if status.is_server_error() => server_error_retry(
                 format!(
                     "{}: {}",
                     status,
                     String::from_utf8_lossy(response.http_response.body())
-                )
-                .into(),
+                ),
+                response.http_response.headers(),
             ),
             _ if status.is_client_error() => {
                 let body = String::from_utf8_lossy(response.http_response.body());
@@ -102,6 +115,45 @@ fn get_error_reason(body: &str) -> String {
     }
 }
 
+/// Describes why Elasticsearch rejected a request, from the errors of the items that failed, or
+/// from the error of the whole request.
+pub(super) fn get_rejection_reason(response: &Response<Bytes>) -> String {
+    let status = response.status();
+    let body = String::from_utf8_lossy(response.body());
+    if status.is_success() {
+        match serde_json::from_str::<EsResultResponse>(&body) {
+            Ok(resp) => {
+                let total = resp.items.len();
+                let mut errors = resp
+                    .items
+                    .into_iter()
+                    .filter_map(|item| item.result().error)
+                    .map(|error| {
+                        format!("error type: {}, reason: {}", error.err_type, error.reason)
+                    })
+                    .collect::<Vec<_>>();
+                let failed = errors.len();
+                errors.dedup();
+                format!(
+                    "{} of {} items failed, {}",
+                    failed,
+                    total,
+                    errors.join("; ")
+                )
+            }
+            Err(_) => get_error_reason(&body),
+        }
+    } else {
+        match serde_json::from_str::<EsErrorResponse>(&body) {
+            Ok(resp) => format!(
+                "{}: error type: {}, reason: {}",
+                status, resp.error.err_type, resp.error.reason
+            ),
+            Err(_) => format!("{}: {}", status, body),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use bytes::Bytes;
@@ -137,6 +189,29 @@ mod tests {
         assert_eq!(reason, "error type: illegal_argument_exception, reason: mapper [message] of different type, current_type [long], merged_type [text]");
     }
 
+    #[test]
+    fn rejection_reason_from_items() {
+        let json = "{\"took\":3,\"errors\":true,\"items\":[{\"create\":{\"_index\":\"test-hgw28jv10u\",\"_type\":\"_doc\",\"_id\":\"aBLq1HcBWD7eBWkW2nj4\",\"status\":400,\"error\":{\"type\":\"mapper_parsing_exception\",\"reason\":\"failed to parse field [host]\"}}},{\"create\":{\"_index\":\"test-hgw28jv10u\",\"_type\":\"_doc\",\"_id\":\"bBLq1HcBWD7eBWkW2nj4\",\"status\":201}}]}";
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .body(Bytes::from(json))
+            .unwrap();
+        assert_eq!(
+            get_rejection_reason(&response),
+            "1 of 2 items failed, error type: mapper_parsing_exception, reason: failed to parse field [host]"
+        );
+
+        let json = "{\"error\":{\"root_cause\":[],\"type\":\"illegal_argument_exception\",\"reason\":\"The bulk request must be terminated by a newline\"},\"status\":400}";
+        let response = Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Bytes::from(json))
+            .unwrap();
+        assert_eq!(
+            get_rejection_reason(&response),
+            "400 Bad Request: error type: illegal_argument_exception, reason: The bulk request must be terminated by a newline"
+        );
+    }
+
     #[test]
     fn get_create_error_reason() {
         let json = "{\"took\":3,\"errors\":true,\"items\":[{\"create\":{\"_index\":\"test-hgw28jv10u\",\"_type\":\"_doc\",\"_id\":\"aBLq1HcBWD7eBWkW2nj4\",\"status\":400,\"error\":{\"type\":\"mapper_parsing_exception\",\"reason\":\"object mapping for [host] tried to parse field [host] as object, but found a concrete value\"}}}]}";
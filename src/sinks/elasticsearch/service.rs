@@ -13,14 +13,14 @@ use hyper::{service::Service, Body, Request};
 use tower::ServiceExt;
 use vector_core::{internal_event::EventsSent, stream::DriverResponse, ByteSizeOf};
 
-use crate::sinks::elasticsearch::sign_request;
+use crate::sinks::elasticsearch::{retry::get_rejection_reason, sign_request};
 use crate::{
     event::{EventFinalizers, EventStatus, Finalizable},
     http::{Auth, HttpClient},
     internal_events::ElasticsearchResponseError,
     sinks::util::{
         http::{HttpBatchService, RequestConfig},
-        Compression, ElementCount,
+        Compression, ElementCount, EncodedLength,
     },
 };
 
@@ -50,6 +50,12 @@ impl Finalizable for ElasticsearchRequest {
     }
 }
 
+impl EncodedLength for ElasticsearchRequest {
+    fn encoded_length(&self) -> usize {
+        self.payload.len()
+    }
+}
+
 #[derive(Clone)]
 pub struct ElasticsearchService {
     batch_service: HttpBatchService<
@@ -136,6 +142,10 @@ impl DriverResponse for ElasticsearchResponse {
             output: None,
         }
     }
+
+    fn rejection_reason(&self) -> Option<String> {
+        Some(get_rejection_reason(&self.http_response))
+    }
 }
 
 impl Service<ElasticsearchRequest> for ElasticsearchService {
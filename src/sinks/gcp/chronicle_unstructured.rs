@@ -35,7 +35,8 @@ use crate::{
             metadata::{RequestMetadata, RequestMetadataBuilder},
             partitioner::KeyPartitioner,
             request_builder::EncodeResult,
-            BatchConfig, Compression, RequestBuilder, SinkBatchSettings, TowerRequestConfig,
+            BatchConfig, Compression, EncodedLength, RequestBuilder, SinkBatchSettings,
+            TowerRequestConfig,
         },
         Healthcheck,
     },
@@ -279,6 +280,12 @@ impl Finalizable for ChronicleRequest {
     }
 }
 
+impl EncodedLength for ChronicleRequest {
+    fn encoded_length(&self) -> usize {
+        self.body.len()
+    }
+}
+
 #[derive(Clone, Debug)]
 struct ChronicleEncoder {
     customer_id: String,
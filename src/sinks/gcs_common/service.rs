@@ -15,7 +15,7 @@ use crate::{
     event::{EventFinalizers, EventStatus, Finalizable},
     gcp::GcpAuthenticator,
     http::{get_http_scheme_from_uri, HttpClient, HttpError},
-    sinks::util::metadata::RequestMetadata,
+    sinks::util::{metadata::RequestMetadata, EncodedLength},
 };
 
 #[derive(Debug, Clone)]
@@ -50,6 +50,12 @@ impl Finalizable for GcsRequest {
     }
 }
 
+impl EncodedLength for GcsRequest {
+    fn encoded_length(&self) -> usize {
+        self.body.len()
+    }
+}
+
 // Settings required to produce a request that do not change per
 // request. All possible values are pre-computed for direct use in
 // producing a request.
@@ -149,6 +149,15 @@ impl HttpSinkConfig {
         let tls = TlsSettings::from_options(&self.tls)?;
         Ok(HttpClient::new(tls, cx.proxy())?)
     }
+
+    fn build_healthcheck(&self, cx: &SinkContext, client: HttpClient) -> super::Healthcheck {
+        match cx.healthcheck.uri.clone() {
+            Some(healthcheck_uri) => {
+                healthcheck(healthcheck_uri, self.auth.clone(), client).boxed()
+            }
+            None => future::ok(()).boxed(),
+        }
+    }
 }
 
 struct HttpSink {
@@ -187,13 +196,7 @@ impl SinkConfig for HttpSinkConfig {
         cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
         let client = self.build_http_client(&cx)?;
-
-        let healthcheck = match cx.healthcheck.uri {
-            Some(healthcheck_uri) => {
-                healthcheck(healthcheck_uri, self.auth.clone(), client.clone()).boxed()
-            }
-            None => future::ok(()).boxed(),
-        };
+        let healthcheck = self.build_healthcheck(&cx, client.clone());
 
         let mut request = self.request.clone();
         request.add_old_option(self.headers.clone());
@@ -233,6 +236,14 @@ impl SinkConfig for HttpSinkConfig {
         Ok((sink, healthcheck))
     }
 
+    async fn rebuild_healthcheck(
+        &self,
+        cx: SinkContext,
+    ) -> crate::Result<Option<super::Healthcheck>> {
+        let client = self.build_http_client(&cx)?;
+        Ok(Some(self.build_healthcheck(&cx, client)))
+    }
+
     fn input(&self) -> Input {
         Input::new(self.encoding.config().1.input_type() & DataType::Log)
     }
@@ -224,6 +224,10 @@ impl SinkConfig for KafkaSinkConfig {
         Ok((VectorSink::from_event_streamsink(sink), hc))
     }
 
+    async fn rebuild_healthcheck(&self, _cx: SinkContext) -> crate::Result<Option<Healthcheck>> {
+        Ok(Some(healthcheck(self.clone()).boxed()))
+    }
+
     fn input(&self) -> Input {
         Input::new(self.encoding.config().input_type() & (DataType::Log | DataType::Metric))
     }
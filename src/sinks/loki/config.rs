@@ -179,6 +179,18 @@ impl SinkConfig for LokiConfig {
         Ok((VectorSink::from_event_streamsink(sink), healthcheck))
     }
 
+    async fn rebuild_healthcheck(
+        &self,
+        cx: SinkContext,
+    ) -> crate::Result<Option<crate::sinks::Healthcheck>> {
+        let client = self.build_client(cx)?;
+        let config = LokiConfig {
+            auth: self.auth.choose_one(&self.endpoint.auth)?,
+            ..self.clone()
+        };
+        Ok(Some(healthcheck(config, client).boxed()))
+    }
+
     fn input(&self) -> Input {
         Input::new(self.encoding.config().input_type() & DataType::Log)
     }
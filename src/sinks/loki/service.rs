@@ -1,4 +1,7 @@
-use std::task::{Context, Poll};
+use std::{
+    task::{Context, Poll},
+    time::Duration,
+};
 
 use bytes::Bytes;
 use futures::future::BoxFuture;
@@ -15,7 +18,11 @@ use vector_core::{
 
 use crate::{
     http::{get_http_scheme_from_uri, Auth, HttpClient},
-    sinks::util::{metadata::RequestMetadata, retries::RetryLogic, Compression, UriSerde},
+    internal_events::LokiResponseRejected,
+    sinks::util::{
+        http::retry_after, metadata::RequestMetadata, retries::RetryLogic, Compression,
+        EncodedLength, UriSerde,
+    },
 };
 
 #[derive(Clone)]
@@ -27,7 +34,7 @@ impl RetryLogic for LokiRetryLogic {
 
     fn is_retriable_error(&self, error: &Self::Error) -> bool {
         match error {
-            LokiError::ServerError { code } => match *code {
+            LokiError::ServerError { code, .. } => match *code {
                 StatusCode::TOO_MANY_REQUESTS => true,
                 StatusCode::NOT_IMPLEMENTED => false,
                 _ if code.is_server_error() => true,
@@ -36,12 +43,26 @@ impl RetryLogic for LokiRetryLogic {
             LokiError::HttpError { .. } => true,
         }
     }
+
+    fn error_retry_after(&self, error: &Self::Error) -> Option<Duration> {
+        match error {
+            LokiError::ServerError {
+                code: StatusCode::TOO_MANY_REQUESTS,
+                retry_after,
+            } => Some(retry_after.unwrap_or_default()),
+            LokiError::ServerError { retry_after, .. } => *retry_after,
+            LokiError::HttpError { .. } => None,
+        }
+    }
 }
 
 #[derive(Debug, Snafu)]
 pub enum LokiError {
     #[snafu(display("Server responded with an error: {}", code))]
-    ServerError { code: StatusCode },
+    ServerError {
+        code: StatusCode,
+        retry_after: Option<Duration>,
+    },
     #[snafu(display("Failed to make HTTP(S) request: {}", error))]
     HttpError { error: crate::http::HttpError },
 }
@@ -50,11 +71,20 @@ pub enum LokiError {
 pub struct LokiResponse {
     protocol: &'static str,
     metadata: RequestMetadata,
+    /// Why Loki rejected the events, if it did.
+    rejection_reason: Option<String>,
 }
 
 impl DriverResponse for LokiResponse {
     fn event_status(&self) -> EventStatus {
-        EventStatus::Delivered
+        match self.rejection_reason {
+            Some(_) => EventStatus::Rejected,
+            None => EventStatus::Delivered,
+        }
+    }
+
+    fn rejection_reason(&self) -> Option<String> {
+        self.rejection_reason.clone()
     }
 
     fn events_sent(&self) -> EventsSent {
@@ -88,6 +118,12 @@ impl Finalizable for LokiRequest {
     }
 }
 
+impl EncodedLength for LokiRequest {
+    fn encoded_length(&self) -> usize {
+        self.payload.len()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct LokiService {
     endpoint: UriSerde,
@@ -140,9 +176,33 @@ impl Service<LokiRequest> for LokiService {
                     let status = response.status();
 
                     if status.is_success() {
-                        Ok(LokiResponse { protocol, metadata })
+                        Ok(LokiResponse {
+                            protocol,
+                            metadata,
+                            rejection_reason: None,
+                        })
+                    } else if status.is_client_error() && status != StatusCode::TOO_MANY_REQUESTS {
+                        // Requests Loki refuses to accept, such as ones with out of order or too
+                        // old entries, aren't retried, and the reason it gives is kept.
+                        let body = hyper::body::to_bytes(response.into_body())
+                            .await
+                            .unwrap_or_default();
+                        let reason =
+                            format!("{}: {}", status, String::from_utf8_lossy(&body).trim());
+                        emit!(LokiResponseRejected {
+                            status,
+                            reason: &reason,
+                        });
+                        Ok(LokiResponse {
+                            protocol,
+                            metadata,
+                            rejection_reason: Some(reason),
+                        })
                     } else {
-                        Err(LokiError::ServerError { code: status })
+                        Err(LokiError::ServerError {
+                            code: status,
+                            retry_after: retry_after(response.headers()),
+                        })
                     }
                 }
                 Err(error) => Err(LokiError::HttpError { error }),
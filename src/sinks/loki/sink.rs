@@ -316,7 +316,7 @@ pub struct LokiSink {
     pub(super) encoder: EventEncoder,
     batch_settings: BatcherSettings,
     out_of_order_action: OutOfOrderAction,
-    service: Svc<LokiService, LokiRetryLogic>,
+    service: Svc<LokiService, LokiRetryLogic, LokiRequest>,
 }
 
 impl LokiSink {
@@ -170,6 +170,16 @@ impl SinkConfig for NewRelicConfig {
         Ok((super::VectorSink::from_event_streamsink(sink), healthcheck))
     }
 
+    async fn rebuild_healthcheck(
+        &self,
+        cx: SinkContext,
+    ) -> crate::Result<Option<super::Healthcheck>> {
+        let tls_settings = TlsSettings::from_options(&None)?;
+        let client = HttpClient::new(tls_settings, &cx.proxy)?;
+        let credentials = Arc::from(NewRelicCredentials::from(self));
+        self.build_healthcheck(client, credentials).map(Some)
+    }
+
     fn input(&self) -> Input {
         Input::new(DataType::Log | DataType::Metric)
     }
@@ -23,7 +23,7 @@ use vector_core::{
 use super::{NewRelicCredentials, NewRelicSinkError};
 use crate::{
     http::{get_http_scheme_from_uri, HttpClient},
-    sinks::util::{metadata::RequestMetadata, Compression},
+    sinks::util::{metadata::RequestMetadata, Compression, EncodedLength},
 };
 
 #[derive(Debug, Clone)]
@@ -41,6 +41,12 @@ impl Finalizable for NewRelicApiRequest {
     }
 }
 
+impl EncodedLength for NewRelicApiRequest {
+    fn encoded_length(&self) -> usize {
+        self.payload.len()
+    }
+}
+
 #[derive(Debug)]
 pub struct NewRelicApiResponse {
     event_status: EventStatus,
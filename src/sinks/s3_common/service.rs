@@ -15,6 +15,7 @@ use vector_core::{
 };
 
 use super::config::S3Options;
+use crate::sinks::util::EncodedLength;
 
 #[derive(Debug, Clone)]
 pub struct S3Request {
@@ -31,6 +32,12 @@ impl Finalizable for S3Request {
     }
 }
 
+impl EncodedLength for S3Request {
+    fn encoded_length(&self) -> usize {
+        self.body.len()
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct S3Metadata {
     pub partition_key: String,
@@ -6,7 +6,7 @@ use vector_core::{
     ByteSizeOf,
 };
 
-use crate::sinks::util::ElementCount;
+use crate::sinks::util::{ElementCount, EncodedLength};
 
 #[derive(Clone, Debug)]
 pub struct HecRequest {
@@ -38,3 +38,9 @@ impl Finalizable for HecRequest {
         std::mem::take(&mut self.finalizers)
     }
 }
+
+impl EncodedLength for HecRequest {
+    fn encoded_length(&self) -> usize {
+        self.body.len()
+    }
+}
@@ -4,6 +4,8 @@ pub struct HecResponse {
     pub event_status: EventStatus,
     pub events_count: usize,
     pub events_byte_size: usize,
+    /// Why Splunk rejected the events, if it did.
+    pub rejection_reason: Option<String>,
 }
 
 impl AsRef<EventStatus> for HecResponse {
@@ -24,4 +26,8 @@ impl DriverResponse for HecResponse {
             output: None,
         }
     }
+
+    fn rejection_reason(&self) -> Option<String> {
+        self.rejection_reason.clone()
+    }
 }
@@ -45,6 +45,32 @@ struct HecAckResponseBody {
     ack_id: Option<u64>,
 }
 
+/// The body of an error response from Splunk.
+#[derive(Deserialize, Debug)]
+struct HecErrorResponseBody {
+    text: String,
+    code: Option<u16>,
+    #[serde(rename = "invalid-event-number")]
+    invalid_event_number: Option<usize>,
+}
+
+/// Describes why Splunk rejected a request, from the body of its response.
+fn rejection_reason(body: &[u8]) -> String {
+    match serde_json::from_slice::<HecErrorResponseBody>(body) {
+        Ok(error) => {
+            let mut reason = error.text;
+            if let Some(code) = error.code {
+                reason = format!("{} (code {})", reason, code);
+            }
+            if let Some(invalid_event_number) = error.invalid_event_number {
+                reason = format!("{}, invalid event number: {}", reason, invalid_event_number);
+            }
+            reason
+        }
+        Err(_) => String::from_utf8_lossy(body).into_owned(),
+    }
+}
+
 impl<S> HecService<S>
 where
     S: Service<HecRequest> + Send + 'static,
@@ -121,6 +147,7 @@ where
 
         Box::pin(async move {
             let response = response.await.map_err(Into::into)?;
+            let mut rejection_reason = None;
             let event_status = if response.is_successful() {
                 if let Some(ack_finalizer_tx) = ack_finalizer_tx {
                     let _ack_slot = ack_slot.expect("poll_ready not called before invoking call");
@@ -130,7 +157,15 @@ where
                             if let Some(ack_id) = body.ack_id {
                                 let (tx, rx) = oneshot::channel();
                                 match ack_finalizer_tx.send((ack_id, tx)).await {
-                                    Ok(_) => rx.await.unwrap_or(EventStatus::Rejected),
+                                    Ok(_) => {
+                                        let status = rx.await.unwrap_or(EventStatus::Rejected);
+                                        if status == EventStatus::Rejected {
+                                            rejection_reason = Some(
+                                                "Splunk did not acknowledge the events.".into(),
+                                            );
+                                        }
+                                        status
+                                    }
                                     // If we cannot send ack ids to the ack client, fall back to default behavior
                                     Err(error) => {
                                         emit!(SplunkIndexerAcknowledgementUnavailableError {
@@ -157,6 +192,7 @@ where
             } else if response.is_transient() {
                 EventStatus::Errored
             } else {
+                rejection_reason = Some(self::rejection_reason(response.body()));
                 EventStatus::Rejected
             };
 
@@ -164,6 +200,7 @@ where
                 event_status,
                 events_count,
                 events_byte_size,
+                rejection_reason,
             })
         })
     }
@@ -290,7 +327,7 @@ mod tests {
                 },
                 build_http_batch_service,
                 request::HecRequest,
-                service::{HecAckResponseBody, HecService, HttpRequestBuilder},
+                service::{rejection_reason, HecAckResponseBody, HecService, HttpRequestBuilder},
                 EndpointTarget,
             },
             util::Compression,
@@ -530,6 +567,21 @@ mod tests {
         assert_eq!(EventStatus::Delivered, response.event_status)
     }
 
+    #[test]
+    fn describes_rejections() {
+        assert_eq!(
+            rejection_reason(
+                br#"{"text":"Invalid data format","code":6,"invalid-event-number":2}"#
+            ),
+            "Invalid data format (code 6), invalid event number: 2"
+        );
+        assert_eq!(
+            rejection_reason(br#"{"text":"Incorrect index","code":7}"#),
+            "Incorrect index (code 7)"
+        );
+        assert_eq!(rejection_reason(b"Bad Request"), "Bad Request");
+    }
+
     #[tokio::test]
     async fn service_poll_ready_multiple_times() {
         let mock_server = get_hec_mock_server(true, ack_response_always_fail).await;
@@ -160,6 +160,13 @@ impl SinkConfig for HecLogsSinkConfig {
         Ok((sink, healthcheck))
     }
 
+    async fn rebuild_healthcheck(&self, cx: SinkContext) -> crate::Result<Option<Healthcheck>> {
+        let client = create_client(&self.tls, cx.proxy())?;
+        Ok(Some(
+            build_healthcheck(self.endpoint.clone(), self.default_token.clone(), client).boxed(),
+        ))
+    }
+
     fn input(&self) -> Input {
         Input::new(self.encoding.config().input_type() & DataType::Log)
     }
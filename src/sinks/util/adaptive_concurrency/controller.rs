@@ -42,6 +42,8 @@ pub(super) struct Inner {
     current_rtt: Mean,
     had_back_pressure: bool,
     reached_limit: bool,
+    // The service signalled it is overloaded, so the limit isn't increased until this has passed.
+    throttled_until: Instant,
 }
 
 #[cfg(test)]
@@ -77,6 +79,7 @@ impl<L> Controller<L> {
                 current_rtt: Default::default(),
                 had_back_pressure: false,
                 reached_limit: false,
+                throttled_until: instant_now(),
             })),
             #[cfg(test)]
             stats: Arc::new(Mutex::new(ControllerStatistics::default())),
@@ -109,7 +112,16 @@ impl<L> Controller<L> {
     /// Adjust the controller to a response, based on type of response
     /// given (backpressure or not) and if it should be used as a valid
     /// RTT measurement.
-    fn adjust_to_response_inner(&self, start: Instant, is_back_pressure: bool, use_rtt: bool) {
+    ///
+    /// `throttled` is the delay the service asked for when it explicitly
+    /// signalled that it is overloaded.
+    fn adjust_to_response_inner(
+        &self,
+        start: Instant,
+        is_back_pressure: bool,
+        use_rtt: bool,
+        throttled: Option<Duration>,
+    ) {
         let now = instant_now();
         let mut inner = self.inner.lock().expect("Controller mutex is poisoned");
 
@@ -123,6 +135,24 @@ impl<L> Controller<L> {
             inner.had_back_pressure = true;
         }
 
+        // An explicit signal from the service doesn't need confirming by the
+        // RTT, so the limit is decreased right away, rather than at the end
+        // of the current interval. Further signals received while throttled
+        // are handled as normal back pressure, so that a burst of them only
+        // decreases the limit once.
+        if let Some(delay) = throttled {
+            inner.had_back_pressure = true;
+            if now >= inner.throttled_until {
+                inner.next_update = now;
+            }
+            let past_rtt = inner.past_rtt.state().map_or(Duration::ZERO, |past_rtt| {
+                Duration::from_secs_f64(past_rtt.mean)
+            });
+            if let Some(until) = now.checked_add(delay.max(past_rtt)) {
+                inner.throttled_until = inner.throttled_until.max(until);
+            }
+        }
+
         #[cfg(test)]
         let mut stats = self.stats.lock().expect("Stats mutex is poisoned");
 
@@ -181,7 +211,7 @@ impl<L> Controller<L> {
 
                     // Only manage the concurrency if `concurrency` was set to "adaptive"
                     if self.concurrency.is_none() {
-                        self.manage_limit(&mut inner, past_rtt, current_rtt);
+                        self.manage_limit(&mut inner, past_rtt, current_rtt, now);
                     }
 
                     // Reset values for next interval
@@ -202,6 +232,7 @@ impl<L> Controller<L> {
         inner: &mut MutexGuard<Inner>,
         past_rtt: MeanVariance,
         current_rtt: Option<f64>,
+        now: Instant,
     ) {
         let past_rtt_deviation = past_rtt.variance.sqrt();
         let threshold = past_rtt_deviation * self.settings.rtt_deviation_scale;
@@ -213,6 +244,7 @@ impl<L> Controller<L> {
         if inner.current_limit < super::MAX_CONCURRENCY
             && inner.reached_limit
             && !inner.had_back_pressure
+            && now >= inner.throttled_until
             && current_rtt.is_some()
             && current_rtt.unwrap() <= past_rtt.mean
         {
@@ -241,6 +273,37 @@ impl<L> Controller<L> {
             past_rtt_deviation: Duration::from_secs_f64(past_rtt_deviation),
         });
     }
+
+    /// Drops the limit back to a single request, as when the circuit breaker
+    /// in front of the service opens. The limit then increases again as
+    /// normal once the service has recovered.
+    pub(super) fn reset_limit(&self) {
+        // Only manage the concurrency if `concurrency` was set to "adaptive"
+        if self.concurrency.is_some() {
+            return;
+        }
+
+        let mut inner = self.inner.lock().expect("Controller mutex is poisoned");
+        if inner.current_limit > 1 {
+            self.semaphore.forget_permits(inner.current_limit - 1);
+            inner.current_limit = 1;
+        }
+        inner.had_back_pressure = true;
+
+        let past_rtt = inner.past_rtt.state();
+        emit!(AdaptiveConcurrencyLimit {
+            concurrency: inner.current_limit as u64,
+            reached_limit: inner.reached_limit,
+            had_back_pressure: inner.had_back_pressure,
+            current_rtt: inner.current_rtt.average().map(Duration::from_secs_f64),
+            past_rtt: past_rtt.map_or(Duration::ZERO, |past_rtt| {
+                Duration::from_secs_f64(past_rtt.mean)
+            }),
+            past_rtt_deviation: past_rtt.map_or(Duration::ZERO, |past_rtt| {
+                Duration::from_secs_f64(past_rtt.variance.sqrt())
+            }),
+        });
+    }
 }
 
 impl<L> Controller<L>
@@ -258,10 +321,16 @@ where
         let response_action = response
             .as_ref()
             .map(|resp| self.logic.should_retry_response(resp));
+        let mut throttled = None;
         let is_back_pressure = match &response_action {
+            Ok(RetryAction::Throttled(_, retry_after)) => {
+                throttled = Some(retry_after.unwrap_or_default());
+                true
+            }
             Ok(action) => matches!(action, RetryAction::Retry(_)),
             Err(error) => {
                 if let Some(error) = error.downcast_ref::<L::Error>() {
+                    throttled = self.logic.error_retry_after(error);
                     self.logic.is_retriable_error(error)
                 } else if error.downcast_ref::<Elapsed>().is_some() {
                     true
@@ -280,6 +349,6 @@ where
         };
         // Only adjust to the RTT when the request was successfully processed.
         let use_rtt = matches!(response_action, Ok(RetryAction::Successful));
-        self.adjust_to_response_inner(start, is_back_pressure, use_rtt)
+        self.adjust_to_response_inner(start, is_back_pressure, use_rtt, throttled)
     }
 }
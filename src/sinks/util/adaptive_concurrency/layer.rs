@@ -1,7 +1,9 @@
+use std::sync::Arc;
+
 use tower::Layer;
 
 use super::{AdaptiveConcurrencyLimit, AdaptiveConcurrencySettings};
-use crate::sinks::util::retries::RetryLogic;
+use crate::sinks::util::{retries::RetryLogic, service::CircuitBreaker};
 
 /// Enforces a limit on the concurrent number of requests the underlying
 /// service can handle.
@@ -10,6 +12,7 @@ pub struct AdaptiveConcurrencyLimitLayer<L> {
     concurrency: Option<usize>,
     options: AdaptiveConcurrencySettings,
     logic: L,
+    circuit_breaker: Option<CircuitBreaker>,
 }
 
 impl<L> AdaptiveConcurrencyLimitLayer<L> {
@@ -23,14 +26,37 @@ impl<L> AdaptiveConcurrencyLimitLayer<L> {
             concurrency,
             options,
             logic,
+            circuit_breaker: None,
         }
     }
+
+    /// Drop the limit back to a single request whenever the given circuit
+    /// breaker opens.
+    #[allow(clippy::missing_const_for_fn)] // `Option<CircuitBreaker>` can't be dropped in a const fn
+    pub fn with_circuit_breaker(mut self, circuit_breaker: Option<CircuitBreaker>) -> Self {
+        self.circuit_breaker = circuit_breaker;
+        self
+    }
 }
 
 impl<S, L: RetryLogic> Layer<S> for AdaptiveConcurrencyLimitLayer<L> {
     type Service = AdaptiveConcurrencyLimit<S, L>;
 
     fn layer(&self, service: S) -> Self::Service {
-        AdaptiveConcurrencyLimit::new(service, self.logic.clone(), self.concurrency, self.options)
+        let service = AdaptiveConcurrencyLimit::new(
+            service,
+            self.logic.clone(),
+            self.concurrency,
+            self.options,
+        );
+        if let Some(circuit_breaker) = &self.circuit_breaker {
+            let controller = Arc::downgrade(&service.controller);
+            circuit_breaker.on_open(move || {
+                if let Some(controller) = controller.upgrade() {
+                    controller.reset_limit();
+                }
+            });
+        }
+        service
     }
 }
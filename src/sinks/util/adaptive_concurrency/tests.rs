@@ -175,7 +175,7 @@ impl SinkConfig for TestConfig {
 
         // Dig deep to get at the internal controller statistics
         let stats = Arc::clone(
-            &Pin::new(&sink.get_ref().get_ref().get_ref().get_ref())
+            &Pin::new(&sink.get_ref().get_ref().get_ref().get_ref().get_ref())
                 .get_ref()
                 .controller
                 .stats,
@@ -1,6 +1,7 @@
 use serde_json::value::{to_raw_value, RawValue, Value};
 
 use super::super::batch::{err_event_too_large, Batch, BatchSize, PushResult};
+use crate::sinks::util::EncodedLength;
 
 pub type BoxedRawValue = Box<RawValue>;
 
@@ -62,6 +63,13 @@ impl Batch for JsonArrayBuffer {
     }
 }
 
+impl EncodedLength for BoxedRawValue {
+    // Each value is followed by a separator in the encoded array.
+    fn encoded_length(&self) -> usize {
+        self.get().len() + 1
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use serde_json::json;
@@ -2,11 +2,14 @@ pub mod sort;
 
 use std::cmp::Ordering;
 
-use vector_core::event::metric::{Metric, MetricValue, Sample};
+use vector_core::{
+    event::metric::{Metric, MetricValue, Sample},
+    ByteSizeOf,
+};
 
 use crate::sinks::util::{
     batch::{Batch, BatchConfig, BatchError, BatchSize, PushResult},
-    Merged, SinkBatchSettings,
+    EncodedLength, Merged, SinkBatchSettings,
 };
 
 mod normalize;
@@ -91,6 +94,14 @@ impl Batch for MetricsBuffer {
     }
 }
 
+/// Batched metrics are only encoded by the sink's service, so their size in memory stands in for
+/// their encoded length.
+impl EncodedLength for Metric {
+    fn encoded_length(&self) -> usize {
+        self.size_of()
+    }
+}
+
 fn finalize_metric(metric: &mut Metric) {
     if let MetricValue::Distribution { samples, .. } = metric.data_mut().value_mut() {
         let compressed_samples = compress_distribution(samples);
@@ -4,7 +4,7 @@ use super::super::{
     batch::{Batch, BatchConfig, BatchError, PushResult},
     ElementCount,
 };
-use crate::sinks::util::{Merged, SinkBatchSettings};
+use crate::sinks::util::{EncodedLength, Merged, SinkBatchSettings};
 
 pub trait Partition<K> {
     fn partition(&self) -> K;
@@ -102,6 +102,12 @@ impl<T: ByteSizeOf, K> ByteSizeOf for PartitionInnerBuffer<T, K> {
     }
 }
 
+impl<T: EncodedLength, K> EncodedLength for PartitionInnerBuffer<T, K> {
+    fn encoded_length(&self) -> usize {
+        self.inner.encoded_length()
+    }
+}
+
 impl<T: ElementCount, K> ElementCount for PartitionInnerBuffer<T, K> {
     fn element_count(&self) -> usize {
         self.inner.element_count()
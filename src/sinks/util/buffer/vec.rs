@@ -1,4 +1,4 @@
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 
 use super::{err_event_too_large, Batch, BatchSize, PushResult};
 
@@ -70,6 +70,18 @@ impl EncodedLength for Bytes {
     }
 }
 
+impl EncodedLength for BytesMut {
+    fn encoded_length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T: EncodedLength> EncodedLength for Vec<T> {
+    fn encoded_length(&self) -> usize {
+        self.iter().map(EncodedLength::encoded_length).sum()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
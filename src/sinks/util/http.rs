@@ -10,8 +10,9 @@ use std::{
 };
 
 use bytes::{Buf, Bytes};
+use chrono::{DateTime, Utc};
 use futures::{future::BoxFuture, ready, Sink};
-use http::StatusCode;
+use http::{header::RETRY_AFTER, HeaderMap, StatusCode};
 use hyper::{body, Body};
 use indexmap::IndexMap;
 use pin_project::pin_project;
@@ -21,7 +22,8 @@ use vector_core::ByteSizeOf;
 
 use super::{
     retries::{RetryAction, RetryLogic},
-    sink, uri, Batch, EncodedEvent, Partition, TowerBatchedSink, TowerPartitionSink,
+    service::RETRY_MAX_DURATION_SECONDS_DEFAULT,
+    sink, uri, Batch, EncodedEvent, EncodedLength, Partition, TowerBatchedSink, TowerPartitionSink,
     TowerRequestConfig, TowerRequestSettings,
 };
 use crate::{
@@ -63,7 +65,7 @@ pub trait HttpSink: Send + Sync + 'static {
 pub struct BatchedHttpSink<T, B, RL = HttpRetryLogic>
 where
     B: Batch,
-    B::Output: ByteSizeOf + Clone + Send + 'static,
+    B::Output: ByteSizeOf + EncodedLength + Clone + Send + 'static,
     T: HttpSink<Input = B::Input, Output = B::Output>,
     RL: RetryLogic<Response = http::Response<Bytes>> + Send + 'static,
 {
@@ -84,7 +86,7 @@ where
 impl<T, B> BatchedHttpSink<T, B>
 where
     B: Batch,
-    B::Output: ByteSizeOf + Clone + Send + 'static,
+    B::Output: ByteSizeOf + EncodedLength + Clone + Send + 'static,
     T: HttpSink<Input = B::Input, Output = B::Output>,
 {
     pub fn new(
@@ -108,7 +110,7 @@ where
 impl<T, B, RL> BatchedHttpSink<T, B, RL>
 where
     B: Batch,
-    B::Output: ByteSizeOf + Clone + Send + 'static,
+    B::Output: ByteSizeOf + EncodedLength + Clone + Send + 'static,
     RL: RetryLogic<Response = http::Response<Bytes>, Error = HttpError> + Send + 'static,
     T: HttpSink<Input = B::Input, Output = B::Output>,
 {
@@ -144,7 +146,7 @@ where
 impl<T, B, RL> Sink<Event> for BatchedHttpSink<T, B, RL>
 where
     B: Batch,
-    B::Output: ByteSizeOf + Clone + Send + 'static,
+    B::Output: ByteSizeOf + EncodedLength + Clone + Send + 'static,
     T: HttpSink<Input = B::Input, Output = B::Output>,
     RL: RetryLogic<Response = http::Response<Bytes>> + Send + 'static,
 {
@@ -201,7 +203,7 @@ where
 pub struct PartitionHttpSink<T, B, K, RL = HttpRetryLogic>
 where
     B: Batch,
-    B::Output: ByteSizeOf + Clone + Send + 'static,
+    B::Output: ByteSizeOf + EncodedLength + Clone + Send + 'static,
     B::Input: Partition<K>,
     K: Hash + Eq + Clone + Send + 'static,
     T: HttpSink<Input = B::Input, Output = B::Output>,
@@ -222,7 +224,7 @@ where
 impl<T, B, K> PartitionHttpSink<T, B, K, HttpRetryLogic>
 where
     B: Batch,
-    B::Output: ByteSizeOf + Clone + Send + 'static,
+    B::Output: ByteSizeOf + EncodedLength + Clone + Send + 'static,
     B::Input: Partition<K>,
     K: Hash + Eq + Clone + Send + 'static,
     T: HttpSink<Input = B::Input, Output = B::Output>,
@@ -248,7 +250,7 @@ where
 impl<T, B, K, RL> PartitionHttpSink<T, B, K, RL>
 where
     B: Batch,
-    B::Output: ByteSizeOf + Clone + Send + 'static,
+    B::Output: ByteSizeOf + EncodedLength + Clone + Send + 'static,
     B::Input: Partition<K>,
     K: Hash + Eq + Clone + Send + 'static,
     T: HttpSink<Input = B::Input, Output = B::Output>,
@@ -292,7 +294,7 @@ where
 impl<T, B, K, RL> Sink<Event> for PartitionHttpSink<T, B, K, RL>
 where
     B: Batch,
-    B::Output: ByteSizeOf + Clone + Send + 'static,
+    B::Output: ByteSizeOf + EncodedLength + Clone + Send + 'static,
     B::Input: Partition<K>,
     K: Hash + Eq + Clone + Send + 'static,
     T: HttpSink<Input = B::Input, Output = B::Output>,
@@ -440,12 +442,15 @@ impl RetryLogic for HttpRetryLogic {
         let status = response.status();
 
         match status {
-            StatusCode::TOO_MANY_REQUESTS => RetryAction::Retry("too many requests".into()),
+            StatusCode::TOO_MANY_REQUESTS => {
+                RetryAction::Throttled("too many requests".into(), retry_after(response.headers()))
+            }
             StatusCode::NOT_IMPLEMENTED => {
                 RetryAction::DontRetry("endpoint not implemented".into())
             }
-            _ if status.is_server_error() => RetryAction::Retry(
-                format!("{}: {}", status, String::from_utf8_lossy(response.body())).into(),
+            _ if status.is_server_error() => server_error_retry(
+                format!("{}: {}", status, String::from_utf8_lossy(response.body())),
+                response.headers(),
             ),
             _ if status.is_success() => RetryAction::Successful,
             _ => RetryAction::DontRetry(format!("response status: {}", status).into()),
@@ -453,6 +458,34 @@ impl RetryLogic for HttpRetryLogic {
     }
 }
 
+/// Gets the delay the service asked for before retrying, from the `Retry-After` header.
+///
+/// Both forms of the header are supported: a number of seconds, and an HTTP date. Dates in the past
+/// result in no delay, and delays are capped at the default `retry_max_duration_secs`, so that a
+/// bogus header can't stall the sink indefinitely.
+pub fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    let delay = match value.parse::<u64>() {
+        Ok(secs) => Duration::from_secs(secs),
+        Err(_) => {
+            let date = DateTime::parse_from_rfc2822(value).ok()?;
+            (date.with_timezone(&Utc) - Utc::now())
+                .to_std()
+                .unwrap_or_default()
+        }
+    };
+    Some(delay.min(Duration::from_secs(RETRY_MAX_DURATION_SECONDS_DEFAULT)))
+}
+
+/// Retries a server error, treating it as the service being overloaded if it asked for a delay
+/// before retrying.
+pub fn server_error_retry(reason: String, headers: &HeaderMap) -> RetryAction {
+    match retry_after(headers) {
+        Some(retry_after) => RetryAction::Throttled(reason.into(), Some(retry_after)),
+        None => RetryAction::Retry(reason.into()),
+    }
+}
+
 /// A more generic version of `HttpRetryLogic` that accepts anything that can be converted
 /// to a status code
 #[derive(Debug)]
@@ -490,7 +523,9 @@ where
         let status = (self.func)(response);
 
         match status {
-            StatusCode::TOO_MANY_REQUESTS => RetryAction::Retry("too many requests".into()),
+            StatusCode::TOO_MANY_REQUESTS => {
+                RetryAction::Throttled("too many requests".into(), None)
+            }
             StatusCode::NOT_IMPLEMENTED => {
                 RetryAction::DontRetry("endpoint not implemented".into())
             }
@@ -558,8 +593,9 @@ mod test {
         let response_400 = Response::builder().status(400).body(Bytes::new()).unwrap();
         let response_501 = Response::builder().status(501).body(Bytes::new()).unwrap();
 
-        assert!(logic.should_retry_response(&response_429).is_retryable());
+        assert!(logic.should_retry_response(&response_429).is_throttled());
         assert!(logic.should_retry_response(&response_500).is_retryable());
+        assert!(!logic.should_retry_response(&response_500).is_throttled());
         assert!(logic
             .should_retry_response(&response_400)
             .is_not_retryable());
@@ -568,6 +604,56 @@ mod test {
             .is_not_retryable());
     }
 
+    #[test]
+    fn util_http_retry_logic_retry_after() {
+        let logic = HttpRetryLogic;
+
+        let response_429 = Response::builder()
+            .status(429)
+            .header("Retry-After", "30")
+            .body(Bytes::new())
+            .unwrap();
+        let response_503 = Response::builder()
+            .status(503)
+            .header("Retry-After", "Wed, 21 Oct 2015 07:28:00 GMT")
+            .body(Bytes::new())
+            .unwrap();
+
+        assert!(matches!(
+            logic.should_retry_response(&response_429),
+            RetryAction::Throttled(_, Some(delay)) if delay == Duration::from_secs(30)
+        ));
+        // Dates in the past mean the request can be retried right away.
+        assert!(matches!(
+            logic.should_retry_response(&response_503),
+            RetryAction::Throttled(_, Some(delay)) if delay == Duration::ZERO
+        ));
+    }
+
+    #[test]
+    fn retry_after_parses_dates() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(retry_after(&headers), None);
+
+        headers.insert(RETRY_AFTER, "soon".parse().unwrap());
+        assert_eq!(retry_after(&headers), None);
+
+        let date = (Utc::now() + chrono::Duration::seconds(120)).to_rfc2822();
+        headers.insert(RETRY_AFTER, date.parse().unwrap());
+        let delay = retry_after(&headers).unwrap();
+        assert!(delay > Duration::from_secs(110) && delay <= Duration::from_secs(120));
+    }
+
+    #[test]
+    fn retry_after_is_capped() {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, "18446744073709551615".parse().unwrap());
+        assert_eq!(
+            retry_after(&headers),
+            Some(Duration::from_secs(RETRY_MAX_DURATION_SECONDS_DEFAULT))
+        );
+    }
+
     #[tokio::test]
     async fn util_http_it_makes_http_requests() {
         let addr = next_addr();
@@ -16,6 +16,9 @@ use crate::Error;
 pub enum RetryAction {
     /// Indicate that this request should be retried with a reason
     Retry(Cow<'static, str>),
+    /// Indicate that this request should be retried with a reason, as the service is overloaded,
+    /// waiting at least as long as the service asked for, if it did
+    Throttled(Cow<'static, str>, Option<Duration>),
     /// Indicate that this request should not be retried with a reason
     DontRetry(Cow<'static, str>),
     /// Indicate that this request should not be retried but the request was successful
@@ -28,6 +31,14 @@ pub trait RetryLogic: Clone + Send + Sync + 'static {
 
     fn is_retriable_error(&self, error: &Self::Error) -> bool;
 
+    /// The delay the service asked for before retrying after the given error, such as with a
+    /// `Retry-After` header.
+    ///
+    /// Errors with such a delay are treated as the service signalling that it is overloaded.
+    fn error_retry_after(&self, _error: &Self::Error) -> Option<Duration> {
+        None
+    }
+
     fn should_retry_response(&self, _response: &Self::Response) -> RetryAction {
         // Treat the default as the request is successful
         RetryAction::Successful
@@ -80,11 +91,16 @@ impl<L: RetryLogic> FixedRetryPolicy<L> {
         self.current_duration
     }
 
-    fn build_retry(&self) -> RetryPolicyFuture<L> {
+    /// Builds the retry, waiting at least `retry_after` if the service asked for a delay, up to the
+    /// maximum duration between retries.
+    fn build_retry(&self, retry_after: Option<Duration>) -> RetryPolicyFuture<L> {
         let policy = self.advance();
-        let delay = Box::pin(sleep(self.backoff()));
+        let backoff = retry_after.map_or(self.backoff(), |retry_after| {
+            cmp::max(cmp::min(retry_after, self.max_duration), self.backoff())
+        });
+        let delay = Box::pin(sleep(backoff));
 
-        debug!(message = "Retrying request.", delay_ms = %self.backoff().as_millis());
+        debug!(message = "Retrying request.", delay_ms = %backoff.as_millis());
         RetryPolicyFuture { delay, policy }
     }
 }
@@ -109,7 +125,24 @@ where
                     }
 
                     warn!(message = "Retrying after response.", reason = %reason);
-                    Some(self.build_retry())
+                    Some(self.build_retry(None))
+                }
+
+                RetryAction::Throttled(reason, retry_after) => {
+                    if self.remaining_attempts == 0 {
+                        error!(
+                            message = "Throttled but retries exhausted; dropping the request.",
+                            reason = ?reason
+                        );
+                        return None;
+                    }
+
+                    warn!(
+                        message = "Retrying after being throttled.",
+                        reason = %reason,
+                        retry_after_secs = ?retry_after.map(|retry_after| retry_after.as_secs_f64())
+                    );
+                    Some(self.build_retry(retry_after))
                 }
 
                 RetryAction::DontRetry(reason) => {
@@ -128,7 +161,7 @@ where
                 if let Some(expected) = error.downcast_ref::<L::Error>() {
                     if self.logic.is_retriable_error(expected) {
                         warn!(message = "Retrying after error.", error = %expected);
-                        Some(self.build_retry())
+                        Some(self.build_retry(self.logic.error_retry_after(expected)))
                     } else {
                         error!(
                             message = "Non-retriable error; dropping the request.",
@@ -138,7 +171,7 @@ where
                     }
                 } else if error.downcast_ref::<Elapsed>().is_some() {
                     warn!("Request timed out. If this happens often while the events are actually reaching their destination, try decreasing `batch.max_bytes` and/or using `compression` if applicable. Alternatively `request.timeout_secs` can be increased.");
-                    Some(self.build_retry())
+                    Some(self.build_retry(None))
                 } else {
                     error!(
                         message = "Unexpected error type; dropping the request.",
@@ -170,7 +203,11 @@ impl<L: RetryLogic> Future for RetryPolicyFuture<L> {
 
 impl RetryAction {
     pub const fn is_retryable(&self) -> bool {
-        matches!(self, RetryAction::Retry(_))
+        matches!(self, RetryAction::Retry(_) | RetryAction::Throttled(..))
+    }
+
+    pub const fn is_throttled(&self) -> bool {
+        matches!(self, RetryAction::Throttled(..))
     }
 
     pub const fn is_not_retryable(&self) -> bool {
@@ -348,6 +385,57 @@ mod tests {
         assert_eq!(fut.await.unwrap(), "world");
     }
 
+    #[tokio::test]
+    async fn throttled_waits_for_retry_after() {
+        trace_init();
+
+        time::pause();
+
+        let policy = FixedRetryPolicy::new(
+            5,
+            Duration::from_secs(1),
+            Duration::from_secs(10),
+            SvcRetryLogic,
+        );
+
+        let (mut svc, mut handle) = mock::spawn_layer(RetryLayer::new(policy));
+
+        assert_ready_ok!(svc.poll_ready());
+
+        let mut fut = task::spawn(svc.call("hello"));
+        assert_request_eq!(handle, "hello").send_response("slow down");
+        assert_pending!(fut.poll());
+
+        // The backoff has passed, but not the delay the service asked for.
+        time::advance(Duration::from_secs(2)).await;
+        assert_pending!(fut.poll());
+        assert_pending!(handle.poll_request());
+
+        time::advance(Duration::from_secs(4)).await;
+        assert_pending!(fut.poll());
+
+        assert_request_eq!(handle, "hello").send_response("world");
+        assert_eq!(fut.await.unwrap(), "world");
+    }
+
+    #[tokio::test]
+    async fn throttled_wait_is_capped() {
+        time::pause();
+
+        let policy = FixedRetryPolicy::new(
+            5,
+            Duration::from_secs(1),
+            Duration::from_secs(10),
+            SvcRetryLogic,
+        );
+
+        let retry = policy.build_retry(Some(Duration::MAX));
+        assert_eq!(
+            retry.delay.deadline(),
+            time::Instant::now() + Duration::from_secs(10)
+        );
+    }
+
     #[test]
     fn backoff_grows_to_max() {
         let mut policy = FixedRetryPolicy::new(
@@ -390,6 +478,14 @@ mod tests {
         fn is_retriable_error(&self, error: &Self::Error) -> bool {
             error.0
         }
+
+        fn should_retry_response(&self, response: &Self::Response) -> RetryAction {
+            if *response == "slow down" {
+                RetryAction::Throttled("slow down".into(), Some(Duration::from_secs(5)))
+            } else {
+                RetryAction::Successful
+            }
+        }
     }
 
     #[derive(Debug)]
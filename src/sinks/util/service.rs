@@ -1,4 +1,4 @@
-use std::{hash::Hash, marker::PhantomData, sync::Arc, time::Duration};
+use std::{hash::Hash, marker::PhantomData, num::NonZeroU64, sync::Arc, time::Duration};
 
 use tower::{
     layer::{util::Stack, Layer},
@@ -10,6 +10,8 @@ use tower::{
 use vector_config::configurable_component;
 
 pub use crate::sinks::util::service::{
+    byte_rate_limit::{ByteRateLimit, ByteRateLimiter},
+    circuit_breaker::{CircuitBreaker, CircuitBreakerConfig, CircuitBreakerService, CircuitState},
     concurrency::{concurrency_is_none, Concurrency},
     map::Map,
 };
@@ -20,15 +22,26 @@ use crate::sinks::util::{
     retries::{FixedRetryPolicy, RetryLogic},
     service::map::MapLayer,
     sink::Response,
-    Batch, BatchSink, Partition, PartitionBatchSink,
+    Batch, BatchSink, EncodedLength, Partition, PartitionBatchSink,
 };
 
+mod byte_rate_limit;
+mod circuit_breaker;
 mod concurrency;
 mod map;
 
-pub type Svc<S, L> = RateLimit<AdaptiveConcurrencyLimit<Retry<FixedRetryPolicy<L>, Timeout<S>>, L>>;
-pub type TowerBatchedSink<S, B, RL> = BatchSink<Svc<S, RL>, B>;
-pub type TowerPartitionSink<S, B, RL, K> = PartitionBatchSink<Svc<S, RL>, B, K>;
+pub type Svc<S, L, Request> = RateLimit<
+    ByteRateLimit<
+        AdaptiveConcurrencyLimit<
+            Retry<FixedRetryPolicy<L>, CircuitBreakerService<Timeout<S>, L>>,
+            L,
+        >,
+        Request,
+    >,
+>;
+pub type TowerBatchedSink<S, B, RL> = BatchSink<Svc<S, RL, <B as Batch>::Output>, B>;
+pub type TowerPartitionSink<S, B, RL, K> =
+    PartitionBatchSink<Svc<S, RL, <B as Batch>::Output>, B, K>;
 
 pub trait ServiceBuilderExt<L> {
     fn map<R1, R2, F>(self, f: F) -> ServiceBuilder<Stack<MapLayer<R1, R2>, L>>
@@ -39,7 +52,9 @@ pub trait ServiceBuilderExt<L> {
         self,
         settings: TowerRequestSettings,
         retry_logic: RL,
-    ) -> ServiceBuilder<Stack<TowerRequestLayer<RL, Request>, L>>;
+    ) -> ServiceBuilder<Stack<TowerRequestLayer<RL, Request>, L>>
+    where
+        Request: EncodedLength;
 }
 
 impl<L> ServiceBuilderExt<L> for ServiceBuilder<L> {
@@ -54,10 +69,14 @@ impl<L> ServiceBuilderExt<L> for ServiceBuilder<L> {
         self,
         settings: TowerRequestSettings,
         retry_logic: RL,
-    ) -> ServiceBuilder<Stack<TowerRequestLayer<RL, Request>, L>> {
+    ) -> ServiceBuilder<Stack<TowerRequestLayer<RL, Request>, L>>
+    where
+        Request: EncodedLength,
+    {
         self.layer(TowerRequestLayer {
             settings,
             retry_logic,
+            request_size: Request::encoded_length,
             _pd: std::marker::PhantomData,
         })
     }
@@ -86,6 +105,12 @@ pub struct TowerRequestConfig {
     /// The maximum number of requests allowed within the `rate_limit_duration_secs` time window.
     pub rate_limit_num: Option<u64>,
 
+    /// The maximum number of bytes sent per second, measured as the encoded size of each request.
+    ///
+    /// Requests exceeding the limit are delayed rather than dropped. Up to one second worth of bytes
+    /// can be sent at once after being idle. Retries are not counted against the limit.
+    pub rate_limit_bytes_per_sec: Option<NonZeroU64>,
+
     /// The maximum number of retries to make for failed requests.
     ///
     /// The default, for all intents and purposes, represents an infinite number of retries.
@@ -102,6 +127,10 @@ pub struct TowerRequestConfig {
     #[configurable(derived)]
     #[serde(default)]
     pub adaptive_concurrency: AdaptiveConcurrencySettings,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub circuit_breaker: CircuitBreakerConfig,
 }
 
 pub const CONCURRENCY_DEFAULT: Concurrency = Concurrency::None;
@@ -125,10 +154,12 @@ impl TowerRequestConfig {
             timeout_secs: Some(TIMEOUT_SECONDS_DEFAULT),
             rate_limit_duration_secs: Some(RATE_LIMIT_DURATION_SECONDS_DEFAULT),
             rate_limit_num: Some(RATE_LIMIT_NUM_DEFAULT),
+            rate_limit_bytes_per_sec: None,
             retry_attempts: Some(RETRY_ATTEMPTS_DEFAULT),
             retry_max_duration_secs: Some(RETRY_MAX_DURATION_SECONDS_DEFAULT),
             retry_initial_backoff_secs: Some(RETRY_INITIAL_BACKOFF_SECONDS_DEFAULT),
             adaptive_concurrency: AdaptiveConcurrencySettings::const_default(),
+            circuit_breaker: CircuitBreakerConfig::const_default(),
         }
     }
 
@@ -151,6 +182,11 @@ impl TowerRequestConfig {
         self
     }
 
+    pub const fn rate_limit_bytes_per_sec(mut self, rate_limit_bytes_per_sec: NonZeroU64) -> Self {
+        self.rate_limit_bytes_per_sec = Some(rate_limit_bytes_per_sec);
+        self
+    }
+
     pub const fn retry_attempts(mut self, retry_attempts: usize) -> Self {
         self.retry_attempts = Some(retry_attempts);
         self
@@ -183,6 +219,9 @@ impl TowerRequestConfig {
                 .rate_limit_num
                 .or(defaults.rate_limit_num)
                 .unwrap_or(RATE_LIMIT_NUM_DEFAULT),
+            rate_limit_bytes_per_sec: self
+                .rate_limit_bytes_per_sec
+                .or(defaults.rate_limit_bytes_per_sec),
             retry_attempts: self
                 .retry_attempts
                 .or(defaults.retry_attempts)
@@ -198,6 +237,7 @@ impl TowerRequestConfig {
                     .unwrap_or(RETRY_INITIAL_BACKOFF_SECONDS_DEFAULT),
             ),
             adaptive_concurrency: self.adaptive_concurrency,
+            circuit_breaker: self.circuit_breaker,
         }
     }
 }
@@ -208,10 +248,12 @@ pub struct TowerRequestSettings {
     pub timeout: Duration,
     pub rate_limit_duration: Duration,
     pub rate_limit_num: u64,
+    pub rate_limit_bytes_per_sec: Option<NonZeroU64>,
     pub retry_attempts: usize,
     pub retry_max_duration_secs: Duration,
     pub retry_initial_backoff_secs: Duration,
     pub adaptive_concurrency: AdaptiveConcurrencySettings,
+    pub circuit_breaker: CircuitBreakerConfig,
 }
 
 impl TowerRequestSettings {
@@ -240,7 +282,7 @@ impl TowerRequestSettings {
         S::Future: Send + 'static,
         B: Batch,
         B::Input: Partition<K>,
-        B::Output: Send + Clone + 'static,
+        B::Output: EncodedLength + Send + Clone + 'static,
         K: Hash + Eq + Clone + Send + 'static,
     {
         let service = ServiceBuilder::new()
@@ -264,7 +306,7 @@ impl TowerRequestSettings {
         S::Response: Send + Response,
         S::Future: Send + 'static,
         B: Batch,
-        B::Output: Send + Clone + 'static,
+        B::Output: EncodedLength + Send + Clone + 'static,
     {
         let service = ServiceBuilder::new()
             .settings(self.clone(), retry_logic)
@@ -277,6 +319,7 @@ impl TowerRequestSettings {
 pub struct TowerRequestLayer<L, Request> {
     settings: TowerRequestSettings,
     retry_logic: L,
+    request_size: fn(&Request) -> usize,
     _pd: PhantomData<Request>,
 }
 
@@ -289,21 +332,38 @@ where
     RL: RetryLogic<Response = S::Response> + Send + 'static,
     Request: Clone + Send + 'static,
 {
-    type Service = Svc<S, RL>;
+    type Service = Svc<S, RL, Request>;
 
     fn layer(&self, inner: S) -> Self::Service {
         let policy = self.settings.retry_policy(self.retry_logic.clone());
+        let limiter = self
+            .settings
+            .rate_limit_bytes_per_sec
+            .map(ByteRateLimiter::new);
+
+        let circuit_breaker = self.settings.circuit_breaker.build();
+
         ServiceBuilder::new()
             .rate_limit(
                 self.settings.rate_limit_num,
                 self.settings.rate_limit_duration,
             )
-            .layer(AdaptiveConcurrencyLimitLayer::new(
-                self.settings.concurrency,
-                self.settings.adaptive_concurrency,
-                self.retry_logic.clone(),
-            ))
+            // The byte rate limit is waited for before a permit is taken from the adaptive concurrency
+            // limit, so that throttled requests don't hold on to permits and the wait isn't mistaken
+            // for the time taken by the service.
+            .layer_fn(|inner| ByteRateLimit::new(inner, limiter.clone(), self.request_size))
+            .layer(
+                AdaptiveConcurrencyLimitLayer::new(
+                    self.settings.concurrency,
+                    self.settings.adaptive_concurrency,
+                    self.retry_logic.clone(),
+                )
+                .with_circuit_breaker(circuit_breaker.clone()),
+            )
             .retry(policy)
+            .layer_fn(|inner| {
+                CircuitBreakerService::new(inner, circuit_breaker.clone(), self.retry_logic.clone())
+            })
             .timeout(self.settings.timeout)
             .service(inner)
     }
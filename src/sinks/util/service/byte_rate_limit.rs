@@ -0,0 +1,246 @@
+use std::{
+    fmt,
+    future::Future,
+    num::NonZeroU64,
+    pin::Pin,
+    sync::{Arc, Mutex, MutexGuard},
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures::ready;
+use tokio::time::{sleep_until, Instant, Sleep};
+use tower::Service;
+
+use crate::internal_events::ByteRateLimitWait;
+
+/// The amount of time worth of bytes which can be sent in a burst, after the limiter has been idle.
+const BURST_DURATION: Duration = Duration::from_secs(1);
+
+/// Limits the rate of bytes sent, shared between all clones of a service.
+///
+/// This is a generic cell rate algorithm: each request sent moves the theoretical time at which all
+/// bytes sent so far would have been sent at the configured rate forward by its cost, and the next
+/// request may only be sent once that time is at most `BURST_DURATION` ahead. Requests are never
+/// dropped, only delayed.
+#[derive(Clone, Debug)]
+pub struct ByteRateLimiter {
+    bytes_per_sec: NonZeroU64,
+    theoretical_arrival: Arc<Mutex<Option<Instant>>>,
+}
+
+impl ByteRateLimiter {
+    pub fn new(bytes_per_sec: NonZeroU64) -> Self {
+        Self {
+            bytes_per_sec,
+            theoretical_arrival: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Option<Instant>> {
+        self.theoretical_arrival
+            .lock()
+            .expect("byte rate limiter mutex poisoned")
+    }
+
+    /// Returns the instant at which the next request may be sent.
+    fn ready_at(&self) -> Instant {
+        let now = Instant::now();
+        self.lock().map_or(now, |arrival| {
+            arrival
+                .checked_sub(BURST_DURATION)
+                .map_or(now, |ready_at| ready_at.max(now))
+        })
+    }
+
+    /// Accounts for a request of the given number of bytes being sent.
+    fn charge(&self, bytes: usize) {
+        let now = Instant::now();
+        let cost = Duration::from_secs_f64(bytes as f64 / self.bytes_per_sec.get() as f64);
+
+        let mut theoretical_arrival = self.lock();
+        *theoretical_arrival =
+            Some(theoretical_arrival.map_or(now, |arrival| arrival.max(now)) + cost);
+    }
+}
+
+/// Delays requests so that the rate of bytes sent does not exceed the limit, when one is configured.
+///
+/// The service doesn't become ready until the limit allows another request to be sent, so that the
+/// services below it, like the adaptive concurrency limit, aren't held up while waiting. Since the
+/// size of a request is only known once it is sent, it is counted against the limit afterwards.
+pub struct ByteRateLimit<S, Request> {
+    inner: S,
+    limiter: Option<ByteRateLimiter>,
+    request_size: fn(&Request) -> usize,
+    /// The time spent waiting for the limit, once the next request is allowed to be sent.
+    waited: Option<Duration>,
+    wait_start: Option<Instant>,
+    sleep: Option<Pin<Box<Sleep>>>,
+}
+
+impl<S, Request> ByteRateLimit<S, Request> {
+    pub(super) const fn new(
+        inner: S,
+        limiter: Option<ByteRateLimiter>,
+        request_size: fn(&Request) -> usize,
+    ) -> Self {
+        Self {
+            inner,
+            limiter,
+            request_size,
+            waited: None,
+            wait_start: None,
+            sleep: None,
+        }
+    }
+
+    /// Gets a reference to the inner service.
+    pub const fn get_ref(&self) -> &S {
+        &self.inner
+    }
+}
+
+impl<S, Request> Service<Request> for ByteRateLimit<S, Request>
+where
+    S: Service<Request>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        if let Some(limiter) = &self.limiter {
+            if self.waited.is_none() {
+                loop {
+                    let now = Instant::now();
+                    let ready_at = limiter.ready_at();
+                    if ready_at <= now {
+                        break;
+                    }
+                    self.wait_start.get_or_insert(now);
+
+                    match &mut self.sleep {
+                        Some(sleep) => sleep.as_mut().reset(ready_at),
+                        None => self.sleep = Some(Box::pin(sleep_until(ready_at))),
+                    }
+                    ready!(self
+                        .sleep
+                        .as_mut()
+                        .expect("sleep was just set")
+                        .as_mut()
+                        .poll(cx));
+                }
+                self.waited = Some(
+                    self.wait_start
+                        .take()
+                        .map_or(Duration::ZERO, |start| start.elapsed()),
+                );
+            }
+        }
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        if let Some(limiter) = &self.limiter {
+            limiter.charge((self.request_size)(&request));
+            emit!(ByteRateLimitWait {
+                wait: self.waited.take().unwrap_or_default()
+            });
+        }
+        self.inner.call(request)
+    }
+}
+
+impl<S: Clone, Request> Clone for ByteRateLimit<S, Request> {
+    fn clone(&self) -> Self {
+        Self::new(self.inner.clone(), self.limiter.clone(), self.request_size)
+    }
+}
+
+impl<S: fmt::Debug, Request> fmt::Debug for ByteRateLimit<S, Request> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ByteRateLimit")
+            .field("inner", &self.inner)
+            .field("limiter", &self.limiter)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::FutureExt;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    fn limited(
+        bytes_per_sec: u64,
+    ) -> ByteRateLimit<impl Service<usize, Response = Instant, Error = ()> + Clone, usize> {
+        let service = tower::service_fn(|_: usize| async { Ok::<_, ()>(Instant::now()) });
+        ByteRateLimit::new(
+            service,
+            NonZeroU64::new(bytes_per_sec).map(ByteRateLimiter::new),
+            |bytes: &usize| *bytes,
+        )
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn allows_a_burst_then_shapes() {
+        let mut service = limited(100);
+        let start = Instant::now();
+
+        // Requests are sent right away until one second worth of bytes has been sent.
+        let sent = service.ready().await.unwrap().call(100).await.unwrap();
+        assert_eq!(sent, start);
+        let sent = service.ready().await.unwrap().call(50).await.unwrap();
+        assert_eq!(sent, start);
+
+        // The rest are delayed according to the size of the ones before them, rather than dropped.
+        let sent = service.ready().await.unwrap().call(200).await.unwrap();
+        assert_eq!(sent - start, Duration::from_millis(500));
+        let sent = service.ready().await.unwrap().call(1).await.unwrap();
+        assert_eq!(sent - start, Duration::from_millis(2500));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn waits_before_becoming_ready() {
+        let mut service = limited(100);
+        service.ready().await.unwrap().call(200).await.unwrap();
+
+        // The wait happens while polling for readiness, so the inner service isn't asked for
+        // capacity before the request can be sent.
+        assert!(service.ready().now_or_never().is_none());
+        tokio::time::advance(Duration::from_millis(999)).await;
+        assert!(service.ready().now_or_never().is_none());
+        tokio::time::advance(Duration::from_millis(1)).await;
+        assert!(service.ready().now_or_never().is_some());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn clones_share_the_limit() {
+        let mut first = limited(100);
+        let mut second = first.clone();
+        let start = Instant::now();
+
+        first.ready().await.unwrap().call(150).await.unwrap();
+        let sent = second.ready().await.unwrap().call(100).await.unwrap();
+        assert_eq!(sent - start, Duration::from_millis(500));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn unlimited_without_limiter() {
+        let mut service = limited(0);
+        let start = Instant::now();
+        for _ in 0..10 {
+            let sent = service
+                .ready()
+                .await
+                .unwrap()
+                .call(1_000_000)
+                .await
+                .unwrap();
+            assert_eq!(sent, start);
+        }
+    }
+}
@@ -0,0 +1,524 @@
+use std::{
+    fmt,
+    future::Future,
+    num::NonZeroU32,
+    pin::Pin,
+    sync::{Arc, Mutex, MutexGuard},
+    task::{Context, Poll, Waker},
+    time::Duration,
+};
+
+use futures::{future::BoxFuture, ready};
+use tokio::time::{sleep_until, Instant, Sleep};
+use tower::Service;
+use vector_config::configurable_component;
+
+use crate::{
+    internal_events::CircuitBreakerStateChanged,
+    sinks::util::retries::{RetryAction, RetryLogic},
+};
+
+const CIRCUIT_BREAKER_COOL_DOWN_SECONDS_DEFAULT: u64 = 30;
+
+/// Configuration of the circuit breaker for outbound requests.
+///
+/// When the service is failing every request, the circuit breaker stops sending requests to it, and
+/// events are held in the sink's buffer instead. After a cool-down, a single request is sent to
+/// probe whether the service has recovered.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct CircuitBreakerConfig {
+    /// The number of consecutive failed requests after which the circuit breaker opens.
+    ///
+    /// Requests fail when they time out, can't be sent, or get a response which would be retried. Responses where
+    /// the service explicitly asks to slow down neither count as failures nor reset the count, but they do keep an
+    /// open circuit breaker from closing.
+    ///
+    /// The circuit breaker is disabled unless this is set.
+    pub failure_threshold: Option<NonZeroU32>,
+
+    /// The amount of time, in seconds, the circuit breaker stays open before probing the service.
+    pub cool_down_secs: u64,
+}
+
+impl CircuitBreakerConfig {
+    pub const fn const_default() -> Self {
+        Self {
+            failure_threshold: None,
+            cool_down_secs: CIRCUIT_BREAKER_COOL_DOWN_SECONDS_DEFAULT,
+        }
+    }
+
+    pub(super) fn build(&self) -> Option<CircuitBreaker> {
+        self.failure_threshold.map(|failure_threshold| {
+            CircuitBreaker::new(failure_threshold, Duration::from_secs(self.cool_down_secs))
+        })
+    }
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self::const_default()
+    }
+}
+
+/// The state of a circuit breaker.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CircuitState {
+    /// Requests are sent as normal.
+    Closed,
+    /// Requests are held back until the cool-down has passed.
+    Open,
+    /// A single request is being sent to probe whether the service has recovered.
+    HalfOpen,
+}
+
+impl CircuitState {
+    /// The value of the `circuit_breaker_state` gauge for this state.
+    pub const fn as_gauge(self) -> f64 {
+        match self {
+            Self::Closed => 0.0,
+            Self::HalfOpen => 1.0,
+            Self::Open => 2.0,
+        }
+    }
+}
+
+type Listener = Box<dyn Fn() + Send + Sync>;
+
+struct Inner {
+    state: CircuitState,
+    consecutive_failures: u32,
+    open_until: Instant,
+    probe_in_flight: bool,
+    waiters: Vec<Waker>,
+    // The breaker is built outside of the component's span, so its initial state is only reported
+    // once it is first used.
+    reported: bool,
+}
+
+impl Inner {
+    fn transition(&mut self, state: CircuitState) {
+        self.state = state;
+        emit!(CircuitBreakerStateChanged { state });
+        for waker in self.waiters.drain(..) {
+            waker.wake();
+        }
+    }
+}
+
+/// Tracks the outcome of requests to a service, shared between all clones of a service.
+#[derive(Clone)]
+pub struct CircuitBreaker {
+    failure_threshold: NonZeroU32,
+    cool_down: Duration,
+    inner: Arc<Mutex<Inner>>,
+    on_open: Arc<Mutex<Vec<Listener>>>,
+}
+
+/// What the result of a request shows about the service.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Outcome {
+    /// The service handled the request, even if it rejected it.
+    Success,
+    /// The service is failing.
+    Failure,
+    /// The service asked to slow down, which shows neither that it is failing nor that it has
+    /// recovered.
+    Throttled,
+}
+
+/// What a service was made ready to send.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Permit {
+    Request,
+    Probe,
+}
+
+impl CircuitBreaker {
+    pub fn new(failure_threshold: NonZeroU32, cool_down: Duration) -> Self {
+        Self {
+            failure_threshold,
+            cool_down,
+            inner: Arc::new(Mutex::new(Inner {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                open_until: Instant::now(),
+                probe_in_flight: false,
+                waiters: Vec::new(),
+                reported: false,
+            })),
+            on_open: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Inner> {
+        self.inner.lock().expect("circuit breaker mutex poisoned")
+    }
+
+    pub fn state(&self) -> CircuitState {
+        self.lock().state
+    }
+
+    /// Registers a function to call whenever the circuit breaker opens.
+    pub(crate) fn on_open(&self, listener: impl Fn() + Send + Sync + 'static) {
+        self.on_open
+            .lock()
+            .expect("circuit breaker mutex poisoned")
+            .push(Box::new(listener));
+    }
+
+    fn poll_permit(
+        &self,
+        cx: &mut Context<'_>,
+        sleep: &mut Option<Pin<Box<Sleep>>>,
+    ) -> Poll<Permit> {
+        loop {
+            let mut inner = self.lock();
+            if !inner.reported {
+                inner.reported = true;
+                emit!(CircuitBreakerStateChanged { state: inner.state });
+            }
+            match inner.state {
+                CircuitState::Closed => return Poll::Ready(Permit::Request),
+                CircuitState::Open => {
+                    if Instant::now() >= inner.open_until {
+                        inner.transition(CircuitState::HalfOpen);
+                        continue;
+                    }
+                    let open_until = inner.open_until;
+                    drop(inner);
+
+                    match sleep {
+                        Some(sleep) => sleep.as_mut().reset(open_until),
+                        None => *sleep = Some(Box::pin(sleep_until(open_until))),
+                    }
+                    ready!(sleep
+                        .as_mut()
+                        .expect("sleep was just set")
+                        .as_mut()
+                        .poll(cx));
+                }
+                CircuitState::HalfOpen => {
+                    if !inner.probe_in_flight {
+                        inner.probe_in_flight = true;
+                        return Poll::Ready(Permit::Probe);
+                    }
+                    if !inner
+                        .waiters
+                        .iter()
+                        .any(|waker| waker.will_wake(cx.waker()))
+                    {
+                        inner.waiters.push(cx.waker().clone());
+                    }
+                    return Poll::Pending;
+                }
+            }
+        }
+    }
+
+    /// Gives back a probe permit which wasn't used to send a request.
+    fn release_probe(&self) {
+        let mut inner = self.lock();
+        inner.probe_in_flight = false;
+        for waker in inner.waiters.drain(..) {
+            waker.wake();
+        }
+    }
+
+    fn record(&self, permit: Permit, outcome: Outcome) {
+        let mut inner = self.lock();
+        if permit == Permit::Probe {
+            inner.probe_in_flight = false;
+        }
+
+        match outcome {
+            Outcome::Success => {
+                inner.consecutive_failures = 0;
+                // Any request getting through shows the service has recovered, not just the probe.
+                if inner.state != CircuitState::Closed {
+                    inner.transition(CircuitState::Closed);
+                }
+                return;
+            }
+            // A throttled probe opens the breaker again, so that the service is left to recover
+            // rather than probed again right away.
+            Outcome::Throttled if permit != Permit::Probe => return,
+            Outcome::Throttled | Outcome::Failure => (),
+        }
+
+        inner.consecutive_failures = inner.consecutive_failures.saturating_add(1);
+        let open = match inner.state {
+            CircuitState::Closed => inner.consecutive_failures >= self.failure_threshold.get(),
+            CircuitState::HalfOpen => permit == Permit::Probe,
+            CircuitState::Open => false,
+        };
+        if open {
+            inner.open_until = Instant::now() + self.cool_down;
+            inner.transition(CircuitState::Open);
+            drop(inner);
+
+            for listener in self
+                .on_open
+                .lock()
+                .expect("circuit breaker mutex poisoned")
+                .iter()
+            {
+                listener();
+            }
+        }
+    }
+}
+
+impl fmt::Debug for CircuitBreaker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CircuitBreaker")
+            .field("failure_threshold", &self.failure_threshold)
+            .field("cool_down", &self.cool_down)
+            .field("state", &self.state())
+            .finish()
+    }
+}
+
+/// Classifies the result of a request, telling a failing service apart from one rejecting the
+/// request or asking to slow down.
+fn outcome<L: RetryLogic>(logic: &L, result: &Result<L::Response, crate::Error>) -> Outcome {
+    match result {
+        Ok(response) => match logic.should_retry_response(response) {
+            RetryAction::Retry(_) => Outcome::Failure,
+            RetryAction::Throttled(..) => Outcome::Throttled,
+            RetryAction::DontRetry(_) | RetryAction::Successful => Outcome::Success,
+        },
+        Err(error) => match error.downcast_ref::<L::Error>() {
+            Some(error) if !logic.is_retriable_error(error) => Outcome::Success,
+            Some(error) if logic.error_retry_after(error).is_some() => Outcome::Throttled,
+            _ => Outcome::Failure,
+        },
+    }
+}
+
+/// Holds requests back while the circuit breaker is open, when one is configured.
+///
+/// Rather than failing requests, the service doesn't become ready, so that events back up into the
+/// sink's buffer, and retries wait for the service to recover.
+pub struct CircuitBreakerService<S, L> {
+    inner: S,
+    breaker: Option<CircuitBreaker>,
+    logic: L,
+    permit: Option<Permit>,
+    sleep: Option<Pin<Box<Sleep>>>,
+}
+
+impl<S, L> CircuitBreakerService<S, L> {
+    pub(super) const fn new(inner: S, breaker: Option<CircuitBreaker>, logic: L) -> Self {
+        Self {
+            inner,
+            breaker,
+            logic,
+            permit: None,
+            sleep: None,
+        }
+    }
+}
+
+impl<S, L, Request> Service<Request> for CircuitBreakerService<S, L>
+where
+    S: Service<Request, Error = crate::Error>,
+    S::Future: Send + 'static,
+    S::Response: Send + 'static,
+    L: RetryLogic<Response = S::Response>,
+{
+    type Response = S::Response;
+    type Error = crate::Error;
+    type Future = BoxFuture<'static, Result<S::Response, crate::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        if let Some(breaker) = &self.breaker {
+            if self.permit.is_none() {
+                self.permit = Some(ready!(breaker.poll_permit(cx, &mut self.sleep)));
+            }
+        }
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        let future = self.inner.call(request);
+        let (breaker, permit) = match (&self.breaker, self.permit.take()) {
+            (Some(breaker), Some(permit)) => (breaker.clone(), permit),
+            _ => return Box::pin(future),
+        };
+
+        let logic = self.logic.clone();
+        Box::pin(async move {
+            let result = future.await;
+            breaker.record(permit, outcome(&logic, &result));
+            result
+        })
+    }
+}
+
+impl<S, L> Drop for CircuitBreakerService<S, L> {
+    fn drop(&mut self) {
+        if let (Some(breaker), Some(Permit::Probe)) = (&self.breaker, self.permit) {
+            breaker.release_probe();
+        }
+    }
+}
+
+impl<S: Clone, L: Clone> Clone for CircuitBreakerService<S, L> {
+    fn clone(&self) -> Self {
+        Self::new(self.inner.clone(), self.breaker.clone(), self.logic.clone())
+    }
+}
+
+impl<S: fmt::Debug, L> fmt::Debug for CircuitBreakerService<S, L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CircuitBreakerService")
+            .field("inner", &self.inner)
+            .field("breaker", &self.breaker)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use futures::FutureExt;
+    use tower::ServiceExt;
+    use tower_test::{assert_request_eq, mock};
+
+    use super::*;
+
+    #[derive(Clone, Debug)]
+    struct TestRetryLogic;
+
+    impl RetryLogic for TestRetryLogic {
+        type Error = std::io::Error;
+        type Response = &'static str;
+
+        fn is_retriable_error(&self, _error: &Self::Error) -> bool {
+            true
+        }
+
+        fn should_retry_response(&self, response: &Self::Response) -> RetryAction {
+            match *response {
+                "throttled" => RetryAction::Throttled("throttled".into(), None),
+                "retry" => RetryAction::Retry("retry".into()),
+                _ => RetryAction::Successful,
+            }
+        }
+    }
+
+    type Mock = mock::Mock<&'static str, &'static str>;
+
+    fn breaker() -> CircuitBreaker {
+        CircuitBreaker::new(NonZeroU32::new(2).unwrap(), Duration::from_secs(10))
+    }
+
+    async fn send(
+        service: &mut CircuitBreakerService<Mock, TestRetryLogic>,
+        handle: &mut mock::Handle<&'static str, &'static str>,
+        response: &'static str,
+    ) {
+        let response_future = service.ready().await.unwrap().call("request");
+        assert_request_eq!(handle, "request").send_response(response);
+        assert_eq!(response_future.await.unwrap(), response);
+    }
+
+    fn is_ready(service: &mut CircuitBreakerService<Mock, TestRetryLogic>) -> bool {
+        service.ready().now_or_never().is_some()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn opens_after_consecutive_failures_and_probes() {
+        let (mock, mut handle) = mock::pair::<&'static str, &'static str>();
+        let breaker = breaker();
+        let mut service = CircuitBreakerService::new(mock, Some(breaker.clone()), TestRetryLogic);
+
+        // Successes in between reset the count of failures, while throttling doesn't count.
+        send(&mut service, &mut handle, "retry").await;
+        send(&mut service, &mut handle, "ok").await;
+        send(&mut service, &mut handle, "retry").await;
+        send(&mut service, &mut handle, "throttled").await;
+        assert_eq!(breaker.state(), CircuitState::Closed);
+
+        send(&mut service, &mut handle, "retry").await;
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(!is_ready(&mut service));
+
+        // After the cool-down a single probe is let through.
+        tokio::time::advance(Duration::from_secs(10)).await;
+        let mut other = service.clone();
+        let probe = service.ready().await.unwrap().call("probe");
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        assert!(!is_ready(&mut other));
+
+        // A failed probe opens the breaker again.
+        assert_request_eq!(handle, "probe").send_response("retry");
+        probe.await.unwrap();
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(!is_ready(&mut other));
+
+        tokio::time::advance(Duration::from_secs(10)).await;
+        send(&mut other, &mut handle, "ok").await;
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert!(is_ready(&mut service));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn throttling_keeps_breaker_open() {
+        let (mock, mut handle) = mock::pair::<&'static str, &'static str>();
+        let breaker = breaker();
+        let mut service = CircuitBreakerService::new(mock, Some(breaker.clone()), TestRetryLogic);
+        send(&mut service, &mut handle, "retry").await;
+        send(&mut service, &mut handle, "retry").await;
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        // A throttled probe doesn't show the service has recovered.
+        tokio::time::advance(Duration::from_secs(10)).await;
+        send(&mut service, &mut handle, "throttled").await;
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(!is_ready(&mut service));
+
+        tokio::time::advance(Duration::from_secs(10)).await;
+        send(&mut service, &mut handle, "ok").await;
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn unused_probe_is_released() {
+        let (mock, mut handle) = mock::pair::<&'static str, &'static str>();
+        let breaker = breaker();
+        let mut service = CircuitBreakerService::new(mock, Some(breaker.clone()), TestRetryLogic);
+        send(&mut service, &mut handle, "retry").await;
+        send(&mut service, &mut handle, "retry").await;
+        tokio::time::advance(Duration::from_secs(10)).await;
+
+        let mut probing = service.clone();
+        assert!(is_ready(&mut probing));
+        assert!(!is_ready(&mut service));
+        drop(probing);
+        assert!(is_ready(&mut service));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn notifies_on_open() {
+        let (mock, mut handle) = mock::pair::<&'static str, &'static str>();
+        let breaker = breaker();
+        let opened = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&opened);
+        breaker.on_open(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+        let mut service = CircuitBreakerService::new(mock, Some(breaker), TestRetryLogic);
+
+        let response_future = service.ready().await.unwrap().call("request");
+        assert_request_eq!(handle, "request")
+            .send_error(std::io::Error::new(std::io::ErrorKind::Other, "down"));
+        response_future.await.unwrap_err();
+        send(&mut service, &mut handle, "retry").await;
+        assert_eq!(opened.load(Ordering::SeqCst), 1);
+    }
+}
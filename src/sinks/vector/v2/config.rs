@@ -10,19 +10,20 @@ use crate::{
     config::{
         AcknowledgementsConfig, GenerateConfig, ProxyConfig, SinkContext, SinkHealthcheckOptions,
     },
-    proto::vector as proto,
     sinks::{
         util::{
             retries::RetryLogic, BatchConfig, RealtimeEventBasedDefaultBatchSettings,
             ServiceBuilderExt, TowerRequestConfig,
         },
         vector::v2::{
-            service::{VectorResponse, VectorService},
+            partitioner::{EndpointHealth, EndpointPartitioner},
+            service::{VectorResponse, VectorRouterService, VectorService},
             sink::VectorSink,
             VectorSinkError,
         },
         Healthcheck, VectorSink as VectorSinkType,
     },
+    template::Template,
     tls::{tls_connector_builder, MaybeTlsSettings, TlsEnableableConfig},
 };
 
@@ -36,6 +37,21 @@ pub struct VectorConfig {
     /// The address _must_ include a port.
     address: String,
 
+    /// Additional downstream Vector addresses to route events to.
+    ///
+    /// Events are distributed between `address` and these addresses based on the rendered
+    /// `partition_key`, which _must_ be set when this option is used.
+    #[serde(default)]
+    endpoints: Vec<String>,
+
+    /// The key used to route events to a downstream Vector.
+    ///
+    /// Events with the same key are always sent to the same address, which is required when
+    /// running stateful transforms such as `reduce`, `aggregate`, or `dedupe` on an aggregator
+    /// tier. Keys are assigned with rendezvous hashing, so if an address becomes unreachable, only
+    /// the keys routed to it are remapped to the remaining addresses.
+    partition_key: Option<Template>,
+
     /// Whether or not to compress requests.
     ///
     /// If set to `true`, requests will be compressed with [`gzip`][gzip_docs].
@@ -74,6 +90,8 @@ impl GenerateConfig for VectorConfig {
 fn default_config(address: &str) -> VectorConfig {
     VectorConfig {
         address: address.to_owned(),
+        endpoints: Vec::new(),
+        partition_key: None,
         compression: false,
         batch: BatchConfig::default(),
         request: TowerRequestConfig::default(),
@@ -88,19 +106,44 @@ impl VectorConfig {
         cx: SinkContext,
     ) -> crate::Result<(VectorSinkType, Healthcheck)> {
         let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
-        let uri = with_default_scheme(&self.address, tls.is_tls())?;
+        if !self.endpoints.is_empty() && self.partition_key.is_none() {
+            return Err(Box::new(VectorSinkError::MissingPartitionKey));
+        }
+
+        let addresses = std::iter::once(&self.address)
+            .chain(&self.endpoints)
+            .cloned()
+            .collect::<Vec<_>>();
+        let uris = addresses
+            .iter()
+            .map(|address| with_default_scheme(address, tls.is_tls()))
+            .collect::<crate::Result<Vec<_>>>()?;
 
         let client = new_client(&tls, cx.proxy())?;
 
-        let healthcheck_uri = cx
-            .healthcheck
-            .uri
-            .clone()
-            .map(|uri| uri.uri)
-            .unwrap_or_else(|| uri.clone());
-        let healthcheck_client = VectorService::new(client.clone(), healthcheck_uri, false);
-        let healthcheck = healthcheck(healthcheck_client, cx.healthcheck);
-        let service = VectorService::new(client, uri, self.compression);
+        let healthcheck_uris = match cx.healthcheck.uri.clone() {
+            Some(uri) => vec![uri.uri],
+            None => uris.clone(),
+        };
+        let healthcheck_clients = healthcheck_uris
+            .into_iter()
+            .map(|uri| VectorService::new(client.clone(), uri, false))
+            .collect();
+        let healthcheck = healthcheck(healthcheck_clients, cx.healthcheck);
+
+        let health = EndpointHealth::new(uris.len());
+        if !self.endpoints.is_empty() {
+            let probes = uris
+                .iter()
+                .map(|uri| VectorService::new(client.clone(), uri.clone(), false))
+                .collect();
+            health.spawn_prober(probes);
+        }
+        let services = uris
+            .into_iter()
+            .map(|uri| VectorService::new(client.clone(), uri, self.compression))
+            .collect();
+        let service = VectorRouterService::new(services, addresses.clone(), health.clone());
         let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
         let batch_settings = self.batch.into_batcher_settings()?;
 
@@ -108,9 +151,15 @@ impl VectorConfig {
             .settings(request_settings, VectorGrpcRetryLogic)
             .service(service);
 
+        let partitioner = self
+            .partition_key
+            .clone()
+            .map(|key| EndpointPartitioner::new(key, addresses, health));
+
         let sink = VectorSink {
             batch_settings,
             service,
+            partitioner,
         };
 
         Ok((
@@ -120,26 +169,22 @@ impl VectorConfig {
     }
 }
 
-/// Check to see if the remote service accepts new events.
+/// Check to see if the remote services accept new events.
 async fn healthcheck(
-    mut service: VectorService,
+    services: Vec<VectorService>,
     options: SinkHealthcheckOptions,
 ) -> crate::Result<()> {
     if !options.enabled {
         return Ok(());
     }
 
-    let request = service.client.health_check(proto::HealthCheckRequest {});
-
-    if let Ok(response) = request.await {
-        let status = proto::ServingStatus::from_i32(response.into_inner().status);
-
-        if let Some(proto::ServingStatus::Serving) = status {
-            return Ok(());
+    for mut service in services {
+        if !service.is_serving().await {
+            return Err(Box::new(VectorSinkError::Health));
         }
     }
 
-    Err(Box::new(VectorSinkError::Health))
+    Ok(())
 }
 
 /// grpc doesn't like an address without a scheme, so we default to http or https if one isn't
@@ -189,7 +234,7 @@ fn new_client(
     let settings = tls_settings.tls().cloned();
     https.set_callback(move |c, _uri| {
         if let Some(settings) = &settings {
-            settings.apply_connect_configuration(c);
+            settings.apply_connect_configuration(c)?;
         }
 
         Ok(())
@@ -1,6 +1,7 @@
 use snafu::Snafu;
 
 mod config;
+mod partitioner;
 mod service;
 mod sink;
 
@@ -17,6 +18,9 @@ pub enum VectorSinkError {
 
     #[snafu(display("URL has no host."))]
     NoHost,
+
+    #[snafu(display("`partition_key` must be set when `endpoints` is used."))]
+    MissingPartitionKey,
 }
 
 #[cfg(test)]
@@ -123,6 +127,23 @@ mod tests {
         assert_eq!(receiver.try_recv(), Ok(BatchStatus::Rejected));
     }
 
+    #[tokio::test]
+    async fn endpoints_require_partition_key() {
+        let config: VectorConfig = toml::from_str(
+            r#"
+            address = "127.0.0.1:6000"
+            endpoints = ["127.0.0.1:6001"]
+            "#,
+        )
+        .unwrap();
+
+        let error = config.build(SinkContext::new_test()).await.err().unwrap();
+        assert!(matches!(
+            error.downcast_ref::<VectorSinkError>(),
+            Some(VectorSinkError::MissingPartitionKey)
+        ));
+    }
+
     #[test]
     fn test_with_default_scheme() {
         assert_eq!(
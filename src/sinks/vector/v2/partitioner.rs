@@ -0,0 +1,245 @@
+use std::{
+    hash::Hasher,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use vector_core::{event::Event, partition::Partitioner};
+
+use crate::{
+    internal_events::TemplateRenderingError, sinks::vector::v2::service::VectorService,
+    template::Template,
+};
+
+/// How often endpoints marked as down are probed to find out whether they have recovered.
+const ENDPOINT_PROBE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Tracks which of the configured endpoints are currently considered reachable.
+///
+/// An endpoint that fails a request is marked as down, and the keys it owns are routed to their
+/// next-best endpoint. It stays down until a health probe reports it as serving again, so that
+/// routing doesn't flap back to an endpoint that is still failing.
+#[derive(Clone, Debug)]
+pub struct EndpointHealth {
+    down: Arc<Vec<AtomicBool>>,
+}
+
+impl EndpointHealth {
+    pub fn new(endpoints: usize) -> Self {
+        Self {
+            down: Arc::new((0..endpoints).map(|_| AtomicBool::new(false)).collect()),
+        }
+    }
+
+    pub fn mark_down(&self, index: usize) {
+        self.down[index].store(true, Ordering::Relaxed);
+    }
+
+    pub fn mark_up(&self, index: usize) {
+        self.down[index].store(false, Ordering::Relaxed);
+    }
+
+    pub fn is_up(&self, index: usize) -> bool {
+        !self.down[index].load(Ordering::Relaxed)
+    }
+
+    /// Periodically probes the endpoints marked as down with `services`, which must be in the same
+    /// order as the endpoints, and marks them as up once they report as serving.
+    ///
+    /// The probing task exits once every other handle to this `EndpointHealth` has been dropped.
+    pub fn spawn_prober(&self, mut services: Vec<VectorService>) {
+        let down = Arc::downgrade(&self.down);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(ENDPOINT_PROBE_INTERVAL);
+            loop {
+                interval.tick().await;
+
+                let health = match down.upgrade() {
+                    Some(down) => Self { down },
+                    None => break,
+                };
+                for (index, service) in services.iter_mut().enumerate() {
+                    if !health.is_up(index) && service.is_serving().await {
+                        health.mark_up(index);
+                    }
+                }
+            }
+        });
+    }
+}
+
+/// Picks the endpoint for `key` among `endpoints` with rendezvous (highest random weight) hashing.
+///
+/// Every endpoint gets a score for the key, and the reachable endpoint with the highest score
+/// wins. If every endpoint is down, the owner of the key is picked rather than an arbitrary one.
+pub fn select_endpoint(endpoints: &[String], health: &EndpointHealth, key: &[u8]) -> usize {
+    let mut best: Option<(u64, usize)> = None;
+    let mut best_up: Option<(u64, usize)> = None;
+
+    for (index, endpoint) in endpoints.iter().enumerate() {
+        let score = rendezvous_score(key, endpoint);
+        if best.map_or(true, |(best_score, _)| score > best_score) {
+            best = Some((score, index));
+        }
+        if health.is_up(index) && best_up.map_or(true, |(best_score, _)| score > best_score) {
+            best_up = Some((score, index));
+        }
+    }
+
+    best_up.or(best).map_or(0, |(_, index)| index)
+}
+
+/// Picks the endpoint to send a request partitioned to `endpoint` to.
+///
+/// This is the endpoint itself while it is up. Otherwise the request, and every retry of it, is
+/// sent to the reachable endpoint that ranks highest for the address of the original one.
+pub fn reroute_endpoint(endpoints: &[String], health: &EndpointHealth, endpoint: usize) -> usize {
+    if health.is_up(endpoint) {
+        endpoint
+    } else {
+        select_endpoint(endpoints, health, endpoints[endpoint].as_bytes())
+    }
+}
+
+/// Partitions events by the endpoint that owns their rendered partition key.
+///
+/// Ownership is decided with rendezvous hashing, see [`select_endpoint`]. Removing an endpoint only remaps the keys it owned, and the mapping is stable across Vector instances as
+/// long as they are configured with the same set of addresses.
+pub struct EndpointPartitioner {
+    key: Template,
+    endpoints: Vec<String>,
+    health: EndpointHealth,
+}
+
+impl EndpointPartitioner {
+    pub const fn new(key: Template, endpoints: Vec<String>, health: EndpointHealth) -> Self {
+        Self {
+            key,
+            endpoints,
+            health,
+        }
+    }
+}
+
+impl Partitioner for EndpointPartitioner {
+    type Item = Event;
+    type Key = usize;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        let key = self
+            .key
+            .render(item)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("partition_key"),
+                    drop_event: false,
+                });
+            })
+            .unwrap_or_default();
+
+        select_endpoint(&self.endpoints, &self.health, &key)
+    }
+}
+
+fn rendezvous_score(key: &[u8], endpoint: &str) -> u64 {
+    let mut hasher = seahash::SeaHasher::default();
+    hasher.write(key);
+    hasher.write_u8(0);
+    hasher.write(endpoint.as_bytes());
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::LogEvent;
+
+    fn partitioner(endpoints: &[&str]) -> EndpointPartitioner {
+        EndpointPartitioner::new(
+            Template::try_from("{{ key }}").unwrap(),
+            endpoints.iter().map(ToString::to_string).collect(),
+            EndpointHealth::new(endpoints.len()),
+        )
+    }
+
+    fn event(key: usize) -> Event {
+        LogEvent::from_iter([("key".to_string(), key.to_string().into())]).into()
+    }
+
+    #[test]
+    fn routes_keys_consistently() {
+        let partitioner = partitioner(&["a:6000", "b:6000", "c:6000"]);
+
+        for key in 0..100 {
+            assert_eq!(
+                partitioner.partition(&event(key)),
+                partitioner.partition(&event(key))
+            );
+        }
+
+        let used = (0..100)
+            .map(|key| partitioner.partition(&event(key)))
+            .collect::<std::collections::HashSet<_>>();
+        assert_eq!(used.len(), 3);
+    }
+
+    #[test]
+    fn down_endpoint_only_remaps_its_keys() {
+        let partitioner = partitioner(&["a:6000", "b:6000", "c:6000"]);
+        let before = (0..100)
+            .map(|key| partitioner.partition(&event(key)))
+            .collect::<Vec<_>>();
+
+        partitioner.health.mark_down(1);
+
+        for (key, owner) in before.iter().enumerate() {
+            let after = partitioner.partition(&event(key));
+            if *owner == 1 {
+                assert_ne!(after, 1);
+            } else {
+                assert_eq!(after, *owner);
+            }
+        }
+
+        partitioner.health.mark_up(1);
+
+        for (key, owner) in before.iter().enumerate() {
+            assert_eq!(partitioner.partition(&event(key)), *owner);
+        }
+    }
+
+    #[test]
+    fn down_endpoint_reroutes_requests() {
+        let endpoints = ["a:6000", "b:6000", "c:6000"].map(String::from);
+        let health = EndpointHealth::new(endpoints.len());
+
+        assert_eq!(reroute_endpoint(&endpoints, &health, 1), 1);
+
+        health.mark_down(1);
+        let rerouted = reroute_endpoint(&endpoints, &health, 1);
+        assert_ne!(rerouted, 1);
+
+        health.mark_down(rerouted);
+        let rerouted_again = reroute_endpoint(&endpoints, &health, 1);
+        assert_ne!(rerouted_again, 1);
+        assert_ne!(rerouted_again, rerouted);
+
+        health.mark_up(1);
+        assert_eq!(reroute_endpoint(&endpoints, &health, 1), 1);
+    }
+
+    #[test]
+    fn all_endpoints_down_routes_to_owner() {
+        let partitioner = partitioner(&["a:6000", "b:6000"]);
+        let owner = partitioner.partition(&event(7));
+
+        partitioner.health.mark_down(0);
+        partitioner.health.mark_down(1);
+
+        assert_eq!(partitioner.partition(&event(7)), owner);
+    }
+}
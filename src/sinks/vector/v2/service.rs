@@ -16,7 +16,13 @@ use crate::{
     event::{EventFinalizers, EventStatus, Finalizable},
     internal_events::EndpointBytesSent,
     proto::vector as proto_vector,
-    sinks::{util::uri, vector::v2::VectorSinkError},
+    sinks::{
+        util::{uri, EncodedLength},
+        vector::v2::{
+            partitioner::{reroute_endpoint, EndpointHealth},
+            VectorSinkError,
+        },
+    },
     Error,
 };
 
@@ -48,6 +54,8 @@ impl DriverResponse for VectorResponse {
 
 #[derive(Clone, Default)]
 pub struct VectorRequest {
+    /// Index of the endpoint this request is routed to.
+    pub endpoint: usize,
     pub events: Vec<EventWrapper>,
     pub finalizers: EventFinalizers,
     pub events_byte_size: usize,
@@ -59,6 +67,12 @@ impl Finalizable for VectorRequest {
     }
 }
 
+impl EncodedLength for VectorRequest {
+    fn encoded_length(&self) -> usize {
+        self.events.iter().map(Message::encoded_len).sum()
+    }
+}
+
 impl VectorService {
     pub fn new(
         hyper_client: hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>,
@@ -80,6 +94,21 @@ impl VectorService {
             endpoint,
         }
     }
+
+    /// Checks whether the remote service reports that it accepts new events.
+    pub async fn is_serving(&mut self) -> bool {
+        match self
+            .client
+            .health_check(proto_vector::HealthCheckRequest {})
+            .await
+        {
+            Ok(response) => matches!(
+                proto_vector::ServingStatus::from_i32(response.into_inner().status),
+                Some(proto_vector::ServingStatus::Serving)
+            ),
+            Err(_) => false,
+        }
+    }
 }
 
 impl tower::Service<VectorRequest> for VectorService {
@@ -128,6 +157,70 @@ impl tower::Service<VectorRequest> for VectorService {
     }
 }
 
+/// Routes each request to the endpoint it was partitioned to.
+///
+/// Requests that fail with an error indicating the endpoint itself is unreachable mark it as down
+/// in the shared [`EndpointHealth`], so that new events are routed away from it until it recovers.
+/// The endpoint is picked again for every attempt, so retries of a request whose endpoint is down
+/// go to a reachable one instead.
+#[derive(Clone, Debug)]
+pub struct VectorRouterService {
+    services: Vec<VectorService>,
+    endpoints: Vec<String>,
+    health: EndpointHealth,
+}
+
+impl VectorRouterService {
+    pub fn new(
+        services: Vec<VectorService>,
+        endpoints: Vec<String>,
+        health: EndpointHealth,
+    ) -> Self {
+        Self {
+            services,
+            endpoints,
+            health,
+        }
+    }
+}
+
+impl tower::Service<VectorRequest> for VectorRouterService {
+    type Response = VectorResponse;
+    type Error = Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // See `VectorService::poll_ready`, which is always ready as well.
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: VectorRequest) -> Self::Future {
+        let endpoint = reroute_endpoint(&self.endpoints, &self.health, request.endpoint);
+        let health = self.health.clone();
+        let future = self.services[endpoint].call(request);
+
+        Box::pin(async move {
+            let result = future.await;
+            match &result {
+                Ok(_) => health.mark_up(endpoint),
+                Err(error) => {
+                    if let Some(VectorSinkError::Request { source }) = error.downcast_ref() {
+                        if matches!(
+                            source.code(),
+                            tonic::Code::Unavailable
+                                | tonic::Code::Unknown
+                                | tonic::Code::DeadlineExceeded
+                        ) {
+                            health.mark_down(endpoint);
+                        }
+                    }
+                }
+            }
+            result
+        })
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct HyperSvc {
     uri: Uri,
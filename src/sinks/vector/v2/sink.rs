@@ -13,7 +13,7 @@ use crate::{
     event::{proto::EventWrapper, Event, EventFinalizers, Finalizable},
     sinks::{
         util::{SinkBuilderExt, StreamSink},
-        vector::v2::service::VectorRequest,
+        vector::v2::{partitioner::EndpointPartitioner, service::VectorRequest},
     },
 };
 
@@ -26,6 +26,7 @@ struct EventData {
 pub struct VectorSink<S> {
     pub batch_settings: BatcherSettings,
     pub service: S,
+    pub partitioner: Option<EndpointPartitioner>,
 }
 
 impl<S> VectorSink<S>
@@ -36,6 +37,26 @@ where
     S::Error: fmt::Debug + Into<crate::Error> + Send,
 {
     async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        if let Some(partitioner) = self.partitioner {
+            return input
+                .batched_partitioned(partitioner, self.batch_settings)
+                .map(|(endpoint, events)| {
+                    let mut request = VectorRequest {
+                        endpoint,
+                        ..Default::default()
+                    };
+                    for mut event in events {
+                        request.events_byte_size += event.size_of();
+                        request.finalizers.merge(event.take_finalizers());
+                        request.events.push(EventWrapper::from(event));
+                    }
+                    request
+                })
+                .into_driver(self.service)
+                .run()
+                .await;
+        }
+
         input
             .map(|mut event| EventData {
                 byte_size: event.size_of(),
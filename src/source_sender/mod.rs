@@ -1,10 +1,13 @@
-use std::{collections::HashMap, fmt};
+use std::{collections::HashMap, fmt, num::NonZeroUsize, time::Instant};
 
 use chrono::Utc;
 use futures::{Stream, StreamExt};
 use metrics::{register_histogram, Histogram};
 use value::Value;
-use vector_buffers::topology::channel::{self, LimitedReceiver, LimitedSender};
+use vector_buffers::{
+    topology::channel::{self, LimitedReceiver, LimitedSender},
+    MemoryBufferSize,
+};
 #[cfg(test)]
 use vector_core::event::{into_event_stream, EventStatus};
 use vector_core::{
@@ -29,6 +32,7 @@ pub struct Builder {
     inner: Option<Inner>,
     named_inners: HashMap<String, Inner>,
     lag_time: Option<Histogram>,
+    stamp_ingest_timestamps: bool,
 }
 
 impl Builder {
@@ -40,6 +44,18 @@ impl Builder {
             inner: self.inner,
             named_inners: self.named_inners,
             lag_time: self.lag_time,
+            stamp_ingest_timestamps: self.stamp_ingest_timestamps,
+        }
+    }
+
+    /// Stamps the events sent through the outputs added afterwards with the instant they were
+    /// ingested, which is only needed when event latency is recorded.
+    // https://github.com/rust-lang/rust/issues/73255
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn with_ingest_timestamps(self, enabled: bool) -> Self {
+        Self {
+            stamp_ingest_timestamps: enabled,
+            ..self
         }
     }
 
@@ -50,13 +66,18 @@ impl Builder {
                     self.buf_size,
                     DEFAULT_OUTPUT.to_owned(),
                     self.lag_time.clone(),
+                    self.stamp_ingest_timestamps,
                 );
                 self.inner = Some(inner);
                 rx
             }
             Some(name) => {
-                let (inner, rx) =
-                    Inner::new_with_buffer(self.buf_size, name.clone(), self.lag_time.clone());
+                let (inner, rx) = Inner::new_with_buffer(
+                    self.buf_size,
+                    name.clone(),
+                    self.lag_time.clone(),
+                    self.stamp_ingest_timestamps,
+                );
                 self.named_inners.insert(name, inner);
                 rx
             }
@@ -86,12 +107,13 @@ impl SourceSender {
             inner: None,
             named_inners: Default::default(),
             lag_time: Some(register_histogram!(LAG_TIME_NAME)),
+            stamp_ingest_timestamps: false,
         }
     }
 
     pub fn new_with_buffer(n: usize) -> (Self, LimitedReceiver<EventArray>) {
         let lag_time = Some(register_histogram!(LAG_TIME_NAME));
-        let (inner, rx) = Inner::new_with_buffer(n, DEFAULT_OUTPUT.to_owned(), lag_time);
+        let (inner, rx) = Inner::new_with_buffer(n, DEFAULT_OUTPUT.to_owned(), lag_time, false);
         (
             Self {
                 inner: Some(inner),
@@ -157,7 +179,7 @@ impl SourceSender {
     ) -> impl Stream<Item = EventArray> + Unpin {
         // The lag_time parameter here will need to be filled in if this function is ever used for
         // non-test situations.
-        let (inner, recv) = Inner::new_with_buffer(100, name.clone(), None);
+        let (inner, recv) = Inner::new_with_buffer(100, name.clone(), None, false);
         let recv = recv.into_stream().map(move |mut events| {
             events.iter_events_mut().for_each(|mut event| {
                 let metadata = event.metadata_mut();
@@ -220,6 +242,7 @@ struct Inner {
     inner: LimitedSender<EventArray>,
     output: String,
     lag_time: Option<Histogram>,
+    stamp_ingest_timestamps: bool,
 }
 
 impl fmt::Debug for Inner {
@@ -227,6 +250,7 @@ impl fmt::Debug for Inner {
         fmt.debug_struct("Inner")
             .field("inner", &self.inner)
             .field("output", &self.output)
+            .field("stamp_ingest_timestamps", &self.stamp_ingest_timestamps)
             // `metrics::Histogram` is missing `impl Debug`
             .finish()
     }
@@ -237,23 +261,31 @@ impl Inner {
         n: usize,
         output: String,
         lag_time: Option<Histogram>,
+        stamp_ingest_timestamps: bool,
     ) -> (Self, LimitedReceiver<EventArray>) {
-        let (tx, rx) = channel::limited(n);
+        let limit = MemoryBufferSize::MaxEvents(
+            NonZeroUsize::new(n).expect("buffer size must be greater than zero"),
+        );
+        let (tx, rx) = channel::limited(limit);
         (
             Self {
                 inner: tx,
                 output,
                 lag_time,
+                stamp_ingest_timestamps,
             },
             rx,
         )
     }
 
-    async fn send(&mut self, events: EventArray) -> Result<(), ClosedError> {
+    async fn send(&mut self, mut events: EventArray) -> Result<(), ClosedError> {
         let reference = Utc::now().timestamp_millis();
         events
             .iter_events()
             .for_each(|event| self.emit_lag_time(event, reference));
+        if self.stamp_ingest_timestamps {
+            stamp_ingest_timestamp(&mut events, Instant::now());
+        }
         let byte_size = events.size_of();
         let count = events.len();
         self.inner.send(events).await.map_err(|_| ClosedError)?;
@@ -290,11 +322,15 @@ impl Inner {
         let mut byte_size = 0;
 
         let reference = Utc::now().timestamp_millis();
+        let now = Instant::now();
         let events = events.into_iter().map(Into::into);
-        for events in array::events_into_arrays(events, Some(CHUNK_SIZE)) {
+        for mut events in array::events_into_arrays(events, Some(CHUNK_SIZE)) {
             events
                 .iter_events()
                 .for_each(|event| self.emit_lag_time(event, reference));
+            if self.stamp_ingest_timestamps {
+                stamp_ingest_timestamp(&mut events, now);
+            }
             let this_count = events.len();
             let this_size = events.size_of();
             match self.inner.send(events).await {
@@ -348,6 +384,17 @@ impl Inner {
     }
 }
 
+/// Stamps each event that hasn't been already with the instant it was ingested, so that its
+/// end-to-end latency can be measured once it reaches a sink.
+fn stamp_ingest_timestamp(events: &mut EventArray, now: Instant) {
+    for mut event in events.iter_events_mut() {
+        let metadata = event.metadata_mut();
+        if metadata.ingest_timestamp().is_none() {
+            metadata.set_ingest_timestamp(now);
+        }
+    }
+}
+
 fn get_timestamp_millis(value: &Value) -> Option<i64> {
     match value {
         Value::Timestamp(timestamp) => Some(timestamp.timestamp_millis()),
@@ -399,6 +446,24 @@ mod tests {
         .await;
     }
 
+    #[tokio::test]
+    async fn stamps_ingest_timestamp() {
+        let (mut sender, mut stream) = SourceSender::new_test();
+        let before = Instant::now();
+        sender
+            .send_event(Event::Log(LogEvent::from("Log message")))
+            .await
+            .expect("Send should not fail");
+
+        let event = stream.next().await.expect("Event should be received");
+        let ingested = event
+            .metadata()
+            .ingest_timestamp()
+            .expect("Event should have an ingest timestamp");
+        assert!(ingested >= before);
+        assert_eq!(event.metadata().handoff_timestamp(), Some(ingested));
+    }
+
     async fn emit_and_test(make_event: impl FnOnce(DateTime<Utc>) -> Event) {
         let _ = metrics::init_test();
         let (mut sender, _stream) = SourceSender::new_test();
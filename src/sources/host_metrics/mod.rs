@@ -27,6 +27,10 @@ mod disk;
 mod filesystem;
 mod memory;
 mod network;
+#[cfg(target_os = "linux")]
+mod process;
+#[cfg(target_os = "linux")]
+mod tcp;
 
 /// Collector types.
 #[configurable_component]
@@ -57,6 +61,28 @@ pub enum Collector {
 
     /// Network.
     Network,
+
+    /// Process.
+    #[cfg(target_os = "linux")]
+    Process,
+
+    /// TCP.
+    #[cfg(target_os = "linux")]
+    Tcp,
+}
+
+impl Collector {
+    /// Whether the collector is used when `collectors` isn't set.
+    ///
+    /// The process and TCP collectors emit many series, for every process and TCP state, so they
+    /// must be requested explicitly.
+    const fn is_default(self) -> bool {
+        match self {
+            #[cfg(target_os = "linux")]
+            Self::Process | Self::Tcp => false,
+            _ => true,
+        }
+    }
 }
 
 /// Filtering configuration.
@@ -82,7 +108,7 @@ pub struct HostMetricsConfig {
 
     /// The list of host metric collector services to use.
     ///
-    /// Defaults to all collectors.
+    /// Defaults to all collectors, except for `process` and `tcp`.
     pub collectors: Option<Vec<Collector>>,
 
     /// Overrides the default namespace for the metrics emitted by the source.
@@ -108,6 +134,16 @@ pub struct HostMetricsConfig {
     #[configurable(derived)]
     #[serde(default)]
     pub network: network::NetworkConfig,
+
+    #[cfg(target_os = "linux")]
+    #[configurable(derived)]
+    #[serde(default)]
+    pub(crate) process: process::ProcessConfig,
+
+    #[cfg(target_os = "linux")]
+    #[configurable(derived)]
+    #[serde(default)]
+    pub(crate) tcp: tcp::TcpConfig,
 }
 
 const fn default_scrape_interval() -> f64 {
@@ -183,7 +219,7 @@ impl HostMetricsConfig {
 
     fn has_collector(&self, collector: Collector) -> bool {
         match &self.collectors {
-            None => true,
+            None => collector.is_default(),
             Some(collectors) => collectors.iter().any(|&c| c == collector),
         }
     }
@@ -243,6 +279,14 @@ impl HostMetrics {
         if self.config.has_collector(Collector::Network) {
             self.network_metrics(&mut buffer).await;
         }
+        #[cfg(target_os = "linux")]
+        if self.config.has_collector(Collector::Process) {
+            self.process_metrics(&mut buffer).await;
+        }
+        #[cfg(target_os = "linux")]
+        if self.config.has_collector(Collector::Tcp) {
+            self.tcp_metrics(&mut buffer).await;
+        }
 
         let metrics = buffer.metrics;
         emit!(EventsReceived {
@@ -352,6 +396,14 @@ where
     filter_result_sync(result, message)
 }
 
+/// The root of the proc filesystem, as configured through `PROCFS_ROOT`.
+#[cfg(target_os = "linux")]
+pub(self) fn procfs_root() -> std::path::PathBuf {
+    std::env::var_os("PROCFS_ROOT")
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|| std::path::PathBuf::from("/proc"))
+}
+
 #[allow(clippy::missing_const_for_fn)]
 fn init_roots() {
     #[cfg(target_os = "linux")]
@@ -543,12 +595,7 @@ pub(self) mod tests {
 
     #[tokio::test]
     async fn filters_on_collectors() {
-        let all_metrics_count = HostMetrics::new(HostMetricsConfig::default())
-            .capture_metrics()
-            .await
-            .len();
-
-        for collector in &[
+        let collectors = [
             #[cfg(target_os = "linux")]
             Collector::CGroups,
             Collector::Cpu,
@@ -558,7 +605,20 @@ pub(self) mod tests {
             Collector::Host,
             Collector::Memory,
             Collector::Network,
-        ] {
+            #[cfg(target_os = "linux")]
+            Collector::Process,
+            #[cfg(target_os = "linux")]
+            Collector::Tcp,
+        ];
+        let all_metrics_count = HostMetrics::new(HostMetricsConfig {
+            collectors: Some(collectors.to_vec()),
+            ..Default::default()
+        })
+        .capture_metrics()
+        .await
+        .len();
+
+        for collector in &collectors {
             let some_metrics = HostMetrics::new(HostMetricsConfig {
                 collectors: Some(vec![*collector]),
                 ..Default::default()
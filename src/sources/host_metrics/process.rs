@@ -0,0 +1,330 @@
+use std::path::Path;
+
+use tokio::fs;
+use vector_config::configurable_component;
+
+use super::{procfs_root, FilterList, HostMetrics, MetricsBuffer};
+use crate::event::metric::MetricTags;
+
+/// Options for the “process” metrics collector.
+///
+/// This collector is only available on Linux systems.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(default)]
+pub(crate) struct ProcessConfig {
+    /// Lists of process name patterns to include or exclude.
+    ///
+    /// The process name is the executable name reported by the kernel, which is truncated to 15
+    /// characters.
+    names: FilterList,
+
+    /// Lists of process command line patterns to include or exclude.
+    ///
+    /// The command line arguments are joined with spaces before being matched. Kernel threads have
+    /// no command line, and so are never matched by these patterns.
+    cmdlines: FilterList,
+
+    /// The maximum number of processes to report metrics for.
+    ///
+    /// Every process produces its own set of series, tagged with its PID, so this bounds the
+    /// cardinality of the metrics emitted by this collector. Processes are considered in order of
+    /// their PID, and any processes beyond this limit are skipped.
+    #[derivative(Default(value = "100"))]
+    max_processes: usize,
+}
+
+/// The subset of `/proc/<pid>/stat` used by this collector.
+#[derive(Debug, PartialEq)]
+struct ProcessStat {
+    name: String,
+    user_ticks: u64,
+    system_ticks: u64,
+    threads: u64,
+    rss_pages: u64,
+}
+
+impl ProcessStat {
+    fn parse(stat: &str) -> Option<Self> {
+        // The process name is enclosed in parentheses and may itself contain spaces or
+        // parentheses, so split on the last closing one.
+        let start = stat.find('(')?;
+        let end = stat.rfind(')')?;
+        let name = stat.get(start + 1..end)?.to_string();
+        let fields = stat.get(end + 1..)?.split_whitespace().collect::<Vec<_>>();
+
+        // Field numbers are as documented in proc(5), where the state is field 3.
+        let field = |number: usize| -> Option<u64> { fields.get(number - 3)?.parse().ok() };
+
+        Some(Self {
+            name,
+            user_ticks: field(14)?,
+            system_ticks: field(15)?,
+            threads: field(20)?,
+            rss_pages: field(24)?,
+        })
+    }
+}
+
+/// Parses the `read_bytes` and `write_bytes` counters from `/proc/<pid>/io`.
+fn parse_io(io: &str) -> Option<(u64, u64)> {
+    let mut read_bytes = None;
+    let mut write_bytes = None;
+    for line in io.lines() {
+        match line.split_once(':') {
+            Some(("read_bytes", value)) => read_bytes = value.trim().parse().ok(),
+            Some(("write_bytes", value)) => write_bytes = value.trim().parse().ok(),
+            _ => {}
+        }
+    }
+    Some((read_bytes?, write_bytes?))
+}
+
+fn sysconf(name: libc::c_int, default: u64) -> u64 {
+    match unsafe { libc::sysconf(name) } {
+        value if value > 0 => value as u64,
+        _ => default,
+    }
+}
+
+impl HostMetrics {
+    pub(super) async fn process_metrics(&self, output: &mut MetricsBuffer) {
+        output.name = "process";
+        let config = &self.config.process;
+        let root = procfs_root();
+
+        let mut pids = match list_pids(&root).await {
+            Ok(pids) => pids,
+            Err(error) => {
+                error!(message = "Failed to list processes.", %error, internal_log_rate_secs = 60);
+                return;
+            }
+        };
+        pids.sort_unstable();
+
+        let clock_ticks = sysconf(libc::_SC_CLK_TCK, 100) as f64;
+        let page_size = sysconf(libc::_SC_PAGESIZE, 4096) as f64;
+
+        let mut reported = 0;
+        for pid in pids {
+            // Processes may exit at any point while they are being read, so any failure to read
+            // their data simply skips them.
+            let dir = root.join(pid.to_string());
+            let stat = match fs::read_to_string(dir.join("stat")).await {
+                Ok(stat) => match ProcessStat::parse(&stat) {
+                    Some(stat) => stat,
+                    None => continue,
+                },
+                Err(_) => continue,
+            };
+            if !config.names.contains_str(Some(&stat.name)) {
+                continue;
+            }
+
+            let cmdline = fs::read(dir.join("cmdline"))
+                .await
+                .map(|cmdline| {
+                    String::from_utf8_lossy(&cmdline)
+                        .split('\0')
+                        .filter(|arg| !arg.is_empty())
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .unwrap_or_default();
+            let cmdline = (!cmdline.is_empty()).then(|| cmdline.as_str());
+            if !config.cmdlines.contains_str(cmdline) {
+                continue;
+            }
+
+            if reported == config.max_processes {
+                warn!(
+                    message = "Reached the maximum number of processes to report metrics for; skipping remaining processes.",
+                    max_processes = config.max_processes,
+                    internal_log_rate_secs = 60,
+                );
+                break;
+            }
+            reported += 1;
+
+            let tags = |extra: Option<(&str, &str)>| {
+                let mut tags = MetricTags::from([
+                    (String::from("pid"), pid.to_string()),
+                    (String::from("name"), stat.name.clone()),
+                ]);
+                if let Some((key, value)) = extra {
+                    tags.insert(key.into(), value.into());
+                }
+                tags
+            };
+
+            output.counter(
+                "process_cpu_seconds_total",
+                stat.user_ticks as f64 / clock_ticks,
+                tags(Some(("mode", "user"))),
+            );
+            output.counter(
+                "process_cpu_seconds_total",
+                stat.system_ticks as f64 / clock_ticks,
+                tags(Some(("mode", "system"))),
+            );
+            output.gauge(
+                "process_memory_rss_bytes",
+                stat.rss_pages as f64 * page_size,
+                tags(None),
+            );
+            output.gauge("process_threads", stat.threads as f64, tags(None));
+
+            // The file descriptors and I/O counters of processes owned by other users are only
+            // readable with elevated privileges.
+            if let Ok(fds) = count_entries(&dir.join("fd")).await {
+                output.gauge("process_open_fds", fds as f64, tags(None));
+            }
+            if let Some((read_bytes, write_bytes)) = fs::read_to_string(dir.join("io"))
+                .await
+                .ok()
+                .and_then(|io| parse_io(&io))
+            {
+                output.counter("process_io_read_bytes_total", read_bytes as f64, tags(None));
+                output.counter(
+                    "process_io_written_bytes_total",
+                    write_bytes as f64,
+                    tags(None),
+                );
+            }
+        }
+    }
+}
+
+async fn list_pids(root: &Path) -> std::io::Result<Vec<u32>> {
+    let mut pids = Vec::new();
+    let mut entries = fs::read_dir(root).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        if let Some(pid) = entry
+            .file_name()
+            .to_str()
+            .and_then(|name| name.parse().ok())
+        {
+            pids.push(pid);
+        }
+    }
+    Ok(pids)
+}
+
+async fn count_entries(path: &Path) -> std::io::Result<usize> {
+    let mut count = 0;
+    let mut entries = fs::read_dir(path).await?;
+    while entries.next_entry().await?.is_some() {
+        count += 1;
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::{
+        super::{
+            tests::{assert_filtered_metrics, count_tag},
+            HostMetrics, HostMetricsConfig, MetricsBuffer,
+        },
+        parse_io, ProcessConfig, ProcessStat,
+    };
+
+    async fn process_metrics(config: ProcessConfig) -> Vec<crate::event::Metric> {
+        let mut buffer = MetricsBuffer::new(None);
+        HostMetrics::new(HostMetricsConfig {
+            process: config,
+            ..Default::default()
+        })
+        .process_metrics(&mut buffer)
+        .await;
+        buffer.metrics
+    }
+
+    #[test]
+    fn parses_stat() {
+        let stat = "1234 (my (weird) proc) S 1 1234 1234 0 -1 4194560 1000 0 0 0 250 50 0 0 20 0 7 0 100 123456789 321 18446744073709551615 1 1 0 0 0 0 0 4096 0 0 0 0 17 3 0 0 0 0 0";
+        assert_eq!(
+            ProcessStat::parse(stat),
+            Some(ProcessStat {
+                name: "my (weird) proc".into(),
+                user_ticks: 250,
+                system_ticks: 50,
+                threads: 7,
+                rss_pages: 321,
+            })
+        );
+        assert_eq!(ProcessStat::parse("1234 (truncated) S 1"), None);
+    }
+
+    #[test]
+    fn parses_io() {
+        let io = "rchar: 100\nwchar: 200\nsyscr: 3\nsyscw: 4\nread_bytes: 4096\nwrite_bytes: 8192\ncancelled_write_bytes: 0\n";
+        assert_eq!(parse_io(io), Some((4096, 8192)));
+        assert_eq!(parse_io("rchar: 100\n"), None);
+    }
+
+    #[tokio::test]
+    async fn generates_process_metrics() {
+        // Report every process, since the default limit could leave out the current one.
+        let metrics = process_metrics(ProcessConfig {
+            max_processes: usize::MAX,
+            ..Default::default()
+        })
+        .await;
+        assert!(!metrics.is_empty());
+
+        // All metrics are named process_*
+        assert!(metrics
+            .iter()
+            .all(|metric| metric.name().starts_with("process_")));
+
+        // They should all have "pid" and "name" tags
+        assert_eq!(count_tag(&metrics, "pid"), metrics.len());
+        assert_eq!(count_tag(&metrics, "name"), metrics.len());
+
+        // The current process must be included
+        let pid = std::process::id().to_string();
+        assert!(metrics.iter().any(|metric| metric.tag_matches("pid", &pid)));
+    }
+
+    #[tokio::test]
+    async fn not_collected_by_default() {
+        let metrics = HostMetrics::new(HostMetricsConfig::default())
+            .capture_metrics()
+            .await;
+        assert!(!metrics
+            .iter()
+            .any(|metric| metric.name().starts_with("process_")));
+    }
+
+    #[tokio::test]
+    async fn process_metrics_limits_processes() {
+        let metrics = process_metrics(ProcessConfig {
+            max_processes: 1,
+            ..Default::default()
+        })
+        .await;
+
+        let pids = metrics
+            .iter()
+            .filter_map(|metric| metric.tags().unwrap().get("pid").cloned())
+            .collect::<HashSet<_>>();
+        assert_eq!(pids.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn process_metrics_filters_on_name() {
+        assert_filtered_metrics("name", |names| async move {
+            process_metrics(ProcessConfig {
+                names,
+                max_processes: usize::MAX,
+                ..Default::default()
+            })
+            .await
+        })
+        .await;
+    }
+}
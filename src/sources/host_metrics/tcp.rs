@@ -0,0 +1,272 @@
+use std::{collections::BTreeMap, path::Path};
+
+use tokio::fs;
+use vector_common::btreemap;
+use vector_config::configurable_component;
+
+use super::{procfs_root, FilterList, HostMetrics, MetricsBuffer};
+
+/// TCP states, indexed by their numeric value in `/proc/net/tcp`.
+const STATES: [&str; 13] = [
+    "unknown",
+    "established",
+    "syn_sent",
+    "syn_recv",
+    "fin_wait1",
+    "fin_wait2",
+    "time_wait",
+    "close",
+    "close_wait",
+    "last_ack",
+    "listen",
+    "closing",
+    "new_syn_recv",
+];
+const LISTEN: usize = 10;
+
+/// Counters from `/proc/net/snmp` (`Tcp`) and `/proc/net/netstat` (`TcpExt`), with the metric
+/// names they are emitted as.
+const COUNTERS: [(&str, &str, &str); 9] = [
+    ("Tcp", "ActiveOpens", "tcp_active_opens_total"),
+    ("Tcp", "PassiveOpens", "tcp_passive_opens_total"),
+    ("Tcp", "AttemptFails", "tcp_attempt_fails_total"),
+    ("Tcp", "EstabResets", "tcp_established_resets_total"),
+    ("Tcp", "RetransSegs", "tcp_retransmitted_segments_total"),
+    ("Tcp", "InErrs", "tcp_receive_errors_total"),
+    ("TcpExt", "ListenOverflows", "tcp_listen_overflows_total"),
+    ("TcpExt", "ListenDrops", "tcp_listen_drops_total"),
+    ("TcpExt", "SyncookiesSent", "tcp_syncookies_sent_total"),
+];
+
+/// Options for the “tcp” metrics collector.
+///
+/// This collector is only available on Linux systems.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(default)]
+pub(crate) struct TcpConfig {
+    /// Lists of local port patterns to include or exclude.
+    ///
+    /// This only applies to the metrics computed from individual sockets, and not to the counters
+    /// reported by the kernel for the whole host.
+    ports: FilterList,
+}
+
+/// The subset of a socket entry in `/proc/net/tcp` used by this collector.
+#[derive(Debug, PartialEq)]
+struct Socket {
+    local_port: u16,
+    state: usize,
+    tx_queue: u64,
+    rx_queue: u64,
+}
+
+impl Socket {
+    fn parse(line: &str) -> Option<Self> {
+        let mut fields = line.split_whitespace();
+        let _slot = fields.next()?;
+        let (_, local_port) = fields.next()?.rsplit_once(':')?;
+        let _remote = fields.next()?;
+        let state = fields.next()?;
+        let (tx_queue, rx_queue) = fields.next()?.split_once(':')?;
+
+        Some(Self {
+            local_port: u16::from_str_radix(local_port, 16).ok()?,
+            state: usize::from_str_radix(state, 16)
+                .ok()
+                .filter(|state| *state < STATES.len())?,
+            tx_queue: u64::from_str_radix(tx_queue, 16).ok()?,
+            rx_queue: u64::from_str_radix(rx_queue, 16).ok()?,
+        })
+    }
+}
+
+/// Parses the header/value line pairs of `/proc/net/snmp` and `/proc/net/netstat` into a map
+/// keyed by section and field name.
+fn parse_snmp(contents: &str) -> BTreeMap<(String, String), f64> {
+    let mut values = BTreeMap::new();
+    let mut lines = contents.lines();
+    while let (Some(header), Some(data)) = (lines.next(), lines.next()) {
+        let (section, names, data) = match (header.split_once(':'), data.split_once(':')) {
+            (Some((section, names)), Some((_, data))) => (section, names, data),
+            _ => continue,
+        };
+        for (name, value) in names.split_whitespace().zip(data.split_whitespace()) {
+            if let Ok(value) = value.parse() {
+                values.insert((section.to_string(), name.to_string()), value);
+            }
+        }
+    }
+    values
+}
+
+impl HostMetrics {
+    pub(super) async fn tcp_metrics(&self, output: &mut MetricsBuffer) {
+        output.name = "tcp";
+        let root = procfs_root();
+
+        let mut connections = [0u64; STATES.len()];
+        let mut tx_queued = [0u64; STATES.len()];
+        let mut rx_queued = [0u64; STATES.len()];
+        let mut backlogs = BTreeMap::<u16, u64>::new();
+
+        for file in ["net/tcp", "net/tcp6"] {
+            let contents = match fs::read_to_string(root.join(file)).await {
+                Ok(contents) => contents,
+                // IPv6 may be disabled, in which case `tcp6` does not exist.
+                Err(_) if file == "net/tcp6" => continue,
+                Err(error) => {
+                    error!(message = "Failed to load TCP socket info.", %error, internal_log_rate_secs = 60);
+                    continue;
+                }
+            };
+
+            for socket in contents.lines().skip(1).filter_map(Socket::parse) {
+                if !self
+                    .config
+                    .tcp
+                    .ports
+                    .contains_str(Some(&socket.local_port.to_string()))
+                {
+                    continue;
+                }
+                connections[socket.state] += 1;
+                tx_queued[socket.state] += socket.tx_queue;
+                rx_queued[socket.state] += socket.rx_queue;
+                if socket.state == LISTEN {
+                    // For listening sockets, the receive queue is the accept backlog.
+                    *backlogs.entry(socket.local_port).or_default() += socket.rx_queue;
+                }
+            }
+        }
+
+        for (index, state) in STATES.iter().enumerate().skip(1) {
+            output.gauge(
+                "tcp_connections",
+                connections[index] as f64,
+                btreemap! { "state" => *state },
+            );
+            output.gauge(
+                "tcp_tx_queued_bytes",
+                tx_queued[index] as f64,
+                btreemap! { "state" => *state },
+            );
+            output.gauge(
+                "tcp_rx_queued_bytes",
+                rx_queued[index] as f64,
+                btreemap! { "state" => *state },
+            );
+        }
+        for (port, backlog) in backlogs {
+            output.gauge(
+                "tcp_listen_backlog",
+                backlog as f64,
+                btreemap! { "port" => port.to_string() },
+            );
+        }
+
+        let mut counters = BTreeMap::new();
+        for file in ["net/snmp", "net/netstat"] {
+            match read_snmp(&root.join(file)).await {
+                Ok(values) => counters.extend(values),
+                Err(error) => {
+                    error!(message = "Failed to load TCP statistics.", %error, internal_log_rate_secs = 60);
+                }
+            }
+        }
+        for (section, field, name) in COUNTERS {
+            if let Some(value) = counters.get(&(section.to_string(), field.to_string())) {
+                output.counter(name, *value, BTreeMap::new());
+            }
+        }
+    }
+}
+
+async fn read_snmp(path: &Path) -> std::io::Result<BTreeMap<(String, String), f64>> {
+    fs::read_to_string(path)
+        .await
+        .map(|contents| parse_snmp(&contents))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        super::{
+            tests::{count_name, count_tag},
+            HostMetrics, HostMetricsConfig, MetricsBuffer,
+        },
+        parse_snmp, Socket, STATES,
+    };
+
+    #[test]
+    fn parses_sockets() {
+        let listen = "   0: 00000000:0016 00000000:0000 0A 00000000:00000003 00:00000000 00000000     0        0 22388 1 0000000000000000 100 0 0 10 0";
+        assert_eq!(
+            Socket::parse(listen),
+            Some(Socket {
+                local_port: 22,
+                state: 10,
+                tx_queue: 0,
+                rx_queue: 3,
+            })
+        );
+
+        let established = "   1: 0000000000000000FFFF00000100007F:1F90 0000000000000000FFFF00000100007F:D3A2 01 000000C8:00000000 01:00000014 00000000  1000        0 54321 2 0000000000000000 20 4 30 10 -1";
+        assert_eq!(
+            Socket::parse(established),
+            Some(Socket {
+                local_port: 8080,
+                state: 1,
+                tx_queue: 200,
+                rx_queue: 0,
+            })
+        );
+
+        assert_eq!(Socket::parse("  sl  local_address rem_address   st"), None);
+    }
+
+    #[test]
+    fn parses_snmp() {
+        let snmp = "Ip: Forwarding DefaultTTL\nIp: 1 64\nTcp: RtoAlgorithm MaxConn RetransSegs\nTcp: 1 -1 42\n";
+        let values = parse_snmp(snmp);
+        assert_eq!(
+            values.get(&("Tcp".into(), "RetransSegs".into())),
+            Some(&42.0)
+        );
+        assert_eq!(values.get(&("Tcp".into(), "MaxConn".into())), Some(&-1.0));
+        assert_eq!(values.get(&("Ip".into(), "DefaultTTL".into())), Some(&64.0));
+    }
+
+    #[tokio::test]
+    async fn generates_tcp_metrics() {
+        let mut buffer = MetricsBuffer::new(None);
+        HostMetrics::new(HostMetricsConfig::default())
+            .tcp_metrics(&mut buffer)
+            .await;
+        let metrics = buffer.metrics;
+
+        // All metrics are named tcp_*
+        assert!(metrics
+            .iter()
+            .all(|metric| metric.name().starts_with("tcp_")));
+
+        // Connection counts are reported for every state, even if there are none in it
+        assert_eq!(count_name(&metrics, "tcp_connections"), STATES.len() - 1);
+        assert_eq!(
+            count_tag(&metrics, "state"),
+            3 * (STATES.len() - 1),
+            "every socket-level metric should have a \"state\" tag"
+        );
+        assert_eq!(count_name(&metrics, "tcp_retransmitted_segments_total"), 1);
+    }
+
+    #[tokio::test]
+    async fn not_collected_by_default() {
+        let metrics = HostMetrics::new(HostMetricsConfig::default())
+            .capture_metrics()
+            .await;
+        assert!(!metrics
+            .iter()
+            .any(|metric| metric.name().starts_with("tcp_")));
+    }
+}
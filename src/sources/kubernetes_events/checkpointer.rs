@@ -0,0 +1,230 @@
+//! Keeps track of the `Event`s that were already emitted, so that they aren't emitted again when
+//! the watch is restarted, either by Vector or by the API server.
+//!
+//! `resourceVersion`s are opaque and can't be compared for ordering, so instead of keeping the
+//! last one seen, the version of each `Event` still present in the cluster is kept. An `Event` is
+//! emitted again only when its version changes, which happens when it's updated, for example
+//! when its series count is incremented.
+
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use k8s_openapi::api::events::v1::Event;
+use serde::{Deserialize, Serialize};
+
+const CHECKPOINT_FILE_NAME: &str = "checkpoints.json";
+const TMP_FILE_NAME: &str = "checkpoints.new.json";
+
+/// The version of an `Event` that was emitted.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+struct Seen {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    namespace: Option<String>,
+    resource_version: String,
+}
+
+/// The contents of the checkpoint file, generic over the events so that they can be written
+/// without being cloned.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(tag = "version")]
+enum State<E> {
+    #[serde(rename = "1")]
+    V1 { events: E },
+}
+
+pub(super) struct Checkpointer {
+    path: PathBuf,
+    tmp_path: PathBuf,
+    events: BTreeMap<String, Seen>,
+    dirty: bool,
+}
+
+impl Checkpointer {
+    /// Loads the checkpoints kept in `data_dir`.
+    ///
+    /// A missing or unreadable checkpoint file is treated as empty, in which case all `Event`s
+    /// currently in the cluster are emitted.
+    pub(super) fn load(data_dir: &Path) -> Self {
+        let path = data_dir.join(CHECKPOINT_FILE_NAME);
+        let events = match fs::read(&path) {
+            Ok(contents) => match serde_json::from_slice(&contents) {
+                Ok(State::<BTreeMap<String, Seen>>::V1 { events }) => events,
+                Err(error) => {
+                    warn!(message = "Unable to parse checkpoints, ignoring them.", %error, ?path);
+                    BTreeMap::new()
+                }
+            },
+            Err(error) if error.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(error) => {
+                warn!(message = "Unable to read checkpoints, ignoring them.", %error, ?path);
+                BTreeMap::new()
+            }
+        };
+
+        Self {
+            tmp_path: data_dir.join(TMP_FILE_NAME),
+            path,
+            events,
+            dirty: false,
+        }
+    }
+
+    /// Records the current version of the `Event`, returning `true` if it wasn't seen before.
+    pub(super) fn observe(&mut self, event: &Event) -> bool {
+        let (key, seen) = match entry(event) {
+            Some(entry) => entry,
+            // Without a version, there is nothing to de-duplicate on.
+            None => return true,
+        };
+
+        if self.events.get(&key) == Some(&seen) {
+            return false;
+        }
+        self.events.insert(key, seen);
+        self.dirty = true;
+        true
+    }
+
+    /// Forgets the `Event`, which was deleted from the cluster.
+    pub(super) fn forget(&mut self, event: &Event) {
+        if let Some((key, _)) = entry(event) {
+            if self.events.remove(&key).is_some() {
+                self.dirty = true;
+            }
+        }
+    }
+
+    /// Forgets the `Event`s watched in `namespace`, or in all namespaces if `None`, that are no
+    /// longer `listed` in the cluster.
+    pub(super) fn retain_listed(&mut self, namespace: Option<&str>, listed: &[Event]) {
+        let listed = listed
+            .iter()
+            .filter_map(|event| entry(event).map(|(key, _)| key))
+            .collect::<std::collections::HashSet<_>>();
+
+        let before = self.events.len();
+        self.events.retain(|key, seen| {
+            listed.contains(key)
+                || namespace.map_or(false, |namespace| {
+                    seen.namespace.as_deref() != Some(namespace)
+                })
+        });
+        self.dirty |= self.events.len() != before;
+    }
+
+    /// Writes the checkpoints to disk, if they changed since they were last written.
+    pub(super) fn persist(&mut self) -> io::Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+
+        // Write to a temporary file first so that the checkpoints are never left half-written.
+        let contents = serde_json::to_vec(&State::V1 {
+            events: &self.events,
+        })?;
+        fs::write(&self.tmp_path, contents)?;
+        fs::rename(&self.tmp_path, &self.path)?;
+
+        self.dirty = false;
+        Ok(())
+    }
+}
+
+/// The key identifying the `Event` and the version of it.
+fn entry(event: &Event) -> Option<(String, Seen)> {
+    let metadata = &event.metadata;
+    let resource_version = metadata.resource_version.clone()?;
+    let key = match (&metadata.uid, &metadata.name) {
+        (Some(uid), _) => uid.clone(),
+        (None, Some(name)) => format!(
+            "{}/{}",
+            metadata.namespace.as_deref().unwrap_or_default(),
+            name
+        ),
+        (None, None) => return None,
+    };
+
+    Some((
+        key,
+        Seen {
+            namespace: metadata.namespace.clone(),
+            resource_version,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(uid: &str, namespace: &str, resource_version: &str) -> Event {
+        serde_json::from_value(serde_json::json!({
+            "apiVersion": "events.k8s.io/v1",
+            "kind": "Event",
+            "metadata": {
+                "uid": uid,
+                "name": format!("{}.1", uid),
+                "namespace": namespace,
+                "resourceVersion": resource_version,
+            },
+            "eventTime": "2022-08-01T10:00:00.000000Z",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn observes_new_versions_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut checkpointer = Checkpointer::load(dir.path());
+
+        assert!(checkpointer.observe(&event("a", "default", "1")));
+        assert!(!checkpointer.observe(&event("a", "default", "1")));
+        assert!(checkpointer.observe(&event("a", "default", "2")));
+        assert!(checkpointer.observe(&event("b", "default", "2")));
+
+        checkpointer.forget(&event("a", "default", "2"));
+        assert!(checkpointer.observe(&event("a", "default", "2")));
+    }
+
+    #[test]
+    fn persists_across_restarts() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut checkpointer = Checkpointer::load(dir.path());
+        assert!(checkpointer.observe(&event("a", "default", "1")));
+        checkpointer.persist().unwrap();
+
+        let mut checkpointer = Checkpointer::load(dir.path());
+        assert!(!checkpointer.observe(&event("a", "default", "1")));
+        assert!(checkpointer.observe(&event("b", "default", "1")));
+    }
+
+    #[test]
+    fn ignores_corrupted_checkpoints() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join(CHECKPOINT_FILE_NAME), "{").unwrap();
+
+        let mut checkpointer = Checkpointer::load(dir.path());
+        assert!(checkpointer.observe(&event("a", "default", "1")));
+    }
+
+    #[test]
+    fn retains_listed_in_namespace() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut checkpointer = Checkpointer::load(dir.path());
+        checkpointer.observe(&event("a", "default", "1"));
+        checkpointer.observe(&event("b", "default", "1"));
+        checkpointer.observe(&event("c", "kube-system", "1"));
+
+        checkpointer.retain_listed(Some("default"), &[event("b", "default", "1")]);
+
+        assert!(checkpointer.observe(&event("a", "default", "1")));
+        assert!(!checkpointer.observe(&event("b", "default", "1")));
+        assert!(!checkpointer.observe(&event("c", "kube-system", "1")));
+
+        checkpointer.retain_listed(None, &[]);
+        assert!(checkpointer.observe(&event("c", "kube-system", "1")));
+    }
+}
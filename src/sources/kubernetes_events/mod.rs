@@ -0,0 +1,460 @@
+//! This mod implements the `kubernetes_events` source.
+//! It watches the `Event` objects of the cluster, such as the ones reporting
+//! an `OOMKilling`, a `FailedScheduling` or a `BackOff`, through the
+//! `events.k8s.io/v1` API, and emits each of them as a log event.
+
+#![deny(missing_docs)]
+
+use std::{path::PathBuf, time::Duration};
+
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use futures::{stream, FutureExt, StreamExt};
+use k8s_openapi::api::events::v1::Event as KubeEvent;
+use kube::{
+    api::{Api, ListParams},
+    runtime::watcher,
+    Client,
+};
+use vector_config::configurable_component;
+use vector_core::{config::LogNamespace, ByteSizeOf};
+
+use crate::{
+    config::{
+        log_schema, DataType, GenerateConfig, Output, SourceConfig, SourceContext,
+        SourceDescription,
+    },
+    event::{Event, LogEvent},
+    internal_events::{
+        KubernetesEventsCheckpointWriteError, KubernetesEventsReceived,
+        KubernetesEventsSerializationError, KubernetesEventsWatchError, StreamClosedError,
+    },
+    kubernetes,
+    shutdown::ShutdownSignal,
+    sources, SourceSender,
+};
+
+mod checkpointer;
+
+use checkpointer::Checkpointer;
+
+const COMPONENT_ID: &str = "kubernetes_events";
+
+/// How often the checkpoints are written to disk, if they changed.
+const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How long to wait before watching again after the watch failed.
+const WATCH_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// The fields of the `Event` holding its timestamp, from the most to the least precise.
+const TIMESTAMP_POINTERS: [&str; 4] = [
+    "/eventTime",
+    "/series/lastObservedTime",
+    "/deprecatedLastTimestamp",
+    "/metadata/creationTimestamp",
+];
+
+/// Configuration for the `kubernetes_events` source.
+#[configurable_component(source)]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields, default)]
+pub struct Config {
+    /// The namespaces to watch `Event`s in.
+    ///
+    /// If empty, the `Event`s of all namespaces are watched.
+    namespaces: Vec<String>,
+
+    /// Specifies the label selector to filter `Event`s with.
+    label_selector: String,
+
+    /// Specifies the field selector to filter `Event`s with, such as `type=Warning`.
+    field_selector: String,
+
+    /// Optional path to a kubeconfig file readable by Vector. If not set,
+    /// Vector will try to connect to Kubernetes using in-cluster configuration.
+    kube_config_file: Option<PathBuf>,
+
+    /// The directory used to persist the versions of the `Event`s already emitted.
+    ///
+    /// By default, the global `data_dir` option is used. Please make sure the user Vector is running as has write permissions to this directory.
+    data_dir: Option<PathBuf>,
+}
+
+inventory::submit! {
+    SourceDescription::new::<Config>(COMPONENT_ID)
+}
+
+impl GenerateConfig for Config {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(&Self::default()).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "kubernetes_events")]
+impl SourceConfig for Config {
+    async fn build(&self, cx: SourceContext) -> crate::Result<sources::Source> {
+        let client = kubernetes::client(self.kube_config_file.as_deref()).await?;
+        let data_dir = cx
+            .globals
+            .resolve_and_make_data_subdir(self.data_dir.as_ref(), cx.key.id())?;
+
+        Ok(Box::pin(
+            run(
+                client,
+                self.namespaces.clone(),
+                self.list_params(),
+                data_dir,
+                cx.out,
+                cx.shutdown,
+            )
+            .map(Ok),
+        ))
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn source_type(&self) -> &'static str {
+        COMPONENT_ID
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+impl Config {
+    fn list_params(&self) -> ListParams {
+        ListParams {
+            label_selector: (!self.label_selector.is_empty()).then(|| self.label_selector.clone()),
+            field_selector: (!self.field_selector.is_empty()).then(|| self.field_selector.clone()),
+            ..Default::default()
+        }
+    }
+}
+
+/// Watches the `Event`s of each namespace, or of the whole cluster if no namespace is given, and
+/// sends the ones that weren't already sent.
+async fn run(
+    client: Client,
+    namespaces: Vec<String>,
+    list_params: ListParams,
+    data_dir: PathBuf,
+    mut out: SourceSender,
+    shutdown: ShutdownSignal,
+) {
+    let mut checkpointer = Checkpointer::load(&data_dir);
+
+    let scopes = if namespaces.is_empty() {
+        vec![None]
+    } else {
+        namespaces.into_iter().map(Some).collect()
+    };
+    let watchers = scopes.into_iter().map(|namespace| {
+        let api = match &namespace {
+            Some(namespace) => Api::<KubeEvent>::namespaced(client.clone(), namespace),
+            None => Api::<KubeEvent>::all(client.clone()),
+        };
+        watcher(api, list_params.clone())
+            .map(move |result| (namespace.clone(), result))
+            .boxed()
+    });
+    let mut watchers = stream::select_all(watchers).take_until(shutdown);
+
+    let mut checkpoint_interval = tokio::time::interval(CHECKPOINT_INTERVAL);
+    loop {
+        tokio::select! {
+            item = watchers.next() => match item {
+                Some((namespace, Ok(event))) => {
+                    for event in filter_new(&mut checkpointer, namespace.as_deref(), event) {
+                        let event = match create_event(event) {
+                            Ok(event) => event,
+                            Err(error) => {
+                                emit!(KubernetesEventsSerializationError { error });
+                                continue;
+                            }
+                        };
+                        emit!(KubernetesEventsReceived {
+                            byte_size: event.size_of(),
+                            namespace: namespace.as_deref(),
+                        });
+
+                        if let Err(error) = out.send_event(event).await {
+                            emit!(StreamClosedError { error, count: 1 });
+                            persist(&mut checkpointer);
+                            return;
+                        }
+                    }
+                }
+                Some((namespace, Err(error))) => {
+                    emit!(KubernetesEventsWatchError {
+                        error,
+                        namespace: namespace.as_deref(),
+                    });
+                    // The watcher retries on the next poll, so don't spin on a failing API server.
+                    tokio::time::sleep(WATCH_RETRY_DELAY).await;
+                }
+                None => break,
+            },
+            _ = checkpoint_interval.tick() => persist(&mut checkpointer),
+        }
+    }
+
+    persist(&mut checkpointer);
+}
+
+fn persist(checkpointer: &mut Checkpointer) {
+    if let Err(error) = checkpointer.persist() {
+        emit!(KubernetesEventsCheckpointWriteError { error });
+    }
+}
+
+/// Applies the watcher event to the checkpoints, returning the `Event`s that weren't seen before.
+fn filter_new(
+    checkpointer: &mut Checkpointer,
+    namespace: Option<&str>,
+    event: watcher::Event<KubeEvent>,
+) -> Vec<KubeEvent> {
+    match event {
+        watcher::Event::Applied(event) => {
+            if checkpointer.observe(&event) {
+                vec![event]
+            } else {
+                Vec::new()
+            }
+        }
+        watcher::Event::Deleted(event) => {
+            checkpointer.forget(&event);
+            Vec::new()
+        }
+        // The watch was (re)started, and all the current `Event`s are listed.
+        watcher::Event::Restarted(events) => {
+            checkpointer.retain_listed(namespace, &events);
+            events
+                .into_iter()
+                .filter(|event| checkpointer.observe(event))
+                .collect()
+        }
+    }
+}
+
+/// Converts the `Event` into a log event, with its `note` as the message.
+fn create_event(mut event: KubeEvent) -> Result<Event, serde_json::Error> {
+    // The managed fields only describe which manager set each field, drop them to keep events
+    // small.
+    event.metadata.managed_fields = None;
+
+    let value = serde_json::to_value(&event)?;
+    let timestamp = TIMESTAMP_POINTERS
+        .iter()
+        .filter_map(|pointer| value.pointer(pointer)?.as_str())
+        .find_map(|timestamp| DateTime::parse_from_rfc3339(timestamp).ok())
+        .map(|timestamp| timestamp.with_timezone(&Utc))
+        .unwrap_or_else(Utc::now);
+
+    let mut log = match value {
+        serde_json::Value::Object(fields) => LogEvent::from(
+            fields
+                .into_iter()
+                .map(|(key, value)| (key, value.into()))
+                .collect::<std::collections::BTreeMap<_, _>>(),
+        ),
+        _ => LogEvent::default(),
+    };
+
+    if let Some(note) = log.remove("note") {
+        log.insert(log_schema().message_key(), note);
+    }
+    log.insert(log_schema().timestamp_key(), timestamp);
+    log.insert(log_schema().source_type_key(), Bytes::from(COMPONENT_ID));
+
+    Ok(log.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use chrono::TimeZone;
+    use serde_json::json;
+    use vector_core::event::Value;
+
+    use super::*;
+    use crate::test_util::{collect_n, next_addr};
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<Config>();
+    }
+
+    fn event_json(uid: &str, resource_version: &str, reason: &str) -> serde_json::Value {
+        json!({
+            "apiVersion": "events.k8s.io/v1",
+            "kind": "Event",
+            "metadata": {
+                "name": format!("{}.16f7d3a2b9e1c0d4", uid),
+                "namespace": "default",
+                "uid": uid,
+                "resourceVersion": resource_version,
+                "creationTimestamp": "2022-08-01T10:00:00Z",
+                "managedFields": [{"manager": "kubelet", "operation": "Update"}],
+            },
+            "eventTime": "2022-08-01T10:00:00.123456Z",
+            "reportingController": "kubelet",
+            "reportingInstance": "node-1",
+            "action": "Killing",
+            "reason": reason,
+            "regarding": {"kind": "Pod", "namespace": "default", "name": "app-0"},
+            "note": format!("{} happened", reason),
+            "type": "Warning",
+        })
+    }
+
+    fn kube_event(uid: &str, resource_version: &str, reason: &str) -> KubeEvent {
+        serde_json::from_value(event_json(uid, resource_version, reason)).unwrap()
+    }
+
+    #[test]
+    fn creates_log_events() {
+        let event = create_event(kube_event("a", "1", "OOMKilling")).unwrap();
+        let log = event.as_log();
+
+        assert_eq!(log["message"], "OOMKilling happened".into());
+        assert_eq!(log["reason"], "OOMKilling".into());
+        assert_eq!(log["type"], "Warning".into());
+        assert_eq!(log["regarding.name"], "app-0".into());
+        assert_eq!(log["metadata.uid"], "a".into());
+        assert_eq!(log["source_type"], COMPONENT_ID.into());
+        assert_eq!(
+            log["timestamp"],
+            Value::from(Utc.ymd(2022, 8, 1).and_hms_micro(10, 0, 0, 123456))
+        );
+        assert!(log.get("metadata.managedFields").is_none());
+        assert!(log.get("note").is_none());
+    }
+
+    #[test]
+    fn filters_seen_events() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut checkpointer = Checkpointer::load(dir.path());
+
+        let events = filter_new(
+            &mut checkpointer,
+            None,
+            watcher::Event::Restarted(vec![
+                kube_event("a", "1", "BackOff"),
+                kube_event("b", "2", "BackOff"),
+            ]),
+        );
+        assert_eq!(events.len(), 2);
+
+        // Listing again after the watch was restarted.
+        let events = filter_new(
+            &mut checkpointer,
+            None,
+            watcher::Event::Restarted(vec![
+                kube_event("a", "1", "BackOff"),
+                kube_event("b", "3", "BackOff"),
+            ]),
+        );
+        assert_eq!(events, vec![kube_event("b", "3", "BackOff")]);
+
+        let events = filter_new(
+            &mut checkpointer,
+            None,
+            watcher::Event::Applied(kube_event("a", "1", "BackOff")),
+        );
+        assert!(events.is_empty());
+
+        let events = filter_new(
+            &mut checkpointer,
+            None,
+            watcher::Event::Deleted(kube_event("a", "1", "BackOff")),
+        );
+        assert!(events.is_empty());
+    }
+
+    /// Serves the `Event`s of the `default` namespace, as the API server would.
+    fn stub_api_server(list: Vec<serde_json::Value>, watch: Vec<serde_json::Value>) -> String {
+        use warp::Filter;
+
+        let addr = next_addr();
+        let routes =
+            warp::path!("apis" / "events.k8s.io" / "v1" / "namespaces" / "default" / "events")
+                .and(warp::query::<HashMap<String, String>>())
+                .map(move |query: HashMap<String, String>| {
+                    if query.get("watch").map(String::as_str) == Some("true") {
+                        let lines = watch
+                            .iter()
+                            .map(|event| {
+                                Ok::<_, std::io::Error>(format!(
+                                    "{}\n",
+                                    json!({"type": "ADDED", "object": event})
+                                ))
+                            })
+                            .collect::<Vec<_>>();
+                        // Keep the watch open, as the API server does until it times out.
+                        let body =
+                            hyper::Body::wrap_stream(stream::iter(lines).chain(stream::pending()));
+                        warp::http::Response::new(body)
+                    } else {
+                        let body = json!({
+                            "apiVersion": "events.k8s.io/v1",
+                            "kind": "EventList",
+                            "metadata": {"resourceVersion": "10"},
+                            "items": list,
+                        });
+                        warp::http::Response::new(hyper::Body::from(body.to_string()))
+                    }
+                });
+        tokio::spawn(warp::serve(routes).bind(addr));
+
+        format!("http://{}", addr)
+    }
+
+    #[tokio::test]
+    async fn watches_events_from_api_server() {
+        let url = stub_api_server(
+            vec![
+                event_json("a", "5", "OOMKilling"),
+                event_json("b", "6", "FailedScheduling"),
+            ],
+            vec![event_json("c", "11", "BackOff")],
+        );
+        let client = Client::try_from(kube::Config::new(url.parse().unwrap())).unwrap();
+
+        // `a` was already emitted before the restart.
+        let dir = tempfile::tempdir().unwrap();
+        let mut checkpointer = Checkpointer::load(dir.path());
+        checkpointer.observe(&kube_event("a", "5", "OOMKilling"));
+        checkpointer.persist().unwrap();
+
+        let (tx, rx) = SourceSender::new_test();
+        let (trigger, shutdown, _) = ShutdownSignal::new_wired();
+        let source = tokio::spawn(run(
+            client,
+            vec!["default".to_owned()],
+            ListParams::default(),
+            dir.path().to_owned(),
+            tx,
+            shutdown,
+        ));
+
+        let events = tokio::time::timeout(Duration::from_secs(10), collect_n(rx, 2))
+            .await
+            .expect("events weren't received");
+        let reasons = events
+            .iter()
+            .map(|event| event.as_log()["reason"].to_string_lossy())
+            .collect::<Vec<_>>();
+        assert_eq!(reasons, vec!["FailedScheduling", "BackOff"]);
+
+        trigger.cancel();
+        source.await.unwrap();
+
+        let mut checkpointer = Checkpointer::load(dir.path());
+        assert!(!checkpointer.observe(&kube_event("c", "11", "BackOff")));
+    }
+}
@@ -2,14 +2,15 @@
 
 #![deny(missing_docs)]
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use file_source::paths_provider::PathsProvider;
 use k8s_openapi::api::core::v1::{Namespace, Pod};
 use kube::runtime::reflector::{store::Store, ObjectRef};
 
-use super::path_helpers::build_pod_logs_directory;
-use crate::kubernetes::pod_manager_logic::extract_static_pod_config_hashsum;
+use crate::kubernetes::{
+    path_helpers::build_pod_logs_directory, pod_manager_logic::extract_static_pod_config_hashsum,
+};
 
 /// A paths provider implementation that uses the state obtained from the
 /// the k8s API.
@@ -98,25 +99,33 @@ fn extract_pod_logs_directory(pod: &Pod) -> Option<PathBuf> {
     Some(build_pod_logs_directory(namespace, name, uid))
 }
 
+/// Excludes the comma-separated containers of the `Pod` from the log collection.
 const CONTAINER_EXCLUSION_ANNOTATION_KEY: &str = "vector.dev/exclude-containers";
 
+/// Restricts the log collection to the comma-separated containers of the `Pod`.
+/// Exclusions still apply to the included containers.
+const CONTAINER_INCLUSION_ANNOTATION_KEY: &str = "vector.dev/include-containers";
+
 fn extract_excluded_containers_for_pod(pod: &Pod) -> impl Iterator<Item = &str> {
-    let metadata = &pod.metadata;
-    metadata.annotations.iter().flat_map(|annotations| {
-        annotations
-            .iter()
-            .filter_map(|(key, value)| {
-                if key != CONTAINER_EXCLUSION_ANNOTATION_KEY {
-                    return None;
-                }
-                Some(value)
-            })
-            .flat_map(|containers| containers.split(','))
-            .map(|container| container.trim())
-    })
+    extract_annotated_containers_for_pod(pod, CONTAINER_EXCLUSION_ANNOTATION_KEY)
+        .into_iter()
+        .flatten()
 }
 
-fn build_container_exclusion_patterns<'a>(
+/// Returns the containers to collect the logs of, if the `Pod` restricts them.
+fn extract_included_containers_for_pod(pod: &Pod) -> Option<impl Iterator<Item = &str>> {
+    extract_annotated_containers_for_pod(pod, CONTAINER_INCLUSION_ANNOTATION_KEY)
+}
+
+fn extract_annotated_containers_for_pod<'a>(
+    pod: &'a Pod,
+    annotation_key: &str,
+) -> Option<impl Iterator<Item = &'a str>> {
+    let containers = pod.metadata.annotations.as_ref()?.get(annotation_key)?;
+    Some(containers.split(',').map(|container| container.trim()))
+}
+
+fn build_container_patterns<'a>(
     pod_logs_dir: &'a str,
     containers: impl Iterator<Item = &'a str> + 'a,
 ) -> impl Iterator<Item = glob::Pattern> + 'a {
@@ -156,10 +165,20 @@ where
             // and cache the results into a Vec.
             let excluded_containers = extract_excluded_containers_for_pod(pod);
             let exclusion_patterns: Vec<_> =
-                build_container_exclusion_patterns(dir, excluded_containers).collect();
-
-            // Return paths filtered with container exclusion.
-            exclude_paths(path_iter, exclusion_patterns)
+                build_container_patterns(dir, excluded_containers).collect();
+
+            // Same for the containers to include, if the pod restricts them.
+            let inclusion_patterns: Option<Vec<_>> =
+                extract_included_containers_for_pod(pod).map(|included_containers| {
+                    build_container_patterns(dir, included_containers).collect()
+                });
+
+            // Return paths filtered with container exclusion and inclusion.
+            exclude_paths(path_iter, exclusion_patterns).filter(move |path| {
+                inclusion_patterns
+                    .as_ref()
+                    .map_or(true, |patterns| matches_any(path, patterns))
+            })
         })
 }
 
@@ -179,16 +198,18 @@ fn exclude_paths<'a>(
     iter: impl Iterator<Item = PathBuf> + 'a,
     patterns: impl AsRef<[glob::Pattern]> + 'a,
 ) -> impl Iterator<Item = PathBuf> + 'a {
-    iter.filter(move |path| {
-        !patterns.as_ref().iter().any(|pattern| {
-            pattern.matches_path_with(
-                path,
-                glob::MatchOptions {
-                    require_literal_separator: true,
-                    ..Default::default()
-                },
-            )
-        })
+    iter.filter(move |path| !matches_any(path, patterns.as_ref()))
+}
+
+fn matches_any(path: &Path, patterns: &[glob::Pattern]) -> bool {
+    patterns.iter().any(|pattern| {
+        pattern.matches_path_with(
+            path,
+            glob::MatchOptions {
+                require_literal_separator: true,
+                ..Default::default()
+            },
+        )
     })
 }
 
@@ -199,7 +220,7 @@ mod tests {
     use k8s_openapi::{api::core::v1::Pod, apimachinery::pkg::apis::meta::v1::ObjectMeta};
 
     use super::{
-        build_container_exclusion_patterns, exclude_paths, extract_excluded_containers_for_pod,
+        build_container_patterns, exclude_paths, extract_excluded_containers_for_pod,
         extract_pod_logs_directory, list_pod_log_paths,
     };
 
@@ -406,6 +427,46 @@ mod tests {
                     "/var/log/pods/sandbox0-ns_sandbox0-name_sandbox0-uid/container3/qwe.log",
                 ],
             ),
+            // Pod restricts the containers to collect, and some of the
+            // included containers are excluded.
+            (
+                Pod {
+                    metadata: ObjectMeta {
+                        namespace: Some("sandbox0-ns".to_owned()),
+                        name: Some("sandbox0-name".to_owned()),
+                        uid: Some("sandbox0-uid".to_owned()),
+                        annotations: Some(
+                            vec![
+                                (
+                                    super::CONTAINER_INCLUSION_ANNOTATION_KEY.to_owned(),
+                                    "container1, container3,excluded1".to_owned(),
+                                ),
+                                (
+                                    super::CONTAINER_EXCLUSION_ANNOTATION_KEY.to_owned(),
+                                    "excluded1".to_owned(),
+                                ),
+                            ]
+                            .into_iter()
+                            .collect(),
+                        ),
+                        ..ObjectMeta::default()
+                    },
+                    ..Pod::default()
+                },
+                vec![(
+                    "/var/log/pods/sandbox0-ns_sandbox0-name_sandbox0-uid/*/*.log*",
+                    vec![
+                        "/var/log/pods/sandbox0-ns_sandbox0-name_sandbox0-uid/container1/qwe.log",
+                        "/var/log/pods/sandbox0-ns_sandbox0-name_sandbox0-uid/container2/qwe.log",
+                        "/var/log/pods/sandbox0-ns_sandbox0-name_sandbox0-uid/excluded1/qwe.log",
+                        "/var/log/pods/sandbox0-ns_sandbox0-name_sandbox0-uid/container3/qwe.log",
+                    ],
+                )],
+                vec![
+                    "/var/log/pods/sandbox0-ns_sandbox0-name_sandbox0-uid/container1/qwe.log",
+                    "/var/log/pods/sandbox0-ns_sandbox0-name_sandbox0-uid/container3/qwe.log",
+                ],
+            ),
             // Pod doesn't have the metadata set.
             (Pod::default(), vec![], vec![]),
             // Pod has proper metadata, but doesn't have log files.
@@ -519,7 +580,7 @@ mod tests {
     }
 
     #[test]
-    fn test_build_container_exclusion_patterns() {
+    fn test_build_container_patterns() {
         let cases = vec![
             // No excluded containers - no exclusion patterns.
             (
@@ -546,8 +607,7 @@ mod tests {
 
         for (pod_logs_dir, containers, expected_patterns) in cases {
             let actual_patterns: Vec<_> =
-                build_container_exclusion_patterns(pod_logs_dir, containers.clone().into_iter())
-                    .collect();
+                build_container_patterns(pod_logs_dir, containers.clone().into_iter()).collect();
             let expected_patterns: Vec<_> = expected_patterns
                 .into_iter()
                 .map(|pattern| glob::Pattern::new(pattern).unwrap())
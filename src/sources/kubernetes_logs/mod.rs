@@ -17,12 +17,11 @@ use futures_util::Stream;
 use k8s_openapi::api::core::v1::{Namespace, Node, Pod};
 use kube::{
     api::{Api, ListParams},
-    config::{self, KubeConfigOptions},
     runtime::{
         reflector::{self},
         watcher,
     },
-    Client, Config as ClientConfig,
+    Client,
 };
 use vector_common::TimeZone;
 use vector_config::configurable_component;
@@ -40,7 +39,12 @@ use crate::{
         KubernetesLogsEventNodeAnnotationError, KubernetesLogsEventsReceived,
         KubernetesLogsPodInfo, StreamClosedError,
     },
-    kubernetes::custom_reflector,
+    kubernetes::{
+        self, custom_reflector, namespace_metadata_annotator,
+        namespace_metadata_annotator::NamespaceMetadataAnnotator, node_metadata_annotator,
+        node_metadata_annotator::NodeMetadataAnnotator, pod_metadata_annotator,
+        pod_metadata_annotator::PodMetadataAnnotator,
+    },
     shutdown::ShutdownSignal,
     sources,
     transforms::{FunctionTransform, OutputBuffer},
@@ -49,19 +53,14 @@ use crate::{
 
 mod k8s_paths_provider;
 mod lifecycle;
-mod namespace_metadata_annotator;
-mod node_metadata_annotator;
 mod parser;
 mod partial_events_merger;
-mod path_helpers;
-mod pod_metadata_annotator;
+mod pod_annotations;
 mod transform_utils;
 mod util;
 
-use self::namespace_metadata_annotator::NamespaceMetadataAnnotator;
-use self::node_metadata_annotator::NodeMetadataAnnotator;
 use self::parser::Parser;
-use self::pod_metadata_annotator::PodMetadataAnnotator;
+use self::pod_annotations::MultilineRules;
 use futures::{future::FutureExt, stream::StreamExt};
 use k8s_paths_provider::K8sPathsProvider;
 use lifecycle::Lifecycle;
@@ -143,6 +142,13 @@ pub struct Config {
     /// The default time zone for timestamps without an explicit zone.
     timezone: Option<TimeZone>,
 
+    /// A field to set to the routing key of the event, for use by downstream
+    /// components, such as the `route` transform.
+    ///
+    /// The routing key is the `Namespace` of the `Pod`, unless the `Pod` overrides
+    /// it with the `vector.dev/routing-key` annotation.
+    routing_key_field: Option<String>,
+
     /// Optional path to a kubeconfig file readable by Vector. If not set,
     /// Vector will try to connect to Kubernetes using in-cluster configuration.
     kube_config_file: Option<PathBuf>,
@@ -186,6 +192,7 @@ impl Default for Config {
             glob_minimum_cooldown_ms: default_glob_minimum_cooldown_ms(),
             ingestion_timestamp_field: None,
             timezone: None,
+            routing_key_field: None,
             kube_config_file: None,
             delay_deletion_ms: default_delay_deletion_ms(),
         }
@@ -238,6 +245,7 @@ struct Source {
     fingerprint_lines: usize,
     glob_minimum_cooldown: Duration,
     ingestion_timestamp_field: Option<String>,
+    routing_key_field: Option<String>,
     delay_deletion: Duration,
 }
 
@@ -266,20 +274,7 @@ impl Source {
             prepare_label_selector(config.extra_namespace_label_selector.as_ref());
         let node_selector = prepare_node_selector(self_node_name.as_str())?;
 
-        // If the user passed a custom Kubeconfig use it, otherwise
-        // we attempt to load the local kubec-config, followed by the
-        // in-cluster environment variables
-        let client_config = match &config.kube_config_file {
-            Some(kc) => {
-                ClientConfig::from_custom_kubeconfig(
-                    config::Kubeconfig::read_from(kc)?,
-                    &KubeConfigOptions::default(),
-                )
-                .await?
-            }
-            None => ClientConfig::infer().await?,
-        };
-        let client = Client::try_from(client_config)?;
+        let client = kubernetes::client(config.kube_config_file.as_deref()).await?;
 
         let data_dir = globals.resolve_and_make_data_subdir(config.data_dir.as_ref(), key.id())?;
 
@@ -315,6 +310,7 @@ impl Source {
             fingerprint_lines: config.fingerprint_lines,
             glob_minimum_cooldown,
             ingestion_timestamp_field: config.ingestion_timestamp_field.clone(),
+            routing_key_field: config.routing_key_field.clone(),
             delay_deletion,
         })
     }
@@ -342,6 +338,7 @@ impl Source {
             fingerprint_lines,
             glob_minimum_cooldown,
             ingestion_timestamp_field,
+            routing_key_field,
             delay_deletion,
         } = self;
 
@@ -405,6 +402,7 @@ impl Source {
 
         let paths_provider =
             K8sPathsProvider::new(pod_state.clone(), ns_state.clone(), exclude_paths);
+        let multiline_rules = MultilineRules::new(pod_state.clone());
         let annotator = PodMetadataAnnotator::new(pod_state, pod_fields_spec);
         let ns_annotator = NamespaceMetadataAnnotator::new(ns_state, namespace_fields_spec);
         let node_annotator = NodeMetadataAnnotator::new(node_state, node_field_spec);
@@ -515,6 +513,14 @@ impl Source {
                 }
             }
 
+            if let (Some(field), Some(info)) = (routing_key_field.as_deref(), file_info.as_ref()) {
+                let routing_key = annotator
+                    .get(info.pod_namespace, info.pod_name)
+                    .and_then(|pod| pod_annotations::routing_key(&pod).map(str::to_owned))
+                    .unwrap_or_else(|| info.pod_namespace.to_owned());
+                event.as_mut_log().insert(field, routing_key);
+            }
+
             checkpoints.update(line.file_id, line.offset);
             event
         });
@@ -525,7 +531,8 @@ impl Source {
         });
         let (events_count, _) = events.size_hint();
 
-        let mut stream = partial_events_merger.transform(Box::pin(events));
+        let stream = partial_events_merger.transform(Box::pin(events));
+        let mut stream = pod_annotations::aggregate_multiline(stream, multiline_rules);
         let event_processing_loop = out.send_event_stream(&mut stream);
 
         let mut lifecycle = Lifecycle::new();
@@ -0,0 +1,392 @@
+//! Per-`Pod` overrides of how the logs are processed, set with annotations on
+//! the `Pod`.
+//!
+//! The containers to collect the logs of are selected with annotations too,
+//! see the [`k8s_paths_provider`](super::k8s_paths_provider).
+
+#![deny(missing_docs)]
+
+use std::{collections::HashMap, convert::TryFrom, sync::Arc};
+
+use futures::{Stream, StreamExt};
+use k8s_openapi::api::core::v1::Pod;
+use kube::runtime::reflector::{store::Store, ObjectRef};
+use serde::{
+    de::{value::StrDeserializer, IntoDeserializer},
+    Deserialize,
+};
+use snafu::{ResultExt, Snafu};
+
+use super::FILE_KEY;
+use crate::{
+    config::log_schema,
+    event::{Event, Value},
+    internal_events::KubernetesLogsPodAnnotationError,
+    kubernetes::path_helpers::parse_log_file_path,
+    line_agg::{self, LineAgg},
+    sources::util::{multiline_config, MultilineConfig},
+};
+
+/// Regular expression pattern that matches the start of a new message.
+/// Setting it enables the multiline aggregation of the logs of the `Pod`.
+pub const MULTILINE_START_PATTERN_ANNOTATION_KEY: &str = "vector.dev/multiline-start-pattern";
+
+/// Regular expression pattern that determines whether or not more lines
+/// should be read. Defaults to the start pattern.
+pub const MULTILINE_CONDITION_PATTERN_ANNOTATION_KEY: &str =
+    "vector.dev/multiline-condition-pattern";
+
+/// The multiline aggregation mode, as in the `file` source. Defaults to
+/// `halt_before`.
+pub const MULTILINE_MODE_ANNOTATION_KEY: &str = "vector.dev/multiline-mode";
+
+/// The maximum amount of time to wait for the next additional line, in
+/// milliseconds.
+pub const MULTILINE_TIMEOUT_MS_ANNOTATION_KEY: &str = "vector.dev/multiline-timeout-ms";
+
+/// The routing key of the events of the `Pod`, overriding the default one.
+pub const ROUTING_KEY_ANNOTATION_KEY: &str = "vector.dev/routing-key";
+
+const DEFAULT_MULTILINE_TIMEOUT_MS: u64 = 1_000;
+
+#[derive(Debug, Snafu)]
+enum AnnotationError {
+    #[snafu(display("invalid multiline mode {:?}: {}", mode, source))]
+    InvalidMultilineMode {
+        mode: String,
+        source: serde::de::value::Error,
+    },
+    #[snafu(display("invalid multiline timeout {:?}: {}", timeout_ms, source))]
+    InvalidMultilineTimeout {
+        timeout_ms: String,
+        source: std::num::ParseIntError,
+    },
+    #[snafu(display("{}", source))]
+    InvalidMultilinePattern { source: multiline_config::Error },
+}
+
+impl AnnotationError {
+    const fn annotation(&self) -> &'static str {
+        match self {
+            Self::InvalidMultilineMode { .. } => MULTILINE_MODE_ANNOTATION_KEY,
+            Self::InvalidMultilineTimeout { .. } => MULTILINE_TIMEOUT_MS_ANNOTATION_KEY,
+            Self::InvalidMultilinePattern {
+                source: multiline_config::Error::InvalidMultilineStartPattern { .. },
+            } => MULTILINE_START_PATTERN_ANNOTATION_KEY,
+            Self::InvalidMultilinePattern { .. } => MULTILINE_CONDITION_PATTERN_ANNOTATION_KEY,
+        }
+    }
+}
+
+fn annotation<'a>(pod: &'a Pod, key: &str) -> Option<&'a str> {
+    pod.metadata
+        .annotations
+        .as_ref()?
+        .get(key)
+        .map(String::as_str)
+}
+
+/// Returns the routing key the `Pod` overrides the default one with.
+pub fn routing_key(pod: &Pod) -> Option<&str> {
+    annotation(pod, ROUTING_KEY_ANNOTATION_KEY)
+}
+
+/// Builds the multiline aggregation configuration set with the annotations of
+/// the `Pod`, if any.
+fn multiline_config(pod: &Pod) -> Result<Option<line_agg::Config>, AnnotationError> {
+    let start_pattern = match annotation(pod, MULTILINE_START_PATTERN_ANNOTATION_KEY) {
+        Some(start_pattern) => start_pattern,
+        None => return Ok(None),
+    };
+
+    let mode = annotation(pod, MULTILINE_MODE_ANNOTATION_KEY)
+        .map(|mode| {
+            let deserializer: StrDeserializer<'_, serde::de::value::Error> =
+                mode.into_deserializer();
+            line_agg::Mode::deserialize(deserializer).context(InvalidMultilineModeSnafu { mode })
+        })
+        .transpose()?
+        .unwrap_or(line_agg::Mode::HaltBefore);
+    let timeout_ms = annotation(pod, MULTILINE_TIMEOUT_MS_ANNOTATION_KEY)
+        .map(|timeout_ms| {
+            timeout_ms
+                .parse()
+                .context(InvalidMultilineTimeoutSnafu { timeout_ms })
+        })
+        .transpose()?
+        .unwrap_or(DEFAULT_MULTILINE_TIMEOUT_MS);
+
+    let config = MultilineConfig {
+        start_pattern: start_pattern.to_owned(),
+        condition_pattern: annotation(pod, MULTILINE_CONDITION_PATTERN_ANNOTATION_KEY)
+            .unwrap_or(start_pattern)
+            .to_owned(),
+        mode,
+        timeout_ms,
+    };
+    line_agg::Config::try_from(&config)
+        .context(InvalidMultilinePatternSnafu)
+        .map(Some)
+}
+
+/// The multiline aggregation configurations of the `Pod`s.
+pub struct MultilineRules {
+    pods_state_reader: Store<Pod>,
+    /// The configurations by `Pod` uid, along with the `resourceVersion` of the
+    /// `Pod` they were built from, so that they are only rebuilt on changes.
+    configs: HashMap<String, (String, Option<Arc<line_agg::Config>>)>,
+}
+
+impl MultilineRules {
+    /// Create a new [`MultilineRules`].
+    pub fn new(pods_state_reader: Store<Pod>) -> Self {
+        Self {
+            pods_state_reader,
+            configs: HashMap::new(),
+        }
+    }
+
+    /// Returns the multiline aggregation configuration of the `Pod` the log
+    /// `file` belongs to.
+    pub fn get(&mut self, file: &str) -> Option<Arc<line_agg::Config>> {
+        let file_info = parse_log_file_path(file)?;
+        let obj = ObjectRef::<Pod>::new(file_info.pod_name).within(file_info.pod_namespace);
+        let pod = self.pods_state_reader.get(&obj)?;
+        let uid = pod.metadata.uid.as_ref()?;
+        let resource_version = pod.metadata.resource_version.as_deref().unwrap_or_default();
+
+        let is_new = match self.configs.get(uid) {
+            Some((built_from, config)) if built_from == resource_version => return config.clone(),
+            Some(_) => false,
+            None => true,
+        };
+        if is_new {
+            self.forget_deleted_pods();
+        }
+
+        let config = match multiline_config(&pod) {
+            Ok(config) => config.map(Arc::new),
+            Err(error) => {
+                emit!(KubernetesLogsPodAnnotationError {
+                    pod_namespace: file_info.pod_namespace,
+                    pod_name: file_info.pod_name,
+                    annotation: error.annotation(),
+                    error,
+                });
+                None
+            }
+        };
+        self.configs
+            .insert(uid.clone(), (resource_version.to_owned(), config.clone()));
+        config
+    }
+
+    fn forget_deleted_pods(&mut self) {
+        let pods = self.pods_state_reader.state();
+        if self.configs.len() < pods.len() {
+            return;
+        }
+
+        let uids = pods
+            .iter()
+            .filter_map(|pod| pod.metadata.uid.as_deref())
+            .collect::<std::collections::HashSet<_>>();
+        self.configs.retain(|uid, _| uids.contains(uid.as_str()));
+    }
+}
+
+/// Aggregates the multiline messages of the `Pod`s that enable it with
+/// annotations, passing the other events through as is.
+pub fn aggregate_multiline<S>(
+    events: S,
+    mut rules: MultilineRules,
+) -> impl Stream<Item = Event> + Send + Unpin
+where
+    S: Stream<Item = Event> + Send + Unpin + 'static,
+{
+    let message_key = log_schema().message_key();
+    let logic = line_agg::Logic::per_key(move |file: &String, _: &Event| rules.get(file));
+    let lines = events.map(move |event| {
+        let log = event.as_log();
+        let file = log
+            .get(FILE_KEY)
+            .map(Value::to_string_lossy)
+            .unwrap_or_default();
+        let message = log
+            .get(message_key)
+            .and_then(Value::as_bytes)
+            .cloned()
+            .unwrap_or_default();
+        (file, message, event)
+    });
+
+    LineAgg::new(lines, logic).map(move |(_, message, mut event)| {
+        let log = event.as_mut_log();
+        if log.contains(message_key) {
+            log.insert(message_key, message);
+        }
+        event
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+    use kube::runtime::{reflector::store::Writer, watcher};
+
+    use super::*;
+    use crate::event::LogEvent;
+
+    fn pod(name: &str, annotations: &[(&str, &str)]) -> Pod {
+        Pod {
+            metadata: ObjectMeta {
+                namespace: Some("sandbox0-ns".to_owned()),
+                name: Some(name.to_owned()),
+                uid: Some(format!("{}-uid", name)),
+                resource_version: Some("1".to_owned()),
+                annotations: Some(
+                    annotations
+                        .iter()
+                        .map(|(key, value)| (key.to_string(), value.to_string()))
+                        .collect(),
+                ),
+                ..ObjectMeta::default()
+            },
+            ..Pod::default()
+        }
+    }
+
+    fn file(pod_name: &str) -> String {
+        format!(
+            "/var/log/pods/sandbox0-ns_{}_{}-uid/container/0.log",
+            pod_name, pod_name
+        )
+    }
+
+    #[test]
+    fn builds_multiline_config_from_annotations() {
+        assert!(multiline_config(&pod("sandbox0-name", &[]))
+            .unwrap()
+            .is_none());
+
+        let config = multiline_config(&pod(
+            "sandbox0-name",
+            &[(MULTILINE_START_PATTERN_ANNOTATION_KEY, "^[^\\s]")],
+        ))
+        .unwrap()
+        .unwrap();
+        assert_eq!(config.condition_pattern.as_str(), "^[^\\s]");
+        assert_eq!(config.mode, line_agg::Mode::HaltBefore);
+        assert_eq!(config.timeout, Duration::from_millis(1_000));
+
+        let config = multiline_config(&pod(
+            "sandbox0-name",
+            &[
+                (MULTILINE_START_PATTERN_ANNOTATION_KEY, "^[^\\s]"),
+                (MULTILINE_CONDITION_PATTERN_ANNOTATION_KEY, "^\\s+"),
+                (MULTILINE_MODE_ANNOTATION_KEY, "continue_through"),
+                (MULTILINE_TIMEOUT_MS_ANNOTATION_KEY, "500"),
+            ],
+        ))
+        .unwrap()
+        .unwrap();
+        assert_eq!(config.condition_pattern.as_str(), "^\\s+");
+        assert_eq!(config.mode, line_agg::Mode::ContinueThrough);
+        assert_eq!(config.timeout, Duration::from_millis(500));
+    }
+
+    #[test]
+    fn rejects_invalid_multiline_annotations() {
+        for (annotations, annotation) in [
+            (
+                vec![(MULTILINE_START_PATTERN_ANNOTATION_KEY, "(")],
+                MULTILINE_START_PATTERN_ANNOTATION_KEY,
+            ),
+            (
+                vec![
+                    (MULTILINE_START_PATTERN_ANNOTATION_KEY, "^[^\\s]"),
+                    (MULTILINE_CONDITION_PATTERN_ANNOTATION_KEY, "("),
+                ],
+                MULTILINE_CONDITION_PATTERN_ANNOTATION_KEY,
+            ),
+            (
+                vec![
+                    (MULTILINE_START_PATTERN_ANNOTATION_KEY, "^[^\\s]"),
+                    (MULTILINE_MODE_ANNOTATION_KEY, "sideways"),
+                ],
+                MULTILINE_MODE_ANNOTATION_KEY,
+            ),
+            (
+                vec![
+                    (MULTILINE_START_PATTERN_ANNOTATION_KEY, "^[^\\s]"),
+                    (MULTILINE_TIMEOUT_MS_ANNOTATION_KEY, "soon"),
+                ],
+                MULTILINE_TIMEOUT_MS_ANNOTATION_KEY,
+            ),
+        ] {
+            let error = multiline_config(&pod("sandbox0-name", &annotations)).unwrap_err();
+            assert_eq!(error.annotation(), annotation);
+        }
+    }
+
+    #[test]
+    fn reads_routing_key() {
+        assert_eq!(routing_key(&pod("sandbox0-name", &[])), None);
+        assert_eq!(
+            routing_key(&pod(
+                "sandbox0-name",
+                &[(ROUTING_KEY_ANNOTATION_KEY, "team-a")]
+            )),
+            Some("team-a")
+        );
+    }
+
+    #[tokio::test]
+    async fn aggregates_multiline_per_pod() {
+        let mut pods = Writer::<Pod>::default();
+        pods.apply_watcher_event(&watcher::Event::Applied(pod(
+            "multiline",
+            &[
+                (MULTILINE_START_PATTERN_ANNOTATION_KEY, "^[^\\s]"),
+                (MULTILINE_CONDITION_PATTERN_ANNOTATION_KEY, "^\\s+"),
+                (MULTILINE_MODE_ANNOTATION_KEY, "continue_through"),
+            ],
+        )));
+        pods.apply_watcher_event(&watcher::Event::Applied(pod("plain", &[])));
+
+        let events = [
+            ("multiline", "Exception in thread \"main\""),
+            ("plain", "first line"),
+            ("multiline", "    at Main.main(Main.java:1)"),
+            ("plain", "    second line"),
+            ("multiline", "done"),
+        ]
+        .into_iter()
+        .map(|(pod_name, message)| {
+            let mut log = LogEvent::from(message);
+            log.insert(FILE_KEY, file(pod_name));
+            Event::from(log)
+        })
+        .collect::<Vec<_>>();
+
+        let messages = aggregate_multiline(
+            futures::stream::iter(events),
+            MultilineRules::new(pods.as_reader()),
+        )
+        .map(|event| event.as_log()[log_schema().message_key()].to_string_lossy())
+        .collect::<Vec<_>>()
+        .await;
+
+        assert_eq!(
+            messages,
+            vec![
+                "first line",
+                "    second line",
+                "Exception in thread \"main\"\n    at Main.main(Main.java:1)",
+                "done",
+            ]
+        );
+    }
+}
@@ -47,6 +47,8 @@ pub mod internal_metrics;
 pub mod journald;
 #[cfg(feature = "sources-kafka")]
 pub mod kafka;
+#[cfg(feature = "sources-kubernetes_events")]
+pub mod kubernetes_events;
 #[cfg(feature = "sources-kubernetes_logs")]
 pub mod kubernetes_logs;
 #[cfg(all(feature = "sources-logstash"))]
@@ -187,6 +189,10 @@ pub enum Sources {
     #[cfg(feature = "sources-kafka")]
     Kafka(#[configurable(derived)] kafka::KafkaSourceConfig),
 
+    /// Kubernetes Events.
+    #[cfg(feature = "sources-kubernetes_events")]
+    KubernetesEvents(#[configurable(derived)] kubernetes_events::Config),
+
     /// Kubernetes Logs.
     #[cfg(feature = "sources-kubernetes_logs")]
     KubernetesLogs(#[configurable(derived)] kubernetes_logs::Config),
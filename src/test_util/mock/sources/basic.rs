@@ -1,11 +1,17 @@
-use std::sync::{
-    atomic::{AtomicUsize, Ordering},
-    Arc, Mutex,
+use std::{
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
 };
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use vector_buffers::topology::channel::{limited, LimitedReceiver};
+use vector_buffers::{
+    topology::channel::{limited, LimitedReceiver},
+    MemoryBufferSize,
+};
 use vector_core::config::LogNamespace;
 use vector_core::{
     config::{DataType, Output},
@@ -33,7 +39,9 @@ pub struct BasicSourceConfig {
 
 impl Default for BasicSourceConfig {
     fn default() -> Self {
-        let (_, receiver) = limited(1000);
+        let (_, receiver) = limited(MemoryBufferSize::MaxEvents(
+            NonZeroUsize::new(1000).unwrap(),
+        ));
         Self {
             receiver: Arc::new(Mutex::new(Some(receiver))),
             event_counter: None,
@@ -27,15 +27,13 @@ use {crate::tcp, openssl::x509::X509, std::collections::HashMap};
 
 impl TlsSettings {
     pub(crate) fn acceptor(&self) -> crate::tls::Result<SslAcceptor> {
-        match self.identity {
-            None => Err(TlsError::MissingRequiredIdentity),
-            Some(_) => {
-                let mut acceptor = SslAcceptor::mozilla_intermediate(SslMethod::tls())
-                    .context(CreateAcceptorSnafu)?;
-                self.apply_context(&mut acceptor)?;
-                Ok(acceptor.build())
-            }
+        if !self.has_identity() {
+            return Err(TlsError::MissingRequiredIdentity);
         }
+        let mut acceptor =
+            SslAcceptor::mozilla_intermediate(SslMethod::tls()).context(CreateAcceptorSnafu)?;
+        self.apply_acceptor_context(&mut acceptor)?;
+        Ok(acceptor.build())
     }
 }
 
@@ -44,7 +42,11 @@ impl MaybeTlsSettings {
         let listener = TcpListener::bind(addr).await.context(TcpBindSnafu)?;
 
         let acceptor = match self {
-            Self::Tls(tls) => Some(tls.acceptor()?),
+            Self::Tls(tls) => Some(ReloadingAcceptor {
+                generation: tls.generation(),
+                acceptor: tls.acceptor()?,
+                tls: tls.clone(),
+            }),
             Self::Raw(()) => None,
         };
 
@@ -52,20 +54,48 @@ impl MaybeTlsSettings {
     }
 }
 
+/// An acceptor that is rebuilt whenever the certificates of its settings are reloaded.
+struct ReloadingAcceptor {
+    tls: TlsSettings,
+    generation: u64,
+    acceptor: SslAcceptor,
+}
+
+impl ReloadingAcceptor {
+    /// Returns the acceptor for the latest certificates.
+    ///
+    /// Connections accepted earlier keep using the acceptor they were handshaked with.
+    fn current(&mut self) -> SslAcceptor {
+        let generation = self.tls.generation();
+        if generation != self.generation {
+            match self.tls.acceptor() {
+                Ok(acceptor) => {
+                    self.acceptor = acceptor;
+                    self.generation = generation;
+                }
+                Err(error) => {
+                    error!(message = "Failed to apply reloaded TLS certificates.", %error);
+                }
+            }
+        }
+        self.acceptor.clone()
+    }
+}
+
 pub struct MaybeTlsListener {
     listener: TcpListener,
-    acceptor: Option<SslAcceptor>,
+    acceptor: Option<ReloadingAcceptor>,
 }
 
 impl MaybeTlsListener {
     pub(crate) async fn accept(&mut self) -> crate::tls::Result<MaybeTlsIncomingStream<TcpStream>> {
-        self.listener
+        let (stream, peer_addr) = self
+            .listener
             .accept()
             .await
-            .map(|(stream, peer_addr)| {
-                MaybeTlsIncomingStream::new(stream, peer_addr, self.acceptor.clone())
-            })
-            .context(IncomingListenerSnafu)
+            .context(IncomingListenerSnafu)?;
+        let acceptor = self.acceptor.as_mut().map(ReloadingAcceptor::current);
+        Ok(MaybeTlsIncomingStream::new(stream, peer_addr, acceptor))
     }
 
     async fn into_accept(
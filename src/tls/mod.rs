@@ -22,7 +22,7 @@ pub(crate) use maybe_tls::MaybeTls;
 #[cfg(all(test, feature = "kafka-integration-tests"))]
 pub use settings::TEST_PEM_INTERMEDIATE_CA_PATH;
 pub use settings::{
-    MaybeTlsSettings, TlsConfig, TlsEnableableConfig, TlsSettings, TlsSourceConfig,
+    MaybeTlsSettings, TlsConfig, TlsEnableableConfig, TlsSettings, TlsSourceConfig, TlsVersion,
 };
 #[cfg(test)]
 pub use settings::{TEST_PEM_CA_PATH, TEST_PEM_CRT_PATH, TEST_PEM_KEY_PATH};
@@ -122,6 +122,22 @@ pub enum TlsError {
     #[cfg(any(windows, target_os = "macos"))]
     #[snafu(display("Unable to parse X509 from system cert: {}", source))]
     X509SystemParseError { source: ErrorStack },
+    #[snafu(display("Invalid TLS cipher suites {:?}: {}", ciphersuites, source))]
+    InvalidCipherSuites {
+        ciphersuites: String,
+        source: ErrorStack,
+    },
+    #[snafu(display("Could not set the minimum TLS version: {}", source))]
+    SetMinTlsVersion { source: ErrorStack },
+    #[snafu(display(
+        "Invalid ALPN protocol {:?}, must be between 1 and 255 bytes long",
+        protocol
+    ))]
+    InvalidAlpnProtocol { protocol: String },
+    #[snafu(display("Could not set the ALPN protocols: {}", source))]
+    SetAlpnProtocols { source: ErrorStack },
+    #[snafu(display("Invalid TLS server name {:?}, must be a DNS host name", server_name))]
+    InvalidServerName { server_name: String },
     #[snafu(display("Creating an empty CA stack failed"))]
     NewCaStack { source: ErrorStack },
     #[snafu(display("Could not push intermediate certificate onto stack"))]
@@ -173,20 +189,20 @@ impl MaybeTlsStream<TcpStream> {
 pub(crate) fn tls_connector_builder(settings: &MaybeTlsSettings) -> Result<SslConnectorBuilder> {
     let mut builder = SslConnector::builder(SslMethod::tls()).context(TlsBuildConnectorSnafu)?;
     if let Some(settings) = settings.tls() {
-        settings.apply_context(&mut builder)?;
+        settings.apply_connector_context(&mut builder)?;
     }
     Ok(builder)
 }
 
 fn tls_connector(settings: &MaybeTlsSettings) -> Result<ConnectConfiguration> {
-    let verify_hostname = settings
-        .tls()
-        .map(|settings| settings.verify_hostname)
-        .unwrap_or(true);
-    let configure = tls_connector_builder(settings)?
+    let mut configure = tls_connector_builder(settings)?
         .build()
         .configure()
-        .context(TlsBuildConnectorSnafu)?
-        .verify_hostname(verify_hostname);
+        .context(TlsBuildConnectorSnafu)?;
+    if let Some(settings) = settings.tls() {
+        settings
+            .apply_connect_configuration(&mut configure)
+            .context(TlsBuildConnectorSnafu)?;
+    }
     Ok(configure)
 }
@@ -3,23 +3,36 @@ use std::{
     fs::File,
     io::Read,
     path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, RwLock, RwLockReadGuard,
+    },
 };
 
 use openssl::{
+    error::ErrorStack,
     pkcs12::{ParsedPkcs12, Pkcs12},
     pkey::{PKey, Private},
-    ssl::{ConnectConfiguration, SslContextBuilder, SslVerifyMode},
+    ssl::{
+        select_next_proto, AlpnError, ConnectConfiguration, SslConnector, SslContextBuilder,
+        SslMethod, SslVerifyMode, SslVersion,
+    },
     stack::Stack,
-    x509::{store::X509StoreBuilder, X509},
+    x509::{
+        store::X509StoreBuilder,
+        verify::{X509CheckFlags, X509VerifyParamRef},
+        X509,
+    },
 };
 use snafu::ResultExt;
 use vector_config::configurable_component;
 
 use super::{
-    AddCertToStoreSnafu, AddExtraChainCertSnafu, CaStackPushSnafu, DerExportSnafu,
-    FileOpenFailedSnafu, FileReadFailedSnafu, MaybeTls, NewCaStackSnafu, NewStoreBuilderSnafu,
-    ParsePkcs12Snafu, Pkcs12Snafu, PrivateKeyParseSnafu, Result, SetCertificateSnafu,
-    SetPrivateKeySnafu, SetVerifyCertSnafu, TlsError, TlsIdentitySnafu, X509ParseSnafu,
+    AddCertToStoreSnafu, AddExtraChainCertSnafu, CaStackPushSnafu, CreateAcceptorSnafu,
+    DerExportSnafu, FileOpenFailedSnafu, FileReadFailedSnafu, InvalidCipherSuitesSnafu, MaybeTls,
+    NewCaStackSnafu, NewStoreBuilderSnafu, ParsePkcs12Snafu, Pkcs12Snafu, PrivateKeyParseSnafu,
+    Result, SetAlpnProtocolsSnafu, SetCertificateSnafu, SetMinTlsVersionSnafu, SetPrivateKeySnafu,
+    SetVerifyCertSnafu, TlsBuildConnectorSnafu, TlsError, TlsIdentitySnafu, X509ParseSnafu,
 };
 
 const PEM_START_MARKER: &str = "-----BEGIN ";
@@ -82,6 +95,11 @@ pub struct TlsSourceConfig {
 }
 
 /// Standard TLS options.
+///
+/// Changes to the certificate and key files are picked up without a configuration reload: new
+/// connections use the updated files shortly after they change, while established connections are
+/// left untouched. This relies on the same file watching as `--watch-config`, so it isn't
+/// supported on Windows.
 #[configurable_component]
 #[derive(Clone, Debug, Default)]
 #[serde(deny_unknown_fields)]
@@ -133,6 +151,66 @@ pub struct TlsConfig {
     ///
     /// This has no effect unless `key_file` is set.
     pub key_pass: Option<String>,
+
+    /// The minimum TLS version to allow for connections.
+    ///
+    /// If not set, the minimum version supported by the underlying TLS library is used.
+    pub min_tls_version: Option<TlsVersion>,
+
+    /// The cipher suites to allow for connections, as a colon-separated list in OpenSSL format.
+    ///
+    /// TLS 1.3 cipher suites, such as `TLS_AES_256_GCM_SHA384`, and ciphers for TLS 1.2 and earlier,
+    /// such as `ECDHE-RSA-AES256-GCM-SHA384`, can be mixed in the same list. If only one kind is
+    /// listed, the defaults are kept for the other protocol versions.
+    pub ciphersuites: Option<String>,
+
+    /// The application protocols to negotiate using ALPN, in order of preference.
+    ///
+    /// For outgoing connections, these are offered to the server. For incoming connections, the
+    /// first of these protocols that is also offered by the client is selected, and connections from
+    /// clients that offer none of them are rejected.
+    pub alpn_protocols: Option<Vec<String>>,
+
+    /// The server name to use for outgoing connections, instead of the host being connected to.
+    ///
+    /// The name is sent using Server Name Indication (SNI), and is the one checked against the
+    /// certificate of the remote host when `verify_hostname` is enabled. This is useful when
+    /// connecting to a host by IP address.
+    ///
+    /// Only relevant for outgoing connections.
+    pub server_name: Option<String>,
+}
+
+/// A TLS protocol version.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TlsVersion {
+    /// TLS 1.0.
+    #[serde(rename = "1.0")]
+    V1_0,
+
+    /// TLS 1.1.
+    #[serde(rename = "1.1")]
+    V1_1,
+
+    /// TLS 1.2.
+    #[serde(rename = "1.2")]
+    V1_2,
+
+    /// TLS 1.3.
+    #[serde(rename = "1.3")]
+    V1_3,
+}
+
+impl From<TlsVersion> for SslVersion {
+    fn from(version: TlsVersion) -> Self {
+        match version {
+            TlsVersion::V1_0 => SslVersion::TLS1,
+            TlsVersion::V1_1 => SslVersion::TLS1_1,
+            TlsVersion::V1_2 => SslVersion::TLS1_2,
+            TlsVersion::V1_3 => SslVersion::TLS1_3,
+        }
+    }
 }
 
 impl TlsConfig {
@@ -152,13 +230,146 @@ impl TlsConfig {
 pub struct TlsSettings {
     verify_certificate: bool,
     pub(super) verify_hostname: bool,
+    min_tls_version: Option<TlsVersion>,
+    ciphers: Ciphers,
+    /// The ALPN protocols, in wire format.
+    alpn_protocols: Option<Vec<u8>>,
+    server_name: Option<String>,
+    // Shared between clones, so that reloaded certificates are seen by every user of the settings.
+    material: Arc<RwLock<TlsMaterial>>,
+    watcher: Option<Arc<CertificateWatcher>>,
+}
+
+/// The certificates loaded from the files of a `TlsConfig`.
+#[derive(Clone, Default)]
+struct TlsMaterial {
     authorities: Vec<X509>,
-    pub(super) identity: Option<IdentityStore>, // openssl::pkcs12::ParsedPkcs12 doesn't impl Clone yet
+    identity: Option<IdentityStore>, // openssl::pkcs12::ParsedPkcs12 doesn't impl Clone yet
 }
 
 #[derive(Clone)]
 pub struct IdentityStore(Vec<u8>, String);
 
+/// The `ciphersuites` option, split by the protocol versions it applies to, as OpenSSL configures
+/// them separately.
+#[derive(Clone, Debug, Default)]
+struct Ciphers {
+    /// Ciphers for TLS 1.2 and earlier.
+    cipher_list: Option<String>,
+    /// Cipher suites for TLS 1.3.
+    ciphersuites: Option<String>,
+}
+
+impl Ciphers {
+    fn new(ciphersuites: &str) -> Self {
+        let (tls13, legacy): (Vec<_>, Vec<_>) = ciphersuites
+            .split(':')
+            .map(str::trim)
+            .filter(|cipher| !cipher.is_empty())
+            .partition(|cipher| cipher.starts_with("TLS_"));
+        let join = |ciphers: Vec<&str>| (!ciphers.is_empty()).then(|| ciphers.join(":"));
+        Self {
+            cipher_list: join(legacy),
+            ciphersuites: join(tls13),
+        }
+    }
+
+    fn apply(&self, context: &mut SslContextBuilder) -> std::result::Result<(), ErrorStack> {
+        if let Some(cipher_list) = &self.cipher_list {
+            context.set_cipher_list(cipher_list)?;
+        }
+        if let Some(ciphersuites) = &self.ciphersuites {
+            context.set_ciphersuites(ciphersuites)?;
+        }
+        Ok(())
+    }
+}
+
+/// Reloads the certificates of a `TlsConfig` when their files change.
+///
+/// The files are watched with the same file watcher used for the configuration files, and are
+/// reloaded on its thread, so that new handshakes pick up rotated certificates while established
+/// connections are left alone.
+struct CertificateWatcher {
+    options: TlsConfig,
+    files: Vec<PathBuf>,
+    /// Incremented every time the certificates are reloaded.
+    generation: AtomicU64,
+    /// The client context built from the latest certificates, for connectors that can only swap
+    /// contexts per connection.
+    connector: Mutex<Option<(u64, SslConnector)>>,
+}
+
+impl CertificateWatcher {
+    /// Starts watching the certificate files of `options`, if there are any, reloading them into
+    /// `material` when they change.
+    ///
+    /// The watch stops with the first change after every copy of the settings has been dropped.
+    fn spawn(options: &TlsConfig, material: &Arc<RwLock<TlsMaterial>>) -> Option<Arc<Self>> {
+        // Inline certificates can't change, so there is nothing to watch for them.
+        let files = [&options.ca_file, &options.crt_file, &options.key_file]
+            .into_iter()
+            .flatten()
+            .filter(|path| {
+                path.to_str()
+                    .map_or(true, |path| !path.contains(PEM_START_MARKER))
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        if files.is_empty() {
+            return None;
+        }
+
+        let watcher = Arc::new(Self {
+            options: options.clone(),
+            files: files.clone(),
+            generation: AtomicU64::new(0),
+            connector: Mutex::new(None),
+        });
+
+        let weak_watcher = Arc::downgrade(&watcher);
+        let weak_material = Arc::downgrade(material);
+        if let Err(error) = crate::config::watcher::spawn_watcher(files, None, move || {
+            match (weak_watcher.upgrade(), weak_material.upgrade()) {
+                (Some(watcher), Some(material)) => {
+                    watcher.reload(&material);
+                    true
+                }
+                _ => false,
+            }
+        }) {
+            warn!(
+                message = "Unable to watch TLS certificates, changes to them will not be picked up.",
+                %error,
+                files = ?watcher.files,
+            );
+        }
+
+        Some(watcher)
+    }
+
+    /// Reloads the certificates into `material`.
+    ///
+    /// If the new certificates fail to load, for instance because their files are only partially
+    /// written, the previous ones are kept until the files change again.
+    fn reload(&self, material: &RwLock<TlsMaterial>) {
+        match self.options.load_material() {
+            Ok(loaded) => {
+                *material.write().expect("TLS material lock poisoned") = loaded;
+                self.generation.fetch_add(1, Ordering::AcqRel);
+                info!(message = "Reloaded TLS certificates.", files = ?self.files);
+            }
+            Err(error) => {
+                error!(
+                    message = "Failed to reload TLS certificates, keeping the previous ones.",
+                    %error,
+                    files = ?self.files,
+                );
+            }
+        }
+    }
+}
+
 impl TlsSettings {
     /// Generate a filled out settings struct from the given optional
     /// option set, interpreted as client options. If `options` is
@@ -182,20 +393,50 @@ impl TlsSettings {
             }
         }
 
+        let ciphers = options.ciphers()?;
+        let alpn_protocols = options.alpn_wire_format()?;
+        if let Some(server_name) = &options.server_name {
+            if !is_valid_server_name(server_name) {
+                return Err(TlsError::InvalidServerName {
+                    server_name: server_name.clone(),
+                });
+            }
+        }
+
+        let material = Arc::new(RwLock::new(options.load_material()?));
         Ok(Self {
             verify_certificate: options.verify_certificate.unwrap_or(!for_server),
             verify_hostname: options.verify_hostname.unwrap_or(!for_server),
-            authorities: options.load_authorities()?,
-            identity: options.load_identity()?,
+            min_tls_version: options.min_tls_version,
+            ciphers,
+            alpn_protocols,
+            server_name: options.server_name.clone(),
+            watcher: CertificateWatcher::spawn(options, &material),
+            material,
         })
     }
 
+    fn material(&self) -> RwLockReadGuard<'_, TlsMaterial> {
+        self.material.read().expect("TLS material lock poisoned")
+    }
+
+    pub(super) fn has_identity(&self) -> bool {
+        self.material().identity.is_some()
+    }
+
+    /// The number of times the certificates have been reloaded since these settings were built.
+    pub(super) fn generation(&self) -> u64 {
+        self.watcher
+            .as_ref()
+            .map_or(0, |watcher| watcher.generation.load(Ordering::Acquire))
+    }
+
     fn identity(&self) -> Option<ParsedPkcs12> {
         // This data was test-built previously, so we can just use it
         // here and expect the results will not fail. This can all be
         // reworked when `openssl::pkcs12::ParsedPkcs12` gains the Clone
         // impl.
-        self.identity.as_ref().map(|identity| {
+        self.material().identity.as_ref().map(|identity| {
             Pkcs12::from_der(&identity.0)
                 .expect("Could not build PKCS#12 archive from parsed data")
                 .parse(&identity.1)
@@ -225,20 +466,59 @@ impl TlsSettings {
     }
 
     #[cfg(feature = "sources-gcp_pubsub")]
-    pub fn authorities_pem(&self) -> impl Iterator<Item = Vec<u8>> + '_ {
-        self.authorities.iter().map(|authority| {
-            authority
-                .to_pem()
-                .expect("Invalid stored authority certificate")
-        })
+    pub fn authorities_pem(&self) -> impl Iterator<Item = Vec<u8>> {
+        self.material()
+            .authorities
+            .iter()
+            .map(|authority| {
+                authority
+                    .to_pem()
+                    .expect("Invalid stored authority certificate")
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    /// Configures a context for connecting to a server, offering the ALPN protocols.
+    pub(super) fn apply_connector_context(&self, context: &mut SslContextBuilder) -> Result<()> {
+        self.apply_context(context)?;
+        // The cipher suites were validated when the settings were built.
+        self.ciphers
+            .apply(context)
+            .context(TlsBuildConnectorSnafu)?;
+        if let Some(protocols) = &self.alpn_protocols {
+            context
+                .set_alpn_protos(protocols)
+                .context(SetAlpnProtocolsSnafu)?;
+        }
+        Ok(())
+    }
+
+    /// Configures a context for accepting connections, selecting one of the ALPN protocols offered
+    /// by the client.
+    pub(super) fn apply_acceptor_context(&self, context: &mut SslContextBuilder) -> Result<()> {
+        self.apply_context(context)?;
+        self.ciphers.apply(context).context(CreateAcceptorSnafu)?;
+        if let Some(protocols) = &self.alpn_protocols {
+            let server_protocols = protocols.clone();
+            context.set_alpn_select_callback(move |_, client_protocols| {
+                select_next_proto(&server_protocols, client_protocols).ok_or(AlpnError::ALERT_FATAL)
+            });
+        }
+        Ok(())
     }
 
-    pub(super) fn apply_context(&self, context: &mut SslContextBuilder) -> Result<()> {
+    fn apply_context(&self, context: &mut SslContextBuilder) -> Result<()> {
         context.set_verify(if self.verify_certificate {
             SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT
         } else {
             SslVerifyMode::NONE
         });
+        if let Some(version) = self.min_tls_version {
+            context
+                .set_min_proto_version(Some(version.into()))
+                .context(SetMinTlsVersionSnafu)?;
+        }
         if let Some(identity) = self.identity() {
             context
                 .set_certificate(&identity.cert)
@@ -254,12 +534,11 @@ impl TlsSettings {
                 }
             }
         }
-        if !self.authorities.is_empty() {
+        let authorities = self.material().authorities.clone();
+        if !authorities.is_empty() {
             let mut store = X509StoreBuilder::new().context(NewStoreBuilderSnafu)?;
-            for authority in &self.authorities {
-                store
-                    .add_cert(authority.clone())
-                    .context(AddCertToStoreSnafu)?;
+            for authority in authorities {
+                store.add_cert(authority).context(AddCertToStoreSnafu)?;
             }
             context
                 .set_verify_cert_store(store.build())
@@ -277,12 +556,117 @@ impl TlsSettings {
         Ok(())
     }
 
-    pub fn apply_connect_configuration(&self, connection: &mut ConnectConfiguration) {
+    pub fn apply_connect_configuration(
+        &self,
+        connection: &mut ConnectConfiguration,
+    ) -> std::result::Result<(), ErrorStack> {
         connection.set_verify_hostname(self.verify_hostname);
+
+        // Connectors such as the HTTP client build their TLS context once, so once the certificates
+        // have been reloaded, each new connection is switched over to a context built from them.
+        if let Some(watcher) = &self.watcher {
+            let generation = self.generation();
+            if generation > 0 {
+                match self.reloaded_connector(watcher, generation) {
+                    Ok(connector) => {
+                        if let Err(error) = connection.set_ssl_context(connector.context()) {
+                            error!(message = "Failed to apply reloaded TLS certificates.", %error);
+                        }
+                    }
+                    Err(error) => {
+                        error!(message = "Failed to apply reloaded TLS certificates.", %error);
+                    }
+                }
+            }
+        }
+
+        if let Some(server_name) = &self.server_name {
+            // Prevent the host being connected to from being used for SNI and hostname
+            // verification, and use the configured server name for both instead.
+            connection.set_use_server_name_indication(false);
+            connection.set_verify_hostname(false);
+            connection.set_hostname(server_name)?;
+            if self.verify_hostname {
+                verify_host(connection.param_mut(), server_name)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn reloaded_connector(
+        &self,
+        watcher: &CertificateWatcher,
+        generation: u64,
+    ) -> Result<SslConnector> {
+        let mut cached = watcher
+            .connector
+            .lock()
+            .expect("TLS connector lock poisoned");
+        match &*cached {
+            Some((built, connector)) if *built == generation => Ok(connector.clone()),
+            _ => {
+                let mut builder =
+                    SslConnector::builder(SslMethod::tls()).context(TlsBuildConnectorSnafu)?;
+                self.apply_connector_context(&mut builder)?;
+                let connector = builder.build();
+                // The certificates may have been reloaded again while the connector was being
+                // built, in which case it's only used for this connection, so that the next one
+                // builds a connector from the latest certificates.
+                if self.generation() == generation {
+                    *cached = Some((generation, connector.clone()));
+                }
+                Ok(connector)
+            }
+        }
     }
 }
 
 impl TlsConfig {
+    fn ciphers(&self) -> Result<Ciphers> {
+        let ciphersuites = match &self.ciphersuites {
+            Some(ciphersuites) => ciphersuites,
+            None => return Ok(Ciphers::default()),
+        };
+        let ciphers = Ciphers::new(ciphersuites);
+
+        // Check that OpenSSL accepts the ciphers, so that invalid ones are reported when the
+        // configuration is loaded, rather than on the first connection.
+        SslContextBuilder::new(SslMethod::tls())
+            .and_then(|mut context| ciphers.apply(&mut context))
+            .with_context(|_| InvalidCipherSuitesSnafu {
+                ciphersuites: ciphersuites.clone(),
+            })?;
+
+        Ok(ciphers)
+    }
+
+    fn alpn_wire_format(&self) -> Result<Option<Vec<u8>>> {
+        self.alpn_protocols
+            .as_ref()
+            .map(|protocols| {
+                let mut wire = Vec::new();
+                for protocol in protocols {
+                    if protocol.is_empty() || protocol.len() > 255 {
+                        return Err(TlsError::InvalidAlpnProtocol {
+                            protocol: protocol.clone(),
+                        });
+                    }
+                    wire.push(protocol.len() as u8);
+                    wire.extend_from_slice(protocol.as_bytes());
+                }
+                Ok(wire)
+            })
+            .transpose()
+    }
+
+    fn load_material(&self) -> Result<TlsMaterial> {
+        Ok(TlsMaterial {
+            authorities: self.load_authorities()?,
+            identity: self.load_identity()?,
+        })
+    }
+
     fn load_authorities(&self) -> Result<Vec<X509>> {
         match &self.ca_file {
             None => Ok(vec![]),
@@ -451,6 +835,9 @@ impl fmt::Debug for TlsSettings {
         f.debug_struct("TlsSettings")
             .field("verify_certificate", &self.verify_certificate)
             .field("verify_hostname", &self.verify_hostname)
+            .field("min_tls_version", &self.min_tls_version)
+            .field("ciphers", &self.ciphers)
+            .field("server_name", &self.server_name)
             .finish()
     }
 }
@@ -480,10 +867,11 @@ impl MaybeTlsSettings {
                 if config.enabled.unwrap_or(false) {
                     let tls =
                         TlsSettings::from_options_base(&Some(config.options.clone()), for_server)?;
-                    match (for_server, &tls.identity) {
-                        // Servers require an identity certificate
-                        (true, None) => Err(TlsError::MissingRequiredIdentity),
-                        _ => Ok(Self::Tls(tls)),
+                    // Servers require an identity certificate
+                    if for_server && !tls.has_identity() {
+                        Err(TlsError::MissingRequiredIdentity)
+                    } else {
+                        Ok(Self::Tls(tls))
                     }
                 } else {
                     Ok(Self::Raw(())) // Explicitly disabled, still no TLS settings
@@ -540,6 +928,30 @@ fn der_or_pem<T>(data: Vec<u8>, der_fn: impl Fn(Vec<u8>) -> T, pem_fn: impl Fn(S
     }
 }
 
+/// Checks that a server name is a DNS host name, as IP addresses can't be used for SNI.
+fn is_valid_server_name(server_name: &str) -> bool {
+    !server_name.is_empty()
+        && server_name.len() <= 253
+        && server_name.parse::<std::net::IpAddr>().is_err()
+        && server_name.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && label
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        })
+}
+
+/// Sets up the verification of a host name, the same way OpenSSL does for the host being connected
+/// to.
+fn verify_host(
+    param: &mut X509VerifyParamRef,
+    server_name: &str,
+) -> std::result::Result<(), ErrorStack> {
+    param.set_hostflags(X509CheckFlags::NO_PARTIAL_WILDCARDS);
+    param.set_host(server_name)
+}
+
 /// Open the named file and read its entire contents into memory. If the
 /// file "name" contains a PEM start marker, it is assumed to contain
 /// inline data and is used directly instead of opening a file.
@@ -579,8 +991,8 @@ mod test {
         };
         let settings =
             TlsSettings::from_options(&Some(options)).expect("Failed to load PKCS#12 certificate");
-        assert!(settings.identity.is_some());
-        assert_eq!(settings.authorities.len(), 0);
+        assert!(settings.has_identity());
+        assert_eq!(settings.material().authorities.len(), 0);
     }
 
     #[test]
@@ -592,8 +1004,8 @@ mod test {
         };
         let settings =
             TlsSettings::from_options(&Some(options)).expect("Failed to load PEM certificate");
-        assert!(settings.identity.is_some());
-        assert_eq!(settings.authorities.len(), 0);
+        assert!(settings.has_identity());
+        assert_eq!(settings.material().authorities.len(), 0);
     }
 
     #[test]
@@ -607,8 +1019,8 @@ mod test {
         };
         let settings =
             TlsSettings::from_options(&Some(options)).expect("Failed to load PEM certificate");
-        assert!(settings.identity.is_some());
-        assert_eq!(settings.authorities.len(), 0);
+        assert!(settings.has_identity());
+        assert_eq!(settings.material().authorities.len(), 0);
     }
 
     #[test]
@@ -619,8 +1031,8 @@ mod test {
         };
         let settings = TlsSettings::from_options(&Some(options))
             .expect("Failed to load authority certificate");
-        assert!(settings.identity.is_none());
-        assert_eq!(settings.authorities.len(), 1);
+        assert!(!settings.has_identity());
+        assert_eq!(settings.material().authorities.len(), 1);
     }
 
     #[test]
@@ -634,8 +1046,8 @@ mod test {
         };
         let settings = TlsSettings::from_options(&Some(options))
             .expect("Failed to load authority certificate");
-        assert!(settings.identity.is_none());
-        assert_eq!(settings.authorities.len(), 1);
+        assert!(!settings.has_identity());
+        assert_eq!(settings.material().authorities.len(), 1);
     }
 
     #[test]
@@ -646,8 +1058,8 @@ mod test {
         };
         let settings = TlsSettings::from_options(&Some(options))
             .expect("Failed to load authority certificate");
-        assert!(settings.identity.is_none());
-        assert_eq!(settings.authorities.len(), 2);
+        assert!(!settings.has_identity());
+        assert_eq!(settings.material().authorities.len(), 2);
     }
 
     #[test]
@@ -658,15 +1070,15 @@ mod test {
         };
         let settings = TlsSettings::from_options(&Some(options))
             .expect("Failed to load authority certificate");
-        assert!(settings.identity.is_none());
-        assert_eq!(settings.authorities.len(), 2);
+        assert!(!settings.has_identity());
+        assert_eq!(settings.material().authorities.len(), 2);
     }
 
     #[test]
     fn from_options_none() {
         let settings = TlsSettings::from_options(&None).expect("Failed to generate null settings");
-        assert!(settings.identity.is_none());
-        assert_eq!(settings.authorities.len(), 0);
+        assert!(!settings.has_identity());
+        assert_eq!(settings.material().authorities.len(), 0);
     }
 
     #[test]
@@ -718,6 +1130,129 @@ mod test {
         assert!(config.is_tls());
     }
 
+    #[test]
+    fn from_options_protocol_settings() {
+        let options: TlsConfig = toml::from_str(
+            r#"
+            min_tls_version = "1.2"
+            ciphersuites = "TLS_AES_256_GCM_SHA384:ECDHE-RSA-AES256-GCM-SHA384:ECDHE-ECDSA-AES256-GCM-SHA384"
+            alpn_protocols = ["h2", "http/1.1"]
+            server_name = "vector.local"
+            "#,
+        )
+        .unwrap();
+        let settings = TlsSettings::from_options(&Some(options)).expect("Failed to load settings");
+
+        assert_eq!(settings.min_tls_version, Some(TlsVersion::V1_2));
+        assert_eq!(
+            settings.ciphers.cipher_list.as_deref(),
+            Some("ECDHE-RSA-AES256-GCM-SHA384:ECDHE-ECDSA-AES256-GCM-SHA384")
+        );
+        assert_eq!(
+            settings.ciphers.ciphersuites.as_deref(),
+            Some("TLS_AES_256_GCM_SHA384")
+        );
+        assert_eq!(
+            settings.alpn_protocols.as_deref(),
+            Some(&b"\x02h2\x08http/1.1"[..])
+        );
+        assert_eq!(settings.server_name.as_deref(), Some("vector.local"));
+
+        let mut builder = SslConnector::builder(SslMethod::tls()).unwrap();
+        settings.apply_connector_context(&mut builder).unwrap();
+        let mut connection = builder.build().configure().unwrap();
+        settings
+            .apply_connect_configuration(&mut connection)
+            .unwrap();
+    }
+
+    #[test]
+    fn from_options_bad_ciphersuites() {
+        let options = TlsConfig {
+            ciphersuites: Some("NOT-A-CIPHER".into()),
+            ..Default::default()
+        };
+        let error = TlsSettings::from_options(&Some(options))
+            .expect_err("from_options failed to check ciphersuites");
+        assert!(matches!(error, TlsError::InvalidCipherSuites { .. }));
+    }
+
+    #[test]
+    fn from_options_bad_alpn_protocol() {
+        let options = TlsConfig {
+            alpn_protocols: Some(vec!["h2".into(), "".into()]),
+            ..Default::default()
+        };
+        let error = TlsSettings::from_options(&Some(options))
+            .expect_err("from_options failed to check ALPN protocols");
+        assert!(matches!(error, TlsError::InvalidAlpnProtocol { .. }));
+    }
+
+    #[test]
+    fn from_options_bad_server_name() {
+        for server_name in ["", "10.0.0.1", "::1", "bad name.local", "trailing."] {
+            let options = TlsConfig {
+                server_name: Some(server_name.into()),
+                ..Default::default()
+            };
+            let error = TlsSettings::from_options(&Some(options))
+                .expect_err("from_options failed to check the server name");
+            assert!(matches!(error, TlsError::InvalidServerName { .. }));
+        }
+    }
+
+    #[test]
+    fn from_options_bad_min_tls_version() {
+        let error = toml::from_str::<TlsConfig>(r#"min_tls_version = "1.4""#)
+            .expect_err("TLS 1.4 should not be accepted");
+        assert!(error.to_string().contains("1.4"));
+    }
+
+    #[cfg(all(unix, not(target_os = "macos")))] // https://github.com/vectordotdev/vector/issues/5000
+    #[test]
+    fn reloads_changed_certificates() {
+        let ca_file = crate::test_util::temp_file();
+        std::fs::copy("tests/data/ca/certs/ca.cert.pem", &ca_file).unwrap();
+
+        let options = TlsConfig {
+            ca_file: Some(ca_file.clone()),
+            ..Default::default()
+        };
+        let settings = TlsSettings::from_options(&Some(options)).expect("Failed to load CA file");
+        assert_eq!(settings.material().authorities.len(), 1);
+        assert_eq!(settings.generation(), 0);
+
+        std::fs::copy(
+            "tests/data/ca/intermediate_server/certs/ca-chain.cert.pem",
+            &ca_file,
+        )
+        .unwrap();
+
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(15);
+        while settings.generation() == 0 {
+            assert!(
+                std::time::Instant::now() < deadline,
+                "Timed out waiting for the certificates to be reloaded"
+            );
+            std::thread::sleep(std::time::Duration::from_millis(100));
+        }
+        assert_eq!(settings.material().authorities.len(), 2);
+
+        // Clones share the reloaded certificates.
+        let cloned = settings.clone();
+        assert_eq!(cloned.material().authorities.len(), 2);
+
+        // Invalid certificates are ignored, keeping the previous ones.
+        std::fs::write(&ca_file, "not a certificate").unwrap();
+        let generation = settings.generation();
+        let watcher = settings.watcher.as_ref().expect("Missing watcher");
+        watcher.reload(&settings.material);
+        assert_eq!(settings.material().authorities.len(), 2);
+        assert_eq!(settings.generation(), generation);
+
+        std::fs::remove_file(ca_file).unwrap();
+    }
+
     fn settings_from_config(
         enabled: Option<bool>,
         set_crt: bool,
@@ -6,7 +6,11 @@ use std::{
     time::Instant,
 };
 
-use futures::{stream::FuturesOrdered, FutureExt, StreamExt};
+use futures::{
+    future::{join3, OptionFuture},
+    stream::FuturesOrdered,
+    FutureExt, StreamExt,
+};
 use once_cell::sync::Lazy;
 use stream_cancel::{StreamExt as StreamCancelExt, Trigger, Tripwire};
 use tokio::{
@@ -28,7 +32,10 @@ use vector_core::{
 };
 
 use super::{
+    dead_letter::{RejectionTracker, REJECTED},
     fanout::{self, Fanout},
+    health,
+    latency::{LatencyTracker, ResidencyRecorder},
     schema,
     task::{Task, TaskOutput},
     BuiltBuffer, ConfigDiff,
@@ -75,7 +82,9 @@ pub(self) async fn load_enrichment_tables<'a>(
     // Build enrichment tables
     'tables: for (name, table) in config.enrichment_tables.iter() {
         let table_name = name.to_string();
-        if ENRICHMENT_TABLES.needs_reload(&table_name) {
+        // Tables are also rebuilt when their configuration changed, as it may affect how the data
+        // is loaded.
+        if diff.enrichment_tables.is_changed(name) || ENRICHMENT_TABLES.needs_reload(&table_name) {
             let indexes = if !diff.enrichment_tables.is_added(name) {
                 // If this is an existing enrichment table, we need to store the indexes to reapply
                 // them again post load.
@@ -171,7 +180,9 @@ pub async fn build_pieces(
 
         let mut builder = {
             let _span = span.enter();
-            SourceSender::builder().with_buffer(*SOURCE_SENDER_BUFFER_SIZE)
+            SourceSender::builder()
+                .with_buffer(*SOURCE_SENDER_BUFFER_SIZE)
+                .with_ingest_timestamps(config.global.event_latency.enabled)
         };
         let mut pumps = Vec::new();
         let mut controls = HashMap::new();
@@ -315,7 +326,12 @@ pub async fn build_pieces(
 
         inputs.insert(key.clone(), (input_tx, node.inputs.clone()));
 
-        let (transform_task, transform_outputs) = build_transform(transform, node, input_rx);
+        let (transform_task, transform_outputs) = build_transform(
+            transform,
+            node,
+            input_rx,
+            config.global.event_latency.enabled && config.global.event_latency.component_residency,
+        );
 
         outputs.extend(transform_outputs);
         tasks.insert(key.clone(), transform_task);
@@ -334,6 +350,7 @@ pub async fn build_pieces(
 
         let typetag = sink.inner.sink_type();
         let input_type = sink.inner.input().data_type();
+        let reroute_rejected = sink.reroute_rejected;
 
         if config.schema.validation {
             // At this point, we've validated that all transforms are valid, including any
@@ -372,6 +389,11 @@ pub async fn build_pieces(
             }
         };
 
+        let healthcheck_interval = healthcheck
+            .interval_secs
+            .or(config.healthchecks.interval_secs)
+            .filter(|_| enable_healthcheck);
+
         let cx = SinkContext {
             healthcheck,
             globals: config.global.clone(),
@@ -379,6 +401,15 @@ pub async fn build_pieces(
             schema: config.schema,
         };
 
+        let recheck = healthcheck_interval.map(|interval_secs| {
+            health::recheck_sink(
+                key.clone(),
+                sink.clone_inner(),
+                cx.clone(),
+                Duration::from_secs(interval_secs.get()),
+            )
+        });
+
         let (sink, healthcheck) = match sink.inner.build(cx).await {
             Err(error) => {
                 errors.push(format!("Sink \"{}\": {}", key, error));
@@ -389,6 +420,19 @@ pub async fn build_pieces(
 
         let (trigger, tripwire) = Tripwire::new();
 
+        let rejected = reroute_rejected.then(|| {
+            let (tracker, forwarder) = RejectionTracker::new(key.clone(), typetag);
+            let (fanout, control) = Fanout::new();
+            outputs.insert(OutputId::from((key, REJECTED.to_string())), control);
+            (tracker, forwarder.run(fanout))
+        });
+
+        let latency = config
+            .global
+            .event_latency
+            .enabled
+            .then(|| LatencyTracker::new(config.global.event_latency.component_residency));
+
         let sink = async move {
             // Why is this Arc<Mutex<Option<_>>> needed you ask.
             // In case when this function build_pieces errors
@@ -404,7 +448,16 @@ pub async fn build_pieces(
 
             let mut rx = wrap(rx);
 
-            sink.run(
+            let (tracker, forward_rejected) = match rejected {
+                Some((tracker, forward_rejected)) => (Some(tracker), Some(forward_rejected)),
+                None => (None, None),
+            };
+            let (latency_tracker, record_latency) = match latency {
+                Some((tracker, recorder)) => (Some(tracker), Some(recorder.run())),
+                None => (None, None),
+            };
+
+            let run = sink.run(
                 rx.by_ref()
                     .filter(|events: &EventArray| ready(filter_events_type(events, input_type)))
                     .inspect(|events| {
@@ -413,10 +466,44 @@ pub async fn build_pieces(
                             byte_size: events.size_of(),
                         })
                     })
+                    .map(move |events| match &latency_tracker {
+                        Some(tracker) => tracker.track(events),
+                        None => events,
+                    })
+                    .then(move |events| {
+                        let tracker = tracker.clone();
+                        async move {
+                            match tracker {
+                                Some(tracker) => tracker.track(events).await,
+                                None => events,
+                            }
+                        }
+                    })
                     .take_until_if(tripwire),
+            );
+
+            // The healthcheck is re-run for as long as the sink is running.
+            let run = async move {
+                match recheck {
+                    Some(recheck) => select! {
+                        result = run => result,
+                        _ = recheck => unreachable!("healthchecks are re-run until dropped"),
+                    },
+                    None => run.await,
+                }
+            };
+
+            // The rejected events of a sink keep being forwarded, and the latency of its events
+            // recorded, until every event it received has been finalized.
+            let result = join3(
+                run,
+                OptionFuture::from(forward_rejected),
+                OptionFuture::from(record_latency),
             )
             .await
-            .map(|_| {
+            .0;
+
+            result.map(|_| {
                 debug!("Finished.");
                 TaskOutput::Sink(rx)
             })
@@ -427,8 +514,7 @@ pub async fn build_pieces(
         let component_key = key.clone();
         let healthcheck_task = async move {
             if enable_healthcheck {
-                let duration = Duration::from_secs(10);
-                timeout(duration, healthcheck)
+                let result = timeout(health::HEALTHCHECK_TIMEOUT, healthcheck)
                     .map(|result| match result {
                         Ok(Ok(_)) => {
                             info!("Healthcheck: Passed.");
@@ -458,7 +544,9 @@ pub async fn build_pieces(
                             Err(())
                         }
                     })
-                    .await
+                    .await;
+                health::record_healthcheck(&component_key, result.is_ok());
+                result
             } else {
                 info!("Healthcheck: Disabled.");
                 Ok(TaskOutput::Healthcheck)
@@ -541,17 +629,21 @@ fn build_transform(
     transform: Transform,
     node: TransformNode,
     input_rx: BufferReceiver<EventArray>,
+    record_residency: bool,
 ) -> (Task, HashMap<OutputId, fanout::ControlChannel>) {
     match transform {
         // TODO: avoid the double boxing for function transforms here
-        Transform::Function(t) => build_sync_transform(Box::new(t), node, input_rx),
-        Transform::Synchronous(t) => build_sync_transform(t, node, input_rx),
+        Transform::Function(t) => {
+            build_sync_transform(Box::new(t), node, input_rx, record_residency)
+        }
+        Transform::Synchronous(t) => build_sync_transform(t, node, input_rx, record_residency),
         Transform::Task(t) => build_task_transform(
             t,
             input_rx,
             node.input_details.data_type(),
             node.typetag,
             &node.key,
+            record_residency,
         ),
     }
 }
@@ -560,10 +652,17 @@ fn build_sync_transform(
     t: Box<dyn SyncTransform>,
     node: TransformNode,
     input_rx: BufferReceiver<EventArray>,
+    record_residency: bool,
 ) -> (Task, HashMap<OutputId, fanout::ControlChannel>) {
     let (outputs, controls) = TransformOutputs::new(node.outputs);
 
-    let runner = Runner::new(t, input_rx, node.input_details.data_type(), outputs);
+    let runner = Runner::new(
+        t,
+        input_rx,
+        node.input_details.data_type(),
+        outputs,
+        record_residency,
+    );
     let transform = if node.enable_concurrency {
         runner.run_concurrently().boxed()
     } else {
@@ -590,6 +689,8 @@ struct Runner {
     outputs: TransformOutputs,
     timer: crate::utilization::Timer,
     last_report: Instant,
+    record_residency: bool,
+    residency: Option<ResidencyRecorder>,
 }
 
 impl Runner {
@@ -598,6 +699,7 @@ impl Runner {
         input_rx: BufferReceiver<EventArray>,
         input_type: DataType,
         outputs: TransformOutputs,
+        record_residency: bool,
     ) -> Self {
         Self {
             transform,
@@ -606,6 +708,18 @@ impl Runner {
             outputs,
             timer: crate::utilization::Timer::new(),
             last_report: Instant::now(),
+            record_residency,
+            residency: None,
+        }
+    }
+
+    /// Starts recording residency times, if enabled.
+    ///
+    /// This is done once the runner is running, so that the metric is registered within the span of
+    /// the transform.
+    fn start_residency(&mut self) {
+        if self.record_residency {
+            self.residency = Some(ResidencyRecorder::new());
         }
     }
 
@@ -623,6 +737,9 @@ impl Runner {
     }
 
     async fn send_outputs(&mut self, outputs_buf: &mut TransformOutputsBuf) {
+        if let Some(residency) = &self.residency {
+            outputs_buf.for_each_array_mut(|events| residency.record(events));
+        }
         self.timer.start_wait();
         self.outputs.send(outputs_buf).await;
     }
@@ -631,6 +748,7 @@ impl Runner {
         // 128 is an arbitrary, smallish constant
         const INLINE_BATCH_SIZE: usize = 128;
 
+        self.start_residency();
         let mut outputs_buf = self.outputs.new_buf_with_capacity(INLINE_BATCH_SIZE);
 
         let mut input_rx = self
@@ -652,6 +770,7 @@ impl Runner {
     }
 
     async fn run_concurrently(mut self) -> Result<TaskOutput, ()> {
+        self.start_residency();
         let input_rx = self
             .input_rx
             .take()
@@ -725,6 +844,7 @@ fn build_task_transform(
     input_type: DataType,
     typetag: &str,
     key: &ComponentKey,
+    record_residency: bool,
 ) -> (Task, HashMap<OutputId, fanout::ControlChannel>) {
     let (mut fanout, control) = Fanout::new();
 
@@ -738,16 +858,23 @@ fn build_task_transform(
                 byte_size: events.size_of(),
             })
         });
-    let stream = t
-        .transform(Box::pin(filtered))
-        .inspect(|events: &EventArray| {
-            emit!(EventsSent {
-                count: events.len(),
-                byte_size: events.size_of(),
-                output: None,
-            });
-        });
     let transform = async move {
+        let residency = record_residency.then(ResidencyRecorder::new);
+        let stream = t
+            .transform(Box::pin(filtered))
+            .map(move |mut events: EventArray| {
+                if let Some(residency) = &residency {
+                    residency.record(&mut events);
+                }
+                events
+            })
+            .inspect(|events: &EventArray| {
+                emit!(EventsSent {
+                    count: events.len(),
+                    byte_size: events.size_of(),
+                    output: None,
+                });
+            });
         fanout.send_stream(stream).await;
         debug!("Finished.");
         Ok(TaskOutput::Transform)
@@ -0,0 +1,238 @@
+//! Support for rerouting the events a sink rejects to its `rejected` output.
+//!
+//! Sinks expose a `reroute_rejected` option. When enabled, every event entering the sink is
+//! tracked with an additional finalizer, and a copy of the event is held until the sink finalizes
+//! it. Events finalized as [`BatchStatus::Rejected`] are annotated with the error the sink
+//! reported and forwarded to the `<sink_id>.rejected` output, where they can be consumed like any
+//! other component output.
+//!
+//! The copies of logs and traces share their fields with the events the sink holds, which are
+//! only copied if the sink modifies them. The number of events tracked at once is bounded, so that
+//! once the limit is reached, the sink stops taking in events until earlier ones are finalized.
+
+use std::sync::Arc;
+
+use futures::StreamExt;
+use lookup::path;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use vector_core::{config::LogNamespace, internal_event::EventsSent, ByteSizeOf};
+
+use super::{
+    fanout::Fanout,
+    finalization::{self, FinalizationHandle, FinalizationWatcher},
+};
+use crate::{
+    config::{log_schema, ComponentKey},
+    event::{BatchNotifier, BatchStatus, Event, EventArray, EventFinalizer, EventMutRef},
+};
+
+/// The name of the output that rejected events are rerouted to.
+pub const REJECTED: &str = "rejected";
+
+/// The maximum number of events a sink can hold onto before it has finalized them.
+const MAX_TRACKED_EVENTS: u32 = 10_000;
+
+/// An event held until the sink finalizes it, and the slots of the array it came in.
+type Tracked = (Event, Arc<OwnedSemaphorePermit>);
+
+/// Attaches a tracking finalizer to each event entering a sink.
+#[derive(Clone)]
+pub(super) struct RejectionTracker {
+    handle: FinalizationHandle<Tracked>,
+    permits: Arc<Semaphore>,
+}
+
+impl RejectionTracker {
+    /// Creates a new tracker, along with the forwarder that reroutes the events it tracks once
+    /// they have been rejected.
+    pub(super) fn new(
+        component_key: ComponentKey,
+        component_type: &'static str,
+    ) -> (Self, RejectionForwarder) {
+        let (handle, watcher) = finalization::watcher();
+        let forwarder = RejectionForwarder {
+            component_key,
+            component_type,
+            watcher,
+        };
+        let permits = Arc::new(Semaphore::new(MAX_TRACKED_EVENTS as usize));
+        (Self { handle, permits }, forwarder)
+    }
+
+    /// Tracks the finalization of each event in the given array.
+    ///
+    /// Waits until there is room for the events if too many are already tracked. The events of an
+    /// array share their slots, which are released once all of them have been finalized. Arrays
+    /// larger than the limit take up all of the slots, rather than waiting forever.
+    ///
+    /// The copy of each event kept for rerouting is stripped of its finalizers, so holding onto it
+    /// never delays the acknowledgement of the original event.
+    pub(super) async fn track(&self, mut events: EventArray) -> EventArray {
+        let slots = events.len().min(MAX_TRACKED_EVENTS as usize) as u32;
+        let permit = Arc::new(
+            Arc::clone(&self.permits)
+                .acquire_many_owned(slots)
+                .await
+                .expect("semaphore is never closed"),
+        );
+
+        for mut event in events.iter_events_mut() {
+            let mut copy = match &event {
+                EventMutRef::Log(log) => Event::from((*log).clone()),
+                EventMutRef::Metric(metric) => Event::from((*metric).clone()),
+                EventMutRef::Trace(trace) => Event::from((*trace).clone()),
+            };
+            drop(copy.metadata_mut().take_finalizers());
+
+            let (batch, receiver) = BatchNotifier::new_with_reason_receiver();
+            event
+                .metadata_mut()
+                .add_finalizer(EventFinalizer::new(batch));
+            self.handle.watch((copy, Arc::clone(&permit)), receiver);
+        }
+        events
+    }
+}
+
+/// Forwards the events rejected by a sink to its `rejected` output.
+pub(super) struct RejectionForwarder {
+    component_key: ComponentKey,
+    component_type: &'static str,
+    watcher: FinalizationWatcher<Tracked>,
+}
+
+impl RejectionForwarder {
+    /// Runs until the tracker has been dropped and every tracked event has been finalized.
+    pub(super) async fn run(self, mut fanout: Fanout) {
+        let finalized = self.watcher.finalized();
+        tokio::pin!(finalized);
+
+        while let Some(((mut event, permit), status, reason)) = finalized.next().await {
+            drop(permit);
+            if status == BatchStatus::Rejected {
+                annotate_rejected(
+                    &mut event,
+                    &self.component_key,
+                    self.component_type,
+                    reason.as_deref().unwrap_or("unknown"),
+                );
+                let events = EventArray::from(event);
+                emit!(EventsSent {
+                    count: 1,
+                    byte_size: events.size_of(),
+                    output: Some(REJECTED),
+                });
+                fanout.send(events).await;
+            }
+        }
+    }
+}
+
+/// Annotates an event with the error the given sink rejected it with.
+fn annotate_rejected(
+    event: &mut Event,
+    component_key: &ComponentKey,
+    component_type: &'static str,
+    error: &str,
+) {
+    let data = || {
+        serde_json::json!({
+            "error": error,
+            "component_id": component_key,
+            "component_type": component_type,
+            "component_kind": "sink",
+        })
+    };
+
+    match event {
+        Event::Log(ref mut log) => match log.namespace() {
+            LogNamespace::Legacy => {
+                log.insert(
+                    log_schema().metadata_key().concat(path!("rejected")),
+                    data(),
+                );
+            }
+            LogNamespace::Vector => {
+                log.metadata_mut()
+                    .value_mut()
+                    .insert(path!("vector", "rejected"), data());
+            }
+        },
+        Event::Metric(ref mut metric) => {
+            let m = log_schema().metadata_key();
+            metric.insert_tag(format!("{}.rejected.error", m), error.into());
+            metric.insert_tag(
+                format!("{}.rejected.component_id", m),
+                component_key.to_string(),
+            );
+            metric.insert_tag(
+                format!("{}.rejected.component_type", m),
+                component_type.into(),
+            );
+            metric.insert_tag(format!("{}.rejected.component_kind", m), "sink".into());
+        }
+        Event::Trace(ref mut trace) => {
+            trace.insert(log_schema().metadata_key(), data());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use vector_buffers::{topology::builder::TopologyBuilder, WhenFull};
+
+    use super::*;
+    use crate::{
+        event::{EventContainer, EventStatus, LogEvent},
+        topology::fanout::ControlMessage,
+    };
+
+    #[tokio::test]
+    async fn forwards_only_rejected_events() {
+        let key = ComponentKey::from("out");
+        let (tracker, forwarder) = RejectionTracker::new(key, "test");
+        let (mut fanout, control) = Fanout::new();
+        let (tx, mut rx) =
+            TopologyBuilder::standalone_memory(NonZeroUsize::new(10).unwrap(), WhenFull::Block)
+                .await;
+        control
+            .send(ControlMessage::Add(ComponentKey::from("dlq"), tx))
+            .unwrap();
+        drop(control);
+
+        let forwarder = tokio::spawn(forwarder.run(fanout));
+
+        let delivered = tracker
+            .track(EventArray::from(Event::from(LogEvent::from("ok"))))
+            .await;
+        let rejected = tracker
+            .track(EventArray::from(Event::from(LogEvent::from("bad"))))
+            .await;
+        drop(tracker);
+
+        for event in delivered.into_events() {
+            event.metadata().update_status(EventStatus::Delivered);
+        }
+        for mut event in rejected.into_events() {
+            event
+                .metadata_mut()
+                .take_finalizers()
+                .reject_with("400: mapper_parsing_exception");
+        }
+
+        forwarder.await.unwrap();
+
+        let events = rx.next().await.unwrap().into_events().collect::<Vec<_>>();
+        assert_eq!(events.len(), 1);
+        let log = events[0].as_log();
+        assert_eq!(log["message"], "bad".into());
+        assert_eq!(
+            log["metadata.rejected.error"],
+            "400: mapper_parsing_exception".into()
+        );
+        assert_eq!(log["metadata.rejected.component_id"], "out".into());
+        assert!(rx.next().await.is_none());
+    }
+}
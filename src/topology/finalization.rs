@@ -0,0 +1,77 @@
+//! Support for acting on the finalization of the events entering a sink.
+//!
+//! Rerouting rejected events and recording event latency both attach an additional finalizer to the
+//! events entering a sink. A [`FinalizationWatcher`] waits on those finalizers, and hands back the
+//! data tracked along with each of them once the sink has finalized its events.
+
+use std::sync::Arc;
+
+use async_stream::stream;
+use futures::{stream::FuturesUnordered, Stream, StreamExt};
+use tokio::sync::mpsc;
+
+use crate::event::{BatchStatus, BatchStatusReceiver};
+
+/// Creates a watcher, along with the handle used to give it the batches to watch.
+pub(super) fn watcher<T>() -> (FinalizationHandle<T>, FinalizationWatcher<T>) {
+    let (tx, rx) = mpsc::unbounded_channel();
+    (FinalizationHandle { tx }, FinalizationWatcher { rx })
+}
+
+/// Gives the batches to watch to a [`FinalizationWatcher`].
+pub(super) struct FinalizationHandle<T> {
+    tx: mpsc::UnboundedSender<(T, BatchStatusReceiver)>,
+}
+
+impl<T> Clone for FinalizationHandle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            tx: self.tx.clone(),
+        }
+    }
+}
+
+impl<T> FinalizationHandle<T> {
+    /// Watches the batch of the given receiver, handing `data` back once it has been finalized.
+    pub(super) fn watch(&self, data: T, receiver: BatchStatusReceiver) {
+        // The watcher only goes away once the sink has finished, so there is nothing to do if this
+        // fails.
+        let _ = self.tx.send((data, receiver));
+    }
+}
+
+/// Waits on the batches given to it through its handles.
+pub(super) struct FinalizationWatcher<T> {
+    rx: mpsc::UnboundedReceiver<(T, BatchStatusReceiver)>,
+}
+
+impl<T> FinalizationWatcher<T> {
+    /// Returns the data of each watched batch as it is finalized, along with its status and the
+    /// reason it was rejected with, if the batch records one.
+    ///
+    /// The stream ends once every handle has been dropped and every watched batch finalized.
+    pub(super) fn finalized(mut self) -> impl Stream<Item = (T, BatchStatus, Option<Arc<str>>)> {
+        stream! {
+            let mut pending = FuturesUnordered::new();
+            let mut open = true;
+
+            loop {
+                let finalized = tokio::select! {
+                    watched = self.rx.recv(), if open => {
+                        match watched {
+                            Some((data, receiver)) => pending.push(async move {
+                                let (status, reason) = receiver.with_reason().await;
+                                (data, status, reason)
+                            }),
+                            None => open = false,
+                        }
+                        continue;
+                    },
+                    Some(finalized) = pending.next(), if !pending.is_empty() => finalized,
+                    else => break,
+                };
+                yield finalized;
+            }
+        }
+    }
+}
@@ -0,0 +1,388 @@
+//! Tracks the health of running components.
+//!
+//! The health of each component is derived from the errors and sent events it recently reported,
+//! read from its internal metrics, the state of its circuit breaker if it has one, and, for sinks,
+//! the result of its latest healthcheck. The health of components with their `telemetry` disabled is
+//! unknown, as they report no internal metrics.
+
+use std::{collections::HashMap, sync::RwLock, time::Duration};
+
+use futures::future;
+use once_cell::sync::Lazy;
+use tokio::time::{interval, interval_at, timeout, Instant, MissedTickBehavior};
+
+use crate::{
+    config::{ComponentKey, Config, ConfigDiff, SinkConfig, SinkContext},
+    event::{Metric, MetricValue},
+    internal_events::ComponentHealthUpdated,
+};
+
+/// How often the recent errors of components are evaluated.
+const EVALUATION_INTERVAL: Duration = Duration::from_secs(10);
+
+/// How long a healthcheck can take before it's considered failed.
+pub(super) const HEALTHCHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+const INVARIANT: &str = "Couldn't acquire lock on component health. Please report this.";
+
+static COMPONENTS: Lazy<RwLock<HashMap<ComponentKey, ComponentState>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// The health of a component.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ComponentHealth {
+    /// The component is working as expected.
+    Healthy,
+    /// The component is working, but some of its recent requests failed, or its healthcheck did.
+    Degraded,
+    /// The component isn't getting anything through.
+    Failing,
+    /// The component's health can't be told, as its `telemetry` is disabled.
+    Unknown,
+}
+
+impl ComponentHealth {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Healthy => "healthy",
+            Self::Degraded => "degraded",
+            Self::Failing => "failing",
+            Self::Unknown => "unknown",
+        }
+    }
+
+    /// The value of the `component_health` gauge for this state, if it's known.
+    pub const fn as_gauge(self) -> Option<f64> {
+        match self {
+            Self::Healthy => Some(0.0),
+            Self::Degraded => Some(1.0),
+            Self::Failing => Some(2.0),
+            Self::Unknown => None,
+        }
+    }
+}
+
+/// The metrics of a component read at an evaluation.
+#[derive(Clone, Copy, Debug, Default)]
+struct Observation {
+    errors_total: f64,
+    sent_events_total: f64,
+    circuit_breaker_state: Option<f64>,
+}
+
+#[derive(Debug)]
+struct ComponentState {
+    kind: &'static str,
+    component_type: &'static str,
+    /// Whether the component reports internal metrics, which its health is derived from.
+    telemetry: bool,
+    health: ComponentHealth,
+    /// Whether the latest healthcheck passed, if one has run.
+    healthcheck: Option<bool>,
+    /// The metrics at the previous evaluation, which the recent ones are counted from.
+    previous: Option<Observation>,
+    recent_errors: f64,
+    recent_sent_events: f64,
+    circuit_breaker_state: f64,
+}
+
+impl ComponentState {
+    const fn new(kind: &'static str, component_type: &'static str, telemetry: bool) -> Self {
+        Self {
+            kind,
+            component_type,
+            telemetry,
+            health: if telemetry {
+                ComponentHealth::Healthy
+            } else {
+                ComponentHealth::Unknown
+            },
+            healthcheck: None,
+            previous: None,
+            recent_errors: 0.0,
+            recent_sent_events: 0.0,
+            circuit_breaker_state: 0.0,
+        }
+    }
+
+    fn observe(&mut self, observation: Observation) {
+        // The first observation only sets the baseline. Counters which went backwards were reset,
+        // such as when they expired, so all of their current value is recent.
+        let recent = |current: f64, previous: f64| {
+            if current >= previous {
+                current - previous
+            } else {
+                current
+            }
+        };
+        if let Some(previous) = self.previous {
+            self.recent_errors = recent(observation.errors_total, previous.errors_total);
+            self.recent_sent_events =
+                recent(observation.sent_events_total, previous.sent_events_total);
+        }
+        self.previous = Some(observation);
+        self.circuit_breaker_state = observation.circuit_breaker_state.unwrap_or_default();
+    }
+
+    fn evaluate(&self) -> ComponentHealth {
+        if !self.telemetry {
+            return ComponentHealth::Unknown;
+        }
+
+        let sent_events = self.recent_sent_events > 0.0;
+        let healthcheck_failed = self.healthcheck == Some(false);
+
+        // Events getting through outweigh a failed healthcheck, as the healthcheck may not exercise
+        // the same access as sending does.
+        if self.circuit_breaker_state >= 2.0
+            || (!sent_events && (self.recent_errors > 0.0 || healthcheck_failed))
+        {
+            ComponentHealth::Failing
+        } else if self.circuit_breaker_state >= 1.0
+            || self.recent_errors > 0.0
+            || healthcheck_failed
+        {
+            ComponentHealth::Degraded
+        } else {
+            ComponentHealth::Healthy
+        }
+    }
+
+    fn update(&mut self, key: &ComponentKey) {
+        let previous = self.health;
+        self.health = self.evaluate();
+        emit!(ComponentHealthUpdated {
+            component_key: key,
+            component_kind: self.kind,
+            component_type: self.component_type,
+            health: self.health,
+            previous,
+        });
+    }
+}
+
+/// Starts tracking the components which are new or changed in the given diff, and stops tracking
+/// the removed ones.
+pub(super) fn update_components(diff: &ConfigDiff, config: &Config) {
+    let mut components = COMPONENTS.write().expect(INVARIANT);
+
+    for key in diff
+        .sources
+        .to_remove
+        .iter()
+        .chain(&diff.transforms.to_remove)
+        .chain(&diff.sinks.to_remove)
+    {
+        components.remove(key);
+    }
+
+    let sources = config
+        .sources()
+        .filter(|(key, _)| diff.sources.contains_new(key))
+        .map(|(key, source)| (key, "source", source.inner.source_type(), source.telemetry));
+    let transforms = config
+        .transforms()
+        .filter(|(key, _)| diff.transforms.contains_new(key))
+        .map(|(key, transform)| {
+            (
+                key,
+                "transform",
+                transform.inner.transform_type(),
+                transform.telemetry,
+            )
+        });
+    let sinks = config
+        .sinks()
+        .filter(|(key, _)| diff.sinks.contains_new(key))
+        .map(|(key, sink)| (key, "sink", sink.inner.sink_type(), sink.telemetry));
+    for (key, kind, component_type, telemetry) in sources.chain(transforms).chain(sinks) {
+        let mut state = ComponentState::new(kind, component_type, telemetry);
+        state.update(key);
+        components.insert(key.clone(), state);
+    }
+}
+
+/// Records the result of a sink's healthcheck.
+pub(super) fn record_healthcheck(key: &ComponentKey, passed: bool) {
+    if let Some(state) = COMPONENTS.write().expect(INVARIANT).get_mut(key) {
+        state.healthcheck = Some(passed);
+        state.update(key);
+    }
+}
+
+/// Returns the health of a running component.
+pub fn component_health(key: &ComponentKey) -> Option<ComponentHealth> {
+    COMPONENTS
+        .read()
+        .expect(INVARIANT)
+        .get(key)
+        .map(|state| state.health)
+}
+
+fn evaluate(metrics: &[Metric]) {
+    let mut observations = HashMap::<ComponentKey, Observation>::new();
+    for metric in metrics {
+        if !matches!(
+            metric.name(),
+            "component_errors_total" | "component_sent_events_total" | "circuit_breaker_state"
+        ) {
+            continue;
+        }
+        let value = match metric.value() {
+            MetricValue::Counter { value } | MetricValue::Gauge { value } => *value,
+            _ => continue,
+        };
+        let component_id = match metric.tag_value("component_id") {
+            Some(component_id) => component_id,
+            None => continue,
+        };
+
+        let observation = observations
+            .entry(ComponentKey::from(component_id))
+            .or_default();
+        match metric.name() {
+            "component_errors_total" => observation.errors_total += value,
+            "component_sent_events_total" => observation.sent_events_total += value,
+            _ => observation.circuit_breaker_state = Some(value),
+        }
+    }
+
+    for (key, state) in COMPONENTS.write().expect(INVARIANT).iter_mut() {
+        state.observe(observations.get(key).copied().unwrap_or_default());
+        state.update(key);
+    }
+}
+
+/// Periodically evaluates the health of components from their internal metrics.
+pub(super) async fn monitor() {
+    let controller = match crate::metrics::Controller::get() {
+        Ok(controller) => controller,
+        Err(_) => return,
+    };
+
+    let mut interval = interval(EVALUATION_INTERVAL);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    loop {
+        interval.tick().await;
+        evaluate(&controller.capture_metrics());
+    }
+}
+
+/// Re-runs the healthcheck of a sink every `period`, for as long as it's polled, unless the sink
+/// opts out of it.
+pub(super) async fn recheck_sink(
+    key: ComponentKey,
+    config: Box<dyn SinkConfig>,
+    cx: SinkContext,
+    period: Duration,
+) {
+    let mut interval = interval_at(Instant::now() + period, period);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    loop {
+        interval.tick().await;
+
+        let passed = match config.rebuild_healthcheck(cx.clone()).await {
+            Ok(Some(healthcheck)) => match timeout(HEALTHCHECK_TIMEOUT, healthcheck).await {
+                Ok(Ok(())) => {
+                    debug!("Healthcheck: Passed.");
+                    true
+                }
+                Ok(Err(error)) => {
+                    warn!(message = "Healthcheck: Failed Reason.", %error);
+                    false
+                }
+                Err(_) => {
+                    warn!("Healthcheck: timeout.");
+                    false
+                }
+            },
+            Ok(None) => {
+                warn!("Healthcheck: Re-running it is disabled for this sink.");
+                return future::pending().await;
+            }
+            Err(error) => {
+                warn!(message = "Healthcheck: Failed to build.", %error);
+                false
+            }
+        };
+        record_healthcheck(&key, passed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn observe(state: &mut ComponentState, errors_total: f64, sent_events_total: f64) {
+        state.observe(Observation {
+            errors_total,
+            sent_events_total,
+            circuit_breaker_state: None,
+        });
+    }
+
+    #[test]
+    fn recent_errors_degrade_then_fail() {
+        let mut state = ComponentState::new("sink", "http", true);
+        observe(&mut state, 5.0, 100.0);
+        assert_eq!(state.evaluate(), ComponentHealth::Healthy);
+
+        observe(&mut state, 5.0, 200.0);
+        assert_eq!(state.evaluate(), ComponentHealth::Healthy);
+
+        observe(&mut state, 6.0, 250.0);
+        assert_eq!(state.evaluate(), ComponentHealth::Degraded);
+
+        observe(&mut state, 10.0, 250.0);
+        assert_eq!(state.evaluate(), ComponentHealth::Failing);
+
+        // Recovers once a window passes without errors.
+        observe(&mut state, 10.0, 300.0);
+        assert_eq!(state.evaluate(), ComponentHealth::Healthy);
+
+        // Counters which were reset are counted from zero.
+        observe(&mut state, 2.0, 0.0);
+        assert_eq!(state.evaluate(), ComponentHealth::Failing);
+    }
+
+    #[test]
+    fn failed_healthcheck() {
+        let mut state = ComponentState::new("sink", "http", true);
+        state.healthcheck = Some(false);
+        assert_eq!(state.evaluate(), ComponentHealth::Failing);
+
+        observe(&mut state, 0.0, 0.0);
+        observe(&mut state, 0.0, 10.0);
+        assert_eq!(state.evaluate(), ComponentHealth::Degraded);
+
+        state.healthcheck = Some(true);
+        assert_eq!(state.evaluate(), ComponentHealth::Healthy);
+    }
+
+    #[test]
+    fn circuit_breaker() {
+        let mut state = ComponentState::new("sink", "http", true);
+        for (circuit_breaker_state, health) in [
+            (2.0, ComponentHealth::Failing),
+            (1.0, ComponentHealth::Degraded),
+            (0.0, ComponentHealth::Healthy),
+        ] {
+            state.observe(Observation {
+                circuit_breaker_state: Some(circuit_breaker_state),
+                ..Default::default()
+            });
+            assert_eq!(state.evaluate(), health);
+        }
+    }
+
+    #[test]
+    fn unknown_without_telemetry() {
+        let mut state = ComponentState::new("sink", "http", false);
+        assert_eq!(state.health, ComponentHealth::Unknown);
+
+        observe(&mut state, 10.0, 0.0);
+        observe(&mut state, 20.0, 0.0);
+        state.healthcheck = Some(false);
+        assert_eq!(state.evaluate(), ComponentHealth::Unknown);
+    }
+}
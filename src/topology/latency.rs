@@ -0,0 +1,228 @@
+//! Support for measuring how long events take to go through the topology.
+//!
+//! Sources stamp every event with the instant it was ingested, which is also the first instant it
+//! was handed off to the next component. When `event_latency` is enabled, every array of events
+//! entering a sink is tracked with an additional finalizer, and once the sink has delivered them,
+//! the time since each was ingested is recorded as `event_latency_seconds`.
+//!
+//! When `event_latency.component_residency` is also enabled, each transform records the time since
+//! the events it sends were last handed off, as `component_residency_seconds`, and stamps them as
+//! handed off again. Sinks record the same metric once events are delivered, so that the stage
+//! adding the most delay can be found by comparing components.
+//!
+//! All of these metrics are registered from within the task of the component they belong to, so
+//! that they are tagged with its component labels.
+
+use std::time::Instant;
+
+use futures::StreamExt;
+use metrics::{register_histogram, Histogram};
+use vector_common::finalization::AddBatchNotifier;
+
+use super::finalization::{self, FinalizationHandle, FinalizationWatcher};
+use crate::event::{BatchNotifier, BatchStatus, EventArray};
+
+const EVENT_LATENCY_NAME: &str = "event_latency_seconds";
+const COMPONENT_RESIDENCY_NAME: &str = "component_residency_seconds";
+
+/// Records the residency time of the events sent by a transform.
+pub(super) struct ResidencyRecorder {
+    histogram: Histogram,
+}
+
+impl ResidencyRecorder {
+    /// Creates a new recorder.
+    ///
+    /// This must be called from within the span of the transform.
+    pub(super) fn new() -> Self {
+        Self {
+            histogram: register_histogram!(COMPONENT_RESIDENCY_NAME),
+        }
+    }
+
+    /// Records the time since each of the given events was last handed off, and marks them as
+    /// handed off now.
+    pub(super) fn record(&self, events: &mut EventArray) {
+        let now = Instant::now();
+        for mut event in events.iter_events_mut() {
+            let metadata = event.metadata_mut();
+            if let Some(handed_off) = metadata.handoff_timestamp() {
+                self.histogram
+                    .record(now.saturating_duration_since(handed_off));
+            }
+            metadata.set_handoff_timestamp(now);
+        }
+    }
+}
+
+/// The instants recorded for an array of events that has entered a sink.
+#[derive(Debug, Default)]
+struct TrackedTimestamps {
+    ingested: Vec<Instant>,
+    handed_off: Vec<Instant>,
+}
+
+/// Attaches a latency tracking finalizer to each array of events entering a sink.
+pub(super) struct LatencyTracker {
+    component_residency: bool,
+    handle: FinalizationHandle<TrackedTimestamps>,
+}
+
+impl LatencyTracker {
+    /// Creates a new tracker, along with the recorder that records the latency of the events it
+    /// tracks once they have been delivered.
+    pub(super) fn new(component_residency: bool) -> (Self, LatencyRecorder) {
+        let (handle, watcher) = finalization::watcher();
+        let recorder = LatencyRecorder {
+            component_residency,
+            watcher,
+        };
+        (
+            Self {
+                component_residency,
+                handle,
+            },
+            recorder,
+        )
+    }
+
+    /// Tracks the finalization of the given array of events.
+    ///
+    /// Arrays without any timestamped events are left untouched.
+    pub(super) fn track(&self, mut events: EventArray) -> EventArray {
+        let mut timestamps = TrackedTimestamps::default();
+        for event in events.iter_events() {
+            let metadata = event.metadata();
+            timestamps.ingested.extend(metadata.ingest_timestamp());
+            if self.component_residency {
+                timestamps.handed_off.extend(metadata.handoff_timestamp());
+            }
+        }
+
+        if timestamps.ingested.is_empty() && timestamps.handed_off.is_empty() {
+            return events;
+        }
+
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        events.add_batch_notifier(batch);
+        self.handle.watch(timestamps, receiver);
+        events
+    }
+}
+
+/// Records the latency of the events tracked by a sink.
+pub(super) struct LatencyRecorder {
+    component_residency: bool,
+    watcher: FinalizationWatcher<TrackedTimestamps>,
+}
+
+impl LatencyRecorder {
+    /// Runs until the tracker has been dropped and every tracked event has been finalized.
+    ///
+    /// Only events that were delivered are recorded, since the latency of events that failed to be
+    /// delivered says nothing about how long it takes for events to reach their destination.
+    ///
+    /// This must be run from within the span of the sink.
+    pub(super) async fn run(self) {
+        let latency = register_histogram!(EVENT_LATENCY_NAME);
+        let residency = self
+            .component_residency
+            .then(|| register_histogram!(COMPONENT_RESIDENCY_NAME));
+
+        let finalized = self.watcher.finalized();
+        tokio::pin!(finalized);
+
+        while let Some((timestamps, status, _)) = finalized.next().await {
+            if status == BatchStatus::Delivered {
+                let now = Instant::now();
+                for ingested in timestamps.ingested {
+                    latency.record(now.saturating_duration_since(ingested));
+                }
+                if let Some(residency) = &residency {
+                    for handed_off in timestamps.handed_off {
+                        residency.record(now.saturating_duration_since(handed_off));
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_core::event::{Event, EventContainer, EventStatus, LogEvent};
+
+    use super::*;
+    use crate::{event::MetricValue, metrics::Controller};
+
+    fn histogram_count(name: &str) -> u64 {
+        Controller::get()
+            .expect("There must be a controller")
+            .capture_metrics()
+            .into_iter()
+            .filter(|metric| metric.name() == name)
+            .map(|metric| match metric.value() {
+                MetricValue::AggregatedHistogram { count, .. } => *count,
+                _ => panic!("{} has invalid type", name),
+            })
+            .sum()
+    }
+
+    fn stamped_events(count: usize) -> EventArray {
+        let now = Instant::now();
+        let events = (0..count)
+            .map(|i| {
+                let mut log = LogEvent::from(format!("event {}", i));
+                log.metadata_mut().set_ingest_timestamp(now);
+                log
+            })
+            .collect::<Vec<_>>();
+        EventArray::from(events)
+    }
+
+    #[tokio::test]
+    async fn records_latency_of_delivered_events() {
+        let _ = crate::metrics::init_test();
+        let before = histogram_count(EVENT_LATENCY_NAME);
+
+        let (tracker, recorder) = LatencyTracker::new(false);
+        let recorder = tokio::spawn(recorder.run());
+
+        let delivered = tracker.track(stamped_events(3));
+        let errored = tracker.track(stamped_events(2));
+        let untracked = tracker.track(EventArray::from(Event::from(LogEvent::from("no stamp"))));
+        drop(tracker);
+
+        assert!(untracked
+            .iter_events()
+            .all(|event| event.metadata().finalizers().is_empty()));
+
+        for event in delivered.into_events() {
+            event.metadata().update_status(EventStatus::Delivered);
+        }
+        for event in errored.into_events() {
+            event.metadata().update_status(EventStatus::Errored);
+        }
+
+        recorder.await.unwrap();
+        assert_eq!(histogram_count(EVENT_LATENCY_NAME) - before, 3);
+    }
+
+    #[tokio::test]
+    async fn residency_marks_events_as_handed_off() {
+        let _ = crate::metrics::init_test();
+        let mut events = stamped_events(1);
+        let ingested = events
+            .iter_events()
+            .next()
+            .and_then(|event| event.metadata().ingest_timestamp())
+            .unwrap();
+
+        ResidencyRecorder::new().record(&mut events);
+
+        let event = events.into_events().next().unwrap();
+        let handed_off = event.metadata().handoff_timestamp().unwrap();
+        assert!(handed_off >= ingested);
+        assert_eq!(event.metadata().ingest_timestamp(), Some(ingested));
+    }
+}
@@ -10,6 +10,10 @@ pub(super) use vector_core::fanout;
 pub mod schema;
 
 pub mod builder;
+pub(crate) mod dead_letter;
+mod finalization;
+pub mod health;
+mod latency;
 mod ready_arrays;
 mod running;
 mod task;
@@ -72,6 +76,28 @@ pub struct TapResource {
 type WatchTx = watch::Sender<TapResource>;
 pub type WatchRx = watch::Receiver<TapResource>;
 
+/// Configures which internal metrics are collected, from the `telemetry.metrics` global options and
+/// the components which have `telemetry` disabled.
+fn set_metrics_filter(config: &Config) {
+    let disabled_components = config
+        .sources()
+        .filter_map(|(key, source)| (!source.telemetry).then(|| key.to_string()))
+        .chain(
+            config
+                .transforms()
+                .filter_map(|(key, transform)| (!transform.telemetry).then(|| key.to_string())),
+        )
+        .chain(
+            config
+                .sinks()
+                .filter_map(|(key, sink)| (!sink.telemetry).then(|| key.to_string())),
+        );
+
+    crate::metrics::Controller::get()
+        .expect("Metrics must be initialized")
+        .set_filter(&config.global.telemetry.metrics, disabled_components);
+}
+
 pub async fn start_validated(
     config: Config,
     diff: ConfigDiff,
@@ -82,6 +108,7 @@ pub async fn start_validated(
     crate::metrics::Controller::get()
         .expect("Metrics must be initialized")
         .set_expiry(config.global.expire_metrics);
+    health::update_components(&diff, &config);
 
     let mut running_topology = RunningTopology::new(config, abort_tx);
 
@@ -102,6 +129,9 @@ pub async fn build_or_log_errors(
     diff: &ConfigDiff,
     buffers: HashMap<ComponentKey, BuiltBuffer>,
 ) -> Option<Pieces> {
+    // The filter has to be in place before the components are built, since they register their
+    // metrics while being built.
+    set_metrics_filter(config);
     match builder::build_pieces(config, diff, buffers).await {
         Err(errors) => {
             for error in errors {
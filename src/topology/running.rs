@@ -15,7 +15,7 @@ use tracing::Instrument;
 use vector_buffers::topology::channel::BufferSender;
 use vector_common::trigger::DisabledTrigger;
 
-use super::{TapOutput, TapResource};
+use super::{health, TapOutput, TapResource};
 use crate::{
     config::{ComponentKey, Config, ConfigDiff, HealthcheckOptions, OutputId, Resource},
     event::EventArray,
@@ -43,6 +43,7 @@ pub struct RunningTopology {
     abort_tx: mpsc::UnboundedSender<()>,
     watch: (WatchTx, WatchRx),
     pub(crate) running: Arc<AtomicBool>,
+    health_monitor: tokio::task::JoinHandle<()>,
 }
 
 impl RunningTopology {
@@ -58,6 +59,7 @@ impl RunningTopology {
             abort_tx,
             watch: watch::channel(TapResource::default()),
             running: Arc::new(AtomicBool::new(true)),
+            health_monitor: tokio::spawn(health::monitor()),
         }
     }
 
@@ -100,6 +102,7 @@ impl RunningTopology {
     pub fn stop(self) -> impl Future<Output = ()> {
         // Update the API's health endpoint to signal shutdown
         self.running.store(false, Ordering::Relaxed);
+        self.health_monitor.abort();
         // Create handy handles collections of all tasks for the subsequent
         // operations.
         let mut wait_handles = Vec::new();
@@ -221,6 +224,7 @@ impl RunningTopology {
         // We also shutdown any component that is simply being removed entirely.
         let diff = ConfigDiff::new(&self.config, &new_config);
         let buffers = self.shutdown_diff(&diff, &new_config).await;
+        health::update_components(&diff, &new_config);
 
         // Gives windows some time to make available any port
         // released by shutdown components.
@@ -258,6 +262,7 @@ impl RunningTopology {
         warn!("Failed to completely load new configuration. Restoring old configuration.");
 
         let diff = diff.flip();
+        health::update_components(&diff, &self.config);
         if let Some(mut new_pieces) = build_or_log_errors(&self.config, &diff, buffers).await {
             if self
                 .run_healthchecks(&diff, &mut new_pieces, self.config.healthchecks)
@@ -436,6 +441,7 @@ impl RunningTopology {
         for key in &diff.sinks.to_remove {
             debug!(component = %key, "Removing sink.");
             self.remove_inputs(key, diff, new_config).await;
+            self.remove_outputs(key);
         }
 
         // After that, for any changed sinks, we temporarily detach their inputs (not remove) so
@@ -464,6 +470,7 @@ impl RunningTopology {
                 buffer_tx.insert(key.clone(), self.inputs.get(key).unwrap().clone());
             }
             self.remove_inputs(key, diff, new_config).await;
+            self.remove_outputs(key);
         }
 
         // Now that we've disconnected or temporarily detached the inputs to all changed/removed
@@ -547,6 +554,18 @@ impl RunningTopology {
             self.setup_outputs(key, new_pieces).await;
         }
 
+        // Sinks only have outputs when they reroute the events they reject, but those can feed into
+        // transforms and sinks just the same.
+        for key in diff.sinks.changed_and_added() {
+            if new_pieces.outputs.contains_key(key) {
+                debug!(component = %key, "Configuring outputs for sink.");
+                if let Some(task) = new_pieces.tasks.get(key) {
+                    tap_metadata.insert(key, ("sink", task.typetag().to_string()));
+                }
+                self.setup_outputs(key, new_pieces).await;
+            }
+        }
+
         // Now that all possible outputs are configured, we can start wiring up inputs, starting
         // with transforms.
         for key in diff.transforms.changed_and_added() {
@@ -595,6 +614,7 @@ impl RunningTopology {
                 .collect::<HashMap<_, _>>();
             let mut removals = diff.sources.to_remove.clone();
             removals.extend(diff.transforms.to_remove.iter().cloned());
+            removals.extend(diff.sinks.to_remove.iter().cloned());
             self.watch
                 .0
                 .send(TapResource {
@@ -610,8 +630,6 @@ impl RunningTopology {
                         .changed_and_added()
                         .map(|key| key.to_string())
                         .collect(),
-                    // Note, only sources and transforms are relevant. Sinks do
-                    // not have outputs to tap.
                     removals,
                 })
                 .expect("Couldn't broadcast config changes.");
@@ -875,5 +893,14 @@ fn get_changed_outputs(diff: &ConfigDiff, output_ids: Vec<OutputId>) -> Vec<Outp
         );
     }
 
+    for sink_key in &diff.sinks.to_change {
+        changed_outputs.extend(
+            output_ids
+                .iter()
+                .filter(|id| &id.component == sink_key)
+                .cloned(),
+        );
+    }
+
     changed_outputs
 }
@@ -103,6 +103,11 @@ pub fn merged_definition(
                 ));
             }
         }
+        // If the input is the `rejected` output of a sink, the events are passed through as they
+        // were received by that sink.
+        if let Some(inputs) = config.sink_inputs(key) {
+            definition = definition.merge(merged_definition(inputs, config, cache));
+        }
     }
     definition
 }
@@ -202,6 +207,10 @@ pub(super) fn expanded_definitions(
                 // pipeline definitions.
                 definitions.append(&mut expanded_definitions);
             }
+
+        // A sink only outputs the events it rejected, which are unmodified from its inputs.
+        } else if let Some(inputs) = config.sink_inputs(key) {
+            definitions.append(&mut expanded_definitions(inputs, config, cache));
         }
     }
 
@@ -263,6 +272,11 @@ pub trait ComponentContainer {
         merged_definition: &Definition,
     ) -> Option<Vec<Output>>;
 
+    /// Gets the inputs of the sink with the given key, if any.
+    fn sink_inputs(&self, _key: &ComponentKey) -> Option<&[OutputId]> {
+        None
+    }
+
     /// Gets the transform output for the given port.
     ///
     /// Returns Err(()) if there is no transform with the given key
@@ -331,6 +345,10 @@ impl ComponentContainer for Config {
         self.transform(key)
             .map(|source| source.inner.outputs(merged_definition))
     }
+
+    fn sink_inputs(&self, key: &ComponentKey) -> Option<&[OutputId]> {
+        self.sink(key).map(|sink| sink.inputs.as_slice())
+    }
 }
 
 #[cfg(test)]
@@ -2,7 +2,7 @@ use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 
 use tokio::time::Duration;
-use vector_buffers::{BufferConfig, BufferType, WhenFull};
+use vector_buffers::{BufferConfig, BufferType, MemoryBufferSize, WhenFull};
 use vector_core::config::MEMORY_BUFFER_DEFAULT_MAX_EVENTS;
 
 use crate::config::SinkOuter;
@@ -140,7 +140,7 @@ async fn buffer_drop_fan_out() {
     );
     sink_outer.buffer = BufferConfig {
         stages: vec![BufferType::Memory {
-            max_events: MEMORY_BUFFER_DEFAULT_MAX_EVENTS,
+            size: MemoryBufferSize::MaxEvents(MEMORY_BUFFER_DEFAULT_MAX_EVENTS),
             when_full: WhenFull::DropNewest,
         }],
     };
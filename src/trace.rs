@@ -27,9 +27,13 @@ use tracing_subscriber::{
 };
 pub use tracing_tower::{InstrumentableService, InstrumentedService};
 use value::Value;
+use vector_core::config::TelemetryOptions;
 
 use crate::event::LogEvent;
 
+#[cfg(feature = "opentelemetry")]
+mod otlp;
+
 /// BUFFER contains all of the internal log events generated by Vector between the initialization of `tracing` and early
 /// buffering being stopped, which occurs once the topology reports as having successfully started.
 ///
@@ -70,6 +74,9 @@ pub fn init(color: bool, json: bool, levels: &str) {
         .with(metrics_layer)
         .with(broadcast_layer);
 
+    #[cfg(feature = "opentelemetry")]
+    let subscriber = subscriber.with(otlp::OtlpLayer::default().with_filter(fmt_filter.clone()));
+
     #[cfg(feature = "tokio-console")]
     let subscriber = {
         let console_layer = console_subscriber::ConsoleLayer::builder()
@@ -104,6 +111,21 @@ pub fn init(color: bool, json: bool, levels: &str) {
     }
 }
 
+/// Starts exporting Vector's own spans, if configured to.
+///
+/// This must be called from within a Tokio runtime, once `tracing` has been initialized.
+pub fn start_span_export(options: &TelemetryOptions) -> crate::Result<()> {
+    match &options.tracing {
+        #[cfg(feature = "opentelemetry")]
+        Some(tracing) => otlp::start(tracing),
+        #[cfg(not(feature = "opentelemetry"))]
+        Some(_) => {
+            Err("Vector was built without support for exporting spans with OpenTelemetry.".into())
+        }
+        None => Ok(()),
+    }
+}
+
 #[cfg(test)]
 pub fn reset_early_buffer() -> Option<Vec<LogEvent>> {
     get_early_buffer().replace(Vec::new())
@@ -0,0 +1,415 @@
+//! Export of Vector's own `tracing` spans over OTLP.
+//!
+//! Every component runs within a long-lived span carrying its `component_id`, `component_kind` and
+//! `component_type`. These component spans are not exported themselves, as they only close when the
+//! component shuts down. Instead, each span opened within a component starts a new trace, which is
+//! sampled as a whole, and every span in it is annotated with the fields of the component it was
+//! opened in. Like for internal logs, no other span fields are exported, so as to avoid leaking
+//! sensitive data.
+//!
+//! Finished spans are queued, and exported in batches by a background task. If the queue is full,
+//! because the collector can't keep up or is unreachable, further spans are dropped.
+
+use std::{
+    collections::BTreeMap,
+    num::{NonZeroU128, NonZeroU64},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use once_cell::sync::OnceCell;
+use tokio::{sync::mpsc, time::interval};
+use tonic::transport::{ClientTlsConfig, Endpoint};
+use tracing::Subscriber;
+use tracing_core::span::{Attributes, Id, Record};
+use tracing_subscriber::{layer::Context, registry::LookupSpan, Layer};
+use vector_core::config::TracingExportOptions;
+
+use super::SpanFields;
+use crate::opentelemetry::{
+    Common::{any_value, AnyValue, InstrumentationScope, KeyValue},
+    Resource,
+    Trace::{span::SpanKind, ResourceSpans, ScopeSpans, Span},
+    TraceService::{trace_service_client::TraceServiceClient, ExportTraceServiceRequest},
+};
+
+/// The maximum number of finished spans waiting to be exported.
+const MAX_QUEUED_SPANS: usize = 2048;
+
+/// The maximum number of spans exported in a single request.
+const MAX_BATCH_SIZE: usize = 512;
+
+/// How often queued spans are exported, if there are not enough of them to fill a batch.
+const EXPORT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How long to wait for the collector to accept a batch of spans.
+const EXPORT_TIMEOUT: Duration = Duration::from_secs(10);
+
+static EXPORTER: OnceCell<SpanExporter> = OnceCell::new();
+
+struct SpanExporter {
+    sample_rate: u64,
+    tx: mpsc::Sender<Span>,
+}
+
+/// Starts exporting spans to the given collector.
+///
+/// This must be called from within a Tokio runtime, and only once.
+pub(super) fn start(options: &TracingExportOptions) -> crate::Result<()> {
+    let mut endpoint = Endpoint::from_shared(options.endpoint.clone())
+        .map_err(|error| format!("Invalid `telemetry.tracing.endpoint`: {}", error))?
+        .timeout(EXPORT_TIMEOUT);
+    if endpoint.uri().scheme_str() == Some("https") {
+        endpoint = endpoint.tls_config(ClientTlsConfig::new())?;
+    }
+    let client = TraceServiceClient::new(endpoint.connect_lazy());
+
+    let (tx, rx) = mpsc::channel(MAX_QUEUED_SPANS);
+    EXPORTER
+        .set(SpanExporter {
+            sample_rate: options.sample_rate.get(),
+            tx,
+        })
+        .map_err(|_| "Span export has already been started.")?;
+
+    tokio::spawn(run_exporter(rx, client));
+    Ok(())
+}
+
+async fn run_exporter(
+    mut rx: mpsc::Receiver<Span>,
+    mut client: TraceServiceClient<tonic::transport::Channel>,
+) {
+    let resource = resource();
+    let mut ticks = interval(EXPORT_INTERVAL);
+    let mut batch = Vec::with_capacity(MAX_BATCH_SIZE);
+
+    loop {
+        let done = tokio::select! {
+            span = rx.recv() => match span {
+                Some(span) => {
+                    batch.push(span);
+                    if batch.len() < MAX_BATCH_SIZE {
+                        continue;
+                    }
+                    false
+                }
+                None => true,
+            },
+            _ = ticks.tick() => false,
+        };
+
+        if !batch.is_empty() {
+            let request = export_request(&resource, std::mem::take(&mut batch));
+            if let Err(error) = client.export(request).await {
+                warn!(
+                    message = "Failed to export spans.",
+                    %error,
+                    internal_log_rate_secs = 30
+                );
+            }
+        }
+
+        if done {
+            break;
+        }
+    }
+}
+
+fn resource() -> Resource {
+    let mut attributes = vec![
+        key_value("service.name", "vector".to_owned()),
+        key_value("service.version", crate::get_version()),
+    ];
+    if let Ok(hostname) = crate::get_hostname() {
+        attributes.push(key_value("host.name", hostname));
+    }
+    Resource {
+        attributes,
+        dropped_attributes_count: 0,
+    }
+}
+
+fn export_request(resource: &Resource, spans: Vec<Span>) -> ExportTraceServiceRequest {
+    ExportTraceServiceRequest {
+        resource_spans: vec![ResourceSpans {
+            resource: Some(resource.clone()),
+            scope_spans: vec![ScopeSpans {
+                scope: Some(InstrumentationScope {
+                    name: "vector".to_owned(),
+                    version: crate::get_version(),
+                    attributes: Vec::new(),
+                    dropped_attributes_count: 0,
+                }),
+                spans,
+                schema_url: String::new(),
+            }],
+            schema_url: String::new(),
+        }],
+    }
+}
+
+fn key_value(key: impl Into<String>, value: String) -> KeyValue {
+    KeyValue {
+        key: key.into(),
+        value: Some(AnyValue {
+            value: Some(any_value::Value::StringValue(value)),
+        }),
+    }
+}
+
+fn unix_nanos(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_nanos() as u64)
+}
+
+/// The state of a span, as tracked by the exporter.
+enum SpanState {
+    /// A span that isn't exported, but whose component fields are given to the spans opened within
+    /// it.
+    Context(SpanFields),
+    /// A span that is part of a sampled trace.
+    Sampled(SampledSpan),
+    /// A span that is part of a trace which wasn't sampled.
+    Unsampled,
+}
+
+struct SampledSpan {
+    trace_id: [u8; 16],
+    span_id: [u8; 8],
+    parent_span_id: Option<[u8; 8]>,
+    start: SystemTime,
+    fields: SpanFields,
+}
+
+/// A layer that exports spans over OTLP, once [`start`] has been called.
+#[derive(Default)]
+pub(super) struct OtlpLayer;
+
+impl<S> Layer<S> for OtlpLayer
+where
+    S: Subscriber + for<'lookup> LookupSpan<'lookup>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let exporter = match EXPORTER.get() {
+            Some(exporter) => exporter,
+            None => return,
+        };
+        let span = ctx.span(id).expect("span must already exist!");
+
+        let mut fields = SpanFields::default();
+        attrs.values().record(&mut fields);
+
+        let parent = span.parent();
+        let parent_extensions = parent.as_ref().map(|parent| parent.extensions());
+        let state = match parent_extensions
+            .as_ref()
+            .and_then(|extensions| extensions.get::<SpanState>())
+        {
+            Some(SpanState::Unsampled) => SpanState::Unsampled,
+            Some(SpanState::Sampled(parent)) => SpanState::Sampled(SampledSpan {
+                trace_id: parent.trace_id,
+                span_id: new_span_id(),
+                parent_span_id: Some(parent.span_id),
+                start: SystemTime::now(),
+                fields: fields.inherit(&parent.fields),
+            }),
+            parent => {
+                let fields = match parent {
+                    Some(SpanState::Context(parent)) => fields.inherit(parent),
+                    _ => fields,
+                };
+                if attrs.metadata().fields().field("component_kind").is_some() {
+                    SpanState::Context(fields)
+                } else if rand::random::<u64>() % exporter.sample_rate == 0 {
+                    SpanState::Sampled(SampledSpan {
+                        trace_id: rand::random::<NonZeroU128>().get().to_be_bytes(),
+                        span_id: new_span_id(),
+                        parent_span_id: None,
+                        start: SystemTime::now(),
+                        fields,
+                    })
+                } else {
+                    SpanState::Unsampled
+                }
+            }
+        };
+        drop(parent_extensions);
+
+        span.extensions_mut().insert(state);
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            match span.extensions_mut().get_mut::<SpanState>() {
+                Some(SpanState::Context(fields)) => values.record(fields),
+                Some(SpanState::Sampled(sampled)) => values.record(&mut sampled.fields),
+                _ => {}
+            }
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let exporter = match EXPORTER.get() {
+            Some(exporter) => exporter,
+            None => return,
+        };
+        let span = match ctx.span(&id) {
+            Some(span) => span,
+            None => return,
+        };
+        let sampled = match span.extensions_mut().remove::<SpanState>() {
+            Some(SpanState::Sampled(sampled)) => sampled,
+            _ => return,
+        };
+
+        let span = Span {
+            trace_id: sampled.trace_id.to_vec(),
+            span_id: sampled.span_id.to_vec(),
+            trace_state: String::new(),
+            parent_span_id: sampled
+                .parent_span_id
+                .map(|id| id.to_vec())
+                .unwrap_or_default(),
+            name: span.name().to_owned(),
+            kind: SpanKind::Internal as i32,
+            start_time_unix_nano: unix_nanos(sampled.start),
+            end_time_unix_nano: unix_nanos(SystemTime::now()),
+            attributes: sampled.fields.into_attributes(),
+            dropped_attributes_count: 0,
+            events: Vec::new(),
+            dropped_events_count: 0,
+            links: Vec::new(),
+            dropped_links_count: 0,
+            status: None,
+        };
+
+        // If the queue is full, the span is dropped rather than slowing down Vector.
+        let _ = exporter.tx.try_send(span);
+    }
+}
+
+fn new_span_id() -> [u8; 8] {
+    rand::random::<NonZeroU64>().get().to_be_bytes()
+}
+
+impl SpanFields {
+    /// Adds the fields of the given parent span that this span doesn't set itself.
+    fn inherit(mut self, parent: &Self) -> Self {
+        for (name, value) in &parent.0 {
+            self.0.entry(*name).or_insert_with(|| value.clone());
+        }
+        self
+    }
+
+    fn into_attributes(self) -> Vec<KeyValue> {
+        self.0
+            .into_iter()
+            .collect::<BTreeMap<_, _>>()
+            .into_iter()
+            .map(|(name, value)| key_value(name, value.to_string_lossy()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use tokio::sync::mpsc::UnboundedSender;
+    use tonic::{transport::Server, Request, Response, Status};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use super::*;
+    use crate::{
+        opentelemetry::TraceService::{
+            trace_service_server::{TraceService, TraceServiceServer},
+            ExportTraceServiceResponse,
+        },
+        test_util::next_addr,
+    };
+
+    /// A collector that forwards the spans it receives.
+    struct MockCollector {
+        tx: UnboundedSender<Span>,
+    }
+
+    #[tonic::async_trait]
+    impl TraceService for MockCollector {
+        async fn export(
+            &self,
+            request: Request<ExportTraceServiceRequest>,
+        ) -> Result<Response<ExportTraceServiceResponse>, Status> {
+            let spans = request
+                .into_inner()
+                .resource_spans
+                .into_iter()
+                .flat_map(|resource_spans| resource_spans.scope_spans)
+                .flat_map(|scope_spans| scope_spans.spans);
+            for span in spans {
+                let _ = self.tx.send(span);
+            }
+            Ok(Response::new(ExportTraceServiceResponse {}))
+        }
+    }
+
+    fn attribute<'a>(span: &'a Span, key: &str) -> Option<&'a str> {
+        span.attributes
+            .iter()
+            .find(|attribute| attribute.key == key)
+            .and_then(|attribute| attribute.value.as_ref())
+            .and_then(|value| match &value.value {
+                Some(any_value::Value::StringValue(value)) => Some(value.as_str()),
+                _ => None,
+            })
+    }
+
+    #[tokio::test]
+    async fn exports_spans_with_component_attributes() {
+        let address: SocketAddr = next_addr();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        tokio::spawn(
+            Server::builder()
+                .add_service(TraceServiceServer::new(MockCollector { tx }))
+                .serve(address),
+        );
+
+        start(&TracingExportOptions {
+            endpoint: format!("http://{}", address),
+            sample_rate: NonZeroU64::new(1).unwrap(),
+        })
+        .unwrap();
+
+        let subscriber = tracing_subscriber::registry().with(OtlpLayer::default());
+        tracing::subscriber::with_default(subscriber, || {
+            let component = tracing::error_span!(
+                "sink",
+                component_kind = "sink",
+                component_id = "out",
+                component_type = "console",
+            );
+            let _component = component.enter();
+            let request = tracing::info_span!("request", request_id = 1);
+            let _request = request.enter();
+            let _send = tracing::info_span!("send").entered();
+        });
+
+        let mut spans = Vec::new();
+        while spans.len() < 2 {
+            spans.push(rx.recv().await.unwrap());
+        }
+
+        // Spans are exported as they close, so the innermost span comes first.
+        let (send, request) = (&spans[0], &spans[1]);
+        assert_eq!(send.name, "send");
+        assert_eq!(request.name, "request");
+        assert_eq!(send.trace_id, request.trace_id);
+        assert_eq!(send.parent_span_id, request.span_id);
+        assert!(request.parent_span_id.is_empty());
+        for span in &spans {
+            assert_eq!(attribute(span, "component_id"), Some("out"));
+            assert_eq!(attribute(span, "component_kind"), Some("sink"));
+            assert_eq!(attribute(span, "component_type"), Some("console"));
+            assert_eq!(attribute(span, "request_id"), None);
+        }
+    }
+}
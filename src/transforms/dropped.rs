@@ -0,0 +1,122 @@
+//! Support for rerouting the events a transform discards to its `dropped` output.
+//!
+//! Transforms that can discard events expose a `reroute_dropped` option. When enabled, discarded
+//! events are forwarded, unmodified except for an annotation describing why they were dropped, to
+//! a specially-named output instead.
+
+use std::collections::BTreeMap;
+
+use lookup::path;
+use value::Kind;
+use vector_core::{config::LogNamespace, schema::Definition};
+
+use crate::{
+    config::{log_schema, ComponentKey, DataType, Output},
+    event::Event,
+    schema,
+};
+
+/// The name of the output that discarded events are rerouted to.
+pub const DROPPED: &str = "dropped";
+
+/// Builds the schema definition of the `dropped` output.
+///
+/// When an event is dropped and rerouted, we keep the original event, but also annotate it with
+/// additional metadata.
+pub fn dropped_definition(input_definition: &schema::Definition) -> schema::Definition {
+    let mut dropped_definition = Definition::new_with_default_metadata(
+        Kind::never(),
+        input_definition.log_namespaces().clone(),
+    );
+
+    if input_definition
+        .log_namespaces()
+        .contains(&LogNamespace::Legacy)
+    {
+        dropped_definition = dropped_definition.merge(input_definition.clone().with_field(
+            log_schema().metadata_key(),
+            Kind::object(BTreeMap::from([
+                ("reason".into(), Kind::bytes()),
+                ("message".into(), Kind::bytes()),
+                ("component_id".into(), Kind::bytes()),
+                ("component_type".into(), Kind::bytes()),
+                ("component_kind".into(), Kind::bytes()),
+            ])),
+            Some("metadata"),
+        ));
+    }
+
+    if input_definition
+        .log_namespaces()
+        .contains(&LogNamespace::Vector)
+    {
+        dropped_definition = dropped_definition.merge(
+            input_definition
+                .clone()
+                .with_metadata_field("reason", Kind::bytes())
+                .with_metadata_field("message", Kind::bytes())
+                .with_metadata_field("component_id", Kind::bytes())
+                .with_metadata_field("component_type", Kind::bytes())
+                .with_metadata_field("component_kind", Kind::bytes()),
+        );
+    }
+
+    dropped_definition
+}
+
+/// Builds the `dropped` output for a transform whose discarded events are of the given type.
+pub fn dropped_output(ty: DataType, input_definition: &schema::Definition) -> Output {
+    Output::default(ty)
+        .with_schema_definition(dropped_definition(input_definition))
+        .with_port(DROPPED)
+}
+
+/// Annotates an event with why the given transform dropped it.
+pub fn annotate_dropped(
+    event: &mut Event,
+    component_key: Option<&ComponentKey>,
+    component_type: &'static str,
+    reason: &str,
+    message: &str,
+) {
+    let data = || {
+        serde_json::json!({
+            "reason": reason,
+            "message": message,
+            "component_id": component_key,
+            "component_type": component_type,
+            "component_kind": "transform",
+        })
+    };
+
+    match event {
+        Event::Log(ref mut log) => match log.namespace() {
+            LogNamespace::Legacy => {
+                log.insert(log_schema().metadata_key().concat(path!("dropped")), data());
+            }
+            LogNamespace::Vector => {
+                log.metadata_mut()
+                    .value_mut()
+                    .insert(path!("vector", "dropped"), data());
+            }
+        },
+        Event::Metric(ref mut metric) => {
+            let m = log_schema().metadata_key();
+            metric.insert_tag(format!("{}.dropped.reason", m), reason.into());
+            metric.insert_tag(
+                format!("{}.dropped.component_id", m),
+                component_key
+                    .map(ToString::to_string)
+                    .unwrap_or_else(String::new),
+            );
+            metric.insert_tag(
+                format!("{}.dropped.component_type", m),
+                component_type.into(),
+            );
+            metric.insert_tag(format!("{}.dropped.component_kind", m), "transform".into());
+        }
+        Event::Trace(ref mut trace) => {
+            trace.insert(log_schema().metadata_key(), data());
+        }
+    }
+}
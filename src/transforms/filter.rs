@@ -5,13 +5,16 @@ use vector_config::configurable_component;
 use crate::{
     conditions::{AnyCondition, Condition},
     config::{
-        DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext,
+        ComponentKey, DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext,
         TransformDescription,
     },
     event::Event,
     internal_events::FilterEventDiscarded,
     schema,
-    transforms::{FunctionTransform, OutputBuffer, Transform},
+    transforms::{
+        dropped::{annotate_dropped, dropped_output, DROPPED},
+        SyncTransform, Transform, TransformOutputsBuf,
+    },
 };
 
 /// Configuration for the `filter` transform.
@@ -21,11 +24,21 @@ use crate::{
 pub struct FilterConfig {
     #[configurable(derived)]
     condition: AnyCondition,
+
+    /// Reroutes events that do not match the condition to a named output instead of discarding them.
+    ///
+    /// When enabled, events that would have been discarded are forwarded to a specially-named output,
+    /// `dropped`. They are annotated with additional fields describing why they were dropped.
+    #[serde(default)]
+    reroute_dropped: bool,
 }
 
 impl From<AnyCondition> for FilterConfig {
     fn from(condition: AnyCondition) -> Self {
-        Self { condition }
+        Self {
+            condition,
+            reroute_dropped: false,
+        }
     }
 }
 
@@ -47,17 +60,27 @@ impl GenerateConfig for FilterConfig {
 #[typetag::serde(name = "filter")]
 impl TransformConfig for FilterConfig {
     async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
-        Ok(Transform::function(Filter::new(
-            self.condition.build(&context.enrichment_tables)?,
-        )))
+        let mut filter = Filter::new(self.condition.build(&context.enrichment_tables)?);
+        if self.reroute_dropped {
+            filter = filter.reroute_dropped(context.key.clone());
+        }
+        Ok(Transform::synchronous(filter))
     }
 
     fn input(&self) -> Input {
         Input::all()
     }
 
-    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
-        vec![Output::default(DataType::all())]
+    fn outputs(&self, input_definition: &schema::Definition) -> Vec<Output> {
+        let default_output = Output::default(DataType::all());
+        if self.reroute_dropped {
+            vec![
+                default_output,
+                dropped_output(DataType::all(), input_definition),
+            ]
+        } else {
+            vec![default_output]
+        }
     }
 
     fn enable_concurrency(&self) -> bool {
@@ -77,6 +100,8 @@ pub struct Filter {
     last_emission: Instant,
     emissions_max_delay: Duration,
     emissions_deferred: u64,
+    /// Set when discarded events are rerouted, holding the key used to annotate them.
+    reroute_dropped: Option<Option<ComponentKey>>,
 }
 
 impl Filter {
@@ -86,15 +111,31 @@ impl Filter {
             last_emission: Instant::now(),
             emissions_max_delay: Duration::new(2, 0),
             emissions_deferred: 0,
+            reroute_dropped: None,
         }
     }
+
+    /// Sends the events that do not match the condition to the `dropped` output.
+    pub fn reroute_dropped(mut self, component_key: Option<ComponentKey>) -> Self {
+        self.reroute_dropped = Some(component_key);
+        self
+    }
 }
 
-impl FunctionTransform for Filter {
-    fn transform(&mut self, output: &mut OutputBuffer, event: Event) {
-        let (result, event) = self.condition.check(event);
+impl SyncTransform for Filter {
+    fn transform(&mut self, event: Event, output: &mut TransformOutputsBuf) {
+        let (result, mut event) = self.condition.check(event);
         if result {
             output.push(event);
+        } else if let Some(component_key) = &self.reroute_dropped {
+            annotate_dropped(
+                &mut event,
+                component_key.as_ref(),
+                "filter",
+                "filtered",
+                "Event did not match the filter condition.",
+            );
+            output.push_named(DROPPED, event);
         } else if self.last_emission.elapsed() >= self.emissions_max_delay {
             emit!(FilterEventDiscarded {
                 total: self.emissions_deferred,
@@ -126,4 +167,29 @@ mod test {
         let result = transform_one(&mut filter, event).unwrap();
         assert_eq!(result.metadata(), &metadata);
     }
+
+    #[test]
+    fn reroutes_dropped_events() {
+        let mut filter =
+            Filter::new(Condition::IsMetric).reroute_dropped(Some(ComponentKey::from("filter")));
+        let mut outputs = TransformOutputsBuf::new_with_capacity(
+            vec![
+                Output::default(DataType::all()),
+                Output::default(DataType::all()).with_port(DROPPED),
+            ],
+            1,
+        );
+
+        let event = Event::from(LogEvent::from("message"));
+        filter.transform(event, &mut outputs);
+
+        assert_eq!(outputs.drain().count(), 0);
+        let dropped = outputs.drain_named(DROPPED).collect::<Vec<_>>();
+        assert_eq!(dropped.len(), 1);
+        let log = dropped[0].as_log();
+        assert_eq!(log["message"], "message".into());
+        assert_eq!(log["metadata.dropped.reason"], "filtered".into());
+        assert_eq!(log["metadata.dropped.component_id"], "filter".into());
+        assert_eq!(log["metadata.dropped.component_type"], "filter".into());
+    }
 }
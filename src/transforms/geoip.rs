@@ -1,9 +1,10 @@
-use std::{str::FromStr, sync::Arc};
+use std::str::FromStr;
 
 use serde::Serialize;
 use vector_config::configurable_component;
 
 use crate::{
+    common::geoip::{DatabaseKind, ReloadingDatabase},
     config::{
         DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext,
         TransformDescription,
@@ -28,7 +29,8 @@ pub struct GeoipConfig {
     /// Path to the [MaxMind GeoIP2](https://dev.maxmind.com/geoip/geoip2/downloadable) or [GeoLite2 binary city
     /// database file](https://dev.maxmind.com/geoip/geoip2/geolite2/#Download_Access) (**GeoLite2-City.mmdb**).
     ///
-    /// Other databases, such as the country database, are not supported.
+    /// The country, ISP, ASN, anonymous IP, and connection type databases are also supported. The
+    /// database is reloaded when the file is replaced.
     pub database: String,
 
     /// The default field to insert the resulting GeoIP data into.
@@ -53,8 +55,7 @@ pub struct GeoipConfig {
 #[derivative(Debug)]
 pub struct Geoip {
     #[derivative(Debug = "ignore")]
-    pub dbreader: Arc<maxminddb::Reader<Vec<u8>>>,
-    pub database: String,
+    database: ReloadingDatabase,
     pub source: String,
     pub target: String,
     pub locale: String,
@@ -93,12 +94,14 @@ impl GenerateConfig for GeoipConfig {
 #[typetag::serde(name = "geoip")]
 impl TransformConfig for GeoipConfig {
     async fn build(&self, _context: &TransformContext) -> Result<Transform> {
-        Ok(Transform::function(Geoip::new(
+        let geoip = Geoip::new(
             self.database.clone(),
             self.source.clone(),
             self.target.clone(),
             self.locale.clone(),
-        )?))
+        )?;
+        geoip.database.spawn_reloader();
+        Ok(Transform::function(geoip))
     }
 
     fn input(&self) -> Input {
@@ -114,12 +117,6 @@ impl TransformConfig for GeoipConfig {
     }
 }
 
-// MaxMind GeoIP database files have a type field we can use to recognize specific
-// products. If we encounter one of these two types, we look for ASN/ISP information;
-// otherwise we expect to be working with a City database.
-const ASN_DATABASE_TYPE: &str = "GeoLite2-ASN";
-const ISP_DATABASE_TYPE: &str = "GeoIP2-ISP";
-
 impl Geoip {
     pub fn new(
         database: String,
@@ -128,18 +125,12 @@ impl Geoip {
         locale: String,
     ) -> crate::Result<Self> {
         Ok(Geoip {
-            dbreader: Arc::new(maxminddb::Reader::open_readfile(database.clone())?),
-            database,
+            database: ReloadingDatabase::open(database)?,
             source,
             target,
             locale,
         })
     }
-
-    fn has_isp_db(&self) -> bool {
-        self.dbreader.metadata.database_type == ASN_DATABASE_TYPE
-            || self.dbreader.metadata.database_type == ISP_DATABASE_TYPE
-    }
 }
 
 #[derive(Default, Serialize)]
@@ -150,6 +141,21 @@ struct Isp<'a> {
     organization: &'a str,
 }
 
+#[derive(Default, Serialize)]
+struct AnonymousIp {
+    is_anonymous: bool,
+    is_anonymous_vpn: bool,
+    is_hosting_provider: bool,
+    is_public_proxy: bool,
+    is_residential_proxy: bool,
+    is_tor_exit_node: bool,
+}
+
+#[derive(Default, Serialize)]
+struct ConnectionType<'a> {
+    connection_type: &'a str,
+}
+
 #[derive(Default, Serialize)]
 struct City<'a> {
     city_name: &'a str,
@@ -167,7 +173,10 @@ struct City<'a> {
 
 impl FunctionTransform for Geoip {
     fn transform(&mut self, output: &mut OutputBuffer, mut event: Event) {
+        let database = self.database.load();
         let mut isp: Isp = Default::default();
+        let mut anonymous_ip: AnonymousIp = Default::default();
+        let mut connection_type: ConnectionType = Default::default();
         let mut city: City = Default::default();
         let target_field = self.target.clone();
         let ipaddress = event
@@ -176,9 +185,9 @@ impl FunctionTransform for Geoip {
             .map(|s| s.to_string_lossy());
         if let Some(ipaddress) = &ipaddress {
             match FromStr::from_str(ipaddress) {
-                Ok(ip) => {
-                    if self.has_isp_db() {
-                        if let Ok(data) = self.dbreader.lookup::<maxminddb::geoip2::Isp>(ip) {
+                Ok(ip) => match database.kind {
+                    DatabaseKind::Asn | DatabaseKind::Isp => {
+                        if let Ok(data) = database.reader.lookup::<maxminddb::geoip2::Isp>(ip) {
                             if let Some(as_number) = data.autonomous_system_number {
                                 isp.autonomous_system_number = as_number as i64;
                             }
@@ -192,68 +201,97 @@ impl FunctionTransform for Geoip {
                                 isp.organization = organization;
                             }
                         }
-                    } else if let Ok(data) = self.dbreader.lookup::<maxminddb::geoip2::City>(ip) {
-                        if let Some(city_names) = data.city.and_then(|c| c.names) {
-                            if let Some(city_name) = city_names.get("en") {
-                                city.city_name = city_name;
-                            }
-                        }
-
-                        if let Some(continent_code) = data.continent.and_then(|c| c.code) {
-                            city.continent_code = continent_code;
+                    }
+                    DatabaseKind::AnonymousIp => {
+                        if let Ok(data) =
+                            database.reader.lookup::<maxminddb::geoip2::AnonymousIp>(ip)
+                        {
+                            // Flags that don't apply to an address are left out of the database.
+                            anonymous_ip.is_anonymous = data.is_anonymous.unwrap_or(false);
+                            anonymous_ip.is_anonymous_vpn = data.is_anonymous_vpn.unwrap_or(false);
+                            anonymous_ip.is_hosting_provider =
+                                data.is_hosting_provider.unwrap_or(false);
+                            anonymous_ip.is_public_proxy = data.is_public_proxy.unwrap_or(false);
+                            anonymous_ip.is_residential_proxy =
+                                data.is_residential_proxy.unwrap_or(false);
+                            anonymous_ip.is_tor_exit_node = data.is_tor_exit_node.unwrap_or(false);
                         }
-
-                        if let Some(country) = data.country {
-                            if let Some(country_code) = country.iso_code {
-                                city.country_code = country_code;
-                            }
-                            if let Some(country_name) = country
-                                .names
-                                .as_ref()
-                                .and_then(|names| names.get(&*self.locale))
-                            {
-                                city.country_name = country_name;
+                    }
+                    DatabaseKind::ConnectionType => {
+                        if let Ok(data) = database
+                            .reader
+                            .lookup::<maxminddb::geoip2::ConnectionType>(ip)
+                        {
+                            if let Some(kind) = data.connection_type {
+                                connection_type.connection_type = kind;
                             }
                         }
-
-                        if let Some(location) = data.location {
-                            if let Some(time_zone) = location.time_zone {
-                                city.timezone = time_zone;
+                    }
+                    DatabaseKind::City => {
+                        if let Ok(data) = database.reader.lookup::<maxminddb::geoip2::City>(ip) {
+                            if let Some(city_names) = data.city.and_then(|c| c.names) {
+                                if let Some(city_name) = city_names.get("en") {
+                                    city.city_name = city_name;
+                                }
                             }
-                            if let Some(latitude) = location.latitude {
-                                city.latitude = latitude.to_string();
+
+                            if let Some(continent_code) = data.continent.and_then(|c| c.code) {
+                                city.continent_code = continent_code;
                             }
 
-                            if let Some(longitude) = location.longitude {
-                                city.longitude = longitude.to_string();
+                            if let Some(country) = data.country {
+                                if let Some(country_code) = country.iso_code {
+                                    city.country_code = country_code;
+                                }
+                                if let Some(country_name) = country
+                                    .names
+                                    .as_ref()
+                                    .and_then(|names| names.get(&*self.locale))
+                                {
+                                    city.country_name = country_name;
+                                }
                             }
 
-                            if let Some(metro_code) = location.metro_code {
-                                city.metro_code = metro_code.to_string();
+                            if let Some(location) = data.location {
+                                if let Some(time_zone) = location.time_zone {
+                                    city.timezone = time_zone;
+                                }
+                                if let Some(latitude) = location.latitude {
+                                    city.latitude = latitude.to_string();
+                                }
+
+                                if let Some(longitude) = location.longitude {
+                                    city.longitude = longitude.to_string();
+                                }
+
+                                if let Some(metro_code) = location.metro_code {
+                                    city.metro_code = metro_code.to_string();
+                                }
                             }
-                        }
 
-                        // last subdivision is most specific per https://github.com/maxmind/GeoIP2-java/blob/39385c6ce645374039450f57208b886cf87ade47/src/main/java/com/maxmind/geoip2/model/AbstractCityResponse.java#L96-L107
-                        if let Some(subdivision) = data.subdivisions.as_ref().and_then(|s| s.last())
-                        {
-                            if let Some(name) = subdivision
-                                .names
-                                .as_ref()
-                                .and_then(|names| names.get(&*self.locale))
+                            // last subdivision is most specific per https://github.com/maxmind/GeoIP2-java/blob/39385c6ce645374039450f57208b886cf87ade47/src/main/java/com/maxmind/geoip2/model/AbstractCityResponse.java#L96-L107
+                            if let Some(subdivision) =
+                                data.subdivisions.as_ref().and_then(|s| s.last())
                             {
-                                city.region_name = name;
+                                if let Some(name) = subdivision
+                                    .names
+                                    .as_ref()
+                                    .and_then(|names| names.get(&*self.locale))
+                                {
+                                    city.region_name = name;
+                                }
+
+                                if let Some(iso_code) = subdivision.iso_code {
+                                    city.region_code = iso_code
+                                }
                             }
 
-                            if let Some(iso_code) = subdivision.iso_code {
-                                city.region_code = iso_code
+                            if let Some(postal_code) = data.postal.and_then(|p| p.code) {
+                                city.postal_code = postal_code;
                             }
                         }
-
-                        if let Some(postal_code) = data.postal.and_then(|p| p.code) {
-                            city.postal_code = postal_code;
-                        }
                     }
-                }
+                },
                 Err(error) => {
                     emit!(GeoipIpAddressParseError {
                         error,
@@ -267,10 +305,11 @@ impl FunctionTransform for Geoip {
             });
         };
 
-        let json_value = if self.has_isp_db() {
-            serde_json::to_value(isp)
-        } else {
-            serde_json::to_value(city)
+        let json_value = match database.kind {
+            DatabaseKind::Asn | DatabaseKind::Isp => serde_json::to_value(isp),
+            DatabaseKind::AnonymousIp => serde_json::to_value(anonymous_ip),
+            DatabaseKind::ConnectionType => serde_json::to_value(connection_type),
+            DatabaseKind::City => serde_json::to_value(city),
         };
         if let Ok(json_value) = json_value {
             event.as_mut_log().insert(target_field.as_str(), json_value);
@@ -468,6 +507,76 @@ mod tests {
         }
     }
 
+    #[test]
+    fn geoip_anonymous_ip_lookup_success() {
+        let mut log = LogEvent::default();
+        let _ = log.insert("remote_addr", "186.30.236.233");
+        let _ = log.insert("request_path", "foo/bar");
+
+        let new_event = parse_one(log.into(), "tests/data/GeoIP2-Anonymous-IP-Test.mmdb");
+
+        let mut exp_geoip_attr = HashMap::new();
+        exp_geoip_attr.insert("is_anonymous", "true");
+        exp_geoip_attr.insert("is_anonymous_vpn", "false");
+        exp_geoip_attr.insert("is_hosting_provider", "false");
+        exp_geoip_attr.insert("is_public_proxy", "true");
+        exp_geoip_attr.insert("is_residential_proxy", "false");
+        exp_geoip_attr.insert("is_tor_exit_node", "false");
+
+        for field in exp_geoip_attr.keys() {
+            let k = format!("geo.{}", field).to_string();
+            let geodata = new_event
+                .as_log()
+                .get(k.as_str())
+                .unwrap()
+                .to_string_lossy();
+            assert_eq!(&geodata, exp_geoip_attr.get(field).expect("field exists"));
+        }
+    }
+
+    #[test]
+    fn geoip_connection_type_lookup_success() {
+        let mut log = LogEvent::default();
+        let _ = log.insert("remote_addr", "1.0.0.1");
+        let _ = log.insert("request_path", "foo/bar");
+
+        let new_event = parse_one(log.into(), "tests/data/GeoIP2-Connection-Type-Test.mmdb");
+
+        let geodata = new_event
+            .as_log()
+            .get("geo.connection_type")
+            .unwrap()
+            .to_string_lossy();
+        assert_eq!(geodata, "Dialup");
+    }
+
+    #[test]
+    fn geoip_reloads_replaced_database() {
+        let path = crate::test_util::temp_file();
+        std::fs::copy("tests/data/GeoIP2-City-Test.mmdb", &path).unwrap();
+
+        let mut augment = Geoip::new(
+            path.to_str().unwrap().to_string(),
+            "remote_addr".to_string(),
+            "geo".to_string(),
+            "en".to_string(),
+        )
+        .unwrap();
+
+        // Move the new database into place, as database updaters do.
+        let replacement = crate::test_util::temp_file();
+        std::fs::copy("tests/data/GeoIP2-ISP-Test.mmdb", &replacement).unwrap();
+        std::fs::rename(&replacement, &path).unwrap();
+        augment.database.reload_if_changed();
+
+        let mut log = LogEvent::default();
+        let _ = log.insert("remote_addr", "208.192.1.2");
+        let new_event = transform_one(&mut augment, log.into()).unwrap();
+
+        let geodata = new_event.as_log().get("geo.isp").unwrap().to_string_lossy();
+        assert_eq!(geodata, "Verizon Business");
+    }
+
     fn parse_one(event: Event, database: &str) -> Event {
         let mut augment = Geoip::new(
             database.to_string(),
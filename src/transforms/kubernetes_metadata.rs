@@ -0,0 +1,686 @@
+//! This mod implements the `kubernetes_metadata` transform.
+//! It enriches events coming from `Pod`s with the metadata of the `Pod`, its
+//! `Namespace` and its `Node`, the same way the `kubernetes_logs` source does
+//! for the logs it reads, for events received by any other source.
+
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use k8s_openapi::{
+    api::core::v1::{Namespace, Node, Pod},
+    apimachinery::pkg::apis::meta::v1::Time,
+};
+use kube::{
+    api::{Api, ListParams},
+    runtime::{
+        reflector::{self, store::Store, ObjectRef},
+        watcher,
+    },
+};
+use snafu::Snafu;
+use tokio::task::JoinHandle;
+use vector_config::configurable_component;
+
+use crate::{
+    config::{
+        DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext,
+        TransformDescription,
+    },
+    event::Event,
+    kubernetes::{
+        self, custom_reflector, custom_reflector_with, namespace_metadata_annotator,
+        namespace_metadata_annotator::NamespaceMetadataAnnotator, node_metadata_annotator,
+        node_metadata_annotator::NodeMetadataAnnotator, pod_metadata_annotator,
+        pod_metadata_annotator::PodMetadataAnnotator,
+    },
+    schema,
+    transforms::{FunctionTransform, OutputBuffer, Transform},
+};
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display(
+        "At least one of `pod_uid_field`, `pod_ip_field`, or `pod_name_field` and `pod_namespace_field` must be set"
+    ))]
+    NoPodAssociation,
+}
+
+/// Configuration for the `kubernetes_metadata` transform.
+#[configurable_component(transform)]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields, default)]
+pub struct KubernetesMetadataConfig {
+    /// The field, or tag for metrics, holding the `uid` of the `Pod` the event came from.
+    pod_uid_field: Option<String>,
+
+    /// The field, or tag for metrics, holding the IP address of the `Pod` the event came from.
+    ///
+    /// `Pod`s using the host network share the IP address of their `Node`, so they can't be
+    /// looked up by IP address.
+    pod_ip_field: Option<String>,
+
+    /// The field, or tag for metrics, holding the `name` of the `Pod` the event came from.
+    ///
+    /// Must be set together with `pod_namespace_field`.
+    pod_name_field: Option<String>,
+
+    /// The field, or tag for metrics, holding the `namespace` of the `Pod` the event came from.
+    ///
+    /// Must be set together with `pod_name_field`.
+    pod_namespace_field: Option<String>,
+
+    /// Specifies the label selector to filter the `Pod`s to cache with.
+    label_selector: String,
+
+    /// Specifies the field selector to filter the `Pod`s to cache with.
+    ///
+    /// When Vector runs as a `DaemonSet` and only receives events from `Pod`s on the same `Node`,
+    /// `spec.nodeName=${VECTOR_SELF_NODE_NAME}` keeps only those `Pod`s in the cache.
+    field_selector: String,
+
+    /// Specifies the label selector to filter the `Namespace`s to cache with.
+    namespace_label_selector: String,
+
+    #[configurable(derived)]
+    pod_annotation_fields: pod_metadata_annotator::FieldsSpec,
+
+    #[configurable(derived)]
+    namespace_annotation_fields: namespace_metadata_annotator::FieldsSpec,
+
+    #[configurable(derived)]
+    node_annotation_fields: node_metadata_annotator::FieldsSpec,
+
+    /// Optional path to a kubeconfig file readable by Vector. If not set,
+    /// Vector will try to connect to Kubernetes using in-cluster configuration.
+    kube_config_file: Option<PathBuf>,
+
+    /// How long to delay removing entries from our map when we receive a deletion
+    /// event from the watched stream.
+    delay_deletion_ms: usize,
+}
+
+impl Default for KubernetesMetadataConfig {
+    fn default() -> Self {
+        Self {
+            pod_uid_field: None,
+            pod_ip_field: None,
+            pod_name_field: None,
+            pod_namespace_field: None,
+            label_selector: "".to_string(),
+            field_selector: "".to_string(),
+            namespace_label_selector: "".to_string(),
+            pod_annotation_fields: pod_metadata_annotator::FieldsSpec::default(),
+            namespace_annotation_fields: namespace_metadata_annotator::FieldsSpec::default(),
+            node_annotation_fields: node_metadata_annotator::FieldsSpec::default(),
+            kube_config_file: None,
+            delay_deletion_ms: default_delay_deletion_ms(),
+        }
+    }
+}
+
+const fn default_delay_deletion_ms() -> usize {
+    60_000
+}
+
+inventory::submit! {
+    TransformDescription::new::<KubernetesMetadataConfig>("kubernetes_metadata")
+}
+
+impl GenerateConfig for KubernetesMetadataConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(&Self {
+            pod_ip_field: Some("host".to_string()),
+            ..Default::default()
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "kubernetes_metadata")]
+impl TransformConfig for KubernetesMetadataConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        let association = PodAssociation::new(self)?;
+
+        let client = kubernetes::client(self.kube_config_file.as_deref()).await?;
+        let delay_deletion = Duration::from_millis(self.delay_deletion_ms as u64);
+
+        let pods = Api::<Pod>::all(client.clone());
+        let pod_watcher = watcher(
+            pods,
+            ListParams {
+                field_selector: Some(self.field_selector.clone()),
+                label_selector: Some(self.label_selector.clone()),
+                ..Default::default()
+            },
+        );
+        let pod_store_w = reflector::store::Writer::default();
+        let pod_state = pod_store_w.as_reader();
+        let pod_index = Arc::<RwLock<PodIndex>>::default();
+        let pod_reflector = tokio::spawn(custom_reflector_with(
+            pod_store_w,
+            pod_watcher,
+            delay_deletion,
+            {
+                let pod_index = Arc::clone(&pod_index);
+                move |event| {
+                    pod_index
+                        .write()
+                        .expect("Pod index lock poisoned")
+                        .apply(event)
+                }
+            },
+        ));
+
+        let namespaces = Api::<Namespace>::all(client.clone());
+        let ns_watcher = watcher(
+            namespaces,
+            ListParams {
+                label_selector: Some(self.namespace_label_selector.clone()),
+                ..Default::default()
+            },
+        );
+        let ns_store_w = reflector::store::Writer::default();
+        let ns_state = ns_store_w.as_reader();
+        let ns_reflector = tokio::spawn(custom_reflector(ns_store_w, ns_watcher, delay_deletion));
+
+        let nodes = Api::<Node>::all(client);
+        let node_watcher = watcher(nodes, ListParams::default());
+        let node_store_w = reflector::store::Writer::default();
+        let node_state = node_store_w.as_reader();
+        let node_reflector =
+            tokio::spawn(custom_reflector(node_store_w, node_watcher, delay_deletion));
+
+        let mut transform = KubernetesMetadata::new(
+            association,
+            PodMetadataAnnotator::new(pod_state.clone(), self.pod_annotation_fields.clone()),
+            NamespaceMetadataAnnotator::new(ns_state, self.namespace_annotation_fields.clone()),
+            NodeMetadataAnnotator::new(node_state, self.node_annotation_fields.clone()),
+            pod_state,
+            pod_index,
+        );
+        transform.reflectors = Some(Arc::new(Reflectors(vec![
+            pod_reflector,
+            ns_reflector,
+            node_reflector,
+        ])));
+
+        Ok(Transform::function(transform))
+    }
+
+    fn input(&self) -> Input {
+        Input::new(DataType::Metric | DataType::Log)
+    }
+
+    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
+        vec![Output::default(DataType::Metric | DataType::Log)]
+    }
+
+    fn transform_type(&self) -> &'static str {
+        "kubernetes_metadata"
+    }
+}
+
+/// The fields used to find the `Pod` an event came from.
+#[derive(Clone, Debug)]
+struct PodAssociation {
+    uid_field: Option<String>,
+    ip_field: Option<String>,
+    name_fields: Option<(String, String)>,
+}
+
+impl PodAssociation {
+    fn new(config: &KubernetesMetadataConfig) -> Result<Self, BuildError> {
+        let name_fields = config
+            .pod_namespace_field
+            .clone()
+            .zip(config.pod_name_field.clone());
+        if config.pod_uid_field.is_none() && config.pod_ip_field.is_none() && name_fields.is_none()
+        {
+            return Err(BuildError::NoPodAssociation);
+        }
+
+        Ok(Self {
+            uid_field: config.pod_uid_field.clone(),
+            ip_field: config.pod_ip_field.clone(),
+            name_fields,
+        })
+    }
+}
+
+/// The reflectors feeding the caches, which are stopped once the transform is dropped.
+struct Reflectors(Vec<JoinHandle<()>>);
+
+impl Drop for Reflectors {
+    fn drop(&mut self) {
+        for reflector in &self.0 {
+            reflector.abort();
+        }
+    }
+}
+
+/// The `Pod`s in the cache, indexed by UID and by IP address.
+///
+/// It's kept up to date by the reflector of the cache, and shared by all the clones of the
+/// transform.
+#[derive(Default)]
+struct PodIndex {
+    by_uid: HashMap<String, ObjectRef<Pod>>,
+    /// Deleted `Pod`s are kept for a while, so an IP address may have been reused by a newer `Pod`,
+    /// which should be the one found. The creation time of the `Pod`s is kept to find it.
+    by_ip: HashMap<String, (Option<Time>, ObjectRef<Pod>)>,
+}
+
+impl PodIndex {
+    fn apply(&mut self, event: &watcher::Event<Pod>) {
+        match event {
+            watcher::Event::Applied(pod) => self.insert(pod),
+            watcher::Event::Deleted(pod) => self.remove(pod),
+            watcher::Event::Restarted(pods) => {
+                self.by_uid.clear();
+                self.by_ip.clear();
+                for pod in pods {
+                    self.insert(pod);
+                }
+            }
+        }
+    }
+
+    fn insert(&mut self, pod: &Pod) {
+        let obj = ObjectRef::from_obj(pod);
+        if let Some(uid) = &pod.metadata.uid {
+            self.by_uid.insert(uid.clone(), obj.clone());
+        }
+
+        let created = &pod.metadata.creation_timestamp;
+        for ip in pod_ips(pod) {
+            match self.by_ip.get(ip) {
+                Some((newest, newest_obj)) if newest > created && *newest_obj != obj => (),
+                _ => {
+                    self.by_ip
+                        .insert(ip.clone(), (created.clone(), obj.clone()));
+                }
+            }
+        }
+    }
+
+    fn remove(&mut self, pod: &Pod) {
+        let obj = ObjectRef::from_obj(pod);
+        if let Some(uid) = &pod.metadata.uid {
+            if self.by_uid.get(uid) == Some(&obj) {
+                self.by_uid.remove(uid);
+            }
+        }
+        for ip in pod_ips(pod) {
+            if matches!(self.by_ip.get(ip), Some((_, indexed)) if *indexed == obj) {
+                self.by_ip.remove(ip);
+            }
+        }
+    }
+}
+
+/// Returns the IP addresses of a `Pod`, unless it uses the host network, whose IP address is shared
+/// with its `Node`.
+fn pod_ips(pod: &Pod) -> impl Iterator<Item = &String> {
+    let host_network = pod
+        .spec
+        .as_ref()
+        .and_then(|spec| spec.host_network)
+        .unwrap_or(false);
+    pod.status
+        .as_ref()
+        .filter(|_| !host_network)
+        .into_iter()
+        .flat_map(|status| {
+            status.pod_ip.iter().chain(
+                status
+                    .pod_ips
+                    .iter()
+                    .flatten()
+                    .filter_map(|pod_ip| pod_ip.ip.as_ref()),
+            )
+        })
+}
+
+/// Enriches events with the metadata of the `Pod` they came from, found by UID, IP address, or
+/// name and namespace.
+#[derive(Clone)]
+pub struct KubernetesMetadata {
+    association: PodAssociation,
+    pod_annotator: Arc<PodMetadataAnnotator>,
+    ns_annotator: Arc<NamespaceMetadataAnnotator>,
+    node_annotator: Arc<NodeMetadataAnnotator>,
+    pod_state: Store<Pod>,
+    pod_index: Arc<RwLock<PodIndex>>,
+    reflectors: Option<Arc<Reflectors>>,
+}
+
+impl KubernetesMetadata {
+    fn new(
+        association: PodAssociation,
+        pod_annotator: PodMetadataAnnotator,
+        ns_annotator: NamespaceMetadataAnnotator,
+        node_annotator: NodeMetadataAnnotator,
+        pod_state: Store<Pod>,
+        pod_index: Arc<RwLock<PodIndex>>,
+    ) -> Self {
+        Self {
+            association,
+            pod_annotator: Arc::new(pod_annotator),
+            ns_annotator: Arc::new(ns_annotator),
+            node_annotator: Arc::new(node_annotator),
+            pod_state,
+            pod_index,
+            reflectors: None,
+        }
+    }
+
+    fn find_pod(&self, event: &Event) -> Option<Arc<Pod>> {
+        let uid = self
+            .association
+            .uid_field
+            .as_deref()
+            .and_then(|f| get(event, f));
+        let ip = self
+            .association
+            .ip_field
+            .as_deref()
+            .and_then(|f| get(event, f));
+        if uid.is_some() || ip.is_some() {
+            let index = self.pod_index.read().expect("Pod index lock poisoned");
+            let pod = uid
+                .and_then(|uid| index.by_uid.get(&uid))
+                .and_then(|obj| self.pod_state.get(obj))
+                .or_else(|| {
+                    ip.and_then(|ip| index.by_ip.get(&ip))
+                        .and_then(|(_, obj)| self.pod_state.get(obj))
+                });
+            if pod.is_some() {
+                return pod;
+            }
+        }
+
+        if let Some((namespace_field, name_field)) = &self.association.name_fields {
+            if let (Some(namespace), Some(name)) =
+                (get(event, namespace_field), get(event, name_field))
+            {
+                return self.pod_annotator.get(&namespace, &name);
+            }
+        }
+
+        None
+    }
+}
+
+impl FunctionTransform for KubernetesMetadata {
+    fn transform(&mut self, output: &mut OutputBuffer, mut event: Event) {
+        if let Some(pod) = self.find_pod(&event) {
+            self.pod_annotator.annotate_pod(&mut event, &pod, None);
+
+            if let Some(namespace) = &pod.metadata.namespace {
+                self.ns_annotator.annotate(&mut event, namespace);
+            }
+
+            let node_name = pod.spec.as_ref().and_then(|spec| spec.node_name.as_ref());
+            if let Some(node_name) = node_name {
+                self.node_annotator.annotate(&mut event, node_name);
+            }
+        }
+
+        output.push(event);
+    }
+}
+
+/// Returns the value of the field of a log, or of the tag of a metric.
+fn get(event: &Event, field: &str) -> Option<String> {
+    match event {
+        Event::Log(log) => log.get(field).map(|value| value.to_string_lossy()),
+        Event::Metric(metric) => metric.tag_value(field),
+        Event::Trace(trace) => trace.get(field).map(|value| value.to_string_lossy()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use k8s_openapi::{
+        api::core::v1::{PodIP, PodSpec, PodStatus},
+        apimachinery::pkg::apis::meta::v1::ObjectMeta,
+    };
+    use vector_common::btreemap;
+
+    use super::*;
+    use crate::{
+        event::{LogEvent, Metric, MetricKind, MetricValue},
+        transforms::test::transform_one,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<KubernetesMetadataConfig>();
+    }
+
+    fn pod(name: &str, uid: &str, ip: &str, host_network: bool) -> Pod {
+        Pod {
+            metadata: ObjectMeta {
+                name: Some(name.to_owned()),
+                namespace: Some("sandbox0-ns".to_owned()),
+                uid: Some(uid.to_owned()),
+                labels: Some(btreemap! { "app" => name }),
+                ..ObjectMeta::default()
+            },
+            spec: Some(PodSpec {
+                node_name: Some("sandbox0-node".to_owned()),
+                host_network: Some(host_network),
+                ..PodSpec::default()
+            }),
+            status: Some(PodStatus {
+                pod_ip: Some(ip.to_owned()),
+                pod_ips: Some(vec![PodIP {
+                    ip: Some(ip.to_owned()),
+                }]),
+                ..PodStatus::default()
+            }),
+        }
+    }
+
+    fn transform(config: KubernetesMetadataConfig) -> KubernetesMetadata {
+        let mut pods = reflector::store::Writer::<Pod>::default();
+        let mut pod_index = PodIndex::default();
+        for pod in [
+            pod("sandbox0-name", "sandbox0-uid", "10.0.0.1", false),
+            pod("sandbox1-name", "sandbox1-uid", "10.0.0.2", false),
+            pod("sandbox2-name", "sandbox2-uid", "192.168.0.1", true),
+        ] {
+            let event = watcher::Event::Applied(pod);
+            pods.apply_watcher_event(&event);
+            pod_index.apply(&event);
+        }
+
+        let mut namespaces = reflector::store::Writer::<Namespace>::default();
+        namespaces.apply_watcher_event(&watcher::Event::Applied(Namespace {
+            metadata: ObjectMeta {
+                name: Some("sandbox0-ns".to_owned()),
+                labels: Some(btreemap! { "team" => "sandbox" }),
+                ..ObjectMeta::default()
+            },
+            ..Namespace::default()
+        }));
+
+        let mut nodes = reflector::store::Writer::<Node>::default();
+        nodes.apply_watcher_event(&watcher::Event::Applied(Node {
+            metadata: ObjectMeta {
+                name: Some("sandbox0-node".to_owned()),
+                labels: Some(btreemap! { "zone" => "a" }),
+                ..ObjectMeta::default()
+            },
+            ..Node::default()
+        }));
+
+        KubernetesMetadata::new(
+            PodAssociation::new(&config).unwrap(),
+            PodMetadataAnnotator::new(pods.as_reader(), config.pod_annotation_fields.clone()),
+            NamespaceMetadataAnnotator::new(
+                namespaces.as_reader(),
+                config.namespace_annotation_fields.clone(),
+            ),
+            NodeMetadataAnnotator::new(nodes.as_reader(), config.node_annotation_fields.clone()),
+            pods.as_reader(),
+            Arc::new(RwLock::new(pod_index)),
+        )
+    }
+
+    fn log(fields: &[(&str, &str)]) -> Event {
+        let mut log = LogEvent::default();
+        for (field, value) in fields {
+            log.insert(*field, *value);
+        }
+        log.into()
+    }
+
+    #[test]
+    fn requires_pod_association() {
+        assert!(PodAssociation::new(&KubernetesMetadataConfig::default()).is_err());
+        // The name alone isn't unique.
+        assert!(PodAssociation::new(&KubernetesMetadataConfig {
+            pod_name_field: Some("pod".to_owned()),
+            ..Default::default()
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn annotates_logs_by_ip() {
+        let mut transform = transform(KubernetesMetadataConfig {
+            pod_ip_field: Some("host".to_owned()),
+            ..Default::default()
+        });
+
+        let event = transform_one(&mut transform, log(&[("host", "10.0.0.2")])).unwrap();
+        let log = event.as_log();
+        assert_eq!(log["kubernetes.pod_name"], "sandbox1-name".into());
+        assert_eq!(log["kubernetes.pod_uid"], "sandbox1-uid".into());
+        assert_eq!(log["kubernetes.pod_namespace"], "sandbox0-ns".into());
+        assert_eq!(log["kubernetes.pod_node_name"], "sandbox0-node".into());
+        assert_eq!(log["kubernetes.pod_labels.app"], "sandbox1-name".into());
+        assert_eq!(log["kubernetes.namespace_labels.team"], "sandbox".into());
+        assert_eq!(log["kubernetes.node_labels.zone"], "a".into());
+    }
+
+    #[test]
+    fn skips_host_network_pods_by_ip() {
+        let mut transform = transform(KubernetesMetadataConfig {
+            pod_ip_field: Some("host".to_owned()),
+            ..Default::default()
+        });
+
+        let event = log(&[("host", "192.168.0.1")]);
+        assert_eq!(transform_one(&mut transform, event.clone()), Some(event));
+    }
+
+    #[test]
+    fn annotates_logs_by_uid_then_name() {
+        let mut transform = transform(KubernetesMetadataConfig {
+            pod_uid_field: Some("uid".to_owned()),
+            pod_name_field: Some("pod".to_owned()),
+            pod_namespace_field: Some("namespace".to_owned()),
+            ..Default::default()
+        });
+
+        let event = transform_one(&mut transform, log(&[("uid", "sandbox2-uid")])).unwrap();
+        assert_eq!(
+            event.as_log()["kubernetes.pod_name"],
+            "sandbox2-name".into()
+        );
+
+        let event = transform_one(
+            &mut transform,
+            log(&[
+                ("uid", "unknown-uid"),
+                ("pod", "sandbox0-name"),
+                ("namespace", "sandbox0-ns"),
+            ]),
+        )
+        .unwrap();
+        assert_eq!(event.as_log()["kubernetes.pod_uid"], "sandbox0-uid".into());
+    }
+
+    #[test]
+    fn indexes_newest_pod_by_ip() {
+        let created = |hour| Some(Time(chrono::Utc.ymd(2022, 1, 1).and_hms(hour, 0, 0)));
+        let mut old = pod("old-name", "old-uid", "10.0.0.1", false);
+        old.metadata.creation_timestamp = created(1);
+        let mut new = pod("new-name", "new-uid", "10.0.0.1", false);
+        new.metadata.creation_timestamp = created(2);
+
+        let mut index = PodIndex::default();
+        index.apply(&watcher::Event::Applied(old.clone()));
+        index.apply(&watcher::Event::Applied(new.clone()));
+        // The old `Pod` being updated while it terminates doesn't take the IP address back.
+        index.apply(&watcher::Event::Applied(old.clone()));
+        assert_eq!(index.by_ip["10.0.0.1"].1, ObjectRef::from_obj(&new));
+
+        index.apply(&watcher::Event::Deleted(old));
+        assert_eq!(index.by_ip["10.0.0.1"].1, ObjectRef::from_obj(&new));
+        assert!(!index.by_uid.contains_key("old-uid"));
+
+        index.apply(&watcher::Event::Deleted(new));
+        assert!(index.by_ip.is_empty());
+        assert!(index.by_uid.is_empty());
+    }
+
+    #[test]
+    fn passes_through_unknown_pods() {
+        let mut transform = transform(KubernetesMetadataConfig {
+            pod_uid_field: Some("uid".to_owned()),
+            ..Default::default()
+        });
+
+        let event = log(&[("uid", "unknown-uid")]);
+        assert_eq!(transform_one(&mut transform, event.clone()), Some(event));
+    }
+
+    #[test]
+    fn annotates_metrics_as_tags() {
+        let mut transform = transform(KubernetesMetadataConfig {
+            pod_ip_field: Some("pod_ip".to_owned()),
+            ..Default::default()
+        });
+
+        let metric = Metric::new(
+            "requests",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        )
+        .with_tags(Some(btreemap! { "pod_ip" => "10.0.0.1" }));
+
+        let event = transform_one(&mut transform, metric.into()).unwrap();
+        let metric = event.as_metric();
+        assert_eq!(
+            metric.tag_value("kubernetes.pod_name").as_deref(),
+            Some("sandbox0-name")
+        );
+        assert_eq!(
+            metric.tag_value("kubernetes.pod_ips").as_deref(),
+            Some("10.0.0.1")
+        );
+        assert_eq!(
+            metric.tag_value("kubernetes.pod_labels.app").as_deref(),
+            Some("sandbox0-name")
+        );
+        assert_eq!(
+            metric
+                .tag_value("kubernetes.namespace_labels.team")
+                .as_deref(),
+            Some("sandbox")
+        );
+        assert_eq!(
+            metric.tag_value("kubernetes.node_labels.zone").as_deref(),
+            Some("a")
+        );
+    }
+}
@@ -6,8 +6,8 @@ use vector_config::configurable_component;
 
 use crate::{
     config::{
-        log_schema, DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext,
-        TransformDescription,
+        log_schema, ComponentKey, DataType, GenerateConfig, Input, Output, TransformConfig,
+        TransformContext, TransformDescription,
     },
     event::{
         metric::{Metric, MetricKind, MetricValue, StatisticKind},
@@ -19,7 +19,10 @@ use crate::{
     },
     schema,
     template::{Template, TemplateParseError, TemplateRenderingError},
-    transforms::{FunctionTransform, OutputBuffer, Transform},
+    transforms::{
+        dropped::{annotate_dropped, dropped_output, DROPPED},
+        SyncTransform, Transform, TransformOutputsBuf,
+    },
 };
 
 /// Configuration for the `log_to_metric` transform.
@@ -29,6 +32,15 @@ use crate::{
 pub struct LogToMetricConfig {
     /// A list of metrics to generate.
     pub metrics: Vec<MetricConfig>,
+
+    /// Reroutes log events that could not be converted to a named output instead of discarding them.
+    ///
+    /// A log event is rerouted when none of the configured metrics could be generated from it, and at least
+    /// one of them failed to be generated, for example because a field could not be parsed as a number. It
+    /// is forwarded to a specially-named output, `dropped`, and annotated with additional fields describing
+    /// why it was dropped.
+    #[serde(default)]
+    pub reroute_dropped: bool,
 }
 
 /// Specification of a counter derived from a log event.
@@ -178,6 +190,7 @@ const fn default_kind() -> MetricKind {
 #[derive(Debug, Clone)]
 pub struct LogToMetric {
     config: LogToMetricConfig,
+    component_key: Option<ComponentKey>,
 }
 
 inventory::submit! {
@@ -195,6 +208,7 @@ impl GenerateConfig for LogToMetricConfig {
                 kind: MetricKind::Incremental,
                 tags: None,
             })],
+            reroute_dropped: false,
         })
         .unwrap()
     }
@@ -203,16 +217,26 @@ impl GenerateConfig for LogToMetricConfig {
 #[async_trait::async_trait]
 #[typetag::serde(name = "log_to_metric")]
 impl TransformConfig for LogToMetricConfig {
-    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
-        Ok(Transform::function(LogToMetric::new(self.clone())))
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        Ok(Transform::synchronous(
+            LogToMetric::new(self.clone()).with_component_key(context.key.clone()),
+        ))
     }
 
     fn input(&self) -> Input {
         Input::log()
     }
 
-    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
-        vec![Output::default(DataType::Metric)]
+    fn outputs(&self, input_definition: &schema::Definition) -> Vec<Output> {
+        let default_output = Output::default(DataType::Metric);
+        if self.reroute_dropped {
+            vec![
+                default_output,
+                dropped_output(DataType::Log, input_definition),
+            ]
+        } else {
+            vec![default_output]
+        }
     }
 
     fn enable_concurrency(&self) -> bool {
@@ -226,7 +250,16 @@ impl TransformConfig for LogToMetricConfig {
 
 impl LogToMetric {
     pub const fn new(config: LogToMetricConfig) -> Self {
-        LogToMetric { config }
+        LogToMetric {
+            config,
+            component_key: None,
+        }
+    }
+
+    /// Sets the key of the component, used to annotate rerouted events.
+    pub fn with_component_key(mut self, component_key: Option<ComponentKey>) -> Self {
+        self.component_key = component_key;
+        self
     }
 }
 
@@ -245,6 +278,24 @@ enum TransformError {
     },
 }
 
+impl TransformError {
+    fn message(&self) -> String {
+        match self {
+            TransformError::FieldNotFound { field } => format!("Field {:?} not found.", field),
+            TransformError::FieldNull { field } => format!("Field {:?} is null.", field),
+            TransformError::TemplateParseError(error) => {
+                format!("Failed to parse template: {}.", error)
+            }
+            TransformError::TemplateRenderingError(error) => {
+                format!("Failed to render template: {}.", error)
+            }
+            TransformError::ParseFloatError { field, error } => {
+                format!("Failed to parse field {:?} as a number: {}.", field, error)
+            }
+        }
+    }
+}
+
 fn render_template(s: &str, event: &Event) -> Result<String, TransformError> {
     let template = Template::try_from(s).map_err(TransformError::TemplateParseError)?;
     template
@@ -457,37 +508,61 @@ fn to_metric(config: &MetricConfig, event: &Event) -> Result<Metric, TransformEr
     }
 }
 
-impl FunctionTransform for LogToMetric {
-    fn transform(&mut self, output: &mut OutputBuffer, event: Event) {
+impl SyncTransform for LogToMetric {
+    fn transform(&mut self, mut event: Event, output: &mut TransformOutputsBuf) {
+        let mut converted = false;
+        let mut failure = None;
+
         for config in self.config.metrics.iter() {
-            match to_metric(config, &event) {
+            let error = match to_metric(config, &event) {
                 Ok(metric) => {
                     output.push(Event::Metric(metric));
+                    converted = true;
+                    continue;
                 }
-                Err(TransformError::FieldNull { field }) => emit!(LogToMetricFieldNullError {
+                Err(error) => error,
+            };
+
+            if self.config.reroute_dropped && failure.is_none() {
+                failure = Some(error.message());
+            }
+
+            match error {
+                TransformError::FieldNull { field } => emit!(LogToMetricFieldNullError {
                     field: field.as_ref()
                 }),
-                Err(TransformError::FieldNotFound { field }) => emit!(ParserMissingFieldError {
+                TransformError::FieldNotFound { field } => emit!(ParserMissingFieldError {
                     field: field.as_ref()
                 }),
-                Err(TransformError::ParseFloatError { field, error }) => {
+                TransformError::ParseFloatError { field, error } => {
                     emit!(LogToMetricParseFloatError {
                         field: field.as_ref(),
                         error
                     })
                 }
-                Err(TransformError::TemplateRenderingError(error)) => {
+                TransformError::TemplateRenderingError(error) => {
                     emit!(crate::internal_events::TemplateRenderingError {
                         error,
                         drop_event: false,
                         field: None,
                     })
                 }
-                Err(TransformError::TemplateParseError(error)) => {
+                TransformError::TemplateParseError(error) => {
                     emit!(LogToMetricTemplateParseError { error })
                 }
             }
         }
+
+        if let (false, Some(message)) = (converted, failure) {
+            annotate_dropped(
+                &mut event,
+                self.component_key.as_ref(),
+                "log_to_metric",
+                "conversion_failed",
+                &message,
+            );
+            output.push_named(DROPPED, event);
+        }
     }
 }
 
@@ -745,6 +820,45 @@ mod tests {
         assert_eq!(transform_one(&mut transform, event), None);
     }
 
+    #[test]
+    fn parse_failure_rerouted() {
+        let config = parse_config(
+            r#"
+            reroute_dropped = true
+
+            [[metrics]]
+            type = "counter"
+            field = "status"
+            name = "status_total"
+            increment_by_value = true
+            "#,
+        );
+
+        let event = create_event("status", "not a number");
+        let mut transform =
+            LogToMetric::new(config).with_component_key(Some(ComponentKey::from("to_metric")));
+        let mut outputs = TransformOutputsBuf::new_with_capacity(
+            vec![
+                Output::default(DataType::Metric),
+                Output::default(DataType::Log).with_port(DROPPED),
+            ],
+            1,
+        );
+        transform.transform(event, &mut outputs);
+
+        assert_eq!(outputs.drain().count(), 0);
+        let dropped = outputs.drain_named(DROPPED).collect::<Vec<_>>();
+        assert_eq!(dropped.len(), 1);
+        let log = dropped[0].as_log();
+        assert_eq!(log["status"], "not a number".into());
+        assert_eq!(log["metadata.dropped.reason"], "conversion_failed".into());
+        assert_eq!(log["metadata.dropped.component_id"], "to_metric".into());
+        assert_eq!(
+            log["metadata.dropped.component_type"],
+            "log_to_metric".into()
+        );
+    }
+
     #[test]
     fn missing_field() {
         let config = parse_config(
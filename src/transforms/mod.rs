@@ -10,10 +10,21 @@ pub mod aggregate;
 pub mod aws_ec2_metadata;
 #[cfg(feature = "transforms-dedupe")]
 pub mod dedupe;
+#[cfg(any(
+    feature = "transforms-filter",
+    feature = "transforms-log_to_metric",
+    feature = "transforms-remap",
+    feature = "transforms-sample",
+    feature = "transforms-tag_cardinality_limit",
+    feature = "transforms-throttle",
+))]
+pub(crate) mod dropped;
 #[cfg(feature = "transforms-filter")]
 pub mod filter;
 #[cfg(feature = "transforms-geoip")]
 pub mod geoip;
+#[cfg(feature = "transforms-kubernetes_metadata")]
+pub mod kubernetes_metadata;
 #[cfg(feature = "transforms-log_to_metric")]
 pub mod log_to_metric;
 #[cfg(feature = "transforms-lua")]
@@ -80,6 +91,10 @@ pub enum Transforms {
     #[cfg(feature = "transforms-geoip")]
     Geoip(#[configurable(derived)] geoip::GeoipConfig),
 
+    /// Kubernetes metadata.
+    #[cfg(feature = "transforms-kubernetes_metadata")]
+    KubernetesMetadata(#[configurable(derived)] kubernetes_metadata::KubernetesMetadataConfig),
+
     /// Log to metric.
     #[cfg(feature = "transforms-log_to_metric")]
     LogToMetric(#[configurable(derived)] log_to_metric::LogToMetricConfig),
@@ -138,6 +153,8 @@ impl TransformConfig for Transforms {
             Transforms::Filter(inner) => inner.build(globals).await,
             #[cfg(feature = "transforms-geoip")]
             Transforms::Geoip(inner) => inner.build(globals).await,
+            #[cfg(feature = "transforms-kubernetes_metadata")]
+            Transforms::KubernetesMetadata(inner) => inner.build(globals).await,
             #[cfg(feature = "transforms-log_to_metric")]
             Transforms::LogToMetric(inner) => inner.build(globals).await,
             #[cfg(feature = "transforms-lua")]
@@ -175,6 +192,8 @@ impl TransformConfig for Transforms {
             Transforms::Filter(inner) => inner.input(),
             #[cfg(feature = "transforms-geoip")]
             Transforms::Geoip(inner) => inner.input(),
+            #[cfg(feature = "transforms-kubernetes_metadata")]
+            Transforms::KubernetesMetadata(inner) => inner.input(),
             #[cfg(feature = "transforms-log_to_metric")]
             Transforms::LogToMetric(inner) => inner.input(),
             #[cfg(feature = "transforms-lua")]
@@ -213,6 +232,8 @@ impl TransformConfig for Transforms {
             Transforms::Filter(inner) => inner.outputs(merged_definition),
             #[cfg(feature = "transforms-geoip")]
             Transforms::Geoip(inner) => inner.outputs(merged_definition),
+            #[cfg(feature = "transforms-kubernetes_metadata")]
+            Transforms::KubernetesMetadata(inner) => inner.outputs(merged_definition),
             #[cfg(feature = "transforms-log_to_metric")]
             Transforms::LogToMetric(inner) => inner.outputs(merged_definition),
             #[cfg(feature = "transforms-lua")]
@@ -250,6 +271,8 @@ impl TransformConfig for Transforms {
             Transforms::Filter(inner) => inner.transform_type(),
             #[cfg(feature = "transforms-geoip")]
             Transforms::Geoip(inner) => inner.transform_type(),
+            #[cfg(feature = "transforms-kubernetes_metadata")]
+            Transforms::KubernetesMetadata(inner) => inner.transform_type(),
             #[cfg(feature = "transforms-log_to_metric")]
             Transforms::LogToMetric(inner) => inner.transform_type(),
             #[cfg(feature = "transforms-lua")]
@@ -287,6 +310,8 @@ impl TransformConfig for Transforms {
             Transforms::Filter(inner) => inner.typetag_name(),
             #[cfg(feature = "transforms-geoip")]
             Transforms::Geoip(inner) => inner.typetag_name(),
+            #[cfg(feature = "transforms-kubernetes_metadata")]
+            Transforms::KubernetesMetadata(inner) => inner.typetag_name(),
             #[cfg(feature = "transforms-log_to_metric")]
             Transforms::LogToMetric(inner) => inner.typetag_name(),
             #[cfg(feature = "transforms-lua")]
@@ -324,6 +349,8 @@ impl TransformConfig for Transforms {
             Transforms::Filter(inner) => inner.typetag_deserialize(),
             #[cfg(feature = "transforms-geoip")]
             Transforms::Geoip(inner) => inner.typetag_deserialize(),
+            #[cfg(feature = "transforms-kubernetes_metadata")]
+            Transforms::KubernetesMetadata(inner) => inner.typetag_deserialize(),
             #[cfg(feature = "transforms-log_to_metric")]
             Transforms::LogToMetric(inner) => inner.typetag_deserialize(),
             #[cfg(feature = "transforms-lua")]
@@ -362,6 +389,8 @@ impl TransformConfig for Transforms {
             Transforms::Filter(inner) => inner.nestable(parents),
             #[cfg(feature = "transforms-geoip")]
             Transforms::Geoip(inner) => inner.nestable(parents),
+            #[cfg(feature = "transforms-kubernetes_metadata")]
+            Transforms::KubernetesMetadata(inner) => inner.nestable(parents),
             #[cfg(feature = "transforms-log_to_metric")]
             Transforms::LogToMetric(inner) => inner.nestable(parents),
             #[cfg(feature = "transforms-lua")]
@@ -390,25 +419,31 @@ impl TransformConfig for Transforms {
 
 #[cfg(test)]
 mod test {
-    use vector_core::transform::FunctionTransform;
+    use vector_core::{
+        config::{DataType, Output},
+        transform::{SyncTransform, TransformOutputsBuf},
+    };
 
-    use crate::{event::Event, transforms::OutputBuffer};
+    use crate::event::Event;
 
-    /// Transform a single `Event` through the `FunctionTransform`
+    /// Transform a single `Event` through the `SyncTransform`
+    ///
+    /// Only the events sent to the default output are returned.
     ///
     /// # Panics
     ///
-    /// If `ft` attempts to emit more than one `Event` on transform this
-    /// function will panic.
+    /// If `ft` attempts to emit more than one `Event` on transform, or to
+    /// emit an `Event` to a named output, this function will panic.
     // We allow dead_code here to avoid unused warnings when we compile our
     // benchmarks as tests. It's a valid warning -- the benchmarks don't use
     // this function -- but flagging this function off for bench flags will
     // issue a unused warnings about the import above.
     #[allow(dead_code)]
-    pub fn transform_one(ft: &mut dyn FunctionTransform, event: Event) -> Option<Event> {
-        let mut buf = OutputBuffer::with_capacity(1);
-        ft.transform(&mut buf, event);
+    pub fn transform_one(ft: &mut dyn SyncTransform, event: Event) -> Option<Event> {
+        let mut buf =
+            TransformOutputsBuf::new_with_capacity(vec![Output::default(DataType::all())], 1);
+        ft.transform(event, &mut buf);
         assert!(buf.len() <= 1);
-        buf.into_events().next()
+        buf.drain().next()
     }
 }
@@ -328,6 +328,7 @@ mod tests {
         let outer = TransformOuter {
             inputs: vec!["source".to_string()],
             inner: Box::new(config),
+            telemetry: true,
         };
         let name = ComponentKey::from("foo");
         let mut transforms = IndexMap::new();
@@ -1,19 +1,16 @@
 use std::sync::Arc;
 use std::{
-    collections::BTreeMap,
     fs::File,
     io::{self, Read},
     path::PathBuf,
 };
 
 use lookup::lookup_v2::Path;
-use lookup::path;
 use snafu::{ResultExt, Snafu};
 use value::Kind;
 use vector_common::TimeZone;
 use vector_config::configurable_component;
 use vector_core::compile_vrl;
-use vector_core::config::LogNamespace;
 use vector_core::schema::Definition;
 
 use vector_vrl_functions::set_semantic_meaning::MeaningList;
@@ -26,18 +23,19 @@ use vrl::{
 
 use crate::{
     config::{
-        log_schema, ComponentKey, DataType, Input, Output, TransformConfig, TransformContext,
+        ComponentKey, DataType, Input, Output, TransformConfig, TransformContext,
         TransformDescription,
     },
     event::{Event, TargetEvents, VrlTarget},
     internal_events::{RemapMappingAbort, RemapMappingError},
     schema,
-    transforms::{SyncTransform, Transform, TransformOutputsBuf},
+    transforms::{
+        dropped::{annotate_dropped, dropped_output, DROPPED},
+        SyncTransform, Transform, TransformOutputsBuf,
+    },
     Result,
 };
 
-const DROPPED: &str = "dropped";
-
 /// Configuration for the `remap` transform.
 #[configurable_component(transform)]
 #[derive(Clone, Debug, Derivative)]
@@ -231,54 +229,13 @@ impl TransformConfig for RemapConfig {
                 )
             });
 
-        // When a message is dropped and re-routed, we keep the original event, but also annotate
-        // it with additional metadata.
-        let mut dropped_definition = Definition::new_with_default_metadata(
-            Kind::never(),
-            input_definition.log_namespaces().clone(),
-        );
-
-        if input_definition
-            .log_namespaces()
-            .contains(&LogNamespace::Legacy)
-        {
-            dropped_definition = dropped_definition.merge(input_definition.clone().with_field(
-                log_schema().metadata_key(),
-                Kind::object(BTreeMap::from([
-                    ("reason".into(), Kind::bytes()),
-                    ("message".into(), Kind::bytes()),
-                    ("component_id".into(), Kind::bytes()),
-                    ("component_type".into(), Kind::bytes()),
-                    ("component_kind".into(), Kind::bytes()),
-                ])),
-                Some("metadata"),
-            ));
-        }
-
-        if input_definition
-            .log_namespaces()
-            .contains(&LogNamespace::Vector)
-        {
-            dropped_definition = dropped_definition.merge(
-                input_definition
-                    .clone()
-                    .with_metadata_field("reason", Kind::bytes())
-                    .with_metadata_field("message", Kind::bytes())
-                    .with_metadata_field("component_id", Kind::bytes())
-                    .with_metadata_field("component_type", Kind::bytes())
-                    .with_metadata_field("component_kind", Kind::bytes()),
-            );
-        }
-
         let default_output =
             Output::default(DataType::all()).with_schema_definition(default_definition);
 
         if self.reroute_dropped {
             vec![
                 default_output,
-                Output::default(DataType::all())
-                    .with_schema_definition(dropped_definition)
-                    .with_port(DROPPED),
+                dropped_output(DataType::all(), input_definition),
             ]
         } else {
             vec![default_output]
@@ -403,7 +360,7 @@ where
         &self.runner
     }
 
-    fn dropped_data(&self, reason: &str, error: ExpressionError) -> serde_json::Value {
+    fn annotate_dropped(&self, event: &mut Event, reason: &str, error: ExpressionError) {
         let message = error
             .notes()
             .iter()
@@ -411,50 +368,13 @@ where
             .last()
             .map(|note| note.to_string())
             .unwrap_or_else(|| error.to_string());
-        serde_json::json!({
-                "reason": reason,
-                "message": message,
-                "component_id": self.component_key,
-                "component_type": "remap",
-                "component_kind": "transform",
-        })
-    }
-
-    fn annotate_dropped(&self, event: &mut Event, reason: &str, error: ExpressionError) {
-        match event {
-            Event::Log(ref mut log) => match log.namespace() {
-                LogNamespace::Legacy => {
-                    log.insert(
-                        log_schema().metadata_key().concat(path!("dropped")),
-                        self.dropped_data(reason, error),
-                    );
-                }
-                LogNamespace::Vector => {
-                    log.metadata_mut()
-                        .value_mut()
-                        .insert(path!("vector", "dropped"), self.dropped_data(reason, error));
-                }
-            },
-            Event::Metric(ref mut metric) => {
-                let m = log_schema().metadata_key();
-                metric.insert_tag(format!("{}.dropped.reason", m), reason.into());
-                metric.insert_tag(
-                    format!("{}.dropped.component_id", m),
-                    self.component_key
-                        .as_ref()
-                        .map(ToString::to_string)
-                        .unwrap_or_else(String::new),
-                );
-                metric.insert_tag(format!("{}.dropped.component_type", m), "remap".into());
-                metric.insert_tag(format!("{}.dropped.component_kind", m), "transform".into());
-            }
-            Event::Trace(ref mut trace) => {
-                trace.insert(
-                    log_schema().metadata_key(),
-                    self.dropped_data(reason, error),
-                );
-            }
-        }
+        annotate_dropped(
+            event,
+            self.component_key.as_ref(),
+            "remap",
+            reason,
+            &message,
+        );
     }
 
     fn run_vrl(&mut self, target: &mut VrlTarget) -> std::result::Result<value::Value, Terminate> {
@@ -577,7 +497,7 @@ mod tests {
 
     use indoc::{formatdoc, indoc};
     use vector_common::btreemap;
-    use vector_core::event::EventMetadata;
+    use vector_core::{config::LogNamespace, event::EventMetadata};
 
     use super::*;
     use crate::{
@@ -4,13 +4,16 @@ use vector_config::configurable_component;
 use crate::{
     conditions::{AnyCondition, Condition},
     config::{
-        DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext,
+        ComponentKey, DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext,
         TransformDescription,
     },
     event::Event,
     internal_events::SampleEventDiscarded,
     schema,
-    transforms::{FunctionTransform, OutputBuffer, Transform},
+    transforms::{
+        dropped::{annotate_dropped, dropped_output, DROPPED},
+        SyncTransform, Transform, TransformOutputsBuf,
+    },
 };
 
 /// Configuration for the `sample` transform.
@@ -32,6 +35,13 @@ pub struct SampleConfig {
 
     /// A logical condition used to exclude events from sampling.
     pub exclude: Option<AnyCondition>,
+
+    /// Reroutes events that are not sampled to a named output instead of discarding them.
+    ///
+    /// When enabled, events that would have been discarded are forwarded to a specially-named output,
+    /// `dropped`. They are annotated with additional fields describing why they were dropped.
+    #[serde(default)]
+    pub reroute_dropped: bool,
 }
 
 // TODO: Deprecate the name `sampler`
@@ -49,6 +59,7 @@ impl GenerateConfig for SampleConfig {
             rate: 10,
             key_field: None,
             exclude: None::<AnyCondition>,
+            reroute_dropped: false,
         })
         .unwrap()
     }
@@ -58,22 +69,34 @@ impl GenerateConfig for SampleConfig {
 #[typetag::serde(name = "sample")]
 impl TransformConfig for SampleConfig {
     async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
-        Ok(Transform::function(Sample::new(
+        let mut sample = Sample::new(
             self.rate,
             self.key_field.clone(),
             self.exclude
                 .as_ref()
                 .map(|condition| condition.build(&context.enrichment_tables))
                 .transpose()?,
-        )))
+        );
+        if self.reroute_dropped {
+            sample = sample.reroute_dropped(context.key.clone());
+        }
+        Ok(Transform::synchronous(sample))
     }
 
     fn input(&self) -> Input {
         Input::new(DataType::Log | DataType::Trace)
     }
 
-    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
-        vec![Output::default(DataType::Log | DataType::Trace)]
+    fn outputs(&self, input_definition: &schema::Definition) -> Vec<Output> {
+        let default_output = Output::default(DataType::Log | DataType::Trace);
+        if self.reroute_dropped {
+            vec![
+                default_output,
+                dropped_output(DataType::Log | DataType::Trace, input_definition),
+            ]
+        } else {
+            vec![default_output]
+        }
     }
 
     fn transform_type(&self) -> &'static str {
@@ -111,6 +134,8 @@ pub struct Sample {
     key_field: Option<String>,
     exclude: Option<Condition>,
     count: u64,
+    /// Set when discarded events are rerouted, holding the key used to annotate them.
+    reroute_dropped: Option<Option<ComponentKey>>,
 }
 
 impl Sample {
@@ -120,12 +145,19 @@ impl Sample {
             key_field,
             exclude,
             count: 0,
+            reroute_dropped: None,
         }
     }
+
+    /// Sends the events that are not sampled to the `dropped` output.
+    pub fn reroute_dropped(mut self, component_key: Option<ComponentKey>) -> Self {
+        self.reroute_dropped = Some(component_key);
+        self
+    }
 }
 
-impl FunctionTransform for Sample {
-    fn transform(&mut self, output: &mut OutputBuffer, event: Event) {
+impl SyncTransform for Sample {
+    fn transform(&mut self, event: Event, output: &mut TransformOutputsBuf) {
         let mut event = {
             if let Some(condition) = self.exclude.as_ref() {
                 let (result, event) = condition.check(event);
@@ -165,6 +197,15 @@ impl FunctionTransform for Sample {
                 Event::Metric(_) => panic!("component can never receive metric events"),
             };
             output.push(event);
+        } else if let Some(component_key) = &self.reroute_dropped {
+            annotate_dropped(
+                &mut event,
+                component_key.as_ref(),
+                "sample",
+                "sampled",
+                "Event was not selected by sampling.",
+            );
+            output.push_named(DROPPED, event);
         } else {
             emit!(SampleEventDiscarded);
         }
@@ -212,11 +253,7 @@ mod tests {
         );
         let total_passed = events
             .into_iter()
-            .filter_map(|event| {
-                let mut buf = OutputBuffer::with_capacity(1);
-                sampler.transform(&mut buf, event);
-                buf.into_events().next()
-            })
+            .filter_map(|event| transform_one(&mut sampler, event))
             .count();
         let ideal = 1.0f64 / 2.0f64;
         let actual = total_passed as f64 / num_events as f64;
@@ -230,11 +267,7 @@ mod tests {
         );
         let total_passed = events
             .into_iter()
-            .filter_map(|event| {
-                let mut buf = OutputBuffer::with_capacity(1);
-                sampler.transform(&mut buf, event);
-                buf.into_events().next()
-            })
+            .filter_map(|event| transform_one(&mut sampler, event))
             .count();
         let ideal = 1.0f64 / 25.0f64;
         let actual = total_passed as f64 / num_events as f64;
@@ -253,19 +286,11 @@ mod tests {
         let first_run = events
             .clone()
             .into_iter()
-            .filter_map(|event| {
-                let mut buf = OutputBuffer::with_capacity(1);
-                sampler.transform(&mut buf, event);
-                buf.into_events().next()
-            })
+            .filter_map(|event| transform_one(&mut sampler, event))
             .collect::<Vec<_>>();
         let second_run = events
             .into_iter()
-            .filter_map(|event| {
-                let mut buf = OutputBuffer::with_capacity(1);
-                sampler.transform(&mut buf, event);
-                buf.into_events().next()
-            })
+            .filter_map(|event| transform_one(&mut sampler, event))
             .collect::<Vec<_>>();
 
         assert_eq!(first_run, second_run);
@@ -374,6 +399,31 @@ mod tests {
         assert_eq!(total_passed, 1);
     }
 
+    #[test]
+    fn reroutes_dropped_events() {
+        let mut sampler =
+            Sample::new(2, None, None).reroute_dropped(Some(ComponentKey::from("sampler")));
+        let mut outputs = TransformOutputsBuf::new_with_capacity(
+            vec![
+                Output::default(DataType::Log),
+                Output::default(DataType::Log).with_port(DROPPED),
+            ],
+            2,
+        );
+
+        for event in random_events(2) {
+            sampler.transform(event, &mut outputs);
+        }
+
+        assert_eq!(outputs.drain().count(), 1);
+        let dropped = outputs.drain_named(DROPPED).collect::<Vec<_>>();
+        assert_eq!(dropped.len(), 1);
+        let log = dropped[0].as_log();
+        assert!(log.get("sample_rate").is_none());
+        assert_eq!(log["metadata.dropped.reason"], "sampled".into());
+        assert_eq!(log["metadata.dropped.component_type"], "sample".into());
+    }
+
     fn random_events(n: usize) -> Vec<Event> {
         random_lines(10)
             .take(n)
@@ -2,17 +2,14 @@ use std::{
     borrow::{Borrow, Cow},
     collections::{HashMap, HashSet},
     fmt,
-    future::ready,
-    pin::Pin,
 };
 
 use bloom::{BloomFilter, ASMS};
-use futures::{Stream, StreamExt};
 use vector_config::configurable_component;
 
 use crate::{
     config::{
-        DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext,
+        ComponentKey, DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext,
         TransformDescription,
     },
     event::Event,
@@ -21,7 +18,10 @@ use crate::{
         TagCardinalityValueLimitReached,
     },
     schema,
-    transforms::{TaskTransform, Transform},
+    transforms::{
+        dropped::{annotate_dropped, dropped_output, DROPPED},
+        SyncTransform, Transform, TransformOutputsBuf,
+    },
 };
 
 /// Configuration for the `tag_cardinality_limit` transform.
@@ -38,6 +38,14 @@ pub struct TagCardinalityLimitConfig {
 
     #[serde(flatten)]
     pub mode: Mode,
+
+    /// Reroutes events that exceed the cardinality limit to a named output instead of discarding them.
+    ///
+    /// This only applies when `limit_exceeded_action` is `drop_event`. When enabled, events that would have
+    /// been discarded are forwarded to a specially-named output, `dropped`. They are annotated with
+    /// additional tags describing why they were dropped.
+    #[serde(default)]
+    pub reroute_dropped: bool,
 }
 
 /// Controls the approach taken for tracking tag cardinality.
@@ -86,9 +94,18 @@ pub enum LimitExceededAction {
 #[derive(Debug)]
 pub struct TagCardinalityLimit {
     config: TagCardinalityLimitConfig,
+    component_key: Option<ComponentKey>,
     accepted_tags: HashMap<String, TagValueSet>,
 }
 
+// The topology only clones transforms which enable concurrency, which this one doesn't, so rather
+// than sharing the accepted tags behind a lock, a clone starts tracking them afresh.
+impl Clone for TagCardinalityLimit {
+    fn clone(&self) -> Self {
+        Self::new(self.config.clone()).with_component_key(self.component_key.clone())
+    }
+}
+
 const fn default_limit_exceeded_action() -> LimitExceededAction {
     LimitExceededAction::DropTag
 }
@@ -111,6 +128,7 @@ impl GenerateConfig for TagCardinalityLimitConfig {
             mode: Mode::Exact,
             value_limit: default_value_limit(),
             limit_exceeded_action: default_limit_exceeded_action(),
+            reroute_dropped: false,
         })
         .unwrap()
     }
@@ -119,18 +137,26 @@ impl GenerateConfig for TagCardinalityLimitConfig {
 #[async_trait::async_trait]
 #[typetag::serde(name = "tag_cardinality_limit")]
 impl TransformConfig for TagCardinalityLimitConfig {
-    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
-        Ok(Transform::event_task(TagCardinalityLimit::new(
-            self.clone(),
-        )))
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        Ok(Transform::synchronous(
+            TagCardinalityLimit::new(self.clone()).with_component_key(context.key.clone()),
+        ))
     }
 
     fn input(&self) -> Input {
         Input::metric()
     }
 
-    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
-        vec![Output::default(DataType::Metric)]
+    fn outputs(&self, input_definition: &schema::Definition) -> Vec<Output> {
+        let default_output = Output::default(DataType::Metric);
+        if self.reroutes_dropped() {
+            vec![
+                default_output,
+                dropped_output(DataType::Metric, input_definition),
+            ]
+        } else {
+            vec![default_output]
+        }
     }
 
     fn transform_type(&self) -> &'static str {
@@ -138,6 +164,12 @@ impl TransformConfig for TagCardinalityLimitConfig {
     }
 }
 
+impl TagCardinalityLimitConfig {
+    const fn reroutes_dropped(&self) -> bool {
+        self.reroute_dropped && matches!(self.limit_exceeded_action, LimitExceededAction::DropEvent)
+    }
+}
+
 /// Container for storing the set of accepted values for a given tag key.
 #[derive(Debug)]
 struct TagValueSet {
@@ -207,10 +239,17 @@ impl TagCardinalityLimit {
     fn new(config: TagCardinalityLimitConfig) -> Self {
         Self {
             config,
+            component_key: None,
             accepted_tags: HashMap::new(),
         }
     }
 
+    /// Sets the key of the component, used to annotate rerouted events.
+    fn with_component_key(mut self, component_key: Option<ComponentKey>) -> Self {
+        self.component_key = component_key;
+        self
+    }
+
     /// Takes in key and a value corresponding to a tag on an incoming Metric
     /// Event.  If that value is already part of set of accepted values for that
     /// key, then simply returns true.  If that value is not yet part of the
@@ -220,13 +259,10 @@ impl TagCardinalityLimit {
     /// value indicates to the caller that the value is not accepted for this
     /// key, and the configured limit_exceeded_action should be taken.
     fn try_accept_tag(&mut self, key: &str, value: Cow<'_, String>) -> bool {
-        if !self.accepted_tags.contains_key(key) {
-            self.accepted_tags.insert(
-                key.to_string(),
-                TagValueSet::new(self.config.value_limit, &self.config.mode),
-            );
-        }
-        let tag_value_set = self.accepted_tags.get_mut(key).unwrap();
+        let tag_value_set = self
+            .accepted_tags
+            .entry(key.to_string())
+            .or_insert_with(|| TagValueSet::new(self.config.value_limit, &self.config.mode));
 
         if tag_value_set.contains(value.clone()) {
             // Tag value has already been accepted, nothing more to do.
@@ -249,7 +285,10 @@ impl TagCardinalityLimit {
         }
     }
 
-    fn transform_one(&mut self, mut event: Event) -> Option<Event> {
+    /// Applies the configured limits to the tags of the metric.
+    ///
+    /// Returns the key of the tag that caused the whole event to be rejected, if any.
+    fn apply_limits(&mut self, event: &mut Event) -> Option<String> {
         let metric = event.as_mut_metric();
         if let Some(tags_map) = metric.tags() {
             match self.config.limit_exceeded_action {
@@ -260,7 +299,7 @@ impl TagCardinalityLimit {
                                 tag_key: key,
                                 tag_value: value,
                             });
-                            return None;
+                            return Some(key.clone());
                         }
                     }
                 }
@@ -281,20 +320,37 @@ impl TagCardinalityLimit {
                 }
             }
         }
-        Some(event)
+        None
+    }
+
+    #[cfg(test)]
+    fn transform_one(&mut self, mut event: Event) -> Option<Event> {
+        match self.apply_limits(&mut event) {
+            Some(_) => None,
+            None => Some(event),
+        }
     }
 }
 
-impl TaskTransform<Event> for TagCardinalityLimit {
-    fn transform(
-        self: Box<Self>,
-        task: Pin<Box<dyn Stream<Item = Event> + Send>>,
-    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
-    where
-        Self: 'static,
-    {
-        let mut inner = self;
-        Box::pin(task.filter_map(move |v| ready(inner.transform_one(v))))
+impl SyncTransform for TagCardinalityLimit {
+    fn transform(&mut self, mut event: Event, output: &mut TransformOutputsBuf) {
+        match self.apply_limits(&mut event) {
+            None => output.push(event),
+            Some(key) if self.config.reroutes_dropped() => {
+                annotate_dropped(
+                    &mut event,
+                    self.component_key.as_ref(),
+                    "tag_cardinality_limit",
+                    "limit_exceeded",
+                    &format!(
+                        "Value of tag {:?} exceeds the configured `value_limit`.",
+                        key
+                    ),
+                );
+                output.push_named(DROPPED, event);
+            }
+            Some(_) => {}
+        }
     }
 }
 
@@ -332,6 +388,7 @@ mod tests {
             value_limit,
             limit_exceeded_action,
             mode: Mode::Exact,
+            reroute_dropped: false,
         })
     }
 
@@ -345,6 +402,7 @@ mod tests {
             mode: Mode::Probabilistic(BloomFilterConfig {
                 cache_size_per_key: default_cache_size(),
             }),
+            reroute_dropped: false,
         })
     }
 
@@ -477,4 +535,35 @@ mod tests {
         assert_eq!(new_event2, event2);
         assert_eq!(new_event3, event3);
     }
+
+    #[test]
+    fn tag_cardinality_limit_reroutes_dropped() {
+        let mut transform = TagCardinalityLimit::new(TagCardinalityLimitConfig {
+            value_limit: 1,
+            limit_exceeded_action: LimitExceededAction::DropEvent,
+            mode: Mode::Exact,
+            reroute_dropped: true,
+        })
+        .with_component_key(Some(ComponentKey::from("limiter")));
+        let mut outputs = TransformOutputsBuf::new_with_capacity(
+            vec![
+                Output::default(DataType::Metric),
+                Output::default(DataType::Metric).with_port(DROPPED),
+            ],
+            2,
+        );
+
+        let event1 = make_metric(vec![("tag1".into(), "val1".into())].into_iter().collect());
+        let event2 = make_metric(vec![("tag1".into(), "val2".into())].into_iter().collect());
+        transform.transform(event1.clone(), &mut outputs);
+        transform.transform(event2, &mut outputs);
+
+        assert_eq!(outputs.drain().collect::<Vec<_>>(), vec![event1]);
+        let dropped = outputs.drain_named(DROPPED).collect::<Vec<_>>();
+        assert_eq!(dropped.len(), 1);
+        let tags = dropped[0].as_metric().tags().unwrap();
+        assert_eq!(tags["tag1"], "val2");
+        assert_eq!(tags["metadata.dropped.reason"], "limit_exceeded");
+        assert_eq!(tags["metadata.dropped.component_id"], "limiter");
+    }
 }
@@ -1,19 +1,23 @@
-use std::{num::NonZeroU32, pin::Pin, time::Duration};
+use std::{num::NonZeroU32, sync::Arc, time::Duration};
 
-use async_stream::stream;
-use futures::{stream, Stream, StreamExt};
-use governor::{clock, Quota, RateLimiter};
+use governor::{clock, state::keyed::DefaultKeyedStateStore, Quota, RateLimiter};
 use snafu::Snafu;
 use vector_config::configurable_component;
 
 use crate::{
     conditions::{AnyCondition, Condition},
-    config::{DataType, Input, Output, TransformConfig, TransformContext, TransformDescription},
+    config::{
+        ComponentKey, DataType, Input, Output, TransformConfig, TransformContext,
+        TransformDescription,
+    },
     event::Event,
     internal_events::{TemplateRenderingError, ThrottleEventDiscarded},
     schema,
     template::Template,
-    transforms::{TaskTransform, Transform},
+    transforms::{
+        dropped::{annotate_dropped, dropped_output, DROPPED},
+        SyncTransform, Transform, TransformOutputsBuf,
+    },
 };
 
 /// Configuration for the `throttle` transform.
@@ -37,6 +41,12 @@ pub struct ThrottleConfig {
 
     /// A logical condition used to exclude events from sampling.
     exclude: Option<AnyCondition>,
+
+    /// Reroutes events that exceed the rate limit to a named output instead of discarding them.
+    ///
+    /// When enabled, events that would have been discarded are forwarded to a specially-named output,
+    /// `dropped`. They are annotated with additional fields describing why they were dropped.
+    reroute_dropped: bool,
 }
 
 inventory::submit! {
@@ -49,15 +59,23 @@ impl_generate_config_from_default!(ThrottleConfig);
 #[typetag::serde(name = "throttle")]
 impl TransformConfig for ThrottleConfig {
     async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
-        Throttle::new(self, context, clock::MonotonicClock).map(Transform::event_task)
+        Throttle::new(self, context, clock::MonotonicClock).map(Transform::synchronous)
     }
 
     fn input(&self) -> Input {
         Input::log()
     }
 
-    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
-        vec![Output::default(DataType::Log)]
+    fn outputs(&self, input_definition: &schema::Definition) -> Vec<Output> {
+        let default_output = Output::default(DataType::Log);
+        if self.reroute_dropped {
+            vec![
+                default_output,
+                dropped_output(DataType::Log, input_definition),
+            ]
+        } else {
+            vec![default_output]
+        }
     }
 
     fn transform_type(&self) -> &'static str {
@@ -65,13 +83,18 @@ impl TransformConfig for ThrottleConfig {
     }
 }
 
+type Limiter<C> = RateLimiter<Option<String>, DefaultKeyedStateStore<Option<String>>, C>;
+
 #[derive(Clone)]
 pub struct Throttle<C: clock::Clock<Instant = I>, I: clock::Reference> {
-    quota: Quota,
+    limiter: Arc<Limiter<C>>,
     flush_keys_interval: Duration,
+    clock: C,
+    last_flush: I,
     key_field: Option<Template>,
     exclude: Option<Condition>,
-    clock: C,
+    /// Set when discarded events are rerouted, holding the key used to annotate them.
+    reroute_dropped: Option<Option<ComponentKey>>,
 }
 
 impl<C, I> Throttle<C, I>
@@ -104,97 +127,75 @@ where
             .transpose()?;
 
         Ok(Self {
-            quota,
-            clock,
+            limiter: Arc::new(RateLimiter::dashmap_with_clock(quota, &clock)),
             flush_keys_interval,
+            last_flush: clock.now(),
+            clock,
             key_field: config.key_field.clone(),
             exclude,
+            reroute_dropped: config.reroute_dropped.then(|| context.key.clone()),
         })
     }
+
+    /// Forgets the keys whose rate limit has fully replenished, so that the set of tracked keys
+    /// does not grow without bound.
+    fn flush_keys(&mut self) {
+        let now = self.clock.now();
+        if Duration::from(now.duration_since(self.last_flush)) >= self.flush_keys_interval * 2 {
+            self.limiter.retain_recent();
+            self.last_flush = now;
+        }
+    }
 }
 
-impl<C, I> TaskTransform<Event> for Throttle<C, I>
+impl<C, I> SyncTransform for Throttle<C, I>
 where
-    C: clock::Clock<Instant = I> + Send + 'static,
-    I: clock::Reference + Send + 'static,
+    C: clock::Clock<Instant = I> + Send + Sync + 'static,
+    I: clock::Reference + Send + Sync + 'static,
 {
-    fn transform(
-        self: Box<Self>,
-        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
-    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
-    where
-        Self: 'static,
-    {
-        let mut flush_keys = tokio::time::interval(self.flush_keys_interval * 2);
-
-        let mut flush_stream = tokio::time::interval(Duration::from_millis(1000));
-
-        let limiter = RateLimiter::dashmap_with_clock(self.quota, &self.clock);
-
-        Box::pin(
-            stream! {
-              loop {
-                let mut output = Vec::new();
-                let done = tokio::select! {
-                    biased;
-
-                    maybe_event = input_rx.next() => {
-                        match maybe_event {
-                            None => true,
-                            Some(event) => {
-                                let (throttle, event) = match self.exclude.as_ref() {
-                                        Some(condition) => {
-                                            let (result, event) = condition.check(event);
-                                            (!result, event)
-                                        },
-                                        _ => (true, event)
-                                    };
-                                    if throttle {
-                                        let key = self.key_field.as_ref().and_then(|t| {
-                                            t.render_string(&event)
-                                                .map_err(|error| {
-                                                    emit!(TemplateRenderingError {
-                                                        error,
-                                                        field: Some("key_field"),
-                                                        drop_event: false,
-                                                    })
-                                                })
-                                                .ok()
-                                        });
-
-                                        match limiter.check_key(&key) {
-                                            Ok(()) => {
-                                                output.push(event);
-                                            }
-                                            _ => {
-                                                if let Some(key) = key {
-                                                  emit!(ThrottleEventDiscarded{key})
-                                                } else {
-                                                  emit!(ThrottleEventDiscarded{key: "None".to_string()})
-                                                }
-                                            }
-                                        }
-                                    } else {
-                                        output.push(event)
-                                    }
-                                false
-                            }
-                        }
-                    }
-                    _ = flush_keys.tick() => {
-                        limiter.retain_recent();
-                        false
-                    }
-                    _ = flush_stream.tick() => {
-                        false
-                    }
-                };
-                yield stream::iter(output.into_iter());
-                if done { break }
-              }
+    fn transform(&mut self, event: Event, output: &mut TransformOutputsBuf) {
+        self.flush_keys();
+
+        let (throttle, mut event) = match self.exclude.as_ref() {
+            Some(condition) => {
+                let (result, event) = condition.check(event);
+                (!result, event)
             }
-            .flatten(),
-        )
+            None => (true, event),
+        };
+        if !throttle {
+            output.push(event);
+            return;
+        }
+
+        let key = self.key_field.as_ref().and_then(|t| {
+            t.render_string(&event)
+                .map_err(|error| {
+                    emit!(TemplateRenderingError {
+                        error,
+                        field: Some("key_field"),
+                        drop_event: false,
+                    })
+                })
+                .ok()
+        });
+
+        if self.limiter.check_key(&key).is_ok() {
+            output.push(event);
+        } else if let Some(component_key) = &self.reroute_dropped {
+            annotate_dropped(
+                &mut event,
+                component_key.as_ref(),
+                "throttle",
+                "throttled",
+                "Event exceeded the rate limit.",
+            );
+            output.push_named(DROPPED, event);
+        } else {
+            emit!(ThrottleEventDiscarded {
+                key: key.unwrap_or_else(|| "None".to_string())
+            })
+        }
     }
 }
 
@@ -208,7 +209,7 @@ pub enum ConfigError {
 mod tests {
     use std::task::Poll;
 
-    use futures::SinkExt;
+    use futures::{stream, SinkExt, Stream, StreamExt};
 
     use super::*;
     use crate::event::LogEvent;
@@ -218,28 +219,42 @@ mod tests {
         crate::test_util::test_generate_config::<ThrottleConfig>();
     }
 
+    type TestThrottle =
+        Throttle<clock::FakeRelativeClock, <clock::FakeRelativeClock as clock::Clock>::Instant>;
+
+    fn throttle(config: &str, clock: clock::FakeRelativeClock) -> TestThrottle {
+        let config = toml::from_str::<ThrottleConfig>(config).unwrap();
+        Throttle::new(&config, &TransformContext::default(), clock).unwrap()
+    }
+
+    /// Runs the transform over a stream of events, as the topology does, yielding its default
+    /// output.
+    fn transform_events(
+        mut throttle: TestThrottle,
+        input_rx: impl Stream<Item = Event>,
+    ) -> impl Stream<Item = Event> {
+        input_rx.flat_map(move |event| {
+            let mut outputs =
+                TransformOutputsBuf::new_with_capacity(vec![Output::default(DataType::Log)], 1);
+            throttle.transform(event, &mut outputs);
+            stream::iter(outputs.drain().collect::<Vec<_>>())
+        })
+    }
+
     #[tokio::test]
     async fn throttle_events() {
         let clock = clock::FakeRelativeClock::default();
-        let config = toml::from_str::<ThrottleConfig>(
+        let throttle = throttle(
             r#"
 threshold = 2
 window_secs = 5
 "#,
-        )
-        .unwrap();
-
-        let throttle = Throttle::new(&config, &TransformContext::default(), clock.clone())
-            .map(Transform::event_task)
-            .unwrap();
-
-        let throttle = throttle.into_task();
+            clock.clone(),
+        );
 
         let (mut tx, rx) = futures::channel::mpsc::channel(10);
-        let mut out_stream = throttle.transform_events(Box::pin(rx));
+        let mut out_stream = Box::pin(transform_events(throttle, rx));
 
-        // tokio interval is always immediately ready, so we poll once to make sure
-        // we trip it/set the interval in the future
         assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
 
         tx.send(LogEvent::default().into()).await.unwrap();
@@ -284,7 +299,7 @@ window_secs = 5
     #[tokio::test]
     async fn throttle_exclude() {
         let clock = clock::FakeRelativeClock::default();
-        let config = toml::from_str::<ThrottleConfig>(
+        let throttle = throttle(
             r#"
 threshold = 2
 window_secs = 5
@@ -292,20 +307,12 @@ exclude = """
 exists(.special)
 """
 "#,
-        )
-        .unwrap();
-
-        let throttle = Throttle::new(&config, &TransformContext::default(), clock.clone())
-            .map(Transform::event_task)
-            .unwrap();
-
-        let throttle = throttle.into_task();
+            clock.clone(),
+        );
 
         let (mut tx, rx) = futures::channel::mpsc::channel(10);
-        let mut out_stream = throttle.transform_events(Box::pin(rx));
+        let mut out_stream = Box::pin(transform_events(throttle, rx));
 
-        // tokio interval is always immediately ready, so we poll once to make sure
-        // we trip it/set the interval in the future
         assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
 
         tx.send(LogEvent::default().into()).await.unwrap();
@@ -359,26 +366,18 @@ exists(.special)
     #[tokio::test]
     async fn throttle_buckets() {
         let clock = clock::FakeRelativeClock::default();
-        let config = toml::from_str::<ThrottleConfig>(
+        let throttle = throttle(
             r#"
 threshold = 1
 window_secs = 5
 key_field = "{{ bucket }}"
 "#,
-        )
-        .unwrap();
-
-        let throttle = Throttle::new(&config, &TransformContext::default(), clock.clone())
-            .map(Transform::event_task)
-            .unwrap();
-
-        let throttle = throttle.into_task();
+            clock,
+        );
 
         let (mut tx, rx) = futures::channel::mpsc::channel(10);
-        let mut out_stream = throttle.transform_events(Box::pin(rx));
+        let mut out_stream = Box::pin(transform_events(throttle, rx));
 
-        // tokio interval is always immediately ready, so we poll once to make sure
-        // we trip it/set the interval in the future
         assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
 
         let mut log_a = LogEvent::default();
@@ -406,4 +405,63 @@ key_field = "{{ bucket }}"
         // And still nothing there
         assert_eq!(Poll::Ready(None), futures::poll!(out_stream.next()));
     }
+
+    #[test]
+    fn throttle_flushes_keys_on_its_clock() {
+        let clock = clock::FakeRelativeClock::default();
+        let mut throttle = throttle(
+            r#"
+threshold = 1
+window_secs = 5
+key_field = "{{ bucket }}"
+"#,
+            clock.clone(),
+        );
+        let mut outputs =
+            TransformOutputsBuf::new_with_capacity(vec![Output::default(DataType::Log)], 1);
+
+        let mut log = LogEvent::default();
+        log.insert("bucket", "a");
+        throttle.transform(log.into(), &mut outputs);
+        assert_eq!(throttle.limiter.len(), 1);
+
+        // Keys are only forgotten once two windows have passed on the limiter's clock
+        clock.advance(Duration::from_secs(9));
+        throttle.flush_keys();
+        assert_eq!(throttle.limiter.len(), 1);
+
+        clock.advance(Duration::from_secs(1));
+        throttle.flush_keys();
+        assert_eq!(throttle.limiter.len(), 0);
+    }
+
+    #[test]
+    fn throttle_reroutes_dropped() {
+        let clock = clock::FakeRelativeClock::default();
+        let mut throttle = throttle(
+            r#"
+threshold = 1
+window_secs = 5
+reroute_dropped = true
+"#,
+            clock,
+        );
+        let mut outputs = TransformOutputsBuf::new_with_capacity(
+            vec![
+                Output::default(DataType::Log),
+                Output::default(DataType::Log).with_port(DROPPED),
+            ],
+            2,
+        );
+
+        throttle.transform(LogEvent::default().into(), &mut outputs);
+        throttle.transform(LogEvent::default().into(), &mut outputs);
+
+        assert_eq!(outputs.drain().count(), 1);
+        let dropped = outputs.drain_named(DROPPED).collect::<Vec<_>>();
+        assert_eq!(dropped.len(), 1);
+        let log = dropped[0].as_log();
+        assert_eq!(log["metadata.dropped.reason"], "throttled".into());
+        assert_eq!(log["metadata.dropped.component_type"], "throttle".into());
+    }
 }
@@ -74,10 +74,23 @@ api: {
 				description: """
 					Healthcheck endpoint. Useful to verify that
 					Vector is up and running.
+
+					Pass a comma-separated list of component IDs as the
+					`components` query parameter, such as
+					`/health?components=my_source,my_sink`, to also check
+					those components. The health of each is returned under
+					the `components` key, as `healthy`, `degraded`, `failing`
+					or `unknown`. The health of components with their
+					`telemetry` disabled is `unknown`, as it's derived from
+					their internal metrics, and so is that of components
+					which aren't running.
 					"""
 				responses: {
 					"200": {
-						description: "Vector is initialized and running."
+						description: "Vector is initialized and running, and none of the requested components are failing."
+					}
+					"503": {
+						description: "Vector is shutting down, or one of the requested components is failing or isn't running."
 					}
 				}
 			}
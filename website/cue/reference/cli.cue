@@ -179,6 +179,44 @@ cli: {
 
 			options: _core_options
 		}
+		"buffer": {
+			description: """
+				Inspect, export, or repair the `disk` buffer of a sink. The sink's buffer and the global `data_dir` are
+				looked up in the given configuration. The buffer must not be in use by a running Vector process.
+
+				* `stats` prints the state of the buffer as recorded in its ledger, as JSON.
+				* `dump` prints the unacknowledged events in the buffer to stdout as native JSON, one per line,
+				  without removing them.
+				* `drain` removes all events from the buffer, writing them to the file given by `--output` as
+				  native JSON, one per line, and then prints a report of any data that was skipped.
+				* `repair` copies every event that can still be read to a new buffer, which then replaces the
+				  original, and prints a report of any corrupted data that was skipped.
+				"""
+
+			example: "vector buffer --config /etc/vector/vector.toml drain my_sink --output events.json"
+
+			options: _core_options & {
+				"output": {
+					_short:      "o"
+					description: "The file to write drained events to. It must not already exist. Only used by `drain`."
+					type:        "string"
+					example:     "events.json"
+				}
+			}
+
+			args: {
+				subcommand: {
+					description: "The operation to perform: `stats`, `dump`, `drain`, or `repair`"
+					required:    true
+					type:        "string"
+				}
+				sink: {
+					description: "The ID of the sink whose buffer to operate on"
+					required:    true
+					type:        "string"
+				}
+			}
+		}
 		"generate": {
 			description: "Generate a Vector configuration containing a list of components"
 
@@ -676,6 +676,41 @@ components: {
 						}
 					}
 
+					min_tls_version: {
+						common:      false
+						description: "The minimum TLS version to allow for incoming connections. If not set, the minimum version supported by OpenSSL is used."
+						required:    false
+						type: string: {
+							default: null
+							enum: {
+								"1.0": "TLS 1.0."
+								"1.1": "TLS 1.1."
+								"1.2": "TLS 1.2."
+								"1.3": "TLS 1.3."
+							}
+						}
+					}
+					ciphersuites: {
+						common:      false
+						description: "The cipher suites to allow for incoming connections, as a colon-separated list in OpenSSL format. TLS 1.3 cipher suites and ciphers for TLS 1.2 and earlier can be mixed in the same list. If only one kind is listed, the defaults are kept for the other protocol versions."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["TLS_AES_256_GCM_SHA384:ECDHE-RSA-AES256-GCM-SHA384"]
+						}
+					}
+					alpn_protocols: {
+						common:      false
+						description: "The application protocols to negotiate using ALPN, in order of preference. The first of these protocols that is also offered by the client is selected, and connections from clients that offer none of them are rejected."
+						required:    false
+						type: array: {
+							default: null
+							items: type: string: {
+								examples: ["h2", "http/1.1"]
+							}
+						}
+					}
+
 					if Args.can_verify_certificate {
 						verify_certificate: {
 							common:      false
@@ -748,6 +783,50 @@ components: {
 						}
 					}
 
+					min_tls_version: {
+						common:      false
+						description: "The minimum TLS version to allow for outgoing connections. If not set, the minimum version supported by OpenSSL is used."
+						required:    false
+						type: string: {
+							default: null
+							enum: {
+								"1.0": "TLS 1.0."
+								"1.1": "TLS 1.1."
+								"1.2": "TLS 1.2."
+								"1.3": "TLS 1.3."
+							}
+						}
+					}
+					ciphersuites: {
+						common:      false
+						description: "The cipher suites to allow for outgoing connections, as a colon-separated list in OpenSSL format. TLS 1.3 cipher suites and ciphers for TLS 1.2 and earlier can be mixed in the same list. If only one kind is listed, the defaults are kept for the other protocol versions."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["TLS_AES_256_GCM_SHA384:ECDHE-RSA-AES256-GCM-SHA384"]
+						}
+					}
+					alpn_protocols: {
+						common:      false
+						description: "The application protocols to offer to the remote host using ALPN, in order of preference."
+						required:    false
+						type: array: {
+							default: null
+							items: type: string: {
+								examples: ["h2", "http/1.1"]
+							}
+						}
+					}
+					server_name: {
+						common:      false
+						description: "The server name to use instead of the host being connected to. It is sent using Server Name Indication (SNI), and is the one checked against the remote host's TLS certificate when `verify_hostname` is enabled. This is useful when connecting to a host by IP address."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["vector.example.com"]
+						}
+					}
+
 					if Args.can_verify_certificate {
 						verify_certificate: {
 							common:      false
@@ -946,6 +1025,18 @@ components: {
 				}
 			}
 
+			telemetry: {
+				common:      false
+				description: """
+					Whether internal metrics are collected for this component. When disabled, none of the
+					metrics tagged with this component's `component_id` are exposed, by the
+					`internal_metrics` source or otherwise. Use this to reduce the cardinality of internal
+					metrics in configurations with many components.
+					"""
+				required:    false
+				type: bool: default: true
+			}
+
 			"type": {
 				description: "The component type. This is a required field for all components and tells Vector which component to use."
 				required:    true
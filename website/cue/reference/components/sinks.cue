@@ -74,9 +74,56 @@ components: sinks: [Name=string]: {
 			type: object: {
 				examples: []
 				options: {
+					compression: {
+						common:        false
+						description:   "Compresses records before they are written to the buffer. The compression and encryption settings are recorded in the buffer, and can only be changed once the buffer has been fully drained: Vector refuses to load a buffer that still holds records written with different settings."
+						required:      false
+						relevant_when: "type = \"disk\""
+						type: string: {
+							default: "none"
+							enum: {
+								none: "Records are written uncompressed."
+								zstd: "Records are compressed with [Zstandard](\(urls.zstd))."
+							}
+						}
+					}
+					encryption: {
+						common: false
+						description: """
+							Encrypts records with AES-256-GCM before they are written to the buffer. Exactly one of `key` or
+							`key_file` must be set. Encryption can only be enabled or disabled once the buffer has been fully
+							drained, and records encrypted with a key can only be read back with that same key, so the key
+							must not be changed while the buffer still holds data.
+							"""
+						required:      false
+						relevant_when: "type = \"disk\""
+						type: object: {
+							examples: []
+							options: {
+								key: {
+									common:      true
+									description: "The base64-encoded 256-bit encryption key. Use the `SECRET[<backend_name>.<secret_key>]` notation to retrieve the key from a secret backend instead of storing it in the configuration file."
+									required:    false
+									type: string: {
+										default: null
+										examples: ["SECRET[backend.buffer_key]"]
+									}
+								}
+								key_file: {
+									common:      true
+									description: "Path to a file containing the base64-encoded 256-bit encryption key."
+									required:    false
+									type: string: {
+										default: null
+										examples: ["/etc/vector/buffer.key"]
+									}
+								}
+							}
+						}
+					}
 					max_events: {
 						common:        true
-						description:   "The maximum number of [events](\(urls.vector_data_model)) allowed in the buffer."
+						description:   "The maximum number of [events](\(urls.vector_data_model)) allowed in the buffer. Cannot be set together with `max_size`."
 						required:      false
 						relevant_when: "type = \"memory\""
 						type: uint: {
@@ -87,9 +134,12 @@ components: sinks: [Name=string]: {
 					max_size: {
 						description: """
 							The maximum size of the buffer on the disk. Must be at least ~256 megabytes (268435488 bytes).
+
+							For `memory` buffers, this is optional and limits the buffer by the in-memory size of its
+							events instead of their number. Cannot be set together with `max_events`.
 							"""
 						required:      true
-						relevant_when: "type = \"disk\""
+						relevant_when: "type = \"disk\" or type = \"memory\""
 						type: uint: {
 							examples: [104900000]
 							unit: "bytes"
@@ -196,6 +246,9 @@ components: sinks: [Name=string]: {
 											if codec == "avro" {
 												avro: "Avro encoded event with a given schema."
 											}
+											if codec == "syslog" {
+												syslog: "[Syslog](\(urls.syslog)) formatted event, following either RFC 5424 or RFC 3164."
+											}
 										}
 									}
 								}
@@ -224,6 +277,56 @@ components: sinks: [Name=string]: {
 											}
 										}
 									}
+									if codec == "syslog" {
+										syslog: {
+											description:   "Options for the `syslog` codec."
+											required:      false
+											relevant_when: "codec = `syslog`"
+											type: object: options: {
+												rfc: {
+													description: "The syslog message format."
+													required:    false
+													type: string: {
+														default: "rfc5424"
+														enum: {
+															rfc3164: "The legacy BSD syslog format, as described in RFC 3164."
+															rfc5424: "The syslog protocol, as defined in RFC 5424."
+														}
+													}
+												}
+												facility_field: {
+													description: "The event field holding the facility, either as a name, such as `local0`, or as a number. Defaults to the `user` facility if the field is missing."
+													required:    false
+													type: string: default: "facility"
+												}
+												severity_field: {
+													description: "The event field holding the severity, either as a name, such as `err`, or as a number. Defaults to the `notice` severity if the field is missing."
+													required:    false
+													type: string: default: "severity"
+												}
+												app_name_field: {
+													description: "The event field holding the application name."
+													required:    false
+													type: string: default: "appname"
+												}
+												proc_id_field: {
+													description: "The event field holding the process ID."
+													required:    false
+													type: string: default: "procid"
+												}
+												msg_id_field: {
+													description: "The event field holding the message ID. Only used with the RFC 5424 format."
+													required:    false
+													type: string: default: "msgid"
+												}
+												structured_data_field: {
+													description: "The event field holding the structured data, as an object whose keys are SD-IDs, each holding an object of parameters. Only used with the RFC 5424 format."
+													required:    false
+													type: string: default: "structured_data"
+												}
+											}
+										}
+									}
 								}
 							}
 
@@ -287,6 +390,7 @@ components: sinks: [Name=string]: {
 											character_delimited: "Byte frames are delimited by a chosen character."
 											length_delimited:    "Byte frames are prefixed by an unsigned big-endian 32-bit integer indicating the length."
 											newline_delimited:   "Byte frames are delimited by a newline character."
+											octet_counting:      "Byte frames are prefixed by their length in bytes as an ASCII decimal number followed by a space, as used by syslog over TCP."
 										}
 									}
 								}
@@ -311,6 +415,17 @@ components: sinks: [Name=string]: {
 			}
 		}
 
+		reroute_rejected: {
+			common: false
+			description: """
+				Reroute the events this sink rejects to a separate output, named `rejected`, instead of
+				discarding them. The rejected events can be consumed by other components by using
+				`<sink_id>.rejected` as an input, and are annotated with the error the sink reported.
+				"""
+			required: false
+			type: bool: default: false
+		}
+
 		if features.healthcheck != _|_ {
 			if features.healthcheck.enabled {
 				healthcheck: {
@@ -326,6 +441,22 @@ components: sinks: [Name=string]: {
 								required:    false
 								type: bool: default: true
 							}
+							interval_secs: {
+								common: false
+								description: """
+									How often to re-run the healthcheck while Vector is running, overriding the
+									global `healthchecks.interval_secs` option. Each re-check builds a fresh client
+									for the healthcheck, without rebuilding the sink itself. Only the `datadog_logs`,
+									`elasticsearch`, `http`, `kafka`, `loki`, `new_relic`, and `splunk_hec_logs` sinks
+									support re-running their healthcheck. If unset, the healthcheck only runs upon
+									Vector boot.
+									"""
+								required: false
+								type: uint: {
+									default: null
+									unit:    "seconds"
+								}
+							}
 						}
 					}
 				}
@@ -380,6 +511,35 @@ components: sinks: [Name=string]: {
 									}
 								}
 							}
+							circuit_breaker: {
+								common:      false
+								description: "Configures the circuit breaker, which stops sending requests while the downstream service is failing them."
+								required:    false
+								type: object: {
+									examples: []
+									options: {
+										cool_down_secs: {
+											common:      false
+											description: "The amount of time the circuit breaker stays open before a single request is sent to probe whether the service has recovered."
+											required:    false
+											type: uint: {
+												default: 30
+												unit:    "seconds"
+											}
+										}
+										failure_threshold: {
+											common:      false
+											description: "The number of consecutive failed requests after which the circuit breaker opens. Requests fail when they time out, can't be sent, or get a response which would be retried. Responses where the service explicitly asks to slow down neither count as failures nor reset the count, but they do keep an open circuit breaker from closing. The circuit breaker is disabled unless this is set."
+											required:    false
+											type: uint: {
+												default: null
+												examples: [5]
+												unit: "requests"
+											}
+										}
+									}
+								}
+							}
 							concurrency: {
 								common: true
 								if features.send.request.adaptive_concurrency {
@@ -412,6 +572,22 @@ components: sinks: [Name=string]: {
 									unit:    null
 								}
 							}
+							rate_limit_bytes_per_sec: {
+								common:      false
+								description: """
+									The maximum number of bytes sent per second, measured as the encoded size of each
+									request. Requests exceeding the limit are delayed rather than dropped, and up to one
+									second worth of bytes can be sent at once after being idle. The time spent waiting
+									is recorded by the `byte_rate_limit_wait_seconds` metric. Retries are not counted
+									against the limit.
+									"""
+								required: false
+								type: uint: {
+									default: null
+									examples: [1048576]
+									unit: "bytes"
+								}
+							}
 							retry_attempts: {
 								common:      false
 								description: "The maximum number of retries to make for failed requests. The default, for all intents and purposes, represents an infinite number of retries."
@@ -569,6 +745,40 @@ components: sinks: [Name=string]: {
 			}
 		}
 
+		rejected_events: {
+			title: "Rejected events"
+			body: """
+				When a sink receives a non-retriable error for an event, such as a mapping error in
+				an Elasticsearch bulk response, the event is rejected and, by default, discarded. By
+				enabling the [`reroute_rejected`](#reroute_rejected) option, rejected events are
+				instead sent to the sink's `rejected` output:
+
+				```toml title="vector.toml"
+				[sinks.es]
+				type = "elasticsearch"
+				inputs = ["app_logs"]
+				reroute_rejected = true
+
+				[sinks.dead_letter]
+				type = "file"
+				inputs = ["es.rejected"]
+				path = "/var/lib/vector/rejected-%Y-%m-%d.log"
+				```
+
+				Each rejected event is annotated under `metadata.rejected` with the `error` returned by
+				the downstream service, along with the `component_id`, `component_type`, and
+				`component_kind` of the sink that rejected it. The `elasticsearch`, `splunk_hec_logs`,
+				`splunk_hec_metrics` and `loki` sinks report the error of the downstream service, such
+				as the errors of the items that failed in an Elasticsearch bulk response. Other sinks
+				report the error of the request.
+
+				Tracking rejected events requires holding a copy of every event until the sink has
+				finished with it. The copies of logs and traces share their fields with the original
+				events until the sink modifies them. At most 10,000 events are tracked at once, past
+				which the sink stops taking in events until earlier ones are finished with.
+				"""
+		}
+
 		if features.healthcheck.enabled {
 			healthchecks: {
 				title: "Health checks"
@@ -597,6 +807,19 @@ components: sinks: [Name=string]: {
 							`false`.
 							"""
 					},
+					{
+						title: "Continuous health checks"
+						body: """
+							If `healthcheck.interval_secs`, or the global `healthchecks.interval_secs` option, is
+							set, the health check is re-run at that interval for as long as the sink is running.
+							Its latest result is reported, along with the sink's recent errors and circuit
+							breaker state, as the sink's health through the `/health` endpoint and the GraphQL
+							API, and the `component_health` internal metric.
+
+							Sinks which can't build their health check on its own are built again, and then
+							dropped, each time it's re-run.
+							"""
+					},
 				]
 			}
 		}
@@ -670,6 +893,11 @@ components: sinks: [Name=string]: {
 								```
 
 								These will apply to both `adaptive` and fixed `request.concurrency` values.
+
+								You can also limit the number of bytes sent per second, via the
+								`request.rate_limit_bytes_per_sec` option. Requests are delayed until they fit
+								within the limit, so this shapes traffic to match ingest quotas without dropping
+								any events.
 								"""
 						},
 					]
@@ -682,6 +910,33 @@ components: sinks: [Name=string]: {
 						Other responses will not be retried. You can control the number of
 						retry attempts and backoff rate with the `request.retry_attempts` and
 						`request.retry_backoff_secs` options.
+
+						When a response includes a `Retry-After` header, Vector waits at least
+						as long as it asks for before retrying, even if that is longer than the
+						current backoff. Responses with status 429, or with a `Retry-After`
+						header, are also treated as the service signalling that it is overloaded,
+						which makes adaptive concurrency decrease the concurrency limit right away.
+						"""
+				}
+
+				circuit_breaker: {
+					title: "Circuit breaker"
+					body: """
+						When the downstream service is down, retrying every failed request only adds
+						to its load. Setting `request.circuit_breaker.failure_threshold` opens a
+						circuit breaker after that many consecutive requests have failed. While it is
+						open, no requests are sent, retries included, and events are held in the
+						sink's buffer instead, where the buffer's `when_full` behavior applies as
+						usual.
+
+						After `request.circuit_breaker.cool_down_secs` a single request is sent to
+						probe the service. The circuit breaker closes once a request succeeds, and
+						opens again for another cool-down if the probe fails. Opening the circuit
+						breaker also resets the adaptive concurrency limit to a single request, so
+						that requests ramp back up gradually once the service has recovered.
+
+						The state of the circuit breaker is reported by the `circuit_breaker_state`
+						metric, and by the `circuitBreakerState` field of sinks in the GraphQL API.
 						"""
 				}
 			}
@@ -706,6 +961,8 @@ components: sinks: [Name=string]: {
 		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
 		events_in_total:                      components.sources.internal_metrics.output.metrics.events_in_total
 		utilization:                          components.sources.internal_metrics.output.metrics.utilization
+		component_residency_seconds:          components.sources.internal_metrics.output.metrics.component_residency_seconds
+		event_latency_seconds:                components.sources.internal_metrics.output.metrics.event_latency_seconds
 		buffer_byte_size:                     components.sources.internal_metrics.output.metrics.buffer_byte_size
 		buffer_events:                        components.sources.internal_metrics.output.metrics.buffer_events
 		buffer_received_events_total:         components.sources.internal_metrics.output.metrics.buffer_received_events_total
@@ -713,5 +970,14 @@ components: sinks: [Name=string]: {
 		buffer_sent_events_total:             components.sources.internal_metrics.output.metrics.buffer_sent_events_total
 		buffer_sent_event_bytes_total:        components.sources.internal_metrics.output.metrics.buffer_sent_event_bytes_total
 		buffer_discarded_events_total:        components.sources.internal_metrics.output.metrics.buffer_discarded_events_total
+		component_health:                     components.sources.internal_metrics.output.metrics.component_health
+
+		if features.send != _|_ {
+			if features.send.request.enabled {
+				byte_rate_limit_wait_seconds: components.sources.internal_metrics.output.metrics.byte_rate_limit_wait_seconds
+				circuit_breaker_opened_total: components.sources.internal_metrics.output.metrics.circuit_breaker_opened_total
+				circuit_breaker_state:        components.sources.internal_metrics.output.metrics.circuit_breaker_state
+			}
+		}
 	}
 }
@@ -28,7 +28,7 @@ components: sinks: file: {
 				codec: {
 					enabled: true
 					framing: true
-					enum: ["json", "text"]
+					enum: ["json", "syslog", "text"]
 				}
 			}
 			request: enabled: false
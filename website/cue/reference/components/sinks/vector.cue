@@ -85,6 +85,37 @@ components: sinks: vector: {
 				examples: ["92.12.333.224:\(_port)"]
 			}
 		}
+		endpoints: {
+			description: """
+				Additional downstream Vector addresses to route events to. Events are distributed between `address`
+				and these addresses based on the rendered `partition_key`, which must be set when this option is used.
+				"""
+			common:        false
+			required:      false
+			relevant_when: "version = \"2\""
+			type: array: {
+				default: []
+				items: type: string: examples: ["92.12.333.225:\(_port)"]
+			}
+		}
+		partition_key: {
+			description: """
+				The key used to route events to a downstream Vector. Events with the same key are always sent to the
+				same address, which is required when running stateful transforms such as `reduce`, `aggregate`, or
+				`dedupe` on an aggregator tier. Keys are assigned with rendezvous hashing, so if an address becomes
+				unreachable, only the keys routed to it are remapped to the remaining addresses, and retries of requests
+				sent to it go to the remaining addresses as well. An unreachable address is health checked every five
+				seconds, and keys are only routed back to it once it reports that it is serving again.
+				"""
+			common:        false
+			required:      false
+			relevant_when: "version = \"2\""
+			type: string: {
+				default: null
+				examples: ["{{ user_id }}", "{{ host }}"]
+				syntax: "template"
+			}
+		}
 		compression: {
 			description: "Enable gRPC compression with gzip."
 			common:      true
@@ -68,7 +68,7 @@ components: sources: host_metrics: {
 
 	configuration: {
 		collectors: {
-			description: "The list of host metric collector services to use. Defaults to all collectors."
+			description: "The list of host metric collector services to use. Defaults to all collectors, except for `process` and `tcp`, which emit many series and must be enabled explicitly."
 			common:      true
 			required:    false
 			type: array: {
@@ -83,6 +83,8 @@ components: sources: host_metrics: {
 						host:       "Metrics related to host"
 						memory:     "Metrics related to memory utilization."
 						network:    "Metrics related to network utilization."
+						process:    "Metrics related to individual processes (Linux only)."
+						tcp:        "Metrics related to TCP connections and socket statistics (Linux only)."
 					}
 				}
 			}
@@ -401,6 +403,161 @@ components: sources: host_metrics: {
 				}
 			}
 		}
+		process: {
+			common: false
+			description: #"""
+				Options for the "process" metrics collector.
+
+				Note: this collector is only available on Linux systems.
+				"""#
+			required: false
+			type: object: options: {
+				names: {
+					common:      false
+					required:    false
+					description: "Lists of process name patterns to include or exclude. The process name is the executable name reported by the kernel, truncated to 15 characters."
+					type: object: options: {
+						includes: {
+							required: false
+							common:   false
+							description: """
+								The list of process name patterns for which to gather metrics.
+
+								Defaults to including all processes.
+
+								The patterns are matched using globbing.
+								"""
+							type: array: {
+								default: ["*"]
+								items: type: string: {
+									examples: ["vector", "java*"]
+								}
+							}
+						}
+						excludes: {
+							required: false
+							common:   false
+							description: """
+								The list of process name patterns for which not to gather metrics.
+
+								Defaults to excluding no processes.
+
+								The patterns are matched using globbing.
+								"""
+							type: array: {
+								default: []
+								items: type: string: {
+									examples: ["kworker*"]
+								}
+							}
+						}
+					}
+				}
+				cmdlines: {
+					common:      false
+					required:    false
+					description: "Lists of process command line patterns to include or exclude. The command line arguments are joined with spaces before being matched, and kernel threads are never matched."
+					type: object: options: {
+						includes: {
+							required: false
+							common:   false
+							description: """
+								The list of command line patterns for which to gather metrics.
+
+								Defaults to including all processes.
+
+								The patterns are matched using globbing.
+								"""
+							type: array: {
+								default: ["*"]
+								items: type: string: {
+									examples: ["*--config /etc/vector/*"]
+								}
+							}
+						}
+						excludes: {
+							required: false
+							common:   false
+							description: """
+								The list of command line patterns for which not to gather metrics.
+
+								Defaults to excluding no processes.
+
+								The patterns are matched using globbing.
+								"""
+							type: array: {
+								default: []
+								items: type: string: {
+									examples: ["/usr/bin/python3 *"]
+								}
+							}
+						}
+					}
+				}
+				max_processes: {
+					common:      false
+					required:    false
+					description: "The maximum number of processes to report metrics for. Every process produces its own set of series tagged with its PID, so this bounds the cardinality of the emitted metrics. Processes are considered in order of their PID."
+					type: uint: {
+						unit:    null
+						default: 100
+						examples: [10, 1000]
+					}
+				}
+			}
+		}
+		tcp: {
+			common: false
+			description: #"""
+				Options for the "tcp" metrics collector.
+
+				Note: this collector is only available on Linux systems.
+				"""#
+			required: false
+			type: object: options: {
+				ports: {
+					common:      false
+					required:    false
+					description: "Lists of local port patterns to include or exclude. This only applies to the metrics computed from individual sockets, and not to the counters reported by the kernel for the whole host."
+					type: object: options: {
+						includes: {
+							required: false
+							common:   false
+							description: """
+								The list of local port patterns for which to gather socket metrics.
+
+								Defaults to including all ports.
+
+								The patterns are matched using globbing.
+								"""
+							type: array: {
+								default: ["*"]
+								items: type: string: {
+									examples: ["443", "80*"]
+								}
+							}
+						}
+						excludes: {
+							required: false
+							common:   false
+							description: """
+								The list of local port patterns for which not to gather socket metrics.
+
+								Defaults to excluding no ports.
+
+								The patterns are matched using globbing.
+								"""
+							type: array: {
+								default: []
+								items: type: string: {
+									examples: ["22"]
+								}
+							}
+						}
+					}
+				}
+			}
+		}
 	}
 
 	output: metrics: {
@@ -494,6 +651,48 @@ components: sources: host_metrics: {
 		network_transmit_packets_drop_total: _host & _network_nomac & {description: "The number of packets dropped during transmits on this interface."}
 		network_transmit_packets_total:      _host & _network_nomac & {description: "The number of packets transmitted on this interface."}
 
+		// Host processes
+		process_cpu_seconds_total: _host & _process_counter & {
+			description: "The number of CPU seconds used by the process in different operating modes."
+			tags: mode: {
+				description: "Which mode the process was running in during the given time."
+				required:    true
+				examples: ["system", "user"]
+			}
+		}
+		process_io_read_bytes_total:    _host & _process_counter & {description: "The number of bytes the process caused to be read from storage."}
+		process_io_written_bytes_total: _host & _process_counter & {description: "The number of bytes the process caused to be written to storage."}
+		process_memory_rss_bytes:       _host & _process_gauge & {description:   "The number of bytes of resident memory used by the process."}
+		process_open_fds:               _host & _process_gauge & {description:   "The number of file descriptors opened by the process."}
+		process_threads:                _host & _process_gauge & {description:   "The number of threads in the process."}
+
+		// Host TCP
+		tcp_connections: _host & _tcp_state & {description:     "The number of TCP sockets in the given state."}
+		tcp_rx_queued_bytes: _host & _tcp_state & {description: "The number of bytes waiting in the receive queues of TCP sockets in the given state."}
+		tcp_tx_queued_bytes: _host & _tcp_state & {description: "The number of bytes waiting in the send queues of TCP sockets in the given state."}
+		tcp_listen_backlog: _host & {
+			description:   "The number of connections waiting to be accepted on the listening port."
+			type:          "gauge"
+			relevant_when: "OS is Linux"
+			tags: _host_metrics_tags & {
+				collector: examples: ["tcp"]
+				port: {
+					description: "The local port being listened on."
+					required:    true
+					examples: ["443"]
+				}
+			}
+		}
+		tcp_active_opens_total:           _host & _tcp_counter & {description: "The number of connections opened by this host."}
+		tcp_passive_opens_total:          _host & _tcp_counter & {description: "The number of connections accepted by this host."}
+		tcp_attempt_fails_total:          _host & _tcp_counter & {description: "The number of failed connection attempts."}
+		tcp_established_resets_total:     _host & _tcp_counter & {description: "The number of established connections that were reset."}
+		tcp_retransmitted_segments_total: _host & _tcp_counter & {description: "The number of retransmitted TCP segments."}
+		tcp_receive_errors_total:         _host & _tcp_counter & {description: "The number of TCP segments received in error."}
+		tcp_listen_overflows_total:       _host & _tcp_counter & {description: "The number of times the accept queue of a listening socket overflowed."}
+		tcp_listen_drops_total:           _host & _tcp_counter & {description: "The number of connection requests dropped by listening sockets."}
+		tcp_syncookies_sent_total:        _host & _tcp_counter & {description: "The number of SYN cookies sent, typically in response to a SYN flood."}
+
 		// Helpers
 		_host: {
 			default_namespace: "host"
@@ -583,6 +782,48 @@ components: sources: host_metrics: {
 			}
 		}
 		_network_nomac: _network_gauge & {relevant_when: "OS is not macOS"}
+		_process_tags: _host_metrics_tags & {
+			collector: examples: ["process"]
+			name: {
+				description: "The process name."
+				required:    true
+				examples: ["vector", "systemd"]
+			}
+			pid: {
+				description: "The process ID."
+				required:    true
+				examples: ["1", "4242"]
+			}
+		}
+		_process_counter: {
+			type:          "counter"
+			tags:          _process_tags
+			relevant_when: "OS is Linux"
+		}
+		_process_gauge: {
+			type:          "gauge"
+			tags:          _process_tags
+			relevant_when: "OS is Linux"
+		}
+		_tcp_counter: {
+			type:          "counter"
+			relevant_when: "OS is Linux"
+			tags: _host_metrics_tags & {
+				collector: examples: ["tcp"]
+			}
+		}
+		_tcp_state: {
+			type:          "gauge"
+			relevant_when: "OS is Linux"
+			tags: _host_metrics_tags & {
+				collector: examples: ["tcp"]
+				state: {
+					description: "The TCP connection state."
+					required:    true
+					examples: ["established", "listen", "time_wait"]
+				}
+			}
+		}
 	}
 
 	telemetry: metrics: {
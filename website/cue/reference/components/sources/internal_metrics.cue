@@ -234,6 +234,24 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags
 		}
+		byte_rate_limit_wait_seconds: {
+			description:       "The time requests spent waiting on the `request.rate_limit_bytes_per_sec` limit before being sent."
+			type:              "histogram"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		circuit_breaker_opened_total: {
+			description:       "The number of times the circuit breaker of a sink has opened."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		circuit_breaker_state: {
+			description:       "The state of the circuit breaker of a sink: `0` when closed, `1` when half-open and probing the service, and `2` when open."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		checkpoint_write_errors_total: {
 			description:       "The total number of errors writing checkpoints. This metric is deprecated in favor of `component_errors_total`."
 			type:              "counter"
@@ -417,6 +435,15 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags
 		}
+		event_latency_seconds: {
+			description: """
+				The time, in seconds, from an event being ingested by a source until it's
+				delivered by this sink. Only recorded when `event_latency` is enabled.
+				"""
+			type:              "histogram"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		events_discarded_total: {
 			description:       "The total number of events discarded by this component."
 			type:              "counter"
@@ -520,6 +547,12 @@ components: sources: internal_metrics: {
 				stage:      _stage
 			}
 		}
+		component_health: {
+			description:       "The health of a component: `0` when healthy, `1` when degraded by recent errors, a failed healthcheck or a half-open circuit breaker, and `2` when failing. It isn't reported for components with their `telemetry` disabled."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		component_received_bytes_total: {
 			description:       string | *"The number of raw bytes accepted by this component from source origins."
 			type:              "counter"
@@ -606,6 +639,17 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              component_received_events_total.tags
 		}
+		component_residency_seconds: {
+			description: """
+				The time, in seconds, that events spend in this component, from being handed off by
+				the previous component until being sent on by this one, or delivered if this is a sink.
+				This includes the time spent waiting in the component's input buffer. Only recorded
+				when `event_latency.component_residency` is enabled.
+				"""
+			type:              "histogram"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		component_sent_bytes_total: {
 			description:       "The number of raw bytes sent by this component to destination sinks."
 			type:              "counter"
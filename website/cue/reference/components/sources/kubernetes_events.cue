@@ -0,0 +1,197 @@
+package metadata
+
+components: sources: kubernetes_events: {
+	title: "Kubernetes Events"
+
+	description: """
+		Collects the `Event` objects of a Kubernetes cluster, such as the ones reporting an
+		`OOMKilling`, a `FailedScheduling` or a `BackOff`, via the `events.k8s.io/v1` API.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		collect: {
+			checkpoint: enabled: true
+			from: {
+				service: services.kubernetes
+
+				interface: socket: {
+					api: {
+						title: "Kubernetes Events API"
+						url:   urls.kubernetes_events_api
+					}
+					direction: "outgoing"
+					protocols: ["http"]
+					ssl: "optional"
+				}
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		requirements: [
+			"""
+				[Kubernetes](\(urls.kubernetes)) version `\(services.kubernetes.versions)` is required.
+				""",
+			"""
+				Vector must be allowed to `list` and `watch` the `events` of the `events.k8s.io` API group,
+				in the watched namespaces or in the whole cluster.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: "kubernetes"
+	}
+
+	configuration: {
+		namespaces: {
+			common:      true
+			description: "The namespaces to watch `Event`s in. If empty, the `Event`s of all namespaces are watched."
+			required:    false
+			type: array: {
+				default: []
+				items: type: string: {
+					examples: ["default", "kube-system"]
+				}
+			}
+		}
+		field_selector: {
+			common:      true
+			description: "Specifies the field selector to filter `Event`s with."
+			required:    false
+			type: string: {
+				default: ""
+				examples: ["type=Warning", "reason=OOMKilling", "regarding.kind=Pod,type=Warning"]
+			}
+		}
+		label_selector: {
+			common:      false
+			description: "Specifies the label selector to filter `Event`s with."
+			required:    false
+			type: string: {
+				default: ""
+				examples: ["my_custom_label!=my_value"]
+			}
+		}
+		kube_config_file: {
+			common:      false
+			description: "Optional path to a kubeconfig file readable by Vector. If not set, Vector will try to connect to Kubernetes using in-cluster configuration."
+			required:    false
+			type: string: default: null
+		}
+	}
+
+	output: logs: event: {
+		description: "A Kubernetes `Event`."
+		fields: {
+			message: {
+				description: "The `note` of the `Event`, describing what happened."
+				required:    false
+				common:      true
+				type: string: {
+					default: null
+					examples: ["Back-off restarting failed container"]
+				}
+			}
+			reason: {
+				description: "Why the action was taken."
+				required:    false
+				common:      true
+				type: string: {
+					default: null
+					examples: ["BackOff", "FailedScheduling", "OOMKilling"]
+				}
+			}
+			type: {
+				description: "The type of the `Event`."
+				required:    false
+				common:      true
+				type: string: {
+					default: null
+					enum: {
+						Normal:  "A normal `Event`."
+						Warning: "An `Event` reporting a problem."
+					}
+				}
+			}
+			regarding: {
+				description: "The object the `Event` is about."
+				required:    false
+				common:      true
+				type: object: {
+					examples: [{"kind": "Pod", "namespace": "default", "name": "app-0"}]
+					options: {}
+				}
+			}
+			metadata: {
+				description: "The metadata of the `Event` object, without its managed fields."
+				required:    true
+				type: object: {
+					examples: [{"name": "app-0.16f7d3a2b9e1c0d4", "namespace": "default", "uid": "0f1c2d3e-4b5a-6978-8a9b-0c1d2e3f4a5b"}]
+					options: {}
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["kubernetes_events"]
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: """
+					The time at which the `Event` was last observed: its `eventTime`, or the time of the
+					last occurrence of its series, or its creation time.
+					"""
+			}
+			"*": {
+				description: "The other fields of the `Event` are copied as is into the event, such as `action`, `reportingController` or `series`."
+				required:    true
+				type: "*": {}
+			}
+		}
+	}
+
+	how_it_works: {
+		deduplication: {
+			title: "De-duplication"
+			body:  """
+				When the source starts, and whenever the API server restarts the watch, all the
+				`Event`s currently in the cluster are listed. To not emit them again, the source keeps
+				the `resourceVersion` of every `Event` it emitted in its data directory. An `Event` is
+				emitted again only when it's updated, for example when the count of its series is
+				incremented.
+				"""
+		}
+
+		kubernetes_api_access_control: {
+			title: "Kubernetes API access control"
+			body:  """
+				Vector requires access to the Kubernetes API to watch the `Event`s. A `ClusterRole`
+				granting the `list` and `watch` verbs on the `events` resource of the `events.k8s.io`
+				API group is needed, or a `Role` in each of the watched namespaces.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		events_in_total:                      components.sources.internal_metrics.output.metrics.events_in_total
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_discarded_events_total:     components.sources.internal_metrics.output.metrics.component_discarded_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+	}
+}
@@ -197,6 +197,20 @@ components: sources: kubernetes_logs: {
 			required:    false
 			type: string: default: null
 		}
+		routing_key_field: {
+			common: false
+			description: """
+				A field to set to the routing key of the event, for use by downstream components such as the
+				[`route` transform](\(urls.vector_route_transform)). The routing key is the `Namespace` of the
+				`Pod`, unless the `Pod` overrides it with the `vector.dev/routing-key` annotation, see
+				[Pod annotations](#pod-annotations).
+				"""
+			required: false
+			type: string: {
+				default: null
+				examples: ["routing_key"]
+			}
+		}
 		kube_config_file: {
 			common:      false
 			description: "Optional path to a kubeconfig file readable by Vector. If not set, Vector will try to connect to Kubernetes using in-cluster configuration."
@@ -565,6 +579,38 @@ components: sources: kubernetes_logs: {
 					`container1` and `container2` of the `Pod` marked with the annotation,
 					while logs from other `container`s in the `Pod` will still be
 					collected.
+
+					Conversely, the `vector.dev/include-containers` annotation restricts
+					the collection to the enumerated `container`s. Exclusions still apply
+					to the included `container`s.
+					"""
+		}
+
+		pod_annotations: {
+			title: "Pod annotations"
+			body:  """
+					Besides the [container exclusion](#container-exclusion) ones, `Pod`s
+					can override how their logs are processed with the following
+					*annotations*, without changing the configuration of Vector:
+
+					| Annotation | Description |
+					|:-----------|:------------|
+					| `vector.dev/multiline-start-pattern` | Regular expression pattern that matches the start of a new message. Setting it enables the multiline aggregation of the logs of the `Pod`. |
+					| `vector.dev/multiline-condition-pattern` | Regular expression pattern that determines whether or not more lines should be read. Defaults to the start pattern. |
+					| `vector.dev/multiline-mode` | The multiline aggregation mode, one of `continue_through`, `continue_past`, `halt_before`, or `halt_with`, as in the [`file` source](\(urls.vector_file_source)). Defaults to `halt_before`. |
+					| `vector.dev/multiline-timeout-ms` | The maximum amount of time to wait for the next additional line, in milliseconds. Defaults to `1000`. |
+					| `vector.dev/routing-key` | The routing key written to the `routing_key_field`, instead of the `Namespace` of the `Pod`. |
+
+					Multiline aggregation is applied to the `message` of the events, after
+					partial messages are merged. For example, to aggregate Java stack traces:
+
+					```yaml
+					vector.dev/multiline-start-pattern: '^[^\\s]'
+					vector.dev/multiline-condition-pattern: '^\\s+'
+					vector.dev/multiline-mode: continue_through
+					```
+
+					Invalid annotations are reported and ignored.
 					"""
 		}
 
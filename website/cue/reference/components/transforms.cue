@@ -17,6 +17,7 @@ components: transforms: [Name=string]: {
 		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
 		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
 		utilization:                          components.sources.internal_metrics.output.metrics.utilization
+		component_residency_seconds:          components.sources.internal_metrics.output.metrics.component_residency_seconds
 		component_sent_events_total:          components.sources.internal_metrics.output.metrics.component_sent_events_total
 		component_sent_event_bytes_total:     components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
 	}
@@ -33,6 +33,15 @@ components: transforms: filter: {
 			required: true
 			type: condition: {}
 		}
+		reroute_dropped: {
+			common:   false
+			required: false
+			description: """
+				Send events that do not match the `condition` to the `dropped` output instead of dropping them
+				entirely.
+				"""
+			type: bool: default: false
+		}
 	}
 
 	input: {
@@ -82,4 +91,18 @@ components: transforms: filter: {
 	telemetry: metrics: {
 		events_discarded_total: components.sources.internal_metrics.output.metrics.events_discarded_total
 	}
+
+	outputs: [
+		components._default_output,
+		{
+			name: "dropped"
+			description: """
+				This transform also implements an additional `dropped` output. When `reroute_dropped` is set to
+				`true`, events that do not match the `condition` are sent to the `dropped` output instead of being
+				discarded. For a transform component named `foo`, this output can be accessed by specifying
+				`foo.dropped` as the input to another component. Events sent to this output are annotated with
+				the reason they were dropped.
+				"""
+		},
+	]
 }
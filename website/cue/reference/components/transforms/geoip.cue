@@ -5,7 +5,8 @@ components: transforms: geoip: {
 
 	description: """
 		Enrich events with geolocation data from the MaxMind GeoIP2-City,
-		GeoLite2-City, GeoIP2-ISP and GeoLite2-ASN databases.
+		GeoLite2-City, GeoIP2-Country, GeoLite2-Country, GeoIP2-ISP, GeoLite2-ASN,
+		GeoIP2-Anonymous-IP and GeoIP2-Connection-Type databases.
 
 		Note that this transform has been deprecated in-lieu of using a `geoip` enrichment table
 		along with
@@ -39,8 +40,9 @@ components: transforms: geoip: {
 		database: {
 			description: """
 				Path to the [MaxMind GeoIP2](\(urls.maxmind_geoip2)) or [GeoLite2 binary city
-				database](\(urls.maxmind_geolite2_city)) file (`GeoLite2-City.mmdb`). Other
-				databases, such as the country database, are not supported.
+				database](\(urls.maxmind_geolite2_city)) file (`GeoLite2-City.mmdb`). See
+				[supported databases](#supported-maxmind-databases) for the other databases that can
+				be used.
 				"""
 			required:    true
 			type: string: {
@@ -98,11 +100,29 @@ components: transforms: geoip: {
 				* [GeoIP2-ISP.mmdb](\(urls.maxmind_geoip2_isp)) (paid) — Determine the Internet
 					Service Provider (ISP), organization name, and autonomous system organization
 					and number associated with an IP address.
+				* [GeoIP2-Anonymous-IP.mmdb](\(urls.maxmind_geoip2_anonymous_ip)) (paid) — Determine
+					whether an IP address belongs to a VPN, a hosting provider, a public or residential
+					proxy, or a Tor exit node.
+				* [GeoIP2-Connection-Type.mmdb](\(urls.maxmind_geoip2_connection_type)) (paid) —
+					Determine the connection type of an IP address, such as `Cable/DSL`, `Cellular`,
+					`Corporate`, or `Satellite`.
+
+				The country databases are used in the same way as the city databases, and only the
+				fields that they contain are populated. The kind of database is detected from its
+				metadata.
 
 				The database files should be in the [MaxMind DB file
 				format](\(urls.maxmind_db_file_format)).
 				"""
 		}
+		database_reloading: {
+			title: "Database reloading"
+			body:  """
+				The database file is checked for changes every ten seconds, and it's reloaded when it's
+				replaced, for example by `geoipupdate`. If the new file can't be read, the previous
+				database is kept in use.
+				"""
+		}
 	}
 
 	output: logs: line: {
@@ -110,13 +130,17 @@ components: transforms: geoip: {
 			Available with the [GeoIP2-City](\(urls.maxmind_geoip2_city)) or
 			[GeoLite2-City](\(urls.maxmind_geolite2_city)) database.
 			"""
+		_anonymous_ip_db_blurb: """
+			Available with the [GeoIP2-Anonymous-IP](\(urls.maxmind_geoip2_anonymous_ip)) database
+			"""
 
 		description: "Geo-enriched log event"
 		fields: {
 			geoip: {
 				description: """
 					The root field containing all geolocation data as subfields. Depending on the
-					database used, either the city or the ISP field is populated.
+					database used, either the city, the ISP, the anonymous IP, or the connection type
+					fields are populated.
 					"""
 				required: true
 				type: object: {
@@ -156,6 +180,79 @@ components: transforms: geoip: {
 							}
 							groups: ["ASN", "ISP"]
 						}
+						connection_type: {
+							description: """
+								The connection type associated with the IP address. Available with the
+								[GeoIP2-Connection-Type](\(urls.maxmind_geoip2_connection_type)) database.
+								"""
+							required:    false
+							common:      false
+							type: string: {
+								default: null
+								examples: ["Dialup", "Cable/DSL", "Corporate", "Cellular", "Satellite"]
+							}
+							groups: ["Connection Type"]
+						}
+						is_anonymous: {
+							description: """
+								Whether the IP address belongs to any sort of anonymous network.
+								\(_anonymous_ip_db_blurb).
+								"""
+							required:    false
+							common:      false
+							type: bool: default: false
+							groups: ["Anonymous IP"]
+						}
+						is_anonymous_vpn: {
+							description: """
+								Whether the IP address is registered to an anonymous VPN provider.
+								\(_anonymous_ip_db_blurb).
+								"""
+							required:    false
+							common:      false
+							type: bool: default: false
+							groups: ["Anonymous IP"]
+						}
+						is_hosting_provider: {
+							description: """
+								Whether the IP address belongs to a hosting or VPN provider.
+								\(_anonymous_ip_db_blurb).
+								"""
+							required:    false
+							common:      false
+							type: bool: default: false
+							groups: ["Anonymous IP"]
+						}
+						is_public_proxy: {
+							description: """
+								Whether the IP address belongs to a public proxy.
+								\(_anonymous_ip_db_blurb).
+								"""
+							required:    false
+							common:      false
+							type: bool: default: false
+							groups: ["Anonymous IP"]
+						}
+						is_residential_proxy: {
+							description: """
+								Whether the IP address is on a suspected anonymizing network and belongs to a residential ISP.
+								\(_anonymous_ip_db_blurb).
+								"""
+							required:    false
+							common:      false
+							type: bool: default: false
+							groups: ["Anonymous IP"]
+						}
+						is_tor_exit_node: {
+							description: """
+								Whether the IP address is a Tor exit node.
+								\(_anonymous_ip_db_blurb).
+								"""
+							required:    false
+							common:      false
+							type: bool: default: false
+							groups: ["Anonymous IP"]
+						}
 						city_name: {
 							description: """
 								The city name associated with the IP address. \(_city_db_blurb).
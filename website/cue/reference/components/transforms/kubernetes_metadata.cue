@@ -0,0 +1,167 @@
+package metadata
+
+components: transforms: kubernetes_metadata: {
+	title: "Kubernetes Metadata"
+
+	description: """
+		Enriches events coming from Kubernetes Pods with the metadata of the Pod, its Namespace,
+		and its Node, via the Kubernetes API.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		enrich: {
+			from: service: {
+				name:     services.kubernetes.name
+				url:      services.kubernetes.url
+				versions: services.kubernetes.versions
+			}
+		}
+	}
+
+	support: {
+		requirements: [
+			"""
+				[Kubernetes](\(urls.kubernetes)) version `\(services.kubernetes.versions)` is required.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		pod_uid_field: {
+			common:      true
+			description: "The field, or tag for metrics, holding the `uid` of the Pod the event came from."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["k8s.pod.uid"]
+			}
+		}
+		pod_ip_field: {
+			common: true
+			description: """
+				The field, or tag for metrics, holding the IP address of the Pod the event came from.
+				Pods using the host network share the IP address of their Node, so they can't be looked up
+				by IP address.
+				"""
+			required: false
+			type: string: {
+				default: null
+				examples: ["host", "k8s.pod.ip"]
+			}
+		}
+		pod_name_field: {
+			common:      true
+			description: "The field, or tag for metrics, holding the `name` of the Pod the event came from. Must be set together with `pod_namespace_field`."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["k8s.pod.name"]
+			}
+		}
+		pod_namespace_field: {
+			common:      true
+			description: "The field, or tag for metrics, holding the `namespace` of the Pod the event came from. Must be set together with `pod_name_field`."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["k8s.namespace.name"]
+			}
+		}
+		label_selector: {
+			common:      false
+			description: "Specifies the label selector to filter the Pods to cache with."
+			required:    false
+			type: string: {
+				default: ""
+				examples: ["my_custom_label!=my_value", "my_custom_label!=my_value,my_other_custom_label=my_value"]
+			}
+		}
+		field_selector: {
+			common: false
+			description: """
+				Specifies the field selector to filter the Pods to cache with. When Vector runs as a
+				DaemonSet and only receives events from Pods on the same Node,
+				`spec.nodeName=${VECTOR_SELF_NODE_NAME}` keeps only those Pods in the cache.
+				"""
+			required: false
+			type: string: {
+				default: ""
+				examples: ["spec.nodeName=${VECTOR_SELF_NODE_NAME}"]
+			}
+		}
+		namespace_label_selector: {
+			common:      false
+			description: "Specifies the label selector to filter the Namespaces to cache with."
+			required:    false
+			type: string: {
+				default: ""
+				examples: ["my_custom_label!=my_value"]
+			}
+		}
+		pod_annotation_fields:       components.sources.kubernetes_logs.configuration.pod_annotation_fields
+		namespace_annotation_fields: components.sources.kubernetes_logs.configuration.namespace_annotation_fields
+		node_annotation_fields:      components.sources.kubernetes_logs.configuration.node_annotation_fields
+		kube_config_file:            components.sources.kubernetes_logs.configuration.kube_config_file
+		delay_deletion_ms: {
+			common: false
+			description: """
+				Delay between receiving a `DELETE` event and removing any related metadata Vector has stored. This controls how quickly Vector will remove
+				metadata for resources that have been removed from Kubernetes, a longer delay will allow Vector to continue enriching events after the
+				source Pod has been deleted.
+				"""
+			required: false
+			type: uint: {
+				default: 60_000
+				unit:    "milliseconds"
+			}
+		}
+	}
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: false
+	}
+
+	how_it_works: {
+		pod_association: {
+			title: "Finding the Pod of an event"
+			body: """
+				Vector caches the Pods, Namespaces, and Nodes of the cluster, and looks up the Pod an event
+				came from using the configured fields, in this order:
+
+				* `pod_uid_field`, the `uid` of the Pod.
+				* `pod_ip_field`, one of the IP addresses of the Pod.
+				* `pod_name_field` and `pod_namespace_field`, the name and namespace of the Pod.
+
+				Events are passed through unchanged if no Pod is found. The same fields as the
+				[`kubernetes_logs` source](\(urls.vector_kubernetes_logs_source)) are then added to the
+				event, except for the container ones, as fields for logs and as tags for metrics.
+				"""
+		}
+		metric_tags: {
+			title: "Metric tags"
+			body: """
+				Metric tags are flat strings, so the labels and annotations are added as one tag per entry,
+				named after the configured field followed by a `.` and the key, for example
+				`kubernetes.pod_labels.app`. The IP addresses in `pod_ips` are joined with commas.
+				"""
+		}
+	}
+}
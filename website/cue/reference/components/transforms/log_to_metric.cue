@@ -125,6 +125,17 @@ components: transforms: log_to_metric: {
 				}
 			}
 		}
+		reroute_dropped: {
+			common:   false
+			required: false
+			description: """
+				Send log events that could not be converted to the `dropped` output instead of dropping them
+				entirely. A log event is sent to this output when none of the configured metrics could be
+				generated from it, and at least one of them failed, for example because a field could not be
+				parsed as a number.
+				"""
+			type: bool: default: false
+		}
 	}
 
 	input: {
@@ -431,4 +442,18 @@ components: transforms: log_to_metric: {
 	telemetry: metrics: {
 		processing_errors_total: components.sources.internal_metrics.output.metrics.processing_errors_total
 	}
+
+	outputs: [
+		components._default_output,
+		{
+			name: "dropped"
+			description: """
+				This transform also implements an additional `dropped` output. When `reroute_dropped` is set to
+				`true`, log events that could not be converted are sent to the `dropped` output in their original
+				form. For a transform component named `foo`, this output can be accessed by specifying
+				`foo.dropped` as the input to another component. Events sent to this output are annotated with
+				the reason they were dropped and the conversion error.
+				"""
+		},
+	]
 }
@@ -59,6 +59,14 @@ components: transforms: sample: {
 				unit: null
 			}
 		}
+		reroute_dropped: {
+			common:   false
+			required: false
+			description: """
+				Send events that are not sampled to the `dropped` output instead of dropping them entirely.
+				"""
+			type: bool: default: false
+		}
 	}
 
 	input: {
@@ -70,4 +78,18 @@ components: transforms: sample: {
 	telemetry: metrics: {
 		events_discarded_total: components.sources.internal_metrics.output.metrics.events_discarded_total
 	}
+
+	outputs: [
+		components._default_output,
+		{
+			name: "dropped"
+			description: """
+				This transform also implements an additional `dropped` output. When `reroute_dropped` is set to
+				`true`, events that are not sampled are sent to the `dropped` output instead of being discarded.
+				For a transform component named `foo`, this output can be accessed by specifying `foo.dropped` as
+				the input to another component. Events sent to this output are annotated with the reason they
+				were dropped, and do not have the `sample_rate` field added.
+				"""
+		},
+	]
 }
@@ -68,6 +68,15 @@ components: transforms: tag_cardinality_limit: {
 				unit:    null
 			}
 		}
+		reroute_dropped: {
+			common:   false
+			required: false
+			description: """
+				Send events that exceed the cardinality limit to the `dropped` output instead of dropping them
+				entirely. Only applies when `limit_exceeded_action` is `drop_event`.
+				"""
+			type: bool: default: false
+		}
 	}
 
 	input: {
@@ -218,4 +227,18 @@ components: transforms: tag_cardinality_limit: {
 		tag_value_limit_exceeded_total: components.sources.internal_metrics.output.metrics.tag_value_limit_exceeded_total
 		value_limit_reached_total:      components.sources.internal_metrics.output.metrics.value_limit_reached_total
 	}
+
+	outputs: [
+		components._default_output,
+		{
+			name: "dropped"
+			description: """
+				This transform also implements an additional `dropped` output. When `limit_exceeded_action` is
+				`drop_event` and `reroute_dropped` is set to `true`, events that exceed the cardinality limit are
+				sent to the `dropped` output instead of being discarded. For a transform component named `foo`,
+				this output can be accessed by specifying `foo.dropped` as the input to another component. Events
+				sent to this output are annotated with tags describing why they were dropped.
+				"""
+		},
+	]
 }
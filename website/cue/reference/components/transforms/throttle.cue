@@ -72,6 +72,14 @@ components: transforms: throttle: {
 				unit: "seconds"
 			}
 		}
+		reroute_dropped: {
+			common:   false
+			required: false
+			description: """
+				Send events that exceed the rate limit to the `dropped` output instead of dropping them entirely.
+				"""
+			type: bool: default: false
+		}
 	}
 
 	input: {
@@ -161,4 +169,18 @@ components: transforms: throttle: {
 			]
 		}
 	}
+
+	outputs: [
+		components._default_output,
+		{
+			name: "dropped"
+			description: """
+				This transform also implements an additional `dropped` output. When `reroute_dropped` is set to
+				`true`, events that exceed the rate limit are sent to the `dropped` output instead of being
+				discarded. For a transform component named `foo`, this output can be accessed by specifying
+				`foo.dropped` as the input to another component. Events sent to this output are annotated with
+				the reason they were dropped.
+				"""
+		},
+	]
 }
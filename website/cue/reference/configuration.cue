@@ -51,14 +51,40 @@ configuration: {
 			}
 		}
 
+		event_latency: {
+			common: false
+			description: """
+				Configures the internal metrics measuring how long events take to go through the
+				topology. Events are timestamped when they're ingested by a source. These timestamps
+				aren't kept when events are written to a disk buffer or sent to another Vector instance.
+				"""
+			required: false
+			type: object: options: {
+				enabled: {
+					common:      false
+					description: "Record the `event_latency_seconds` histogram for each sink, measuring the time from an event being ingested by a source until it's delivered by the sink."
+					required:    false
+					type: bool: default: false
+				}
+				component_residency: {
+					common:      false
+					description: "Also record the `component_residency_seconds` histogram for each transform and sink, measuring the time events spend in that component, including the time spent waiting in its input buffer. Comparing components shows which one adds the most delay. Only takes effect when `enabled` is set."
+					required:    false
+					type: bool: default: false
+				}
+			}
+		}
+
 		enrichment_tables: {
 			common:      false
 			description: """
 				Configuration options for an [enrichment table](\(urls.enrichment_tables_concept)) to be used in a
 				[`remap`](\(urls.vector_remap_transform)) transform. Currently supported are:
 
-				* [CSV](\(urls.csv)) files
+				* [CSV](\(urls.csv)), [JSON](\(urls.json)), and [newline-delimited JSON](\(urls.ndjson)) files
 				* [MaxMind](\(urls.maxmind)) databases
+				* [SQLite](\(urls.sqlite)) databases
+				* Rows stored from the events of other components, in memory
 
 				For the lookup in the enrichment tables to be as performant as possible, the data is indexed according
 				to the fields that are used in the search. Note that indices can only be created for fields for which an
@@ -75,8 +101,8 @@ configuration: {
 					type: object: options: {
 						path: {
 							description: """
-								The path of the enrichment table file. Currently, only [CSV](\(urls.csv)) files are
-								supported.
+								The path of the enrichment table file. [CSV](\(urls.csv)), [JSON](\(urls.json)), and
+								[newline-delimited JSON](\(urls.ndjson)) files are supported.
 								"""
 							warnings: [
 								"In order to be used by Vector, you need to assign read access to the enrichment table file.",
@@ -96,15 +122,28 @@ configuration: {
 							type: object: options: {
 								type: {
 									description: """
-										The encoding of the file. Currently, only [CSV](\(urls.csv)) is supported.
+										The encoding of the file.
+
+										With the `json` and `ndjson` encodings, each object is a row and its fields
+										are the columns. Integers, floats, booleans, and nested objects and arrays
+										keep their types. Strings are kept as strings, unless the field is listed
+										in `schema`, for instance as a `timestamp`. Fields missing from an object
+										are `null`.
 										"""
 									required:    false
 									common:      true
-									type: string: default: "csv"
+									type: string: {
+										default: "csv"
+										enum: {
+											csv:    "A [CSV](\(urls.csv)) file."
+											json:   "A [JSON](\(urls.json)) array of objects."
+											ndjson: "A [newline-delimited JSON](\(urls.ndjson)) file, with one object per line."
+										}
+									}
 								}
 
 								delimiter: {
-									description: "The delimiter used to separate fields in each row of the CSV file."
+									description: "The delimiter used to separate fields in each row of the CSV file. Only used with the `csv` encoding."
 									common:      false
 									required:    false
 									type: string: {
@@ -119,7 +158,7 @@ configuration: {
 										headers for each column. This is the default behavior.
 
 										If you set it to `false`, there are no headers and the columns are referred to
-										by their numerical index.
+										by their numerical index. Only used with the `csv` encoding.
 										"""
 									required: false
 									common:   false
@@ -169,8 +208,20 @@ configuration: {
 						* [GeoIP2-ISP.mmdb](\(urls.maxmind_geoip2_isp)) (paid) — Determine the Internet
 							Service Provider (ISP), organization name, and autonomous system organization
 							and number associated with an IP address.
+						* [GeoIP2-Anonymous-IP.mmdb](\(urls.maxmind_geoip2_anonymous_ip)) (paid) — Determine
+							whether an IP address belongs to a VPN, a hosting provider, a public or residential
+							proxy, or a Tor exit node. The `is_anonymous`, `is_anonymous_vpn`,
+							`is_hosting_provider`, `is_public_proxy`, `is_residential_proxy`, and
+							`is_tor_exit_node` fields are returned, and are `false` for flags that don't apply.
+						* [GeoIP2-Connection-Type.mmdb](\(urls.maxmind_geoip2_connection_type)) (paid) —
+							Determine the connection type of an IP address, returned in the `connection_type`
+							field.
+
+						The country databases are used in the same way as the city databases, and only the
+						fields that they contain are set. The kind of database is detected from its metadata.
 
 						The database file should be in the [MaxMind DB file format](\(urls.maxmind_db_file_format)).
+						The enrichment table is reloaded when the file is replaced, for example by `geoipupdate`.
 
 						This enrichment table only supports lookup with IP address.
 						"""
@@ -178,8 +229,8 @@ configuration: {
 						path: {
 							description: """
 								Path to the [MaxMind GeoIP2](\(urls.maxmind_geoip2)) or [GeoLite2 binary city
-								database](\(urls.maxmind_geolite2_city)) file (`GeoLite2-City.mmdb`). Other
-								databases, such as the country database, are not supported.
+								database](\(urls.maxmind_geolite2_city)) file (`GeoLite2-City.mmdb`), or to any
+								of the other supported databases.
 								"""
 							required:    true
 							type: string: {
@@ -200,6 +251,93 @@ configuration: {
 						}
 					}
 				}
+				sqlite: {
+					required:    true
+					description: """
+						Configuration options for [SQLite](\(urls.sqlite)) databases.
+
+						The database is copied to a temporary file when the enrichment table is loaded, and is reloaded when
+						the database file changes. The indexes used by the lookups are created on this copy, so the
+						database file is never modified.
+
+						Exact matches are compared using the `IS` operator, and case-insensitive matches use the
+						`NOCASE` collation, which only folds ASCII characters. Date range searches expect dates to be
+						stored as text in the `YYYY-MM-DD HH:MM:SS` format, or as Unix timestamps in columns declared
+						with an integer type. Values of columns declared with a date or time type are returned as
+						timestamps.
+						"""
+					type: object: options: {
+						path: {
+							description: "The path of the SQLite database file."
+							warnings: [
+								"In order to be used by Vector, you need to assign read access to the database file.",
+							]
+							required: true
+							type: string: {
+								examples: ["/data/inventory.db"]
+							}
+						}
+						table: {
+							description: "The table, or view, of the database to look up rows in."
+							required:    true
+							type: string: {
+								examples: ["assets"]
+							}
+						}
+					}
+				}
+				memory: {
+					required:    true
+					description: """
+						Configuration options for enrichment tables built from the events of other components.
+
+						The table acts as a sink for the components listed in its `inputs`: each incoming log event
+						is stored as a row, replacing any previous row with the same key. Rows can then be looked up
+						like those of any other enrichment table, with any of their fields. Lookups with a case
+						sensitive match on the `key_field` are the fastest, other lookups scan the whole table.
+
+						The rows are only kept in memory, and are lost when Vector restarts or when the
+						configuration of the table changes.
+						"""
+					type: object: options: {
+						inputs: {
+							description: "A list of upstream [source](\(urls.vector_sources)) or [transform](\(urls.vector_transforms)) IDs whose events are stored in the table."
+							required:    true
+							type: array: items: type: string: {
+								examples: ["auth_logs"]
+							}
+						}
+						key_field: {
+							description: "The field of the incoming events holding the key of the row to upsert. Events without this field are discarded."
+							required:    false
+							common:      true
+							type: string: {
+								default: "key"
+								examples: ["user_id"]
+							}
+						}
+						ttl_secs: {
+							description: "The number of seconds a row is kept after it was last upserted. By default, rows never expire."
+							required:    false
+							common:      true
+							type: uint: {
+								default: null
+								examples: [3600]
+								unit: "seconds"
+							}
+						}
+						max_entries: {
+							description: "The maximum number of rows kept in the table. Once reached, the rows that were upserted the longest time ago are evicted first. By default, the number of rows is unbounded."
+							required:    false
+							common:      true
+							type: uint: {
+								default: null
+								examples: [100000]
+								unit: null
+							}
+						}
+					}
+				}
 			}
 		}
 
@@ -312,6 +450,19 @@ configuration: {
 							default: false
 						}
 					}
+
+					interval_secs: {
+						common: false
+						description: """
+							How often to re-run the health checks of sinks while Vector is running. Sink specific
+							option overrides it. If unset, health checks only run on startup.
+							"""
+						required: false
+						type: uint: {
+							default: null
+							unit:    "seconds"
+						}
+					}
 				}
 			}
 		}
@@ -445,6 +596,86 @@ configuration: {
 				}
 			}
 		}
+
+		telemetry: {
+			common:      false
+			description: "Configures the telemetry Vector reports about itself."
+			required:    false
+			type: object: options: {
+				metrics: {
+					common:      false
+					description: """
+						Controls over the internal metrics Vector collects about itself, and so exposes through
+						the `internal_metrics` source. These are useful to limit the cardinality of internal
+						metrics in configurations with many components, or components tagging metrics with
+						high-cardinality values such as file paths.
+						"""
+					required: false
+					type: object: options: {
+						drop_metrics: {
+							common:      false
+							description: "Names of internal metrics which are not collected at all."
+							required:    false
+							type: array: {
+								default: []
+								items: type: string: examples: ["component_received_event_bytes_total"]
+							}
+						}
+						drop_tags: {
+							common:      false
+							description: "Tag keys which are removed from all internal metrics. Series which only differ by these tags are aggregated into a single series."
+							required:    false
+							type: array: {
+								default: []
+								items: type: string: examples: ["file", "component_type"]
+							}
+						}
+						max_series: {
+							common:      false
+							description: """
+								The maximum number of internal metric series to collect. Once reached, new series
+								are discarded and a warning is logged, while existing series continue to be
+								updated. Series expired through `expire_metrics_secs` no longer count towards this
+								limit.
+								"""
+							required: false
+							type: uint: {
+								default: null
+								examples: [10000]
+								unit: null
+							}
+						}
+					}
+				}
+				tracing: {
+					common:      false
+					description: """
+						Exports Vector's internal spans to an [OpenTelemetry](\(urls.opentelemetry)) collector
+						over OTLP/gRPC. Each span opened within a component starts a new trace. Every span in
+						that trace gets the `component_id`, `component_kind`, and `component_type` of its
+						component as attributes. No other span fields are exported.
+						"""
+					required: false
+					type: object: options: {
+						endpoint: {
+							description: "The OTLP/gRPC endpoint to export spans to. Use an `https` URL to connect with TLS."
+							required:    true
+							type: string: examples: ["http://localhost:4317"]
+						}
+						sample_rate: {
+							common:      false
+							description: "The rate at which traces are sampled. One out of every `sample_rate` traces is exported, and all others are discarded."
+							required:    false
+							type: uint: {
+								default: 1
+								examples: [10, 100]
+								unit: null
+							}
+						}
+					}
+				}
+			}
+		}
 	}
 
 	how_it_works: {
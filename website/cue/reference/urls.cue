@@ -281,6 +281,7 @@ urls: {
 	kubernetes_api_server:                      "\(kubernetes)/docs/reference/command-line-tools-reference/kube-apiserver/"
 	kubernetes_authorization:                   "\(kubernetes)/docs/reference/access-authn-authz/authorization/"
 	kubernetes_daemonset:                       "\(kubernetes)/docs/concepts/workloads/controllers/daemonset/"
+	kubernetes_events_api:                      "\(kubernetes)/docs/reference/kubernetes-api/cluster-resources/event-v1/"
 	kubernetes_example_daemonset:               "\(vector_repo)/blob/master/config/kubernetes/vector-daemonset.yaml"
 	kubernetes_limit_resources:                 "\(kubernetes)/docs/tasks/configure-pod-container/assign-cpu-resource/"
 	kubernetes_logging_architecture:            "\(kubernetes)/docs/concepts/cluster-administration/logging/"
@@ -329,7 +330,9 @@ urls: {
 	maxmind:                                    "https://www.maxmind.com/en/home"
 	maxmind_db_file_format:                     "https://maxmind.github.io/MaxMind-DB/"
 	maxmind_geoip2:                             "https://dev.maxmind.com/geoip/geoip2/downloadable"
+	maxmind_geoip2_anonymous_ip:                "https://www.maxmind.com/en/geoip2-anonymous-ip-database"
 	maxmind_geoip2_city:                        "https://www.maxmind.com/en/geoip2-city"
+	maxmind_geoip2_connection_type:             "https://www.maxmind.com/en/geoip2-connection-type-database"
 	maxmind_geoip2_isp:                         "https://www.maxmind.com/en/geoip2-isp-database"
 	maxmind_geolite2_asn:                       "https://dev.maxmind.com/geoip/geoip2/geolite2/#Download_Access"
 	maxmind_geolite2_city:                      "https://dev.maxmind.com/geoip/geoip2/geolite2/#Download_Access"
@@ -345,6 +348,7 @@ urls: {
 	native_json_schema:                         "\(vector_repo)/blob/master/lib/codecs/tests/data/native_encoding/schema.cue"
 	nats:                                       "https://nats.io/"
 	nats_rs:                                    "\(github)/nats-io/nats.rs"
+	ndjson:                                     "http://ndjson.org/"
 	new_bug_report:                             "\(vector_repo)/issues/new?labels=type%3A+bug"
 	new_feature_request:                        "\(vector_repo)/issues/new?labels=type%3A+new+feature"
 	new_relic:                                  "https://newrelic.com/"
@@ -459,6 +463,7 @@ urls: {
 	splunk_hec_setup:                           "https://docs.splunk.com/Documentation/Splunk/latest/Data/UsetheHTTPEventCollector"
 	splunk_hec_metadata:                        "https://docs.splunk.com/Documentation/Splunk/latest/Data/FormateventsforHTTPEventCollector#Event_metadata"
 	specs_instrumentation:                      "\(vector_repo)/blob/master/docs/specs/instrumentation.md)"
+	sqlite:                                     "https://www.sqlite.org"
 	standard_streams:                           "\(wikipedia)/wiki/Standard_streams"
 	statsd:                                     "\(github)/statsd/statsd"
 	statsd_multi:                               "\(github)/statsd/statsd/blob/master/docs/metric_types.md#multi-metric-packets"
@@ -542,6 +547,7 @@ urls: {
 	vector_installer:                           "https://sh.vector.dev"
 	vector_issues:                              "\(vector_repo)/issues"
 	vector_journald_source:                     "/docs/reference/configuration/sources/journald/"
+	vector_kubernetes_events_source:            "/docs/reference/configuration/sources/kubernetes_events/"
 	vector_kubernetes_logs_source:              "/docs/reference/configuration/sources/kubernetes_logs/"
 	vector_level_up:                            "/guides/level-up/"
 	vector_log:                                 "/docs/about/under-the-hood/architecture/data-model/log"